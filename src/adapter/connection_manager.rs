@@ -1,3 +1,4 @@
+use crate::adapter::horizontal_adapter::ClusterTopology;
 use crate::app::manager::AppManager;
 use crate::channel::PresenceMemberInfo;
 use crate::error::Result;
@@ -35,6 +36,12 @@ pub trait HorizontalAdapterInterface: Send + Sync {
         user_id: &str,
         socket_id: &str,
     ) -> Result<()>;
+
+    /// Snapshot of cluster membership, load and cleanup leadership as seen from this node
+    async fn get_cluster_topology(&self) -> ClusterTopology;
+
+    /// Tell other nodes to drop their cached copy of an app
+    async fn broadcast_app_invalidation(&self, app_id: &str) -> Result<()>;
 }
 
 #[async_trait]
@@ -160,4 +167,12 @@ pub trait ConnectionManager: Send + Sync {
     > {
         None // Default: no clustering support
     }
+
+    /// Configure the app invalidation bus if this adapter supports clustering
+    /// Returns Some(receiver) of app IDs invalidated by other nodes, None if not supported
+    async fn configure_app_invalidation_events(
+        &mut self,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<String>> {
+        None // Default: no clustering support
+    }
 }
@@ -15,6 +15,8 @@ use crate::error::Result;
 use crate::options::NatsAdapterConfig;
 #[cfg(feature = "redis-cluster")]
 use crate::options::RedisClusterAdapterConfig; // Import AdapterDriver, RedisConnection
+#[cfg(feature = "redis-cluster")]
+use crate::options::RedisTransportMode;
 use crate::options::{AdapterConfig, AdapterDriver, DatabaseConfig};
 use tracing::{info, warn};
 
@@ -25,6 +27,7 @@ impl AdapterFactory {
     pub async fn create(
         config: &AdapterConfig,
         db_config: &DatabaseConfig,
+        process_id: &str,
     ) -> Result<Arc<Mutex<dyn ConnectionManager + Send + Sync>>> {
         info!(
             "{}",
@@ -52,10 +55,14 @@ impl AdapterFactory {
                     prefix: config.redis.prefix.clone(),
                     request_timeout_ms: config.redis.requests_timeout,
                     cluster_mode: config.redis.cluster_mode,
+                    transport_mode: config.redis.transport_mode,
+                    stream_max_len: config.redis.stream_max_len,
+                    process_id: process_id.to_string(),
                 };
                 match RedisAdapter::new(adapter_options).await {
                     Ok(mut adapter) => {
                         adapter.set_cluster_health(&config.cluster_health).await?;
+                        adapter.set_request_config(&config.requests).await;
                         Ok(Arc::new(Mutex::new(adapter)))
                     }
                     Err(e) => {
@@ -76,6 +83,12 @@ impl AdapterFactory {
             }
             #[cfg(feature = "redis-cluster")]
             AdapterDriver::RedisCluster => {
+                if config.redis.transport_mode != RedisTransportMode::PubSub {
+                    return Err(crate::error::Error::Configuration(
+                        "The Redis Cluster adapter only supports the pubsub transport mode"
+                            .to_string(),
+                    ));
+                }
                 // Use nodes from the specific RedisClusterAdapterConfig if available, else from DatabaseConfig.redis
                 let nodes = if !config.cluster.nodes.is_empty() {
                     config.cluster.nodes.clone()
@@ -105,6 +118,7 @@ impl AdapterFactory {
                 match RedisClusterAdapter::new(cluster_adapter_config).await {
                     Ok(mut adapter) => {
                         adapter.set_cluster_health(&config.cluster_health).await?;
+                        adapter.set_request_config(&config.requests).await;
                         Ok(Arc::new(Mutex::new(adapter)))
                     }
                     Err(e) => {
@@ -135,10 +149,15 @@ impl AdapterFactory {
                     token: config.nats.token.clone(),
                     connection_timeout_ms: config.nats.connection_timeout_ms,
                     nodes_number: config.nats.nodes_number,
+                    jetstream: config.nats.jetstream,
+                    stream_max_messages: config.nats.stream_max_messages,
+                    stream_max_age_seconds: config.nats.stream_max_age_seconds,
+                    process_id: process_id.to_string(),
                 };
                 match NatsAdapter::new(nats_cfg).await {
                     Ok(mut adapter) => {
                         adapter.set_cluster_health(&config.cluster_health).await?;
+                        adapter.set_request_config(&config.requests).await;
                         Ok(Arc::new(Mutex::new(adapter)))
                     }
                     Err(e) => {
@@ -0,0 +1,32 @@
+// src/adapter/handler/audit_logging.rs
+use super::ConnectionHandler;
+use crate::audit::{AuditEvent, AuditLog};
+use crate::rate_limiter::middleware::{TrustedClientIp, UntrustedClientAddress};
+use axum::http::{Extensions, HeaderMap};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+impl ConnectionHandler {
+    /// Record security-relevant events in the given audit log
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    pub fn audit(&self, event: AuditEvent) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(event);
+        }
+    }
+
+    /// Address of the client of a request, read only from the peer address and the
+    /// proxies trusted in `rate_limiter.api_rate_limit`. IP filters and bans decide on it.
+    pub fn trusted_client_ip(
+        &self,
+        headers: &HeaderMap,
+        extensions: &Extensions,
+    ) -> Result<Option<IpAddr>, UntrustedClientAddress> {
+        TrustedClientIp::from_config(&self.server_options.rate_limiter.api_rate_limit)
+            .resolve(headers, extensions)
+    }
+}
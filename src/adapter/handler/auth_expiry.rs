@@ -0,0 +1,175 @@
+// src/adapter/handler/auth_expiry.rs
+use super::ConnectionHandler;
+use crate::app::config::App;
+use crate::audit::{AuditEvent, AuditEventKind};
+use crate::error::Result;
+use crate::protocol::messages::PusherMessage;
+use crate::websocket::SocketId;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+impl ConnectionHandler {
+    /// Track when the authorization of a subscription (`Some(channel)`) or of the
+    /// signed-in user (`None`) lapses. Authorizing again replaces the previous expiry, and
+    /// an authorization without one never lapses.
+    pub async fn track_auth_expiry(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: Option<&str>,
+        expires_at: Option<u64>,
+    ) -> Result<()> {
+        let mut connection_manager = self.connection_manager.lock().await;
+        let Some(conn) = connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        else {
+            return Ok(());
+        };
+
+        // Spawned under the connection lock so the task cannot look before the state is set
+        let mut ws = conn.inner.lock().await;
+        let handle = expires_at
+            .map(|expires_at| self.spawn_auth_expiry(socket_id, app_config, channel, expires_at));
+        ws.state.set_auth_expiry(channel, expires_at, handle);
+        Ok(())
+    }
+
+    /// Renew the expiring authorization of a channel the socket is already subscribed to,
+    /// without subscribing again. Returns false if there is nothing to renew.
+    pub async fn renew_channel_auth(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: &str,
+        expires_at: Option<u64>,
+    ) -> Result<bool> {
+        let mut connection_manager = self.connection_manager.lock().await;
+        let Some(conn) = connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        else {
+            return Ok(false);
+        };
+
+        let mut ws = conn.inner.lock().await;
+        if !ws.state.is_subscribed(channel) || ws.state.auth_expires_at(Some(channel)).is_none() {
+            return Ok(false);
+        }
+        let handle = expires_at.map(|expires_at| {
+            self.spawn_auth_expiry(socket_id, app_config, Some(channel), expires_at)
+        });
+        ws.state.set_auth_expiry(Some(channel), expires_at, handle);
+        debug!(
+            "Renewed authorization of socket {} for {}",
+            socket_id, channel
+        );
+        Ok(true)
+    }
+
+    fn spawn_auth_expiry(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: Option<&str>,
+        expires_at: u64,
+    ) -> JoinHandle<()> {
+        let handler = self.clone();
+        let socket_id = socket_id.clone();
+        let app_config = app_config.clone();
+        let channel = channel.map(str::to_string);
+        let warning = self.server_options.auth_expiry_warning;
+
+        tokio::spawn(async move {
+            sleep(time_until(expires_at.saturating_sub(warning))).await;
+            let message = PusherMessage::auth_expiring(channel.clone(), expires_at);
+            if let Err(e) = handler
+                .send_message_to_socket(&app_config.id, &socket_id, message)
+                .await
+            {
+                debug!("Failed to warn socket {} of auth expiry: {}", socket_id, e);
+            }
+
+            sleep(time_until(expires_at)).await;
+            handler
+                .expire_auth(&socket_id, &app_config, channel.as_deref(), expires_at)
+                .await;
+        })
+    }
+
+    /// Unsubscribe the socket from a channel whose authorization lapsed, or disconnect it
+    /// when the user authentication lapsed
+    async fn expire_auth(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel: Option<&str>,
+        expires_at: u64,
+    ) {
+        let mut connection_manager = self.connection_manager.lock().await;
+        let Some(conn) = connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        else {
+            return;
+        };
+        let mut ws = conn.inner.lock().await;
+        if !ws.state.take_lapsed_auth(channel, expires_at) {
+            return;
+        }
+        let Some(channel) = channel else {
+            debug!("User authentication of socket {} expired", socket_id);
+            self.audit(
+                AuditEvent::new(AuditEventKind::ConnectionClosed, Some(&app_config.id))
+                    .detail("socket_id", socket_id.to_string())
+                    .detail("code", 4009)
+                    .detail("reason", "User authentication expired"),
+            );
+            let _ = ws
+                .close(4009, "User authentication expired".to_string())
+                .await;
+            return;
+        };
+        drop(ws);
+        drop(connection_manager);
+
+        debug!(
+            "Authorization of socket {} for {} expired",
+            socket_id, channel
+        );
+        if let Err(e) = self
+            .unsubscribe_from_channel(socket_id, app_config, channel)
+            .await
+        {
+            warn!(
+                "Failed to unsubscribe socket {} from {} after its authorization expired: {}",
+                socket_id, channel, e
+            );
+            return;
+        }
+
+        let message = PusherMessage::error(
+            4009,
+            format!("Authorization for {channel} expired"),
+            Some(channel.to_string()),
+        );
+        if let Err(e) = self
+            .send_message_to_socket(&app_config.id, socket_id, message)
+            .await
+        {
+            debug!(
+                "Failed to notify socket {} of auth expiry: {}",
+                socket_id, e
+            );
+        }
+    }
+}
+
+fn time_until(timestamp: u64) -> Duration {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Duration::from_secs(timestamp).saturating_sub(now)
+}
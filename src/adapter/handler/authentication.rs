@@ -2,10 +2,24 @@
 use super::ConnectionHandler;
 use super::types::*;
 use crate::app::auth::AuthValidator;
+use crate::app::authorizer::{self, AuthorizerDecision, AuthorizerRequest};
 use crate::app::config::App;
+use crate::app::jwt_auth;
 use crate::channel::ChannelManager;
 use crate::error::{Error, Result};
 use crate::websocket::SocketId;
+use std::collections::BTreeMap;
+use tracing::{debug, warn};
+
+/// Expiry of a verified auth string: the `exp` of a JWT, or the `<expires_at>` of
+/// `<key>:<hmac>:<expires_at>`
+pub fn auth_expires_at(auth: &str) -> Option<u64> {
+    if jwt_auth::is_jwt(auth) {
+        return jwt_auth::expires_at(auth);
+    }
+    let mut parts = auth.splitn(3, ':');
+    parts.nth(2).and_then(|expires_at| expires_at.parse().ok())
+}
 
 impl ConnectionHandler {
     pub async fn verify_channel_authentication(
@@ -19,10 +33,37 @@ impl ConnectionHandler {
             return Ok(true);
         }
 
-        // Private/presence channels require authentication
-        let signature = request.auth.as_ref().ok_or_else(|| {
-            Error::Auth("Authentication signature required for this channel".into())
-        })?;
+        // Private/presence channels require authentication, unless the authorizer decides
+        let Some(signature) = request.auth.as_ref() else {
+            if app_config.authorizer.is_some() {
+                return Ok(false);
+            }
+            return Err(Error::Auth(
+                "Authentication signature required for this channel".into(),
+            ));
+        };
+
+        if let Some(jwt_config) = app_config.jwt_auth.as_ref()
+            && jwt_auth::is_jwt(signature)
+        {
+            let result =
+                jwt_auth::verify(app_config, jwt_config, signature, socket_id).and_then(|claims| {
+                    if !claims.allows_channel(&request.channel) {
+                        return Err(Error::Auth("JWT does not allow this channel".into()));
+                    }
+                    if request.channel.starts_with("presence-") {
+                        claims.check_presence_member(request.channel_data.as_deref())?;
+                    }
+                    Ok(())
+                });
+            if let Err(ref e) = result {
+                debug!(
+                    "JWT authentication for {} failed on socket {}: {}",
+                    request.channel, socket_id, e
+                );
+            }
+            return Ok(result.is_ok());
+        }
 
         // Create a temporary PusherMessage for signature validation
         let temp_message = crate::protocol::messages::PusherMessage {
@@ -49,29 +90,147 @@ impl ConnectionHandler {
         Ok(is_valid)
     }
 
+    pub(crate) async fn store_upgrade_headers(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        headers: BTreeMap<String, String>,
+    ) {
+        let mut connection_manager = self.connection_manager.lock().await;
+        if let Some(conn) = connection_manager
+            .get_connection(socket_id, &app_config.id)
+            .await
+        {
+            conn.inner.lock().await.state.upgrade_headers = Some(headers);
+        }
+    }
+
+    /// Ask the app's authorizer about a subscription that came without a valid signature.
+    /// Presence subscriptions take their member from the decision, not from the client.
+    pub async fn authorize_subscription(
+        &self,
+        app_config: &App,
+        socket_id: &SocketId,
+        request: &mut SubscriptionRequest,
+    ) -> Result<bool> {
+        let Some(config) = app_config.authorizer.as_ref() else {
+            return Ok(false);
+        };
+
+        let headers = {
+            let mut connection_manager = self.connection_manager.lock().await;
+            match connection_manager
+                .get_connection(socket_id, &app_config.id)
+                .await
+            {
+                Some(conn) => conn.inner.lock().await.state.upgrade_headers.clone(),
+                None => None,
+            }
+        }
+        .unwrap_or_default();
+
+        let cache_key = authorizer::cache_key(&app_config.id, &request.channel, &headers);
+        let cached = match &cache_key {
+            Some(cache_key) => self.cache_manager.lock().await.get(cache_key).await,
+            None => Ok(None),
+        };
+        let decision = match cached {
+            Ok(Some(cached)) => serde_json::from_str::<AuthorizerDecision>(&cached).ok(),
+            _ => None,
+        };
+        let decision = match decision {
+            Some(decision) => decision,
+            None => {
+                let authorizer_request = AuthorizerRequest {
+                    socket_id: socket_id.as_ref(),
+                    channel_name: &request.channel,
+                    headers: &headers,
+                };
+                let decision =
+                    match authorizer::request_decision(app_config, config, &authorizer_request)
+                        .await
+                    {
+                        Ok(decision) => decision,
+                        Err(e) => {
+                            warn!(
+                                "Authorizer of app {} failed for {}: {}",
+                                app_config.id, request.channel, e
+                            );
+                            return Ok(false);
+                        }
+                    };
+                // Only the verdict is cached, never a member
+                let verdict = AuthorizerDecision {
+                    authorized: decision.authorized,
+                    channel_data: None,
+                };
+                if config.cache_ttl() > 0
+                    && let Some(cache_key) = &cache_key
+                    && let Ok(value) = serde_json::to_string(&verdict)
+                    && let Err(e) = self
+                        .cache_manager
+                        .lock()
+                        .await
+                        .set(cache_key, &value, config.cache_ttl())
+                        .await
+                {
+                    warn!("Failed to cache authorizer decision: {}", e);
+                }
+                decision
+            }
+        };
+
+        if !decision.authorized {
+            debug!(
+                "Authorizer denied {} to socket {}",
+                request.channel, socket_id
+            );
+            return Ok(false);
+        }
+        if request.channel.starts_with("presence-") {
+            if decision.channel_data.is_none() {
+                warn!(
+                    "Authorizer of app {} allowed {} without channel_data",
+                    app_config.id, request.channel
+                );
+                return Ok(false);
+            }
+            request.channel_data = decision.channel_data;
+        }
+        // A rejected client signature must not set an expiry on the subscription
+        request.auth = None;
+        Ok(true)
+    }
+
     pub async fn verify_signin_authentication(
         &self,
         socket_id: &SocketId,
         app_config: &App,
         request: &SignInRequest,
     ) -> Result<()> {
+        if let Some(jwt_config) = app_config.jwt_auth.as_ref()
+            && jwt_auth::is_jwt(&request.auth)
+        {
+            return jwt_auth::verify(app_config, jwt_config, &request.auth, socket_id)
+                .and_then(|claims| claims.check_signin_user(&request.user_data));
+        }
+
         let auth_validator = AuthValidator::new(self.app_manager.clone());
 
-        // Extract the signature from the auth string (format: "app-key:signature")
-        let signature = if let Some(colon_pos) = request.auth.find(':') {
-            &request.auth[colon_pos + 1..]
-        } else {
-            &request.auth
-        };
+        // The auth string is "<key>:<signature>"; without a key the app key is assumed.
+        // Like channel auth, only the secrets paired with the key are accepted.
+        let (key, signature) = request
+            .auth
+            .split_once(':')
+            .unwrap_or((app_config.key.as_str(), request.auth.as_str()));
 
-        let is_valid = auth_validator
-            .validate_channel_auth(
-                socket_id.clone(),
-                &app_config.key,
-                &request.user_data,
-                signature,
-            )
-            .await?;
+        let is_valid = auth_validator.sign_in_token_is_valid(
+            &socket_id.0,
+            key,
+            &request.user_data,
+            signature,
+            app_config.clone(),
+        );
 
         if !is_valid {
             return Err(Error::Auth("Connection not authorized for signin.".into()));
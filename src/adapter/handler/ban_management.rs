@@ -0,0 +1,87 @@
+// src/adapter/handler/ban_management.rs
+use super::ConnectionHandler;
+use crate::app::config::App;
+use crate::ban::{BanManager, BanTarget};
+use crate::error::{Error, Result};
+use crate::websocket::SocketId;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+impl ConnectionHandler {
+    /// Ban clients that keep violating rate limits or failing authentication
+    pub fn with_ban_manager(mut self, ban_manager: Arc<BanManager>) -> Self {
+        self.ban_manager = Some(ban_manager);
+        self
+    }
+
+    pub fn ban_manager(&self) -> Option<&Arc<BanManager>> {
+        self.ban_manager.as_ref()
+    }
+
+    /// Reject clients whose address is banned in every app or in the given one
+    pub async fn check_ip_ban(
+        &self,
+        app_id: Option<&str>,
+        client_ip: Option<IpAddr>,
+    ) -> Result<()> {
+        if let (Some(ban_manager), Some(ip)) = (&self.ban_manager, client_ip)
+            && ban_manager.find_ip(app_id, ip).await.is_some()
+        {
+            return Err(Error::TemporarilyBanned);
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn check_user_ban(&self, app_config: &App, user_id: &str) -> Result<()> {
+        if let Some(ban_manager) = &self.ban_manager
+            && ban_manager
+                .find(&BanTarget::user(&app_config.id, user_id))
+                .await
+                .is_some()
+        {
+            return Err(Error::TemporarilyBanned);
+        }
+        Ok(())
+    }
+
+    /// Count client event rate-limit and authentication errors of a socket against its
+    /// user, or its address in the app before it signed in. Returns the error to report, which
+    /// becomes `TemporarilyBanned` once the violation gets the client banned.
+    pub(crate) async fn escalate_violation(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        client_ip: Option<IpAddr>,
+        error: Error,
+    ) -> Error {
+        let Some(ban_manager) = &self.ban_manager else {
+            return error;
+        };
+        let reason = match error {
+            Error::ClientEventRateLimit => "rate_limit",
+            Error::Auth(_) | Error::InvalidSignature => "auth_failure",
+            _ => return error,
+        };
+
+        let user_id = match self
+            .connection_manager
+            .lock()
+            .await
+            .get_connection(socket_id, &app_config.id)
+            .await
+        {
+            Some(conn) => conn.inner.lock().await.state.user_id.clone(),
+            None => None,
+        };
+        let target = match (user_id, client_ip) {
+            (Some(user_id), _) => BanTarget::user(&app_config.id, &user_id),
+            (None, Some(ip)) => BanTarget::app_ip(&app_config.id, ip),
+            (None, None) => return error,
+        };
+
+        match ban_manager.record_violation(&target, reason).await {
+            Some(_) => Error::TemporarilyBanned,
+            None => error,
+        }
+    }
+}
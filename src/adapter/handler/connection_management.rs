@@ -3,14 +3,20 @@
 // src/adapter/handler/connection_management.rs
 use super::ConnectionHandler;
 use crate::app::config::App;
+use crate::app::manager::AppChange;
+use crate::audit::{AuditEvent, AuditEventKind};
 use crate::error::{Error, Result};
 use crate::protocol::messages::PusherMessage;
+use crate::quota::QuotaKind;
 use crate::websocket::SocketId;
 use fastwebsockets::{Frame, Payload, WebSocketWrite};
 use hyper::upgrade::Upgraded;
 use hyper_util::rt::TokioIo;
+use std::sync::Arc;
 use tokio::io::WriteHalf;
-use tracing::warn;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
 
 impl ConnectionHandler {
     pub async fn send_message_to_socket(
@@ -92,6 +98,11 @@ impl ConnectionHandler {
             (result, target_socket_count)
         };
 
+        if result.is_ok() {
+            self.record_quota_usage(app_config, QuotaKind::Messages, target_socket_count as u64)
+                .await;
+        }
+
         // Track metrics if message was sent successfully
         if result.is_ok()
             && target_socket_count > 0
@@ -133,6 +144,12 @@ impl ConnectionHandler {
         code: u16,
         reason: &str,
     ) -> Result<()> {
+        self.audit(
+            AuditEvent::new(AuditEventKind::ConnectionClosed, Some(&app_config.id))
+                .detail("socket_id", socket_id.to_string())
+                .detail("code", code)
+                .detail("reason", reason),
+        );
         let mut conn_manager = self.connection_manager.lock().await;
         if let Some(conn) = conn_manager.get_connection(socket_id, &app_config.id).await {
             let mut conn_locked = conn.inner.lock().await;
@@ -177,6 +194,143 @@ impl ConnectionHandler {
         Ok(())
     }
 
+    /// Close every local socket of an app, e.g. after it was disabled or deleted.
+    /// Returns the number of sockets closed.
+    pub async fn disconnect_app_sockets(&self, app_id: &str, reason: &Error) -> usize {
+        let namespace = self
+            .connection_manager
+            .lock()
+            .await
+            .get_namespace(app_id)
+            .await;
+        let Some(namespace) = namespace else {
+            return 0;
+        };
+
+        let sockets = match namespace.get_sockets().await {
+            Ok(sockets) => sockets,
+            Err(e) => {
+                warn!(app_id = %app_id, "Failed to get sockets to disconnect: {}", e);
+                return 0;
+            }
+        };
+
+        let mut closed = 0;
+        for entry in sockets.iter() {
+            match entry
+                .value()
+                .close(reason.close_code(), reason.to_string())
+                .await
+            {
+                Ok(()) => closed += 1,
+                Err(e) => debug!(app_id = %app_id, "Failed to close socket: {}", e),
+            }
+        }
+        self.audit(
+            AuditEvent::new(AuditEventKind::AppConnectionsClosed, Some(app_id))
+                .detail("sockets", closed)
+                .detail("code", reason.close_code())
+                .detail("reason", reason.to_string()),
+        );
+        closed
+    }
+
+    /// Disconnect clients of apps that the app manager reports as deleted or disabled
+    pub fn spawn_app_change_listener(
+        self: &Arc<Self>,
+        mut changes: broadcast::Receiver<AppChange>,
+    ) {
+        let handler = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let (app_id, reason) = match changes.recv().await {
+                    Ok(AppChange::Deleted(app)) => (app.id, Error::ApplicationNotFound),
+                    Ok(AppChange::Updated { previous, current })
+                        if previous.enabled && !current.enabled =>
+                    {
+                        (current.id, Error::ApplicationDisabled)
+                    }
+                    Ok(AppChange::Invalidated { app_id }) => {
+                        handler.enforce_app_state(&app_id).await;
+                        continue;
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Missed {} app changes", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                let closed = handler.disconnect_app_sockets(&app_id, &reason).await;
+                if closed > 0 {
+                    info!(app_id = %app_id, "Disconnected {} sockets: {}", closed, reason);
+                }
+            }
+        });
+    }
+
+    /// Re-read an app that may have changed and disconnect its local clients if it was
+    /// deleted or disabled. Apps without local connections are not looked up.
+    pub async fn enforce_app_state(&self, app_id: &str) -> usize {
+        let namespace = self
+            .connection_manager
+            .lock()
+            .await
+            .get_namespace(app_id)
+            .await;
+        let has_sockets = match namespace {
+            Some(namespace) => namespace
+                .get_sockets()
+                .await
+                .is_ok_and(|sockets| !sockets.is_empty()),
+            None => false,
+        };
+        if !has_sockets {
+            return 0;
+        }
+
+        let reason = match self.app_manager.find_by_id(app_id).await {
+            Ok(None) => Error::ApplicationNotFound,
+            Ok(Some(app)) if !app.enabled => Error::ApplicationDisabled,
+            Ok(Some(_)) => return 0,
+            Err(e) => {
+                warn!(app_id = %app_id, "Failed to re-read invalidated app: {}", e);
+                return 0;
+            }
+        };
+
+        let closed = self.disconnect_app_sockets(app_id, &reason).await;
+        if closed > 0 {
+            info!(app_id = %app_id, "Disconnected {} sockets: {}", closed, reason);
+        }
+        closed
+    }
+
+    /// Apply an app invalidation received from another node
+    pub async fn handle_app_invalidation(&self, app_id: &str) {
+        if let Err(e) = self.app_manager.invalidate_cache(app_id).await {
+            warn!(app_id = %app_id, "Failed to invalidate cached app: {}", e);
+        }
+        self.enforce_app_state(app_id).await;
+    }
+
+    /// Make a change to an app take effect everywhere: disconnect local clients if the app
+    /// is gone or disabled and tell the other nodes to drop their cached copy
+    pub async fn propagate_app_change(&self, app_id: &str) {
+        self.handle_app_invalidation(app_id).await;
+
+        if !self.server_options.app_manager.invalidation.cluster {
+            return;
+        }
+        let connection_manager = self.connection_manager.lock().await;
+        if let Some(horizontal) = connection_manager.as_horizontal_adapter()
+            && let Err(e) = horizontal.broadcast_app_invalidation(app_id).await
+        {
+            warn!(app_id = %app_id, "Failed to broadcast app invalidation: {}", e);
+        }
+    }
+
     async fn send_error_frame(
         ws_tx: &mut WebSocketWrite<WriteHalf<TokioIo<Upgraded>>>,
         error: &Error,
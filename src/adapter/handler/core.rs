@@ -53,6 +53,19 @@ impl ConnectionHandler {
         // Extract channel name from message
         let channel_name = self.extract_channel_from_unsubscribe_message(message)?;
 
+        self.unsubscribe_from_channel(socket_id, app_config, &channel_name)
+            .await
+    }
+
+    /// Remove the socket from a channel, announcing the change to members and webhooks
+    pub(crate) async fn unsubscribe_from_channel(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        channel_name: &str,
+    ) -> Result<()> {
+        let channel_name = channel_name.to_string();
+
         // Get user ID before unsubscribing (for presence channels)
         let user_id = self.get_user_id_for_socket(socket_id, app_config).await?;
 
@@ -315,6 +328,7 @@ impl ConnectionHandler {
                 socket_id
             );
         }
+        self.channel_event_limiters.remove(socket_id);
 
         // Step 4: Queue cleanup work (non-blocking)
         if let Some(task) = disconnect_info {
@@ -404,6 +418,7 @@ impl ConnectionHandler {
                 socket_id
             );
         }
+        self.channel_event_limiters.remove(socket_id);
 
         // Get app configuration
         let app_config = match self.app_manager.find_by_id(app_id).await? {
@@ -0,0 +1,100 @@
+// src/adapter/handler/drain_management.rs
+use super::ConnectionHandler;
+use crate::error::Error;
+use crate::websocket::WebSocketRef;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Granularity of the drain schedule. Sockets are closed in small batches on
+/// every tick so large nodes don't need one timer per connection.
+const DRAIN_TICK_MS: u64 = 100;
+
+impl ConnectionHandler {
+    /// Whether this node is draining (refusing upgrades and shedding connections)
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Flip the node into drain mode.
+    /// Returns `false` if a drain was already in progress.
+    pub fn begin_drain(&self) -> bool {
+        let started = !self.draining.swap(true, Ordering::SeqCst);
+        if started {
+            info!("Node entered drain mode: refusing new WebSocket upgrades");
+        }
+        started
+    }
+
+    /// Enter drain mode and disconnect clients in the background using the
+    /// configured `drain.window_seconds`. Returns `false` if already draining.
+    pub fn spawn_drain(self: &Arc<Self>) -> bool {
+        if !self.begin_drain() {
+            return false;
+        }
+        let handler = Arc::clone(self);
+        let window = Duration::from_secs(self.server_options.drain.window_seconds);
+        tokio::spawn(async move {
+            handler.drain_connections(window).await;
+        });
+        true
+    }
+
+    /// Close every local connection with a reconnect-immediately code, spreading
+    /// the disconnects evenly over `window` so clients don't all reconnect to the
+    /// remaining nodes at the same instant. Returns the number of sockets closed.
+    pub async fn drain_connections(&self, window: Duration) -> usize {
+        let sockets = self.collect_local_sockets().await;
+        let total = sockets.len();
+        if total == 0 {
+            info!("Drain: no connections to disconnect");
+            return 0;
+        }
+
+        let ticks = (window.as_millis() as u64 / DRAIN_TICK_MS).max(1) as usize;
+        let batch_size = total.div_ceil(ticks);
+        info!(
+            "Draining {} connections over {:?} ({} per {}ms)",
+            total, window, batch_size, DRAIN_TICK_MS
+        );
+
+        let reason = Error::ServerDraining;
+        let mut interval = tokio::time::interval(Duration::from_millis(DRAIN_TICK_MS));
+        for batch in sockets.chunks(batch_size) {
+            interval.tick().await;
+            for ws in batch {
+                if let Err(e) = ws.close(reason.close_code(), reason.to_string()).await {
+                    debug!("Failed to close socket during drain: {}", e);
+                }
+            }
+        }
+
+        info!("Drain completed: {} connections disconnected", total);
+        total
+    }
+
+    async fn collect_local_sockets(&self) -> Vec<WebSocketRef> {
+        let namespaces = {
+            let mut connection_manager = self.connection_manager.lock().await;
+            match connection_manager.get_namespaces().await {
+                Ok(namespaces) => namespaces,
+                Err(e) => {
+                    warn!("Failed to get namespaces for drain: {}", e);
+                    return Vec::new();
+                }
+            }
+        };
+
+        let mut sockets = Vec::new();
+        for entry in namespaces.iter() {
+            match entry.value().get_sockets().await {
+                Ok(app_sockets) => {
+                    sockets.extend(app_sockets.iter().map(|s| s.value().clone()));
+                }
+                Err(e) => warn!(app_id = %entry.key(), "Failed to get sockets for drain: {}", e),
+            }
+        }
+        sockets
+    }
+}
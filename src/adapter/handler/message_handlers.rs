@@ -1,9 +1,12 @@
 // src/adapter/handler/message_handlers.rs
 use super::ConnectionHandler;
+use super::authentication::auth_expires_at;
 use super::types::*;
 use crate::app::config::App;
+use crate::channel::ChannelType;
 use crate::error::{Error, Result};
 use crate::protocol::messages::PusherMessage;
+use crate::quota::QuotaKind;
 use crate::websocket::SocketId;
 
 impl ConnectionHandler {
@@ -42,7 +45,7 @@ impl ConnectionHandler {
         &self,
         socket_id: &SocketId,
         app_config: &App,
-        request: SubscriptionRequest,
+        mut request: SubscriptionRequest,
     ) -> Result<()> {
         // Validate the request
         self.validate_subscription_request(app_config, &request)
@@ -67,6 +70,28 @@ impl ConnectionHandler {
                 return Err(e);
             }
         };
+        // Without a valid signature the app's authorizer may still allow the subscription
+        let is_authenticated = is_authenticated
+            || self
+                .authorize_subscription(app_config, socket_id, &mut request)
+                .await?;
+
+        // Subscribing again with a fresh auth renews an expiring authorization in place
+        let requires_authentication =
+            ChannelType::from_name(&request.channel).requires_authentication();
+        if is_authenticated
+            && requires_authentication
+            && self
+                .renew_channel_auth(
+                    socket_id,
+                    app_config,
+                    &request.channel,
+                    request.auth.as_deref().and_then(auth_expires_at),
+                )
+                .await?
+        {
+            return Ok(());
+        }
 
         // Validate presence channel specifics
         if request.channel.starts_with("presence-") {
@@ -83,6 +108,12 @@ impl ConnectionHandler {
         self.handle_post_subscription(socket_id, app_config, &request, &subscription_result)
             .await?;
 
+        if is_authenticated && requires_authentication {
+            let expires_at = request.auth.as_deref().and_then(auth_expires_at);
+            self.track_auth_expiry(socket_id, app_config, Some(&request.channel), expires_at)
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -105,10 +136,13 @@ impl ConnectionHandler {
         // Verify authentication
         self.verify_signin_authentication(socket_id, app_config, &request)
             .await?;
+        self.check_user_ban(app_config, &user_info.id).await?;
 
         // Update connection state
         self.update_connection_with_user_info(socket_id, app_config, &user_info)
             .await?;
+        self.track_auth_expiry(socket_id, app_config, None, auth_expires_at(&request.auth))
+            .await?;
 
         // Handle watchlist functionality
         self.handle_signin_watchlist(socket_id, app_config, &user_info)
@@ -129,6 +163,7 @@ impl ConnectionHandler {
     ) -> Result<()> {
         // Validate the client event
         self.validate_client_event(app_config, &request).await?;
+        self.check_quota(app_config, QuotaKind::Messages).await?;
 
         // Check if socket is subscribed to the channel
         self.verify_channel_subscription(socket_id, app_config, &request.channel)
@@ -1,9 +1,14 @@
 // src/adapter/handler/mod.rs
+pub mod audit_logging;
+pub mod auth_expiry;
 pub mod authentication;
+pub mod ban_management;
 pub mod connection_management;
 mod core;
+pub mod drain_management;
 pub mod message_handlers;
 pub mod origin_validation;
+pub mod quota_management;
 pub mod rate_limiting;
 pub mod signin_management;
 pub mod subscription_management;
@@ -13,31 +18,43 @@ pub mod validation;
 pub mod webhook_management;
 
 use crate::adapter::ConnectionManager;
+use crate::app::authorizer::capture_headers;
 use crate::app::config::App;
 use crate::app::manager::AppManager;
+use crate::audit::{AuditEvent, AuditEventKind, AuditLog};
+use crate::ban::BanManager;
 use crate::cache::manager::CacheManager;
 use crate::error::{Error, Result};
 use crate::metrics::MetricsInterface;
 use crate::options::ServerOptions;
 use crate::protocol::constants::CLIENT_EVENT_PREFIX;
 use crate::protocol::messages::{MessageData, PusherMessage};
+use crate::quota::memory_store::MemoryUsageStore;
+use crate::quota::{QuotaKind, QuotaManager};
 use crate::rate_limiter::RateLimiter;
+use crate::rate_limiter::middleware::UntrustedClientAddress;
 use crate::watchlist::WatchlistManager;
 use crate::webhook::integration::WebhookIntegration;
 use crate::websocket::SocketId;
 
 use crate::adapter::handler::types::{ClientEventRequest, SignInRequest, SubscriptionRequest};
+use axum::http::HeaderMap;
 use dashmap::DashMap;
 use fastwebsockets::{FragmentCollectorRead, Frame, OpCode, WebSocketWrite, upgrade};
 use hyper::upgrade::Upgraded;
 use hyper_util::rt::TokioIo;
 use serde_json::Value;
+use std::net::IpAddr;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize};
 use tokio::io::WriteHalf;
 use tokio::sync::Mutex;
 use tracing::{debug, error, warn};
 
+/// Client event limiters of one socket, keyed by channel limit rule pattern
+type ChannelEventLimiters = DashMap<String, Arc<dyn RateLimiter + Send + Sync>>;
+
+#[derive(Clone)]
 pub struct ConnectionHandler {
     pub(crate) app_manager: Arc<dyn AppManager + Send + Sync>,
     pub(crate) connection_manager: Arc<Mutex<dyn ConnectionManager + Send + Sync>>,
@@ -45,11 +62,18 @@ pub struct ConnectionHandler {
     pub(crate) metrics: Option<Arc<Mutex<dyn MetricsInterface + Send + Sync>>>,
     webhook_integration: Option<Arc<WebhookIntegration>>,
     client_event_limiters: Arc<DashMap<SocketId, Arc<dyn RateLimiter + Send + Sync>>>,
+    /// Limiters for channels whose limit rule sets its own client event rate, keyed by
+    /// the rule's pattern
+    channel_event_limiters: Arc<DashMap<SocketId, ChannelEventLimiters>>,
     watchlist_manager: Arc<WatchlistManager>,
     server_options: Arc<ServerOptions>,
     cleanup_queue: Option<crate::cleanup::CleanupSender>,
     cleanup_consecutive_failures: Arc<AtomicUsize>,
     cleanup_circuit_breaker_opened_at: Arc<AtomicU64>,
+    draining: Arc<AtomicBool>,
+    quota_manager: Arc<QuotaManager>,
+    audit_log: Option<Arc<AuditLog>>,
+    ban_manager: Option<Arc<BanManager>>,
 }
 
 impl ConnectionHandler {
@@ -70,11 +94,16 @@ impl ConnectionHandler {
             metrics,
             webhook_integration,
             client_event_limiters: Arc::new(DashMap::new()),
+            channel_event_limiters: Arc::new(DashMap::new()),
             watchlist_manager: Arc::new(WatchlistManager::new()),
             server_options: Arc::new(server_options),
             cleanup_queue,
             cleanup_consecutive_failures: Arc::new(AtomicUsize::new(0)),
             cleanup_circuit_breaker_opened_at: Arc::new(AtomicU64::new(0)),
+            draining: Arc::new(AtomicBool::new(false)),
+            quota_manager: Arc::new(QuotaManager::new(Arc::new(MemoryUsageStore::new()), None)),
+            audit_log: None,
+            ban_manager: None,
         }
     }
 
@@ -82,6 +111,42 @@ impl ConnectionHandler {
         &self.app_manager
     }
 
+    /// Send the error and a close frame through a freshly upgraded socket that is
+    /// refused before it gets registered
+    async fn reject_socket(
+        socket_tx: &mut WebSocketWrite<WriteHalf<TokioIo<Upgraded>>>,
+        error: Error,
+    ) -> Error {
+        use fastwebsockets::Payload;
+
+        let error_message = PusherMessage::error(error.close_code(), error.to_string(), None);
+        if let Ok(payload_str) = serde_json::to_string(&error_message) {
+            let payload = Payload::from(payload_str.as_bytes());
+            if let Err(e) = socket_tx.write_frame(Frame::text(payload)).await {
+                warn!("Failed to send rejection message: {}", e);
+            }
+        } else {
+            warn!("Failed to serialize rejection message");
+        }
+
+        if let Err(e) = socket_tx
+            .write_frame(Frame::close(
+                error.close_code(),
+                error.to_string().as_bytes(),
+            ))
+            .await
+        {
+            warn!("Failed to send rejection close frame: {}", e);
+        }
+
+        // Ensure frames are flushed
+        if let Err(e) = socket_tx.flush().await {
+            warn!("Failed to flush WebSocket frames during rejection: {}", e);
+        }
+
+        error
+    }
+
     pub fn server_options(&self) -> &ServerOptions {
         &self.server_options
     }
@@ -95,7 +160,12 @@ impl ConnectionHandler {
         fut: upgrade::UpgradeFut,
         app_key: String,
         origin: Option<String>,
+        client_ip: std::result::Result<Option<IpAddr>, UntrustedClientAddress>,
+        headers: HeaderMap,
     ) -> Result<()> {
+        // Without trusted proxy headers, only the IP filter rejects; bans skip the address
+        let trusted_ip = client_ip.as_ref().ok().copied().flatten();
+
         // Early validation and setup
         let app_config = match self.validate_and_get_app(&app_key).await {
             Ok(app) => app,
@@ -142,47 +212,41 @@ impl ConnectionHandler {
                     let metrics_locked = metrics.lock().await;
                     metrics_locked.mark_connection_error(&app_config.id, "origin_not_allowed");
                 }
-
-                // Send error message directly through the raw WebSocket before closing
-                use fastwebsockets::{Frame, Payload};
-
-                // Create and send the error message
-                let error_message = PusherMessage::error(
-                    Error::OriginNotAllowed.close_code(),
-                    Error::OriginNotAllowed.to_string(),
-                    None,
+                self.audit(
+                    AuditEvent::new(AuditEventKind::OriginRejected, Some(&app_config.id))
+                        .ip(trusted_ip)
+                        .detail("origin", origin_str),
                 );
 
-                if let Ok(payload_str) = serde_json::to_string(&error_message) {
-                    let payload = Payload::from(payload_str.as_bytes());
-                    if let Err(e) = socket_tx.write_frame(Frame::text(payload)).await {
-                        warn!("Failed to send origin rejection message: {}", e);
-                    }
-                } else {
-                    warn!("Failed to serialize origin rejection message");
-                }
+                return Err(Self::reject_socket(&mut socket_tx, Error::OriginNotAllowed).await);
+            }
+        }
 
-                // Send close frame
-                if let Err(e) = socket_tx
-                    .write_frame(Frame::close(
-                        Error::OriginNotAllowed.close_code(),
-                        Error::OriginNotAllowed.to_string().as_bytes(),
-                    ))
-                    .await
-                {
-                    warn!("Failed to send origin rejection close frame: {}", e);
-                }
+        if let Some(ref ip_filter) = app_config.ip_filter
+            && !ip_filter.websocket.is_empty()
+            && !client_ip
+                .as_ref()
+                .is_ok_and(|ip| ip_filter.websocket.allows_client(*ip))
+        {
+            debug!(
+                "Rejecting connection to app {} from {:?}",
+                app_config.id, client_ip
+            );
+            if let Some(ref metrics) = self.metrics {
+                let metrics_locked = metrics.lock().await;
+                metrics_locked.mark_connection_error(&app_config.id, "ip_not_allowed");
+                metrics_locked.mark_ip_rejected(&app_config.id, "websocket");
+            }
 
-                // Ensure frames are flushed
-                if let Err(e) = socket_tx.flush().await {
-                    warn!(
-                        "Failed to flush WebSocket frames during origin rejection: {}",
-                        e
-                    );
-                }
+            return Err(Self::reject_socket(&mut socket_tx, Error::IpNotAllowed).await);
+        }
 
-                return Err(Error::OriginNotAllowed);
+        if let Err(e) = self.check_ip_ban(Some(&app_config.id), trusted_ip).await {
+            if let Some(ref metrics) = self.metrics {
+                let metrics_locked = metrics.lock().await;
+                metrics_locked.mark_connection_error(&app_config.id, "banned");
             }
+            return Err(Self::reject_socket(&mut socket_tx, e).await);
         }
 
         // Initialize socket with atomic quota check
@@ -190,6 +254,12 @@ impl ConnectionHandler {
         self.initialize_socket_with_quota_check(socket_id.clone(), socket_tx, &app_config)
             .await?;
 
+        // The authorizer identifies clients by the headers of their upgrade request
+        if app_config.authorizer.is_some() {
+            self.store_upgrade_headers(&socket_id, &app_config, capture_headers(&headers))
+                .await;
+        }
+
         // Setup rate limiting if needed
         self.setup_rate_limiting(&socket_id, &app_config).await?;
 
@@ -202,7 +272,7 @@ impl ConnectionHandler {
 
         // Main message loop
         let result = self
-            .run_message_loop(socket_rx, &socket_id, &app_config)
+            .run_message_loop(socket_rx, &socket_id, &app_config, trusted_ip)
             .await;
 
         // Cleanup
@@ -217,6 +287,10 @@ impl ConnectionHandler {
         socket_tx: WebSocketWrite<WriteHalf<TokioIo<Upgraded>>>,
         app_config: &App,
     ) -> Result<()> {
+        // Daily quota is checked outside the lock; it may need a round trip to Redis
+        self.check_quota(app_config, QuotaKind::ConnectionMinutes)
+            .await?;
+
         // True atomic operation: quota check and socket addition under single lock
         // This is the only way to prevent race conditions
         {
@@ -239,7 +313,6 @@ impl ConnectionHandler {
                     return Err(Error::OverConnectionQuota);
                 }
             }
-
             // Remove any existing connection with the same socket_id (should be rare)
             if let Some(conn) = connection_manager
                 .get_connection(&socket_id, &app_config.id)
@@ -309,6 +382,7 @@ impl ConnectionHandler {
         mut fragment_collector: FragmentCollectorRead<tokio::io::ReadHalf<TokioIo<Upgraded>>>,
         socket_id: &SocketId,
         app_config: &App,
+        client_ip: Option<IpAddr>,
     ) -> Result<()> {
         while let Ok(frame) = fragment_collector
             .read_frame(&mut |_| async { Ok::<_, fastwebsockets::WebSocketError>(()) })
@@ -326,6 +400,9 @@ impl ConnectionHandler {
                         .await
                     {
                         error!("Message handling error for socket {}: {}", socket_id, e);
+                        let e = self
+                            .escalate_violation(socket_id, app_config, client_ip, e)
+                            .await;
                         if e.is_fatal() {
                             self.handle_fatal_error(socket_id, app_config, &e).await?;
                             break;
@@ -407,8 +484,13 @@ impl ConnectionHandler {
 
         // Handle rate limiting for client events
         if event_name.starts_with(CLIENT_EVENT_PREFIX) {
-            self.check_client_event_rate_limit(socket_id, &app_config, event_name)
-                .await?;
+            self.check_client_event_rate_limit(
+                socket_id,
+                &app_config,
+                event_name,
+                message.channel.as_deref(),
+            )
+            .await?;
         }
 
         // Route message to appropriate handler
@@ -522,6 +604,7 @@ impl ConnectionHandler {
     async fn cleanup_socket(&self, socket_id: &SocketId, app_config: &App) {
         // Remove rate limiter
         self.client_event_limiters.remove(socket_id);
+        self.channel_event_limiters.remove(socket_id);
 
         // Clear timeouts
         if let Err(e) = self.clear_activity_timeout(&app_config.id, socket_id).await {
@@ -0,0 +1,28 @@
+// src/adapter/handler/quota_management.rs
+use super::ConnectionHandler;
+use crate::app::config::App;
+use crate::error::Result;
+use crate::quota::{QuotaKind, QuotaManager};
+use std::sync::Arc;
+
+impl ConnectionHandler {
+    /// Track and enforce app quotas with the given manager instead of the default
+    /// in-memory one
+    pub fn with_quota_manager(mut self, quota_manager: Arc<QuotaManager>) -> Self {
+        self.quota_manager = quota_manager;
+        self
+    }
+
+    pub fn quota_manager(&self) -> &Arc<QuotaManager> {
+        &self.quota_manager
+    }
+
+    /// Reject the operation if the app has used up today's quota of `kind`
+    pub async fn check_quota(&self, app_config: &App, kind: QuotaKind) -> Result<()> {
+        self.quota_manager.check(app_config, kind).await
+    }
+
+    pub(crate) async fn record_quota_usage(&self, app_config: &App, kind: QuotaKind, amount: u64) {
+        self.quota_manager.record(app_config, kind, amount).await;
+    }
+}
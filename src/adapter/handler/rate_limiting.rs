@@ -1,7 +1,9 @@
 // src/adapter/handler/rate_limiting.rs
 use super::ConnectionHandler;
+use crate::app::channel_limits::ChannelLimits;
 use crate::app::config::App;
 use crate::error::{Error, Result};
+use crate::rate_limiter::RateLimiter;
 use crate::rate_limiter::memory_limiter::MemoryRateLimiter;
 use crate::websocket::SocketId;
 use std::sync::Arc;
@@ -29,30 +31,34 @@ impl ConnectionHandler {
         socket_id: &SocketId,
         app_config: &App,
         event_name: &str,
+        channel: Option<&str>,
     ) -> Result<()> {
-        if let Some(limiter_arc) = self.client_event_limiters.get(socket_id) {
-            // Track rate limit check
-            if let Some(ref metrics) = self.metrics {
-                let metrics_locked = metrics.lock().await;
-                metrics_locked.mark_rate_limit_check(&app_config.id, "client_events");
+        // Channels with their own rate count against a separate limiter per rule
+        if let Some((pattern, limit)) =
+            channel.and_then(|c| ChannelLimits::resolve(app_config, c).client_events_rule())
+        {
+            if limit == 0 {
+                return Ok(());
             }
+            let limiter = self
+                .channel_event_limiters
+                .entry(socket_id.clone())
+                .or_default()
+                .entry(pattern.to_string())
+                .or_insert_with(|| Arc::new(MemoryRateLimiter::new(limit, 1)))
+                .clone();
+            return self
+                .apply_client_event_limiter(socket_id, app_config, event_name, limiter.as_ref())
+                .await;
+        }
 
-            let limiter = limiter_arc.value();
-            let limit_result = limiter.increment(socket_id.as_ref()).await?;
-
-            if !limit_result.allowed {
-                // Track rate limit trigger
-                if let Some(ref metrics) = self.metrics {
-                    let metrics_locked = metrics.lock().await;
-                    metrics_locked.mark_rate_limit_triggered(&app_config.id, "client_events");
-                }
-
-                warn!(
-                    "Client event rate limit exceeded for socket {}: event '{}'",
-                    socket_id, event_name
-                );
-                return Err(Error::ClientEventRateLimit);
-            }
+        let limiter = self
+            .client_event_limiters
+            .get(socket_id)
+            .map(|limiter| limiter.value().clone());
+        if let Some(limiter) = limiter {
+            self.apply_client_event_limiter(socket_id, app_config, event_name, limiter.as_ref())
+                .await?;
         } else if app_config.max_client_events_per_second > 0 {
             warn!(
                 "Client event rate limiter not found for socket {} though app config expects one",
@@ -63,4 +69,36 @@ impl ConnectionHandler {
 
         Ok(())
     }
+
+    async fn apply_client_event_limiter(
+        &self,
+        socket_id: &SocketId,
+        app_config: &App,
+        event_name: &str,
+        limiter: &(dyn RateLimiter + Send + Sync),
+    ) -> Result<()> {
+        // Track rate limit check
+        if let Some(ref metrics) = self.metrics {
+            let metrics_locked = metrics.lock().await;
+            metrics_locked.mark_rate_limit_check(&app_config.id, "client_events");
+        }
+
+        let limit_result = limiter.increment(socket_id.as_ref()).await?;
+
+        if !limit_result.allowed {
+            // Track rate limit trigger
+            if let Some(ref metrics) = self.metrics {
+                let metrics_locked = metrics.lock().await;
+                metrics_locked.mark_rate_limit_triggered(&app_config.id, "client_events");
+            }
+
+            warn!(
+                "Client event rate limit exceeded for socket {}: event '{}'",
+                socket_id, event_name
+            );
+            return Err(Error::ClientEventRateLimit);
+        }
+
+        Ok(())
+    }
 }
@@ -36,7 +36,7 @@ impl SubscriptionRequest {
                     crate::error::Error::InvalidMessageFormat("Missing channel field".into())
                 })?;
                 let channel_data = if ChannelType::from_name(ch) == ChannelType::Presence {
-                    Some(channel_data.as_ref().unwrap().clone())
+                    channel_data.clone()
                 } else {
                     None
                 };
@@ -1,8 +1,10 @@
 // src/adapter/handler/validation.rs
 use super::ConnectionHandler;
 use super::types::*;
+use crate::app::channel_limits::ChannelLimits;
 use crate::app::config::App;
 use crate::channel::ChannelType;
+use crate::channel::encryption::is_encrypted_channel;
 use crate::error::{Error, Result};
 use crate::protocol::constants::*;
 use crate::utils;
@@ -20,12 +22,13 @@ impl ConnectionHandler {
 
         // Validate channel name
         utils::validate_channel_name(app_config, &request.channel).await?;
+        app_config.check_channel_policy(&request.channel)?;
 
         // Check if authentication is required and provided
         let requires_auth =
             request.channel.starts_with("presence-") || request.channel.starts_with("private-");
 
-        if requires_auth && request.auth.is_none() {
+        if requires_auth && request.auth.is_none() && app_config.authorizer.is_none() {
             return Err(Error::Auth(
                 "Authentication signature required for this channel".into(),
             ));
@@ -54,7 +57,8 @@ impl ConnectionHandler {
             .unwrap_or_default();
         let user_info_size_kb = utils::data_to_bytes_flexible(vec![user_info]) / 1024;
 
-        if let Some(max_size) = app_config.max_presence_member_size_in_kb
+        let limits = ChannelLimits::resolve(app_config, &request.channel);
+        if let Some(max_size) = limits.max_presence_member_size_in_kb
             && user_info_size_kb > max_size as usize
         {
             return Err(Error::Channel(format!(
@@ -63,7 +67,7 @@ impl ConnectionHandler {
         }
 
         // Check member count limit
-        if let Some(max_members) = app_config.max_presence_members_per_channel {
+        if let Some(max_members) = limits.max_presence_members_per_channel {
             let current_count = self
                 .get_channel_member_count(app_config, &request.channel)
                 .await?;
@@ -121,6 +125,13 @@ impl ConnectionHandler {
             )));
         }
 
+        // Clients of encrypted channels only ever see ciphertext the server produced
+        if is_encrypted_channel(&request.channel) {
+            return Err(Error::ClientEvent(
+                "Client events are not supported on encrypted channels".into(),
+            ));
+        }
+
         // Validate channel type
         let channel_type = ChannelType::from_name(&request.channel);
         if !matches!(channel_type, ChannelType::Private | ChannelType::Presence) {
@@ -130,7 +141,9 @@ impl ConnectionHandler {
         }
 
         // Validate payload size
-        if let Some(max_payload_kb) = app_config.max_event_payload_in_kb {
+        if let Some(max_payload_kb) =
+            ChannelLimits::resolve(app_config, &request.channel).max_event_payload_in_kb
+        {
             let payload_size = utils::data_to_bytes_flexible(vec![request.data.clone()]);
             if payload_size > (max_payload_kb as usize * 1024) {
                 return Err(Error::ClientEvent(format!(
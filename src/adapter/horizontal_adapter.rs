@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
@@ -12,6 +12,8 @@ use crate::channel::PresenceMemberInfo;
 use crate::error::{Error, Result};
 
 use crate::metrics::MetricsInterface;
+use crate::namespace::Namespace;
+use crate::options::ClusterRequestConfig;
 use crate::websocket::SocketId;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
@@ -48,6 +50,9 @@ pub enum RequestType {
 
     // State synchronization
     PresenceStateSync, // Send bulk presence state to a specific node
+
+    // App management
+    AppInvalidated, // An app changed; drop cached copies and re-check local connections
 }
 
 /// Request body for horizontal communication
@@ -66,6 +71,9 @@ pub struct RequestBody {
     pub timestamp: Option<u64>,               // For heartbeat timestamp
     pub dead_node_id: Option<String>,         // For dead node notifications
     pub target_node_id: Option<String>,       // Which node should process this request
+    /// Load figures carried by heartbeats
+    #[serde(default)]
+    pub stats: Option<NodeStats>,
 }
 
 /// Response body for horizontal requests
@@ -81,6 +89,12 @@ pub struct ResponseBody {
     pub exists: bool,
     pub channels: HashSet<String>,
     pub members_count: usize, // New field for ChannelMembersCount
+    /// Set on aggregated responses when not every remote node answered
+    #[serde(default)]
+    pub partial: bool,
+    /// Remote nodes that did not answer before the request returned
+    #[serde(default)]
+    pub missing_nodes: Vec<String>,
 }
 
 /// Message for broadcasting events
@@ -102,6 +116,61 @@ pub struct PendingRequest {
     pub(crate) app_id: String,
     pub(crate) responses: Vec<ResponseBody>,
     pub(crate) notify: Arc<Notify>,
+    /// Set once the caller has returned; the entry is kept only to time late responses
+    pub(crate) answered: bool,
+}
+
+/// Running average of how long a remote node takes to answer requests
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct NodeLatency {
+    pub average_ms: f64,
+    pub samples: u64,
+}
+
+impl NodeLatency {
+    /// Weight of the newest sample in the moving average
+    const SMOOTHING: f64 = 0.2;
+
+    fn record(&mut self, sample_ms: f64) {
+        self.average_ms = if self.samples == 0 {
+            sample_ms
+        } else {
+            self.average_ms * (1.0 - Self::SMOOTHING) + sample_ms * Self::SMOOTHING
+        };
+        self.samples += 1;
+    }
+}
+
+/// Nodes that failed to answer cluster requests made within a `collect_partial_results` scope
+#[derive(Debug, Clone, Default)]
+pub struct PartialResults {
+    pub partial: bool,
+    pub missing_nodes: BTreeSet<String>,
+}
+
+tokio::task_local! {
+    static PARTIAL_RESULTS: Arc<std::sync::Mutex<PartialResults>>;
+}
+
+/// Run `future` and report whether any aggregation request it made returned partial results
+pub async fn collect_partial_results<F: Future>(future: F) -> (F::Output, PartialResults) {
+    let collector = Arc::new(std::sync::Mutex::new(PartialResults::default()));
+    let output = PARTIAL_RESULTS.scope(collector.clone(), future).await;
+    let results = collector
+        .lock()
+        .map(|results| results.clone())
+        .unwrap_or_default();
+    (output, results)
+}
+
+/// Record a partial response in the enclosing `collect_partial_results` scope, if any
+pub(crate) fn record_partial_result(missing_nodes: &[String]) {
+    let _ = PARTIAL_RESULTS.try_with(|collector| {
+        if let Ok(mut results) = collector.lock() {
+            results.partial = true;
+            results.missing_nodes.extend(missing_nodes.iter().cloned());
+        }
+    });
 }
 
 /// Presence entry for cluster-wide presence tracking
@@ -119,6 +188,77 @@ pub struct PresenceEntry {
 /// HashMap<node_id, HashMap<channel, HashMap<socket_id, PresenceEntry>>>
 pub type ClusterPresenceRegistry = HashMap<String, HashMap<String, HashMap<String, PresenceEntry>>>;
 
+/// Local load figures a node reports along with each heartbeat
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeStats {
+    pub connections: usize,
+    pub channels: usize,
+}
+
+impl NodeStats {
+    /// Count sockets and occupied channels across all apps on this node
+    pub fn from_namespaces(namespaces: &DashMap<String, Arc<Namespace>>) -> Self {
+        namespaces
+            .iter()
+            .fold(Self::default(), |mut stats, namespace| {
+                stats.connections += namespace.sockets.len();
+                stats.channels += namespace.channels.len();
+                stats
+            })
+    }
+}
+
+/// One node of the cluster as seen from the local node
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterNodeInfo {
+    pub node_id: String,
+    pub is_local: bool,
+    pub alive: bool,
+    /// Time since the last heartbeat was received (None for the local node)
+    pub last_heartbeat_age_ms: Option<u64>,
+    /// Reported with the node's heartbeats; None until the first one arrives
+    pub connections: Option<usize>,
+    pub channels: Option<usize>,
+    pub presence_channels: usize,
+    pub presence_members: usize,
+    /// Average time this node takes to answer cluster requests
+    pub response_latency_ms: Option<f64>,
+    /// Average response time is above `adapter.requests.slow_node_threshold_ms`
+    pub slow: bool,
+}
+
+/// Cluster membership snapshot served by the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterTopology {
+    pub node_id: String,
+    pub cleanup_leader: Option<String>,
+    pub is_cleanup_leader: bool,
+    pub nodes: Vec<ClusterNodeInfo>,
+}
+
+impl ClusterTopology {
+    /// Topology for adapters without clustering: the local node is the whole cluster
+    pub fn single_node(node_id: String, stats: Option<NodeStats>) -> Self {
+        Self {
+            cleanup_leader: Some(node_id.clone()),
+            is_cleanup_leader: true,
+            nodes: vec![ClusterNodeInfo {
+                node_id: node_id.clone(),
+                is_local: true,
+                alive: true,
+                last_heartbeat_age_ms: None,
+                connections: stats.map(|s| s.connections),
+                channels: stats.map(|s| s.channels),
+                presence_channels: 0,
+                presence_members: 0,
+                response_latency_ms: None,
+                slow: false,
+            }],
+            node_id,
+        }
+    }
+}
+
 /// Event emitted when a node dies and orphaned presence members need cleanup
 #[derive(Debug, Clone)]
 pub struct DeadNodeEvent {
@@ -158,8 +298,20 @@ pub struct HorizontalAdapter {
     /// Track node heartbeats: HashMap<node_id, last_heartbeat_received_time>
     pub node_heartbeats: Arc<RwLock<HashMap<String, Instant>>>,
 
+    /// Latest stats reported in each remote node's heartbeat
+    pub node_stats: Arc<RwLock<HashMap<String, NodeStats>>>,
+
+    /// Quorum and slow-node settings for aggregation requests
+    pub request_config: ClusterRequestConfig,
+
+    /// Response latency of each remote node
+    pub node_latencies: Arc<RwLock<HashMap<String, NodeLatency>>>,
+
     /// Sequence counter for conflict resolution
     pub sequence_counter: Arc<AtomicU64>,
+
+    /// Receives app IDs invalidated by other nodes
+    pub app_invalidation_bus: Option<tokio::sync::mpsc::UnboundedSender<String>>,
 }
 
 impl Default for HorizontalAdapter {
@@ -179,7 +331,11 @@ impl HorizontalAdapter {
             metrics: None,
             cluster_presence_registry: Arc::new(RwLock::new(HashMap::new())),
             node_heartbeats: Arc::new(RwLock::new(HashMap::new())),
+            node_stats: Arc::new(RwLock::new(HashMap::new())),
+            request_config: ClusterRequestConfig::default(),
+            node_latencies: Arc::new(RwLock::new(HashMap::new())),
             sequence_counter: Arc::new(AtomicU64::new(0)),
+            app_invalidation_bus: None,
         }
     }
 
@@ -204,13 +360,15 @@ impl HorizontalAdapter {
                     let request_id = entry.key();
                     let request = entry.value();
                     if now.duration_since(request.start_time).as_millis() > timeout as u128 {
-                        expired_requests.push(request_id.clone());
+                        expired_requests.push((request_id.clone(), request.answered));
                     }
                 }
 
                 // Process expired requests
-                for request_id in expired_requests {
-                    warn!("{}", format!("Request {} expired", request_id));
+                for (request_id, answered) in expired_requests {
+                    if !answered {
+                        warn!("{}", format!("Request {} expired", request_id));
+                    }
                     pending_requests_clone.remove(&request_id);
                 }
             }
@@ -250,6 +408,8 @@ impl HorizontalAdapter {
             exists: false,
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         };
 
         // Process based on request type
@@ -408,6 +568,15 @@ impl HorizontalAdapter {
                         }
                     };
 
+                    drop(heartbeats);
+
+                    if let Some(stats) = request.stats {
+                        self.node_stats
+                            .write()
+                            .await
+                            .insert(request.node_id.clone(), stats);
+                    }
+
                     if is_new_node {
                         info!("New node detected: {}", request.node_id);
                         // Note: State sync will be handled by the caller
@@ -429,15 +598,21 @@ impl HorizontalAdapter {
                     // Leader already did full cleanup before sending this message
 
                     // Remove from heartbeat tracking
-                    {
-                        let mut heartbeats = self.node_heartbeats.write().await;
-                        heartbeats.remove(dead_node_id);
-                    }
+                    self.remove_dead_node(dead_node_id).await;
 
                     // Clean up local presence registry only
                     self.cleanup_local_presence_registry(dead_node_id).await;
                 }
             }
+            RequestType::AppInvalidated => {
+                debug!(
+                    "Received app invalidation for {} from node {}",
+                    request.app_id, request.node_id
+                );
+                if let Some(bus) = &self.app_invalidation_bus {
+                    let _ = bus.send(request.app_id.clone());
+                }
+            }
             RequestType::PresenceStateSync => {
                 if let Some(presence_data) = request.user_info {
                     // Deserialize the bulk presence data
@@ -509,21 +684,91 @@ impl HorizontalAdapter {
             metrics.mark_horizontal_adapter_response_received(&response.app_id);
         }
 
-        // Get the pending request and notify waiters
-        if let Some(mut request) = self.pending_requests.get_mut(&response.request_id) {
+        // Get the pending request and notify waiters. Answers after the timeout, or repeated
+        // answers of a node, do not count; nodes that time out are charged by the sender.
+        let timeout = Duration::from_millis(self.requests_timeout);
+        let latency = if let Some(mut request) = self.pending_requests.get_mut(&response.request_id)
+        {
+            let elapsed = request.start_time.elapsed();
+            if elapsed > timeout
+                || request
+                    .responses
+                    .iter()
+                    .any(|r| r.node_id == response.node_id)
+            {
+                return Ok(());
+            }
+            let latency = (response.node_id.clone(), elapsed);
+
             // Add response to the list
             request.responses.push(response);
 
             // Notify any waiting send_request calls that a new response has arrived
             request.notify.notify_one();
+            Some(latency)
+        } else {
+            None
+        };
+
+        if let Some((node_id, elapsed)) = latency {
+            self.record_node_latency(&node_id, elapsed).await;
         }
 
         Ok(())
     }
 
-    /// Send a request to other nodes and wait for responses
+    /// Fold one response time into a node's moving average
+    pub async fn record_node_latency(&self, node_id: &str, elapsed: Duration) {
+        if self.request_config.slow_node_threshold_ms == 0 {
+            return;
+        }
+        let sample_ms = elapsed.as_micros() as f64 / 1000.0;
+        let mut latencies = self.node_latencies.write().await;
+        latencies
+            .entry(node_id.to_string())
+            .or_insert(NodeLatency {
+                average_ms: 0.0,
+                samples: 0,
+            })
+            .record(sample_ms);
+    }
+
+    /// Whether a node's average response time is above the slow threshold
+    pub fn is_slow(&self, latency: Option<&NodeLatency>) -> bool {
+        let threshold = self.request_config.slow_node_threshold_ms;
+        threshold > 0 && latency.is_some_and(|l| l.average_ms > threshold as f64)
+    }
+
+    /// Remote nodes currently flagged as slow
+    pub async fn get_slow_nodes(&self) -> HashSet<String> {
+        let latencies = self.node_latencies.read().await;
+        latencies
+            .iter()
+            .filter(|(_, latency)| self.is_slow(Some(latency)))
+            .map(|(node_id, _)| node_id.clone())
+            .collect()
+    }
+
+    /// Number of remote answers a request needs before it may return.
+    /// `remote_nodes` is how many nodes are expected to answer and `slow_nodes` how many of
+    /// those are flagged as slow.
+    pub fn required_responses(&self, remote_nodes: usize, slow_nodes: usize) -> usize {
+        let eligible = if self.request_config.skip_slow_nodes {
+            remote_nodes.saturating_sub(slow_nodes)
+        } else {
+            remote_nodes
+        };
+        let quorum_ratio = self.request_config.quorum_ratio.clamp(0.0, 1.0);
+        ((eligible as f64 * quorum_ratio).ceil() as usize).min(eligible)
+    }
+
+    /// Send a request to other nodes and wait for enough of them to answer.
+    ///
+    /// Publishing the request is left to the transport; `HorizontalAdapterBase` publishes
+    /// it between [`Self::register_request`] and [`Self::await_quorum`].
+    /// `expected_node_count` includes this node.
     pub async fn send_request(
-        &mut self,
+        &self,
         app_id: &str,
         request_type: RequestType,
         channel: Option<&str>,
@@ -531,15 +776,34 @@ impl HorizontalAdapter {
         user_id: Option<&str>,
         expected_node_count: usize,
     ) -> Result<ResponseBody> {
-        let request_id = Uuid::new_v4().to_string();
-        let start = Instant::now();
+        let request = self
+            .register_request(
+                app_id,
+                request_type,
+                channel,
+                socket_id,
+                user_id,
+                expected_node_count,
+            )
+            .await;
+        Self::await_quorum(|| std::future::ready(self), request, expected_node_count).await
+    }
 
-        // Create the request
-        let _request = RequestBody {
-            request_id: request_id.clone(),
+    /// Track a new request so answers to it are collected, and return it for publishing
+    pub async fn register_request(
+        &self,
+        app_id: &str,
+        request_type: RequestType,
+        channel: Option<&str>,
+        socket_id: Option<&str>,
+        user_id: Option<&str>,
+        expected_node_count: usize,
+    ) -> RequestBody {
+        let request = RequestBody {
+            request_id: Uuid::new_v4().to_string(),
             node_id: self.node_id.clone(),
             app_id: app_id.to_string(),
-            request_type: request_type.clone(),
+            request_type,
             channel: channel.map(String::from),
             socket_id: socket_id.map(String::from),
             user_id: user_id.map(String::from),
@@ -548,49 +812,53 @@ impl HorizontalAdapter {
             timestamp: None,
             dead_node_id: None,
             target_node_id: None,
+            stats: None,
         };
 
-        // Add to pending requests with proper initialization
         self.pending_requests.insert(
-            request_id.clone(),
+            request.request_id.clone(),
             PendingRequest {
-                start_time: start,
+                start_time: Instant::now(),
                 app_id: app_id.to_string(),
                 responses: Vec::with_capacity(expected_node_count.saturating_sub(1)),
                 notify: Arc::new(Notify::new()),
+                answered: false,
             },
         );
 
-        // Track sent request in metrics
         if let Some(metrics_ref) = &self.metrics {
             let metrics = metrics_ref.lock().await;
             metrics.mark_horizontal_adapter_request_sent(app_id);
         }
 
-        // ✅ IMPORTANT: Broadcasting is handled by the adapter implementation
-        // For Redis: RedisAdapter publishes to request_channel in its listeners
-        // For NATS: NatsAdapter would publish to NATS subjects
-        // For HTTP: HttpAdapter would POST to other nodes
-        debug!(
-            "Request {} created for type {:?} on app {} - broadcasting handled by adapter",
-            request_id, request_type, app_id
-        );
+        request
+    }
 
-        // Wait for responses with proper timeout handling
-        let timeout_duration = Duration::from_millis(self.requests_timeout);
+    /// Wait until a quorum of the remote nodes answered a registered request, or the
+    /// request timeout passed, and aggregate the answers.
+    ///
+    /// `adapter` grants access to the adapter for short stretches only, so responses can
+    /// be processed while this waits, e.g. `|| horizontal.lock()`.
+    pub async fn await_quorum<F, Fut, G>(
+        adapter: F,
+        request: RequestBody,
+        expected_node_count: usize,
+    ) -> Result<ResponseBody>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = G>,
+        G: std::ops::Deref<Target = Self>,
+    {
+        let request_id = request.request_id.clone();
+        let app_id = request.app_id.clone();
         let max_expected_responses = expected_node_count.saturating_sub(1);
 
-        // If we don't expect any responses (single node), return immediately
         if max_expected_responses == 0 {
-            debug!(
-                "Single node deployment, no responses expected for request {}",
-                request_id
-            );
-            self.pending_requests.remove(&request_id);
+            adapter().await.pending_requests.remove(&request_id);
             return Ok(ResponseBody {
-                request_id: request_id.clone(),
-                node_id: self.node_id.clone(),
-                app_id: app_id.to_string(),
+                request_id,
+                node_id: request.node_id,
+                app_id,
                 members: HashMap::new(),
                 socket_ids: Vec::new(),
                 sockets_count: 0,
@@ -598,94 +866,156 @@ impl HorizontalAdapter {
                 exists: false,
                 channels: HashSet::new(),
                 members_count: 0,
+                partial: false,
+                missing_nodes: Vec::new(),
             });
         }
 
-        // Improved waiting logic
-        let check_interval = Duration::from_millis(50);
-        let mut checks = 0;
-        let max_checks = (timeout_duration.as_millis() / check_interval.as_millis()) as usize;
-
-        let responses = loop {
-            if checks >= max_checks {
-                let current_responses = self
-                    .pending_requests
-                    .get(&request_id)
-                    .map(|r| r.responses.len())
-                    .unwrap_or(0);
-
-                warn!(
-                    "Request {} timed out after {}ms, got {} responses out of {} expected",
-                    request_id,
-                    start.elapsed().as_millis(),
-                    current_responses,
-                    max_expected_responses
-                );
-                break self
-                    .pending_requests
-                    .remove(&request_id)
-                    .map(|(_, req)| req.responses)
-                    .unwrap_or_default();
-            }
-
-            if let Some(pending_request) = self.pending_requests.get(&request_id) {
-                if pending_request.responses.len() >= max_expected_responses {
+        // Work out how many answers are enough, leaving slow nodes out if configured
+        let start = Instant::now();
+        let (notify, known_nodes, required_responses, timeout_duration) = {
+            let horizontal = adapter().await;
+            let notify = horizontal
+                .pending_requests
+                .get(&request_id)
+                .map(|req| req.notify.clone())
+                .ok_or_else(|| {
+                    Error::Other(format!(
+                        "Request {request_id} not found in pending requests"
+                    ))
+                })?;
+            let known_nodes: Vec<String> = horizontal
+                .node_heartbeats
+                .read()
+                .await
+                .keys()
+                .cloned()
+                .collect();
+            let slow_nodes = horizontal.get_slow_nodes().await;
+            let slow_count = known_nodes
+                .iter()
+                .filter(|node| slow_nodes.contains(*node))
+                .count();
+            (
+                notify,
+                known_nodes,
+                horizontal.required_responses(max_expected_responses, slow_count),
+                Duration::from_millis(horizontal.requests_timeout),
+            )
+        };
+        let deadline = tokio::time::Instant::now() + timeout_duration;
+
+        let (responses, timed_out) = loop {
+            let notified = notify.notified();
+            {
+                let horizontal = adapter().await;
+                let Some(pending_request) = horizontal.pending_requests.get(&request_id) else {
+                    return Err(Error::Other(format!(
+                        "Request {request_id} was removed unexpectedly"
+                    )));
+                };
+                if pending_request.responses.len() >= required_responses {
                     debug!(
-                        "Request {} completed successfully with {}/{} responses in {}ms",
+                        "Request {} completed with {}/{} responses (quorum {}) in {}ms",
                         request_id,
                         pending_request.responses.len(),
                         max_expected_responses,
+                        required_responses,
+                        start.elapsed().as_millis()
+                    );
+                    break (pending_request.responses.clone(), false);
+                }
+            }
+
+            tokio::select! {
+                _ = notified => {}
+                _ = tokio::time::sleep_until(deadline) => {
+                    warn!(
+                        "Request {} timed out after {}ms",
+                        request_id,
                         start.elapsed().as_millis()
                     );
-                    break self
+                    let horizontal = adapter().await;
+                    let responses = horizontal
                         .pending_requests
-                        .remove(&request_id)
-                        .map(|(_, req)| req.responses)
+                        .get(&request_id)
+                        .map(|pending_request| pending_request.responses.clone())
                         .unwrap_or_default();
+                    break (responses, true);
                 }
-            } else {
-                return Err(Error::Other(format!(
-                    "Request {request_id} was removed unexpectedly (possibly by cleanup task)"
-                )));
             }
-
-            tokio::time::sleep(check_interval).await;
-            checks += 1;
         };
 
-        // Use the aggregation method
-        let combined_response = self.aggregate_responses(
+        let responded: HashSet<&str> = responses.iter().map(|r| r.node_id.as_str()).collect();
+        let mut missing_nodes: Vec<String> = known_nodes
+            .iter()
+            .filter(|node| !responded.contains(node.as_str()))
+            .cloned()
+            .collect();
+        missing_nodes.sort();
+        let partial = responses.len() < max_expected_responses;
+
+        let horizontal = adapter().await;
+        let mut combined_response = horizontal.aggregate_responses(
             request_id.clone(),
-            self.node_id.clone(),
-            app_id.to_string(),
-            &request_type,
+            request.node_id,
+            app_id.clone(),
+            &request.request_type,
             responses,
         );
 
         // Validate the aggregated response
-        if let Err(e) = self.validate_aggregated_response(&combined_response, &request_type) {
+        if let Err(e) =
+            horizontal.validate_aggregated_response(&combined_response, &request.request_type)
+        {
             warn!(
                 "Response validation failed for request {}: {}",
                 request_id, e
             );
         }
 
-        // Track metrics
-        if let Some(metrics_ref) = &self.metrics {
+        if partial {
+            debug!(
+                "Request {} returned partial results, missing nodes: {:?}",
+                request_id, missing_nodes
+            );
+            record_partial_result(&missing_nodes);
+            combined_response.partial = true;
+            combined_response.missing_nodes = missing_nodes;
+        }
+
+        // Clean up the pending request after aggregation is complete. When nodes are still
+        // outstanding the entry stays until the cleanup task expires it so answers arriving
+        // within the timeout still update the latency figures. Nodes that never answered in
+        // time are charged the full wait once, here.
+        if timed_out {
+            for node_id in &combined_response.missing_nodes {
+                horizontal
+                    .record_node_latency(node_id, start.elapsed())
+                    .await;
+            }
+        }
+        if partial && !timed_out {
+            if let Some(mut pending_request) = horizontal.pending_requests.get_mut(&request_id) {
+                pending_request.answered = true;
+            }
+        } else {
+            horizontal.pending_requests.remove(&request_id);
+        }
+
+        if let Some(metrics_ref) = &horizontal.metrics {
             let metrics = metrics_ref.lock().await;
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.0; // Convert to milliseconds with 3 decimal places
-
-            metrics.track_horizontal_adapter_resolve_time(app_id, duration_ms);
+            metrics.track_horizontal_adapter_resolve_time(&app_id, duration_ms);
 
             let resolved = combined_response.sockets_count > 0
                 || !combined_response.members.is_empty()
                 || combined_response.exists
                 || !combined_response.channels.is_empty()
                 || combined_response.members_count > 0
-                || !combined_response.channels_with_sockets_count.is_empty()
-                || max_expected_responses == 0;
+                || !combined_response.channels_with_sockets_count.is_empty();
 
-            metrics.track_horizontal_adapter_resolved_promises(app_id, resolved);
+            metrics.track_horizontal_adapter_resolved_promises(&app_id, resolved);
         }
 
         Ok(combined_response)
@@ -710,6 +1040,8 @@ impl HorizontalAdapter {
             exists: false,
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         };
 
         if responses.is_empty() {
@@ -802,6 +1134,9 @@ impl HorizontalAdapter {
                 RequestType::PresenceStateSync => {
                     // These are broadcast-only requests, no response aggregation needed
                 }
+                RequestType::AppInvalidated => {
+                    // These are broadcast-only requests, no response aggregation needed
+                }
             }
         }
 
@@ -933,6 +1268,8 @@ impl HorizontalAdapter {
     pub async fn remove_dead_node(&self, dead_node_id: &str) {
         let mut heartbeats = self.node_heartbeats.write().await;
         heartbeats.remove(dead_node_id);
+        self.node_stats.write().await.remove(dead_node_id);
+        self.node_latencies.write().await.remove(dead_node_id);
     }
 
     /// Clean up local presence registry for a dead node (followers only)
@@ -956,15 +1293,18 @@ impl HorizontalAdapter {
     /// Determine if this node should be the cleanup leader
     /// Excludes dead nodes from the election pool
     pub async fn is_cleanup_leader(&self, dead_nodes: &[String]) -> bool {
-        let heartbeats = self.node_heartbeats.read().await;
-        let mut alive_nodes: Vec<String> = heartbeats.keys().cloned().collect();
-        alive_nodes.push(self.node_id.clone()); // Include ourselves
-
-        // Remove dead nodes from election pool
-        alive_nodes.retain(|node| !dead_nodes.contains(node));
+        self.cleanup_leader(dead_nodes).await.as_ref() == Some(&self.node_id)
+    }
 
-        alive_nodes.sort();
-        alive_nodes.first() == Some(&self.node_id)
+    /// The node elected as cleanup leader: the lowest alive node ID, ourselves included
+    pub async fn cleanup_leader(&self, dead_nodes: &[String]) -> Option<String> {
+        let heartbeats = self.node_heartbeats.read().await;
+        heartbeats
+            .keys()
+            .chain(std::iter::once(&self.node_id)) // Include ourselves
+            .filter(|node| !dead_nodes.contains(node)) // Remove dead nodes from election pool
+            .min()
+            .cloned()
     }
 
     /// Handle cleanup for a dead node (called only by elected leader)
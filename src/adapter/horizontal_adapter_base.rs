@@ -1,12 +1,13 @@
 use std::any::Any;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use crate::adapter::connection_manager::{ConnectionManager, HorizontalAdapterInterface};
 use crate::adapter::horizontal_adapter::{
-    BroadcastMessage, DeadNodeEvent, HorizontalAdapter, OrphanedMember, PendingRequest,
-    RequestBody, RequestType, ResponseBody, current_timestamp, generate_request_id,
+    BroadcastMessage, ClusterNodeInfo, ClusterTopology, DeadNodeEvent, HorizontalAdapter,
+    NodeStats, OrphanedMember, RequestBody, RequestType, ResponseBody, current_timestamp,
+    generate_request_id,
 };
 use crate::adapter::horizontal_transport::{
     HorizontalTransport, TransportConfig, TransportHandlers,
@@ -16,7 +17,7 @@ use crate::channel::PresenceMemberInfo;
 use crate::error::{Error, Result};
 use crate::metrics::MetricsInterface;
 use crate::namespace::Namespace;
-use crate::options::ClusterHealthConfig;
+use crate::options::{ClusterHealthConfig, ClusterRequestConfig};
 use crate::protocol::messages::PusherMessage;
 use crate::websocket::{SocketId, WebSocketRef};
 use async_trait::async_trait;
@@ -25,9 +26,8 @@ use fastwebsockets::WebSocketWrite;
 use hyper::upgrade::Upgraded;
 use hyper_util::rt::TokioIo;
 use tokio::io::WriteHalf;
-use tokio::sync::{Mutex, Notify};
+use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
-use uuid::Uuid;
 
 /// Generic base adapter that handles all common horizontal scaling logic
 pub struct HorizontalAdapterBase<T: HorizontalTransport> {
@@ -154,6 +154,12 @@ where
         Ok(())
     }
 
+    /// Apply quorum and slow-node settings for aggregation requests
+    pub async fn set_request_config(&mut self, request_config: &ClusterRequestConfig) {
+        let mut horizontal = self.horizontal.lock().await;
+        horizontal.request_config = request_config.clone();
+    }
+
     /// Enhanced send_request that properly integrates with HorizontalAdapter
     pub async fn send_request(
         &self,
@@ -165,179 +171,27 @@ where
     ) -> Result<ResponseBody> {
         let node_count = self.transport.get_node_count().await?;
 
-        // Create the request
-        let request_id = Uuid::new_v4().to_string();
-        let node_id = {
-            let horizontal = self.horizontal.lock().await;
-            horizontal.node_id.clone()
-        };
-
-        let request = RequestBody {
-            request_id: request_id.clone(),
-            node_id,
-            app_id: app_id.to_string(),
-            request_type: request_type.clone(),
-            channel: channel.map(String::from),
-            socket_id: socket_id.map(String::from),
-            user_id: user_id.map(String::from),
-            // Cluster presence fields (not used for regular requests)
-            user_info: None,
-            timestamp: None,
-            dead_node_id: None,
-            target_node_id: None,
-        };
-
-        // Add to pending requests
-        {
-            let horizontal = self.horizontal.lock().await;
-            horizontal.pending_requests.insert(
-                request_id.clone(),
-                PendingRequest {
-                    start_time: Instant::now(),
-                    app_id: app_id.to_string(),
-                    responses: Vec::with_capacity(node_count.saturating_sub(1)),
-                    notify: Arc::new(Notify::new()),
-                },
-            );
-
-            if let Some(metrics_ref) = &horizontal.metrics {
-                let metrics = metrics_ref.lock().await;
-                metrics.mark_horizontal_adapter_request_sent(app_id);
-            }
-        }
+        let request = self
+            .horizontal
+            .lock()
+            .await
+            .register_request(
+                app_id,
+                request_type,
+                channel,
+                socket_id,
+                user_id,
+                node_count,
+            )
+            .await;
 
         // Broadcast the request via transport (skip if single node)
         if !self.should_skip_horizontal_communication().await {
             self.transport.publish_request(&request).await?;
         }
 
-        // Wait for responses
-        let timeout_duration = Duration::from_millis(self.config.request_timeout_ms());
-        let max_expected_responses = node_count.saturating_sub(1);
-
-        if max_expected_responses == 0 {
-            self.horizontal
-                .lock()
-                .await
-                .pending_requests
-                .remove(&request_id);
-            return Ok(ResponseBody {
-                request_id,
-                node_id: request.node_id,
-                app_id: app_id.to_string(),
-                members: HashMap::new(),
-                socket_ids: Vec::new(),
-                sockets_count: 0,
-                channels_with_sockets_count: HashMap::new(),
-                exists: false,
-                channels: HashSet::new(),
-                members_count: 0,
-            });
-        }
-
-        // Wait for responses using event-driven approach
-        let start = Instant::now();
-        let notify = {
-            let horizontal = self.horizontal.lock().await;
-            horizontal
-                .pending_requests
-                .get(&request_id)
-                .map(|req| req.notify.clone())
-                .ok_or_else(|| {
-                    Error::Other(format!(
-                        "Request {request_id} not found in pending requests"
-                    ))
-                })?
-        };
-
-        let responses = loop {
-            // Wait for notification or timeout
-            let result = tokio::select! {
-                _ = notify.notified() => {
-                    // Check if we have enough responses
-                    let horizontal = self.horizontal.lock().await;
-                    if let Some(pending_request) = horizontal.pending_requests.get(&request_id) {
-                        if pending_request.responses.len() >= max_expected_responses {
-                            debug!(
-                                "Request {} completed with {}/{} responses in {}ms",
-                                request_id,
-                                pending_request.responses.len(),
-                                max_expected_responses,
-                                start.elapsed().as_millis()
-                            );
-                            // Extract responses without removing the entry yet to avoid race condition
-                            let responses = pending_request.responses.clone();
-                            Some(responses)
-                        } else {
-                            None // Continue waiting
-                        }
-                    } else {
-                        return Err(Error::Other(format!(
-                            "Request {request_id} was removed unexpectedly"
-                        )));
-                    }
-                }
-                _ = tokio::time::sleep(timeout_duration) => {
-                    // Timeout occurred
-                    warn!(
-                        "Request {} timed out after {}ms",
-                        request_id,
-                        start.elapsed().as_millis()
-                    );
-                    let horizontal = self.horizontal.lock().await;
-                    let responses = if let Some(pending_request) = horizontal.pending_requests.get(&request_id) {
-                        pending_request.responses.clone()
-                    } else {
-                        Vec::new()
-                    };
-                    Some(responses)
-                }
-            };
-
-            if let Some(responses) = result {
-                break responses;
-            }
-            // If result is None, continue waiting (notification came but not enough responses yet)
-        };
-
-        // Aggregate responses first, then clean up to prevent race condition
-        let combined_response = {
-            let horizontal = self.horizontal.lock().await;
-            horizontal.aggregate_responses(
-                request_id.clone(),
-                request.node_id,
-                app_id.to_string(),
-                &request_type,
-                responses,
-            )
-        }; // horizontal lock released here
-
-        // Clean up the pending request after aggregation is complete
-        {
-            let horizontal = self.horizontal.lock().await;
-            horizontal.pending_requests.remove(&request_id);
-        }
-
-        // Track metrics
-        {
-            let horizontal = self.horizontal.lock().await;
-            if let Some(metrics_ref) = &horizontal.metrics {
-                let metrics = metrics_ref.lock().await;
-                let duration_ms = start.elapsed().as_micros() as f64 / 1000.0; // Convert to milliseconds with 3 decimal places
-                metrics.track_horizontal_adapter_resolve_time(app_id, duration_ms);
-
-                let resolved = combined_response.sockets_count > 0
-                    || !combined_response.members.is_empty()
-                    || combined_response.exists
-                    || !combined_response.channels.is_empty()
-                    || combined_response.members_count > 0
-                    || !combined_response.channels_with_sockets_count.is_empty();
-
-                metrics.track_horizontal_adapter_resolved_promises(app_id, resolved);
-            }
-        } // horizontal and metrics locks released here
-
-        Ok(combined_response)
+        // The lock is only held in short stretches so responses can come in meanwhile
+        HorizontalAdapter::await_quorum(|| self.horizontal.lock(), request, node_count).await
     }
 
     pub async fn start_listeners(&self) -> Result<()> {
@@ -514,6 +368,7 @@ where
     /// Start heartbeat broadcasting loop
     async fn start_heartbeat_loop(&self) {
         let transport = self.transport.clone();
+        let horizontal = self.horizontal.clone();
         let node_id = self.node_id.clone();
         let heartbeat_interval_ms = self.heartbeat_interval_ms;
 
@@ -523,6 +378,17 @@ where
             loop {
                 interval.tick().await;
 
+                // Piggyback local load on the heartbeat for the admin topology view
+                let stats = {
+                    let mut horizontal_guard = horizontal.lock().await;
+                    horizontal_guard
+                        .local_adapter
+                        .get_namespaces()
+                        .await
+                        .ok()
+                        .map(|namespaces| NodeStats::from_namespaces(&namespaces))
+                };
+
                 let heartbeat_request = RequestBody {
                     request_id: generate_request_id(),
                     node_id: node_id.clone(),
@@ -535,6 +401,7 @@ where
                     timestamp: Some(current_timestamp()),
                     dead_node_id: None,
                     target_node_id: None,
+                    stats,
                 };
 
                 if let Err(e) = transport.publish_request(&heartbeat_request).await {
@@ -660,6 +527,7 @@ where
                                     timestamp: Some(current_timestamp()),
                                     dead_node_id: Some(dead_node_id.clone()),
                                     target_node_id: None,
+                                    stats: None,
                                 };
 
                                 if let Err(e) = transport.publish_request(&dead_node_request).await
@@ -696,6 +564,86 @@ where
         let heartbeats = horizontal.node_heartbeats.read().await;
         heartbeats.clone()
     }
+
+    /// Build a snapshot of cluster membership from heartbeats and the presence registry
+    pub async fn get_cluster_topology(&self) -> ClusterTopology {
+        let mut horizontal = self.horizontal.lock().await;
+        let local_stats = horizontal
+            .local_adapter
+            .get_namespaces()
+            .await
+            .ok()
+            .map(|namespaces| NodeStats::from_namespaces(&namespaces));
+        let dead_nodes = horizontal.get_dead_nodes(self.node_timeout_ms).await;
+        let cleanup_leader = horizontal.cleanup_leader(&dead_nodes).await;
+        let heartbeats = horizontal.node_heartbeats.read().await.clone();
+        let node_stats = horizontal.node_stats.read().await.clone();
+        let latencies = horizontal.node_latencies.read().await.clone();
+        let registry = horizontal.cluster_presence_registry.read().await;
+
+        let presence_sizes = |node_id: &str| {
+            registry
+                .get(node_id)
+                .map(|channels| {
+                    (
+                        channels.len(),
+                        channels.values().map(|sockets| sockets.len()).sum(),
+                    )
+                })
+                .unwrap_or((0, 0))
+        };
+
+        let (presence_channels, presence_members) = presence_sizes(&self.node_id);
+        let mut nodes = vec![ClusterNodeInfo {
+            node_id: self.node_id.clone(),
+            is_local: true,
+            alive: true,
+            last_heartbeat_age_ms: None,
+            connections: local_stats.map(|stats| stats.connections),
+            channels: local_stats.map(|stats| stats.channels),
+            presence_channels,
+            presence_members,
+            response_latency_ms: None,
+            slow: false,
+        }];
+
+        // Nodes known only through the presence registry are listed too, without heartbeat data
+        let mut remote_ids: Vec<&String> = heartbeats
+            .keys()
+            .chain(registry.keys())
+            .filter(|node_id| **node_id != self.node_id)
+            .collect();
+        remote_ids.sort();
+        remote_ids.dedup();
+
+        let now = Instant::now();
+        for node_id in remote_ids {
+            let (presence_channels, presence_members) = presence_sizes(node_id);
+            let stats = node_stats.get(node_id);
+            let latency = latencies.get(node_id);
+            nodes.push(ClusterNodeInfo {
+                node_id: node_id.clone(),
+                is_local: false,
+                alive: heartbeats.contains_key(node_id) && !dead_nodes.contains(node_id),
+                last_heartbeat_age_ms: heartbeats
+                    .get(node_id)
+                    .map(|seen| now.duration_since(*seen).as_millis() as u64),
+                connections: stats.map(|stats| stats.connections),
+                channels: stats.map(|stats| stats.channels),
+                presence_channels,
+                presence_members,
+                response_latency_ms: latency.map(|latency| latency.average_ms),
+                slow: horizontal.is_slow(latency),
+            });
+        }
+
+        ClusterTopology {
+            node_id: self.node_id.clone(),
+            is_cleanup_leader: cleanup_leader.as_ref() == Some(&self.node_id),
+            cleanup_leader,
+            nodes,
+        }
+    }
 }
 
 #[async_trait]
@@ -1189,10 +1137,49 @@ where
         self.set_event_bus(event_sender);
         Some(event_receiver)
     }
+
+    async fn configure_app_invalidation_events(
+        &mut self,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<String>> {
+        let (event_sender, event_receiver) = tokio::sync::mpsc::unbounded_channel();
+        self.horizontal.lock().await.app_invalidation_bus = Some(event_sender);
+        Some(event_receiver)
+    }
 }
 
 #[async_trait]
-impl<T: HorizontalTransport> HorizontalAdapterInterface for HorizontalAdapterBase<T> {
+impl<T: HorizontalTransport + 'static> HorizontalAdapterInterface for HorizontalAdapterBase<T>
+where
+    T::Config: TransportConfig,
+{
+    async fn broadcast_app_invalidation(&self, app_id: &str) -> Result<()> {
+        let request = RequestBody {
+            request_id: crate::adapter::horizontal_adapter::generate_request_id(),
+            node_id: self.node_id.clone(),
+            app_id: app_id.to_string(),
+            request_type: RequestType::AppInvalidated,
+            channel: None,
+            socket_id: None,
+            user_id: None,
+            user_info: None,
+            timestamp: None,
+            dead_node_id: None,
+            target_node_id: None,
+            stats: None,
+        };
+
+        // Send without waiting for response (broadcast) - skip if single node
+        if !self.should_skip_horizontal_communication().await {
+            self.transport.publish_request(&request).await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn get_cluster_topology(&self) -> ClusterTopology {
+        HorizontalAdapterBase::get_cluster_topology(self).await
+    }
+
     /// Broadcast presence member joined to all nodes
     async fn broadcast_presence_join(
         &self,
@@ -1235,6 +1222,7 @@ impl<T: HorizontalTransport> HorizontalAdapterInterface for HorizontalAdapterBas
             timestamp: None,
             dead_node_id: None,
             target_node_id: None,
+            stats: None,
         };
 
         // Send without waiting for response (broadcast) - skip if single node
@@ -1279,6 +1267,7 @@ impl<T: HorizontalTransport> HorizontalAdapterInterface for HorizontalAdapterBas
             timestamp: None,
             dead_node_id: None,
             target_node_id: None,
+            stats: None,
         };
 
         // Send without waiting for response (broadcast) - skip if single node
@@ -1323,6 +1312,7 @@ async fn send_presence_state_to_node<T: HorizontalTransport>(
             app_id: "cluster".to_string(),
             request_type: RequestType::PresenceStateSync,
             target_node_id: Some(target_node_id.to_string()),
+            stats: None,
             user_info: Some(serialized_data), // Reuse this field for bulk data
             channel: None,
             socket_id: None,
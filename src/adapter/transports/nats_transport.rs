@@ -1,14 +1,32 @@
 use crate::adapter::horizontal_adapter::{BroadcastMessage, RequestBody, ResponseBody};
 use crate::adapter::horizontal_transport::{
-    HorizontalTransport, TransportConfig, TransportHandlers,
+    BoxFuture, HorizontalTransport, TransportConfig, TransportHandlers,
 };
 use crate::error::{Error, Result};
 use crate::options::NatsAdapterConfig;
+use async_nats::jetstream::{self, consumer, stream};
 use async_nats::{Client as NatsClient, ConnectOptions as NatsOptions, Subject};
 use async_trait::async_trait;
 use futures::StreamExt;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+/// How long the broadcast consumer of a node that stopped is kept by the server
+const BROADCAST_CONSUMER_INACTIVE_THRESHOLD: Duration = Duration::from_secs(86_400);
+
+/// JetStream stream and consumer names may not contain `.`, `*`, `>` or whitespace
+pub(crate) fn jetstream_name(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
 
 /// NATS transport implementation
 #[derive(Clone)]
@@ -18,6 +36,10 @@ pub struct NatsTransport {
     request_subject: String,
     response_subject: String,
     config: NatsAdapterConfig,
+    /// JetStream context and broadcast stream, present when `jetstream` is enabled
+    jetstream: Option<(jetstream::Context, stream::Stream)>,
+    /// Durable consumer this node reads broadcasts through, named after its process ID
+    broadcast_consumer_name: String,
 }
 
 impl TransportConfig for NatsAdapterConfig {
@@ -72,12 +94,41 @@ impl HorizontalTransport for NatsTransport {
         let request_subject = format!("{}.requests", config.prefix);
         let response_subject = format!("{}.responses", config.prefix);
 
+        let stream_name = jetstream_name(&format!("{}_broadcast", config.prefix));
+        let broadcast_consumer_name =
+            jetstream_name(&format!("{stream_name}_{}", config.process_id));
+        let jetstream = if config.jetstream {
+            let context = jetstream::new(client.clone());
+            let broadcast_stream = context
+                .get_or_create_stream(stream::Config {
+                    name: stream_name.clone(),
+                    subjects: vec![broadcast_subject.clone()],
+                    max_messages: config.stream_max_messages,
+                    max_age: Duration::from_secs(config.stream_max_age_seconds),
+                    discard: stream::DiscardPolicy::Old,
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| {
+                    Error::Internal(format!("Failed to create JetStream broadcast stream: {e}"))
+                })?;
+            info!(
+                "NATS transport using JetStream for broadcasts (stream: {}, max_messages: {})",
+                stream_name, config.stream_max_messages
+            );
+            Some((context, broadcast_stream))
+        } else {
+            None
+        };
+
         Ok(Self {
             client,
             broadcast_subject,
             request_subject,
             response_subject,
             config,
+            jetstream,
+            broadcast_consumer_name,
         })
     }
 
@@ -85,6 +136,18 @@ impl HorizontalTransport for NatsTransport {
         let message_data = serde_json::to_vec(message)
             .map_err(|e| Error::Other(format!("Failed to serialize broadcast message: {e}")))?;
 
+        if let Some((context, _)) = &self.jetstream {
+            // Wait for the server ack so the broadcast is known to be persisted
+            context
+                .publish(self.broadcast_subject.clone(), message_data.into())
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to publish broadcast: {e}")))?
+                .await
+                .map_err(|e| Error::Internal(format!("Broadcast was not acknowledged: {e}")))?;
+            debug!("Published broadcast message via JetStream");
+            return Ok(());
+        }
+
         self.client
             .publish(
                 Subject::from(self.broadcast_subject.clone()),
@@ -136,13 +199,21 @@ impl HorizontalTransport for NatsTransport {
         let response_subject = self.response_subject.clone();
         let response_client = self.client.clone();
 
-        // Subscribe to broadcast channel
-        let mut broadcast_subscription = client
-            .subscribe(Subject::from(broadcast_subject.clone()))
-            .await
-            .map_err(|e| {
-                Error::Internal(format!("Failed to subscribe to broadcast subject: {e}"))
-            })?;
+        // Subscribe to broadcast channel (JetStream mode reads from the stream instead)
+        let broadcast_subscription = match &self.jetstream {
+            Some(_) => {
+                self.spawn_jetstream_broadcast_listener(handlers.on_broadcast.clone());
+                None
+            }
+            None => Some(
+                client
+                    .subscribe(Subject::from(broadcast_subject.clone()))
+                    .await
+                    .map_err(|e| {
+                        Error::Internal(format!("Failed to subscribe to broadcast subject: {e}"))
+                    })?,
+            ),
+        };
 
         // Subscribe to requests channel
         let mut request_subscription = client
@@ -164,14 +235,17 @@ impl HorizontalTransport for NatsTransport {
         );
 
         // Spawn a task to handle broadcast messages
-        let broadcast_handler = handlers.on_broadcast.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = broadcast_subscription.next().await {
-                if let Ok(broadcast) = serde_json::from_slice::<BroadcastMessage>(&msg.payload) {
-                    broadcast_handler(broadcast).await;
+        if let Some(mut broadcast_subscription) = broadcast_subscription {
+            let broadcast_handler = handlers.on_broadcast.clone();
+            tokio::spawn(async move {
+                while let Some(msg) = broadcast_subscription.next().await {
+                    if let Ok(broadcast) = serde_json::from_slice::<BroadcastMessage>(&msg.payload)
+                    {
+                        broadcast_handler(broadcast).await;
+                    }
                 }
-            }
-        });
+            });
+        }
 
         // Spawn a task to handle request messages
         let request_handler = handlers.on_request.clone();
@@ -233,3 +307,125 @@ impl HorizontalTransport for NatsTransport {
         }
     }
 }
+
+impl NatsTransport {
+    /// This node's durable broadcast consumer, created on first use. It starts at the
+    /// broadcasts published after its creation and keeps its position on the server, so a
+    /// node restarted with the same process ID resumes after the last broadcast it acked.
+    pub async fn broadcast_consumer(&self) -> Result<consumer::PullConsumer> {
+        let Some((_, broadcast_stream)) = &self.jetstream else {
+            return Err(Error::Configuration(
+                "JetStream is not enabled for the NATS transport".to_string(),
+            ));
+        };
+        Self::get_or_create_broadcast_consumer(
+            broadcast_stream,
+            &self.broadcast_consumer_name,
+            &self.broadcast_subject,
+        )
+        .await
+    }
+
+    async fn get_or_create_broadcast_consumer(
+        broadcast_stream: &stream::Stream,
+        name: &str,
+        subject: &str,
+    ) -> Result<consumer::PullConsumer> {
+        broadcast_stream
+            .get_or_create_consumer(
+                name,
+                consumer::pull::Config {
+                    durable_name: Some(name.to_string()),
+                    filter_subject: subject.to_string(),
+                    deliver_policy: consumer::DeliverPolicy::New,
+                    ack_policy: consumer::AckPolicy::Explicit,
+                    inactive_threshold: BROADCAST_CONSUMER_INACTIVE_THRESHOLD,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to create JetStream broadcast consumer {name}: {e}"
+                ))
+            })
+    }
+
+    /// Read broadcasts through this node's durable consumer, acking each one once it was
+    /// handled. After a disconnect or a restart with the same process ID the consumer picks
+    /// up after the last acked broadcast and replays anything still retained by the stream.
+    fn spawn_jetstream_broadcast_listener(
+        &self,
+        broadcast_handler: Arc<dyn Fn(BroadcastMessage) -> BoxFuture<'static, ()> + Send + Sync>,
+    ) {
+        let Some((_, broadcast_stream)) = self.jetstream.clone() else {
+            return;
+        };
+        let consumer_name = self.broadcast_consumer_name.clone();
+        let broadcast_subject = self.broadcast_subject.clone();
+
+        tokio::spawn(async move {
+            let mut retry_delay = 500u64;
+            const MAX_RETRY_DELAY: u64 = 10_000;
+
+            loop {
+                let consumer = match Self::get_or_create_broadcast_consumer(
+                    &broadcast_stream,
+                    &consumer_name,
+                    &broadcast_subject,
+                )
+                .await
+                {
+                    Ok(consumer) => consumer,
+                    Err(e) => {
+                        warn!("{}, retrying in {}ms", e, retry_delay);
+                        tokio::time::sleep(Duration::from_millis(retry_delay)).await;
+                        retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+                        continue;
+                    }
+                };
+
+                let mut messages = match consumer.messages().await {
+                    Ok(messages) => messages,
+                    Err(e) => {
+                        warn!(
+                            "Failed to read JetStream broadcasts: {}, retrying in {}ms",
+                            e, retry_delay
+                        );
+                        tokio::time::sleep(Duration::from_millis(retry_delay)).await;
+                        retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+                        continue;
+                    }
+                };
+
+                info!(
+                    "Reading JetStream broadcasts through consumer {}",
+                    consumer_name
+                );
+                retry_delay = 500;
+
+                while let Some(msg) = messages.next().await {
+                    match msg {
+                        Ok(msg) => {
+                            if let Ok(broadcast) =
+                                serde_json::from_slice::<BroadcastMessage>(&msg.payload)
+                            {
+                                broadcast_handler(broadcast).await;
+                            }
+                            if let Err(e) = msg.ack().await {
+                                warn!("Failed to ack JetStream broadcast: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            warn!("JetStream broadcast consumer error: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_millis(retry_delay)).await;
+                retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+            }
+        });
+    }
+}
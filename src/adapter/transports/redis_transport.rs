@@ -1,12 +1,25 @@
 use crate::adapter::horizontal_adapter::{BroadcastMessage, RequestBody, ResponseBody};
 use crate::adapter::horizontal_transport::{
-    HorizontalTransport, TransportConfig, TransportHandlers,
+    BoxFuture, HorizontalTransport, TransportConfig, TransportHandlers,
 };
 use crate::error::{Error, Result};
+use crate::options::RedisTransportMode;
 use async_trait::async_trait;
 use futures::StreamExt;
 use redis::AsyncCommands;
-use tracing::{debug, error, warn};
+use redis::streams::{StreamMaxlen, StreamRangeReply, StreamReadOptions, StreamReadReply};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// Field holding the serialized broadcast in each stream entry
+const STREAM_PAYLOAD_FIELD: &str = "payload";
+/// How long a single XREAD blocks waiting for new broadcasts
+const STREAM_BLOCK_MS: usize = 5000;
+/// Maximum entries fetched per XREAD (bounds catch-up batches after a reconnect)
+const STREAM_READ_COUNT: usize = 500;
+/// How long the stored read position of a node that stopped is kept
+const STREAM_CURSOR_TTL_SECS: u64 = 86_400;
 
 /// Redis adapter configuration
 #[derive(Debug, Clone)]
@@ -15,6 +28,11 @@ pub struct RedisAdapterConfig {
     pub prefix: String,
     pub request_timeout_ms: u64,
     pub cluster_mode: bool,
+    pub transport_mode: RedisTransportMode,
+    pub stream_max_len: usize,
+    /// Names the stored stream read position, so a restarted node with the same ID resumes
+    /// where it stopped
+    pub process_id: String,
 }
 
 impl Default for RedisAdapterConfig {
@@ -24,6 +42,9 @@ impl Default for RedisAdapterConfig {
             prefix: "sockudo".to_string(),
             request_timeout_ms: 5000,
             cluster_mode: false,
+            transport_mode: RedisTransportMode::default(),
+            stream_max_len: 10_000,
+            process_id: uuid::Uuid::new_v4().to_string(),
         }
     }
 }
@@ -47,6 +68,10 @@ pub struct RedisTransport {
     broadcast_channel: String,
     request_channel: String,
     response_channel: String,
+    transport_mode: RedisTransportMode,
+    broadcast_stream: String,
+    stream_cursor_key: String,
+    stream_max_len: usize,
 }
 
 #[async_trait]
@@ -82,6 +107,15 @@ impl HorizontalTransport for RedisTransport {
         let broadcast_channel = format!("{}:#broadcast", config.prefix);
         let request_channel = format!("{}:#requests", config.prefix);
         let response_channel = format!("{}:#responses", config.prefix);
+        let broadcast_stream = format!("{}:#broadcast-stream", config.prefix);
+        let stream_cursor_key = format!("{broadcast_stream}:cursor:{}", config.process_id);
+
+        if config.transport_mode == RedisTransportMode::Streams {
+            info!(
+                "Redis transport using streams for broadcasts (stream: {}, maxlen: ~{})",
+                broadcast_stream, config.stream_max_len
+            );
+        }
 
         Ok(Self {
             client,
@@ -90,6 +124,10 @@ impl HorizontalTransport for RedisTransport {
             broadcast_channel,
             request_channel,
             response_channel,
+            transport_mode: config.transport_mode,
+            broadcast_stream,
+            stream_cursor_key,
+            stream_max_len: config.stream_max_len,
         })
     }
 
@@ -103,11 +141,23 @@ impl HorizontalTransport for RedisTransport {
 
         for attempt in 0..=MAX_RETRIES {
             let mut conn = self.events_connection.clone();
-            match conn
-                .publish::<_, _, i32>(&self.broadcast_channel, &broadcast_json)
-                .await
-            {
-                Ok(_subscriber_count) => {
+            let result = match self.transport_mode {
+                RedisTransportMode::PubSub => conn
+                    .publish::<_, _, i32>(&self.broadcast_channel, &broadcast_json)
+                    .await
+                    .map(|_subscriber_count| ()),
+                RedisTransportMode::Streams => conn
+                    .xadd_maxlen::<_, _, _, _, String>(
+                        &self.broadcast_stream,
+                        StreamMaxlen::Approx(self.stream_max_len),
+                        "*",
+                        &[(STREAM_PAYLOAD_FIELD, &broadcast_json)],
+                    )
+                    .await
+                    .map(|_entry_id| ()),
+            };
+            match result {
+                Ok(()) => {
                     if attempt > 0 {
                         debug!("Broadcast succeeded on retry attempt {}", attempt);
                     }
@@ -177,6 +227,16 @@ impl HorizontalTransport for RedisTransport {
         let request_channel = self.request_channel.clone();
         let response_channel = self.response_channel.clone();
 
+        // In streams mode broadcasts are read from the stream instead of pub/sub;
+        // requests and responses stay on pub/sub since they are time-bounded anyway
+        let mut subscribe_channels = vec![request_channel.clone(), response_channel.clone()];
+        match self.transport_mode {
+            RedisTransportMode::PubSub => subscribe_channels.insert(0, broadcast_channel.clone()),
+            RedisTransportMode::Streams => {
+                self.spawn_broadcast_stream_listener(handlers.on_broadcast.clone())
+            }
+        }
+
         tokio::spawn(async move {
             let mut retry_delay = 500u64; // Start with 500ms delay
             const MAX_RETRY_DELAY: u64 = 10_000; // Max 10 seconds
@@ -201,10 +261,7 @@ impl HorizontalTransport for RedisTransport {
                     }
                 };
 
-                if let Err(e) = pubsub
-                    .subscribe(&[&broadcast_channel, &request_channel, &response_channel])
-                    .await
-                {
+                if let Err(e) = pubsub.subscribe(&subscribe_channels).await {
                     error!(
                         "Failed to subscribe to channels: {}, retrying in {}ms",
                         e, retry_delay
@@ -215,8 +272,8 @@ impl HorizontalTransport for RedisTransport {
                 }
 
                 debug!(
-                    "Redis transport listening on channels: {}, {}, {}",
-                    broadcast_channel, request_channel, response_channel
+                    "Redis transport listening on channels: {}",
+                    subscribe_channels.join(", ")
                 );
 
                 let mut message_stream = pubsub.on_message();
@@ -352,3 +409,160 @@ impl HorizontalTransport for RedisTransport {
         }
     }
 }
+
+impl RedisTransport {
+    /// Read broadcasts from the capped stream, storing the last entry ID this node handled
+    /// in Redis. After a reconnect or a restart with the same process ID the reader resumes
+    /// from that ID, so broadcasts published in the meantime are replayed as long as they
+    /// were not trimmed by MAXLEN.
+    fn spawn_broadcast_stream_listener(
+        &self,
+        on_broadcast: Arc<dyn Fn(BroadcastMessage) -> BoxFuture<'static, ()> + Send + Sync>,
+    ) {
+        let client = self.client.clone();
+        let stream = self.broadcast_stream.clone();
+        let cursor_key = self.stream_cursor_key.clone();
+
+        tokio::spawn(async move {
+            let mut retry_delay = 500u64;
+            const MAX_RETRY_DELAY: u64 = 10_000;
+            let mut last_id: Option<String> = None;
+
+            // Blocking XREAD needs a dedicated connection with a timeout longer than the block
+            let connection_config = redis::AsyncConnectionConfig::new()
+                .set_response_timeout(Duration::from_millis(STREAM_BLOCK_MS as u64 + 5000));
+
+            loop {
+                let mut conn = match client
+                    .get_multiplexed_async_connection_with_config(&connection_config)
+                    .await
+                {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        error!(
+                            "Failed to open broadcast stream connection: {}, retrying in {}ms",
+                            e, retry_delay
+                        );
+                        tokio::time::sleep(Duration::from_millis(retry_delay)).await;
+                        retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+                        continue;
+                    }
+                };
+
+                // First start begins at the current tail; reconnects catch up from last_id
+                let mut cursor = match last_id.take() {
+                    Some(id) => {
+                        info!("Broadcast stream reconnected, catching up from {}", id);
+                        id
+                    }
+                    None => match Self::initial_stream_id(&mut conn, &stream, &cursor_key).await {
+                        Ok(id) => id,
+                        Err(e) => {
+                            error!(
+                                "Failed to read broadcast stream tail: {}, retrying in {}ms",
+                                e, retry_delay
+                            );
+                            tokio::time::sleep(Duration::from_millis(retry_delay)).await;
+                            retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+                            continue;
+                        }
+                    },
+                };
+                retry_delay = 500;
+                debug!(
+                    "Redis transport reading broadcast stream {} from {}",
+                    stream, cursor
+                );
+
+                let read_options = StreamReadOptions::default()
+                    .count(STREAM_READ_COUNT)
+                    .block(STREAM_BLOCK_MS);
+
+                loop {
+                    let reply: redis::RedisResult<Option<StreamReadReply>> = conn
+                        .xread_options(&[&stream], &[&cursor], &read_options)
+                        .await;
+
+                    match reply {
+                        Ok(Some(reply)) => {
+                            let previous = cursor.clone();
+                            for entry in reply.keys.into_iter().flat_map(|key| key.ids) {
+                                match entry.get::<String>(STREAM_PAYLOAD_FIELD) {
+                                    Some(payload) => {
+                                        match serde_json::from_str::<BroadcastMessage>(&payload) {
+                                            Ok(broadcast) => {
+                                                let handler = on_broadcast.clone();
+                                                tokio::spawn(async move {
+                                                    handler(broadcast).await;
+                                                });
+                                            }
+                                            Err(e) => warn!(
+                                                "Failed to parse broadcast stream entry {}: {}",
+                                                entry.id, e
+                                            ),
+                                        }
+                                    }
+                                    None => {
+                                        warn!("Broadcast stream entry {} has no payload", entry.id)
+                                    }
+                                }
+                                cursor = entry.id;
+                            }
+                            if cursor != previous {
+                                let stored: redis::RedisResult<()> = conn
+                                    .set_ex(&cursor_key, &cursor, STREAM_CURSOR_TTL_SECS)
+                                    .await;
+                                if let Err(e) = stored {
+                                    warn!("Failed to store broadcast stream position: {}", e);
+                                }
+                            }
+                        }
+                        Ok(None) => {} // Block timed out without new entries
+                        Err(e) => {
+                            warn!(
+                                "Broadcast stream read failed: {}, reconnecting in {}ms",
+                                e, retry_delay
+                            );
+                            break;
+                        }
+                    }
+                }
+
+                last_id = Some(cursor);
+                tokio::time::sleep(Duration::from_millis(retry_delay)).await;
+                retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+            }
+        });
+    }
+
+    /// Where a starting reader begins: the position stored by a previous run of this
+    /// process, else the current tail
+    async fn initial_stream_id(
+        conn: &mut redis::aio::MultiplexedConnection,
+        stream: &str,
+        cursor_key: &str,
+    ) -> redis::RedisResult<String> {
+        let stored: Option<String> = conn.get(cursor_key).await?;
+        match stored {
+            Some(id) => {
+                info!("Broadcast stream resuming from stored position {}", id);
+                Ok(id)
+            }
+            None => Self::latest_stream_id(conn, stream).await,
+        }
+    }
+
+    /// ID of the newest entry in the stream, or `0-0` if the stream is empty
+    async fn latest_stream_id(
+        conn: &mut redis::aio::MultiplexedConnection,
+        stream: &str,
+    ) -> redis::RedisResult<String> {
+        let reply: StreamRangeReply = conn.xrevrange_count(stream, "+", "-", 1).await?;
+        Ok(reply
+            .ids
+            .into_iter()
+            .next()
+            .map(|entry| entry.id)
+            .unwrap_or_else(|| "0-0".to_string()))
+    }
+}
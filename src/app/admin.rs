@@ -0,0 +1,405 @@
+// src/app/admin.rs
+use crate::adapter::handler::origin_validation::OriginValidator;
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::{self, AuthorizerConfig};
+use crate::app::channel_limits::{self, ChannelLimitRule};
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::config::{App, AppCredential, CredentialState};
+use crate::app::ip_filter::{self, IpFilterConfig};
+use crate::app::jwt_auth::{self, JwtAuthConfig};
+use crate::quota::AppQuotas;
+use crate::webhook::types::Webhook;
+use rand::Rng;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Webhook event types Sockudo can deliver
+pub const WEBHOOK_EVENT_TYPES: &[&str] = &[
+    "cache_miss",
+    "channel_occupied",
+    "channel_vacated",
+    "client_event",
+    "member_added",
+    "member_removed",
+    "subscription_count",
+];
+
+const MAX_IDENTIFIER_LENGTH: usize = 64;
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// How long a replaced secret stays accepted when no grace period is given
+pub const DEFAULT_SECRET_GRACE_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Treats an explicit `null` as `Some(None)` so PATCH requests can clear optional settings
+fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// App fields accepted by the admin API, used both to create an app and to patch one.
+///
+/// Absent fields are left untouched; optional limits can be cleared with `null`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppChanges {
+    pub id: Option<String>,
+    pub key: Option<String>,
+    pub secret: Option<String>,
+    pub enabled: Option<bool>,
+    pub max_connections: Option<u32>,
+    pub enable_client_messages: Option<bool>,
+    pub max_client_events_per_second: Option<u32>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_backend_events_per_second: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_read_requests_per_second: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_presence_members_per_channel: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_presence_member_size_in_kb: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_channel_name_length: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_event_channels_at_once: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_event_name_length: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_event_payload_in_kb: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_event_batch_size: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub enable_user_authentication: Option<Option<bool>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub webhooks: Option<Option<Vec<Webhook>>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub enable_watchlist_events: Option<Option<bool>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub allowed_origins: Option<Option<Vec<String>>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub channel_policy: Option<Option<ChannelPolicy>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub channel_limits: Option<Option<Vec<ChannelLimitRule>>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub quotas: Option<Option<AppQuotas>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub jwt_auth: Option<Option<JwtAuthConfig>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub api_auth: Option<Option<ApiAuthConfig>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub ip_filter: Option<Option<IpFilterConfig>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub authorizer: Option<Option<AuthorizerConfig>>,
+}
+
+impl AppChanges {
+    /// Build a new app, generating any missing id, key or secret. New apps are enabled
+    /// unless `enabled: false` is given.
+    pub fn into_new_app(mut self) -> Result<App, String> {
+        if let Some(secret) = &self.secret
+            && secret.len() < MIN_SECRET_LENGTH
+        {
+            return Err(format!(
+                "secret must be at least {MIN_SECRET_LENGTH} characters long"
+            ));
+        }
+
+        let mut app = App {
+            id: self
+                .id
+                .take()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()),
+            key: self.key.take().unwrap_or_else(generate_app_key),
+            secret: self.secret.take().unwrap_or_else(generate_app_secret),
+            enabled: true,
+            ..Default::default()
+        };
+        self.apply_to(&mut app)?;
+        Ok(app)
+    }
+
+    /// Apply the changes to an existing app. The id, key and secret cannot be changed here;
+    /// secrets are replaced through rotation.
+    pub fn apply_to(self, app: &mut App) -> Result<(), String> {
+        if self.id.as_ref().is_some_and(|id| *id != app.id) {
+            return Err("id cannot be changed".to_string());
+        }
+        if self.key.as_ref().is_some_and(|key| *key != app.key) {
+            return Err("key cannot be changed".to_string());
+        }
+        if self.secret.is_some() {
+            return Err("secret cannot be set directly; rotate it instead".to_string());
+        }
+
+        if let Some(enabled) = self.enabled {
+            app.enabled = enabled;
+        }
+        if let Some(max_connections) = self.max_connections {
+            app.max_connections = max_connections;
+        }
+        if let Some(enable_client_messages) = self.enable_client_messages {
+            app.enable_client_messages = enable_client_messages;
+        }
+        if let Some(max_client_events_per_second) = self.max_client_events_per_second {
+            app.max_client_events_per_second = max_client_events_per_second;
+        }
+        if let Some(value) = self.max_backend_events_per_second {
+            app.max_backend_events_per_second = value;
+        }
+        if let Some(value) = self.max_read_requests_per_second {
+            app.max_read_requests_per_second = value;
+        }
+        if let Some(value) = self.max_presence_members_per_channel {
+            app.max_presence_members_per_channel = value;
+        }
+        if let Some(value) = self.max_presence_member_size_in_kb {
+            app.max_presence_member_size_in_kb = value;
+        }
+        if let Some(value) = self.max_channel_name_length {
+            app.max_channel_name_length = value;
+        }
+        if let Some(value) = self.max_event_channels_at_once {
+            app.max_event_channels_at_once = value;
+        }
+        if let Some(value) = self.max_event_name_length {
+            app.max_event_name_length = value;
+        }
+        if let Some(value) = self.max_event_payload_in_kb {
+            app.max_event_payload_in_kb = value;
+        }
+        if let Some(value) = self.max_event_batch_size {
+            app.max_event_batch_size = value;
+        }
+        if let Some(value) = self.enable_user_authentication {
+            app.enable_user_authentication = value;
+        }
+        if let Some(value) = self.webhooks {
+            app.webhooks = value;
+        }
+        if let Some(value) = self.enable_watchlist_events {
+            app.enable_watchlist_events = value;
+        }
+        if let Some(value) = self.allowed_origins {
+            app.allowed_origins = value;
+        }
+        if let Some(value) = self.channel_policy {
+            app.channel_policy = value;
+        }
+        if let Some(value) = self.channel_limits {
+            app.channel_limits = value;
+        }
+        if let Some(value) = self.quotas {
+            app.quotas = value;
+        }
+        if let Some(value) = self.jwt_auth {
+            app.jwt_auth = value;
+        }
+        if let Some(value) = self.api_auth {
+            app.api_auth = value;
+        }
+        if let Some(value) = self.ip_filter {
+            app.ip_filter = value;
+        }
+        if let Some(value) = self.authorizer {
+            app.authorizer = value;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check an app before it is stored
+pub fn validate_app(app: &App) -> Result<(), String> {
+    validate_identifier("id", &app.id)?;
+    validate_identifier("key", &app.key)?;
+    if app.secret.is_empty() {
+        return Err("secret must not be empty".to_string());
+    }
+
+    let optional_limits = [
+        (
+            "max_presence_members_per_channel",
+            app.max_presence_members_per_channel,
+        ),
+        (
+            "max_presence_member_size_in_kb",
+            app.max_presence_member_size_in_kb,
+        ),
+        ("max_channel_name_length", app.max_channel_name_length),
+        ("max_event_channels_at_once", app.max_event_channels_at_once),
+        ("max_event_name_length", app.max_event_name_length),
+        ("max_event_payload_in_kb", app.max_event_payload_in_kb),
+        ("max_event_batch_size", app.max_event_batch_size),
+    ];
+    for (name, limit) in optional_limits {
+        if limit == Some(0) {
+            return Err(format!("{name} must be greater than 0 when set"));
+        }
+    }
+
+    if let Some(origins) = &app.allowed_origins {
+        OriginValidator::validate_patterns(origins)?;
+    }
+
+    if let Some(policy) = &app.channel_policy {
+        policy.validate()?;
+    }
+
+    if let Some(rules) = &app.channel_limits {
+        channel_limits::validate_rules(rules)?;
+    }
+
+    if let Some(config) = &app.jwt_auth {
+        jwt_auth::validate_config(config)?;
+    }
+
+    if let Some(config) = &app.ip_filter {
+        ip_filter::validate_config(config)?;
+    }
+
+    if let Some(config) = &app.authorizer {
+        authorizer::validate_config(config)?;
+    }
+
+    for (index, credential) in app.credentials.iter().flatten().enumerate() {
+        if credential.secret.is_empty() {
+            return Err("credential secrets must not be empty".to_string());
+        }
+        if !credential.key.is_empty() {
+            validate_identifier(&format!("credentials[{index}].key"), &credential.key)?;
+        }
+    }
+
+    if let Some(webhooks) = &app.webhooks {
+        for (index, webhook) in webhooks.iter().enumerate() {
+            validate_webhook(webhook).map_err(|e| format!("webhooks[{index}]: {e}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_identifier(field: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() || value.len() > MAX_IDENTIFIER_LENGTH {
+        return Err(format!(
+            "{field} must be between 1 and {MAX_IDENTIFIER_LENGTH} characters long"
+        ));
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "{field} may only contain letters, digits, '-', '_' and '.'"
+        ));
+    }
+    Ok(())
+}
+
+fn validate_webhook(webhook: &Webhook) -> Result<(), String> {
+    match &webhook.url {
+        Some(url) if !matches!(url.scheme(), "http" | "https") => {
+            return Err(format!("unsupported url scheme '{}'", url.scheme()));
+        }
+        None if webhook.lambda.is_none() && webhook.lambda_function.is_none() => {
+            return Err("either url or lambda must be set".to_string());
+        }
+        _ => {}
+    }
+
+    if webhook.event_types.is_empty() {
+        return Err("event_types must not be empty".to_string());
+    }
+    if let Some(unknown) = webhook
+        .event_types
+        .iter()
+        .find(|event_type| !WEBHOOK_EVENT_TYPES.contains(&event_type.as_str()))
+    {
+        return Err(format!("unknown event type '{unknown}'"));
+    }
+
+    Ok(())
+}
+
+fn random_hex(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    rand::rng().fill(buffer.as_mut_slice());
+    hex::encode(buffer)
+}
+
+/// Generate a public app key (20 hex characters, like Pusher keys)
+pub fn generate_app_key() -> String {
+    random_hex(10)
+}
+
+/// Generate an app secret (64 hex characters)
+pub fn generate_app_secret() -> String {
+    random_hex(32)
+}
+
+/// Replace the primary secret with a new one and return it. The previous key/secret pair
+/// stays accepted (verify-only) for `grace_period`; a zero grace period revokes every
+/// other pair at once, e.g. after a leak. Expired pairs are dropped.
+pub fn rotate_app_secret(app: &mut App, grace_period: Duration) -> String {
+    rotate_app_credentials(app, grace_period, false);
+    app.secret.clone()
+}
+
+/// Like [`rotate_app_secret`], but with `rotate_key` the app gets a new key as well, so
+/// backends can switch pairs one at a time
+pub fn rotate_app_credentials(app: &mut App, grace_period: Duration, rotate_key: bool) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let previous_secret = std::mem::replace(&mut app.secret, generate_app_secret());
+    let previous_key = if rotate_key {
+        std::mem::replace(&mut app.key, generate_app_key())
+    } else {
+        app.key.clone()
+    };
+
+    let mut credentials: Vec<AppCredential> = app
+        .credentials
+        .take()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|credential| credential.expires_at.is_none_or(|at| now < at))
+        .collect();
+    if grace_period.is_zero() {
+        for credential in &mut credentials {
+            credential.state = CredentialState::Revoked;
+        }
+    } else {
+        // Empty keys stood for the previous app key
+        for credential in &mut credentials {
+            if credential.key.is_empty() {
+                credential.key = previous_key.clone();
+            }
+        }
+        credentials.push(AppCredential {
+            key: previous_key,
+            secret: previous_secret,
+            state: CredentialState::VerifyOnly,
+            expires_at: Some(now + grace_period.as_secs()),
+        });
+    }
+    app.credentials = (!credentials.is_empty()).then_some(credentials);
+}
+
+/// App as shown in admin listings, without its secrets
+pub fn redacted_app(app: &App) -> Value {
+    let mut value = serde_json::to_value(app).unwrap_or(Value::Null);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("secret");
+        if let Some(Value::Array(credentials)) = object.get_mut("credentials") {
+            for credential in credentials.iter_mut().filter_map(Value::as_object_mut) {
+                credential.remove("secret");
+            }
+        }
+    }
+    value
+}
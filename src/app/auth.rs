@@ -1,14 +1,41 @@
 use super::manager::AppManager;
 use crate::app::config::App;
+use crate::cache::manager::CacheManager;
 use crate::error::Error;
 use crate::http_handler::EventQuery;
 use crate::token::{Token, secure_compare};
 use crate::websocket::SocketId;
 use chrono::Utc;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::sync::Arc;
-use tracing::debug;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Seconds `auth_timestamp` may differ from the server clock unless the app sets its own
+pub const DEFAULT_API_TIMESTAMP_SKEW: u64 = 600;
+
+/// How the HTTP API signatures of an app are checked
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ApiAuthConfig {
+    /// Seconds `auth_timestamp` may differ from the server clock
+    #[serde(default)]
+    pub timestamp_skew_seconds: Option<u64>,
+    /// Reject a signature that was already used while its timestamp is still accepted
+    #[serde(default)]
+    pub replay_protection: bool,
+}
+
+/// Split the optional expiry off the HMAC of an auth string. Expiring signatures look like
+/// `<hmac>:<expires_at>` and sign `<string to sign>:<expires_at>`. Returns `None` when the
+/// expiry is not a unix timestamp or has passed.
+pub fn split_signature_expiry(signature: &str) -> Option<(&str, Option<u64>)> {
+    let Some((hmac, expires_at)) = signature.split_once(':') else {
+        return Some((signature, None));
+    };
+    let expires_at: u64 = expires_at.parse().ok()?;
+    (expires_at > Utc::now().timestamp() as u64).then_some((hmac, Some(expires_at)))
+}
 
 #[derive(Debug, Deserialize)]
 pub struct ChannelAuth {
@@ -20,6 +47,8 @@ pub struct ChannelAuth {
 
 pub struct AuthValidator {
     app_manager: Arc<dyn AppManager>,
+    /// Remembers API signatures of apps with replay protection
+    replay_cache: Option<Arc<Mutex<dyn CacheManager + Send + Sync>>>,
 }
 
 #[derive(Debug)]
@@ -30,7 +59,17 @@ pub struct AuthValidationResult {
 
 impl AuthValidator {
     pub fn new(app_manager: Arc<dyn AppManager>) -> Self {
-        AuthValidator { app_manager }
+        AuthValidator {
+            app_manager,
+            replay_cache: None,
+        }
+    }
+
+    /// Remember API signatures in `cache`, so apps with replay protection can reject
+    /// requests that were already seen
+    pub fn with_replay_cache(mut self, cache: Arc<Mutex<dyn CacheManager + Send + Sync>>) -> Self {
+        self.replay_cache = Some(cache);
+        self
     }
 
     pub async fn validate_channel_auth(
@@ -44,8 +83,13 @@ impl AuthValidator {
         if app.is_none() {
             return Err(Error::InvalidAppKey);
         }
-        let is_valid =
-            self.sign_in_token_is_valid(socket_id.0.as_str(), user_data, auth, app.unwrap());
+        let is_valid = self.sign_in_token_is_valid(
+            socket_id.0.as_str(),
+            app_key,
+            user_data,
+            auth,
+            app.unwrap(),
+        );
         Ok(is_valid)
     }
 
@@ -93,9 +137,12 @@ impl AuthValidator {
             }
         };
 
+        let api_auth = app_config.api_auth.unwrap_or_default();
+        let max_skew = api_auth
+            .timestamp_skew_seconds
+            .unwrap_or(DEFAULT_API_TIMESTAMP_SKEW) as i64;
         let current_ts = Utc::now().timestamp();
-        if (current_ts - auth_ts).abs() > 600 {
-            // 600 seconds = 10 minutes
+        if (current_ts - auth_ts).abs() > max_skew {
             debug!(
                 "Timestamp validation failed. Server time: {}, Provided timestamp: {}, Difference: {}s",
                 current_ts,
@@ -182,34 +229,93 @@ impl AuthValidator {
             format!("{uppercased_http_method}\n{request_path}\n{query_string_for_sig}");
         debug!("String to sign: \n{}", string_to_sign); // Use \n for actual newline in log
 
-        let token_signer = Token::new(app_config.key.clone(), app_config.secret.clone());
-        let generated_signature = token_signer.sign(&string_to_sign);
-
-        debug!("Generated signature: {}", generated_signature);
         debug!(
             "Received signature:  {}",
             auth_params_from_query_struct.auth_signature
         );
 
-        if secure_compare(
-            &generated_signature,
-            &auth_params_from_query_struct.auth_signature,
-        ) {
-            Ok(true)
-        } else {
-            Err(Error::Auth("Invalid API signature".to_string()))
+        // Any non-revoked key/secret pair of the app is accepted, so backends can move
+        // to rotated credentials at their own pace
+        let signature_is_valid = app_config
+            .token_for_key(&auth_params_from_query_struct.auth_key)
+            .is_some_and(|token| {
+                token.verify(
+                    &string_to_sign,
+                    &auth_params_from_query_struct.auth_signature,
+                )
+            });
+        if !signature_is_valid {
+            return Err(Error::Auth("Invalid API signature".to_string()));
+        }
+
+        if api_auth.replay_protection {
+            // Remembered until the timestamp falls out of the window
+            let ttl = (auth_ts + max_skew - current_ts).max(1) as u64;
+            self.reject_replayed_signature(
+                &app_config.id,
+                &auth_params_from_query_struct.auth_signature,
+                ttl,
+            )
+            .await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Record a signature, failing if it was recorded before. Cache errors let the request
+    /// through.
+    async fn reject_replayed_signature(
+        &self,
+        app_id: &str,
+        signature: &str,
+        ttl_seconds: u64,
+    ) -> Result<(), Error> {
+        let Some(ref cache) = self.replay_cache else {
+            return Ok(());
+        };
+        let key = format!("api_signature:{app_id}:{}", signature.to_lowercase());
+
+        // A single SET NX, so concurrent replays on any node cannot both pass. The lock is
+        // released before the round trip.
+        let claim = cache.lock().await.set_if_absent(&key, "1", ttl_seconds);
+        match claim.await {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                debug!("Replayed API signature for app {}", app_id);
+                Err(Error::Auth(
+                    "Request signature has already been used".to_string(),
+                ))
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to check API signature replay for app {}: {}",
+                    app_id, e
+                );
+                Ok(())
+            }
         }
     }
 
+    /// Verifies with the secrets paired with `app_key`, so the key must be the app key
+    /// or the key of a non-revoked credential
     pub fn sign_in_token_is_valid(
         &self,
         socket_id: &str,
+        app_key: &str,
         user_data: &str,
         expected_signature: &str,
         app_config: App,
     ) -> bool {
-        let signature = self.sign_in_token_for_user_data(socket_id, user_data, app_config);
-        secure_compare(&signature, expected_signature)
+        let Some((signature, expires_at)) = split_signature_expiry(expected_signature) else {
+            return false;
+        };
+        let mut decoded_string = format!("{socket_id}::user::{user_data}");
+        if let Some(expires_at) = expires_at {
+            decoded_string = format!("{decoded_string}:{expires_at}");
+        }
+        app_config
+            .token_for_key(app_key)
+            .is_some_and(|token| token.verify(&decoded_string, signature))
     }
 
     pub fn sign_in_token_for_user_data(
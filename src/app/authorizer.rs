@@ -0,0 +1,181 @@
+// src/app/authorizer.rs
+use crate::app::config::App;
+use crate::error::{Error, Result};
+use axum::http::HeaderMap;
+use reqwest::{Client, StatusCode, header};
+use ring::digest::{SHA256, digest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+pub const DEFAULT_AUTHORIZER_TIMEOUT_MS: u64 = 2000;
+pub const DEFAULT_AUTHORIZER_CACHE_TTL: u64 = 30;
+
+/// Handshake headers that differ on every connection or only matter for the upgrade
+const SKIPPED_HEADERS: &[&str] = &[
+    "connection",
+    "upgrade",
+    "sec-websocket-key",
+    "sec-websocket-version",
+    "sec-websocket-extensions",
+];
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+/// Server-side endpoint that decides on private and presence subscriptions sent without
+/// a valid signature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuthorizerConfig {
+    pub url: String,
+    /// How long to wait for a decision; defaults to 2000
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// How long a decision is reused; defaults to 30, 0 disables caching
+    #[serde(default)]
+    pub cache_ttl_seconds: Option<u64>,
+}
+
+impl AuthorizerConfig {
+    pub fn cache_ttl(&self) -> u64 {
+        self.cache_ttl_seconds
+            .unwrap_or(DEFAULT_AUTHORIZER_CACHE_TTL)
+    }
+}
+
+/// Body posted to the authorizer, signed like webhooks with `X-Pusher-Key` and
+/// `X-Pusher-Signature`
+#[derive(Debug, Serialize)]
+pub struct AuthorizerRequest<'a> {
+    pub socket_id: &'a str,
+    pub channel_name: &'a str,
+    pub headers: &'a BTreeMap<String, String>,
+}
+
+/// Answer of the authorizer. Presence subscriptions need `channel_data` with the member.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuthorizerDecision {
+    pub authorized: bool,
+    #[serde(default)]
+    pub channel_data: Option<String>,
+}
+
+impl AuthorizerDecision {
+    fn denied() -> Self {
+        Self {
+            authorized: false,
+            channel_data: None,
+        }
+    }
+}
+
+/// Headers of the upgrade request forwarded to the authorizer, including cookies
+pub fn capture_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    let mut captured = BTreeMap::<String, String>::new();
+    for (name, value) in headers {
+        let name = name.as_str();
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        if SKIPPED_HEADERS.contains(&name) {
+            continue;
+        }
+        captured
+            .entry(name.to_string())
+            .and_modify(|existing| {
+                existing.push_str(if name == "cookie" { "; " } else { ", " });
+                existing.push_str(value);
+            })
+            .or_insert_with(|| value.to_string());
+    }
+    captured
+}
+
+/// Cache key of a decision; connections with the same headers share decisions. `None` when
+/// the decision must not be reused: presence decisions carry the member of one connection,
+/// and without headers nothing tells connections apart.
+pub fn cache_key(
+    app_id: &str,
+    channel: &str,
+    headers: &BTreeMap<String, String>,
+) -> Option<String> {
+    if headers.is_empty() || channel.starts_with("presence-") {
+        return None;
+    }
+    let headers = serde_json::to_string(headers).unwrap_or_default();
+    let fingerprint = hex::encode(digest(&SHA256, headers.as_bytes()));
+    Some(format!("authorizer:{app_id}:{channel}:{fingerprint}"))
+}
+
+/// Ask the authorizer for a decision. `401` and `403` deny; other failures are errors so
+/// they are not cached.
+pub async fn request_decision(
+    app: &App,
+    config: &AuthorizerConfig,
+    request: &AuthorizerRequest<'_>,
+) -> Result<AuthorizerDecision> {
+    let body = serde_json::to_string(request)?;
+    let signature = app.token().sign(&body);
+    let timeout = config.timeout_ms.unwrap_or(DEFAULT_AUTHORIZER_TIMEOUT_MS);
+
+    let response = CLIENT
+        .post(&config.url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("X-Pusher-Key", &app.key)
+        .header("X-Pusher-Signature", signature)
+        .timeout(Duration::from_millis(timeout))
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| Error::Other(format!("Authorizer request failed: {e}")))?;
+
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Ok(AuthorizerDecision::denied()),
+        status if status.is_success() => response
+            .json()
+            .await
+            .map_err(|e| Error::Other(format!("Invalid authorizer response: {e}"))),
+        status => Err(Error::Other(format!(
+            "Authorizer answered with status {status}"
+        ))),
+    }
+}
+
+pub fn validate_config(config: &AuthorizerConfig) -> std::result::Result<(), String> {
+    match url::Url::parse(&config.url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+        _ => Err("authorizer.url must be an http or https URL".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_captured_headers_skip_handshake_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("sec-websocket-key", HeaderValue::from_static("abc"));
+        headers.insert("user-agent", HeaderValue::from_static("test"));
+        headers.append("cookie", HeaderValue::from_static("a=1"));
+        headers.append("cookie", HeaderValue::from_static("b=2"));
+
+        let captured = capture_headers(&headers);
+        assert_eq!(captured.len(), 2);
+        assert_eq!(captured["cookie"], "a=1; b=2");
+
+        // Connections differing only in their handshake share cached decisions
+        headers.insert("sec-websocket-key", HeaderValue::from_static("def"));
+        assert_eq!(
+            cache_key("app", "private-a", &captured),
+            cache_key("app", "private-a", &capture_headers(&headers))
+        );
+        assert_ne!(
+            cache_key("app", "private-a", &captured),
+            cache_key("app", "private-b", &captured)
+        );
+        assert!(cache_key("app", "presence-a", &captured).is_none());
+        assert!(cache_key("app", "private-a", &BTreeMap::new()).is_none());
+    }
+}
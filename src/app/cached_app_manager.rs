@@ -1,11 +1,13 @@
 use crate::app::config::App;
-use crate::app::manager::AppManager;
+use crate::app::manager::{AppChange, AppManager};
+use crate::app::migrations::MigrationStatus;
 use crate::cache::manager::CacheManager;
 use crate::error::{Error, Result};
 use crate::options::CacheSettings;
 use async_trait::async_trait;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{Mutex, broadcast};
 use tracing::{debug, warn};
 
 const CACHE_PREFIX_ID: &str = "app:id";
@@ -16,6 +18,9 @@ pub struct CachedAppManager {
     inner: Arc<dyn AppManager + Send + Sync>,
     cache: Arc<Mutex<dyn CacheManager + Send + Sync>>,
     settings: CacheSettings,
+    /// Inner changes re-sent once their cache entries are gone, so subscribers never
+    /// read a stale cached app. None when the inner manager does not report changes.
+    changes: Option<broadcast::Sender<AppChange>>,
 }
 
 impl CachedAppManager {
@@ -24,10 +29,12 @@ impl CachedAppManager {
         cache: Arc<Mutex<dyn CacheManager + Send + Sync>>,
         settings: CacheSettings,
     ) -> Self {
+        let changes = inner.subscribe_changes().map(|_| broadcast::channel(256).0);
         Self {
             inner,
             cache,
             settings,
+            changes,
         }
     }
 
@@ -97,28 +104,81 @@ impl CachedAppManager {
 
     async fn cache_app(&self, app: &App) {
         self.set(&Self::cache_key_for_id(&app.id), app).await;
-        self.set(&Self::cache_key_for_key(&app.key), app).await;
+        for key in app.keys() {
+            self.set(&Self::cache_key_for_key(key), app).await;
+        }
     }
 
-    async fn invalidate_app(&self, app_id: &str, app_key: &str) {
-        self.remove(&Self::cache_key_for_id(app_id)).await;
-        self.remove(&Self::cache_key_for_key(app_key)).await;
+    async fn invalidate_app(&self, app: &App) {
+        self.remove(&Self::cache_key_for_id(&app.id)).await;
+        for key in app.keys() {
+            self.remove(&Self::cache_key_for_key(key)).await;
+        }
     }
 
     async fn invalidate_app_by_id(&self, app_id: &str) {
         let id_key = Self::cache_key_for_id(app_id);
         if let Some(app) = self.get::<App>(&id_key).await {
-            self.invalidate_app(app_id, &app.key).await;
+            self.invalidate_app(&app).await;
         } else {
             self.remove(&id_key).await;
         }
     }
+
+    /// Drop cache entries for apps changed underneath us, then pass the change on
+    fn spawn_change_forwarder(&self) {
+        let (Some(sender), Some(mut receiver)) =
+            (self.changes.clone(), self.inner.subscribe_changes())
+        else {
+            return;
+        };
+        let manager = Self {
+            inner: self.inner.clone(),
+            cache: self.cache.clone(),
+            settings: self.settings.clone(),
+            changes: None,
+        };
+
+        tokio::spawn(async move {
+            loop {
+                let change = match receiver.recv().await {
+                    Ok(change) => change,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "Missed {} app changes; affected cache entries expire after their TTL",
+                            skipped
+                        );
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                if manager.settings.enabled {
+                    match &change {
+                        AppChange::Created(app) | AppChange::Deleted(app) => {
+                            manager.invalidate_app(app).await;
+                        }
+                        AppChange::Updated { previous, current } => {
+                            manager.invalidate_app(previous).await;
+                            manager.invalidate_app(current).await;
+                        }
+                        AppChange::Invalidated { app_id } => {
+                            manager.invalidate_app_by_id(app_id).await;
+                        }
+                    }
+                }
+                let _ = sender.send(change);
+            }
+        });
+    }
 }
 
 #[async_trait]
 impl AppManager for CachedAppManager {
     async fn init(&self) -> Result<()> {
-        self.inner.init().await
+        self.inner.init().await?;
+        self.spawn_change_forwarder();
+        Ok(())
     }
 
     /// Get an app by ID from cache or database
@@ -146,8 +206,11 @@ impl AppManager for CachedAppManager {
             return self.inner.find_by_key(key).await;
         }
 
+        // A cached credential key may have been revoked or expired since it was cached
         let cache_key = Self::cache_key_for_key(key);
-        if let Some(app) = self.get::<App>(&cache_key).await {
+        if let Some(app) = self.get::<App>(&cache_key).await
+            && app.accepts_key(key)
+        {
             return Ok(Some(app));
         }
 
@@ -175,7 +238,9 @@ impl AppManager for CachedAppManager {
         self.inner.update_app(config.clone()).await?;
 
         if self.settings.enabled {
-            self.invalidate_app(&config.id, &config.key).await;
+            // Drop the keys of the cached version too, they may no longer belong to the app
+            self.invalidate_app_by_id(&config.id).await;
+            self.invalidate_app(&config).await;
             self.cache_app(&config).await;
         }
 
@@ -194,7 +259,7 @@ impl AppManager for CachedAppManager {
 
         if self.settings.enabled {
             if let Some(app) = app {
-                self.invalidate_app(app_id, &app.key).await;
+                self.invalidate_app(&app).await;
             } else {
                 self.invalidate_app_by_id(app_id).await;
             }
@@ -220,6 +285,19 @@ impl AppManager for CachedAppManager {
     async fn check_health(&self) -> Result<()> {
         self.inner.check_health().await
     }
+
+    async fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        self.inner.migrate(dry_run).await
+    }
+
+    async fn invalidate_cache(&self, app_id: &str) -> Result<()> {
+        self.invalidate_app_by_id(app_id).await;
+        self.inner.invalidate_cache(app_id).await
+    }
+
+    fn subscribe_changes(&self) -> Option<broadcast::Receiver<AppChange>> {
+        self.changes.as_ref().map(broadcast::Sender::subscribe)
+    }
 }
 
 #[cfg(test)]
@@ -287,6 +365,34 @@ mod tests {
         assert_eq!(found.unwrap().key, "test2_key");
     }
 
+    #[tokio::test]
+    async fn test_update_drops_revoked_credential_key() {
+        use crate::app::config::{AppCredential, CredentialState};
+
+        let manager = create_test_manager().await;
+        let mut app = create_test_app("test5");
+        app.credentials = Some(vec![AppCredential {
+            key: "test5_previous_key".to_string(),
+            secret: "test5_previous_secret".to_string(),
+            state: CredentialState::VerifyOnly,
+            expires_at: None,
+        }]);
+        manager.create_app(app.clone()).await.unwrap();
+
+        let found = manager.find_by_key("test5_previous_key").await.unwrap();
+        assert_eq!(found.unwrap().id, "test5");
+
+        app.credentials = None;
+        manager.update_app(app).await.unwrap();
+        assert!(
+            manager
+                .find_by_key("test5_previous_key")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_update_invalidates_cache() {
         let manager = create_test_manager().await;
@@ -316,6 +422,40 @@ mod tests {
         assert!(manager.find_by_id("test4").await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn test_invalidate_cache_drops_stale_entry() {
+        let inner = Arc::new(MemoryAppManager::new());
+        let cache = Arc::new(Mutex::new(MemoryCacheManager::new(
+            "test".to_string(),
+            MemoryCacheOptions::default(),
+        )));
+        let settings = CacheSettings {
+            enabled: true,
+            ttl: 300,
+        };
+        let manager = CachedAppManager::new(inner.clone(), cache, settings);
+        let mut app = create_test_app("test6");
+
+        manager.create_app(app.clone()).await.unwrap();
+        assert!(manager.find_by_key("test6_key").await.unwrap().is_some());
+
+        // Change the app behind the cache's back, as another node would
+        app.enabled = false;
+        inner.update_app(app).await.unwrap();
+        assert!(manager.find_by_id("test6").await.unwrap().unwrap().enabled);
+
+        manager.invalidate_cache("test6").await.unwrap();
+        assert!(!manager.find_by_id("test6").await.unwrap().unwrap().enabled);
+        assert!(
+            !manager
+                .find_by_key("test6_key")
+                .await
+                .unwrap()
+                .unwrap()
+                .enabled
+        );
+    }
+
     #[tokio::test]
     async fn test_cache_disabled() {
         let inner = Arc::new(MemoryAppManager::new());
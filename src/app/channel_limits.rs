@@ -0,0 +1,125 @@
+// src/app/channel_limits.rs
+use crate::app::channel_policy::wildcard_match;
+use crate::app::config::App;
+use serde::{Deserialize, Serialize};
+
+/// Limits for channels matching `pattern`. Unset limits fall back to the app-wide value.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ChannelLimitRule {
+    /// Full channel name, where `*` matches any run of characters
+    pub pattern: String,
+    #[serde(default)]
+    pub max_presence_members_per_channel: Option<u32>,
+    #[serde(default)]
+    pub max_presence_member_size_in_kb: Option<u32>,
+    #[serde(default)]
+    pub max_event_payload_in_kb: Option<u32>,
+    /// Client events per second a socket may send to channels matching this rule.
+    /// These events count against their own limiter instead of the app-wide one.
+    #[serde(default)]
+    pub max_client_events_per_second: Option<u32>,
+}
+
+/// The limits that apply to one channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelLimits<'a> {
+    /// The first rule matching the channel, if any
+    pub rule: Option<&'a ChannelLimitRule>,
+    pub max_presence_members_per_channel: Option<u32>,
+    pub max_presence_member_size_in_kb: Option<u32>,
+    pub max_event_payload_in_kb: Option<u32>,
+}
+
+impl<'a> ChannelLimits<'a> {
+    /// Resolve the limits of `channel`: the first matching rule of the app, with unset
+    /// limits taken from the app itself
+    pub fn resolve(app: &'a App, channel: &str) -> Self {
+        let rule = app
+            .channel_limits
+            .as_deref()
+            .and_then(|rules| rules.iter().find(|r| wildcard_match(&r.pattern, channel)));
+
+        Self {
+            rule,
+            max_presence_members_per_channel: rule
+                .and_then(|r| r.max_presence_members_per_channel)
+                .or(app.max_presence_members_per_channel),
+            max_presence_member_size_in_kb: rule
+                .and_then(|r| r.max_presence_member_size_in_kb)
+                .or(app.max_presence_member_size_in_kb),
+            max_event_payload_in_kb: rule
+                .and_then(|r| r.max_event_payload_in_kb)
+                .or(app.max_event_payload_in_kb),
+        }
+    }
+
+    /// The matched rule's client event rate, if it sets one. Events to channels without
+    /// one count against the app-wide `max_client_events_per_second`.
+    pub fn client_events_rule(&self) -> Option<(&'a str, u32)> {
+        self.rule.and_then(|r| {
+            r.max_client_events_per_second
+                .map(|limit| (r.pattern.as_str(), limit))
+        })
+    }
+}
+
+/// Check the rules before they are stored
+pub fn validate_rules(rules: &[ChannelLimitRule]) -> Result<(), String> {
+    for (index, rule) in rules.iter().enumerate() {
+        if rule.pattern.is_empty() {
+            return Err(format!("channel_limits[{index}]: pattern is empty"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        App {
+            max_presence_members_per_channel: Some(100),
+            max_event_payload_in_kb: Some(10),
+            channel_limits: Some(vec![
+                ChannelLimitRule {
+                    pattern: "presence-lobby-*".to_string(),
+                    max_presence_members_per_channel: Some(5000),
+                    ..Default::default()
+                },
+                ChannelLimitRule {
+                    pattern: "private-telemetry-*".to_string(),
+                    max_event_payload_in_kb: Some(64),
+                    max_client_events_per_second: Some(50),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matching_rule_overrides_app_limits() {
+        let app = app();
+        let limits = ChannelLimits::resolve(&app, "presence-lobby-1");
+        assert_eq!(limits.max_presence_members_per_channel, Some(5000));
+        assert_eq!(limits.max_event_payload_in_kb, Some(10));
+        assert_eq!(limits.client_events_rule(), None);
+
+        let limits = ChannelLimits::resolve(&app, "private-telemetry-eu");
+        assert_eq!(limits.max_event_payload_in_kb, Some(64));
+        assert_eq!(
+            limits.client_events_rule(),
+            Some(("private-telemetry-*", 50))
+        );
+    }
+
+    #[test]
+    fn test_unmatched_channel_uses_app_limits() {
+        let app = app();
+        let limits = ChannelLimits::resolve(&app, "presence-room");
+        assert!(limits.rule.is_none());
+        assert_eq!(limits.max_presence_members_per_channel, Some(100));
+        assert_eq!(limits.max_event_payload_in_kb, Some(10));
+    }
+}
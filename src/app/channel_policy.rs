@@ -0,0 +1,166 @@
+// src/app/channel_policy.rs
+use crate::channel::ChannelType;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// Whether a rule lets matching channels through
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelAccess {
+    #[default]
+    Allow,
+    Deny,
+}
+
+/// Per-app restrictions on the channels clients may subscribe to and the HTTP API may
+/// publish to
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ChannelPolicy {
+    /// Channel types that cannot be used at all
+    #[serde(default)]
+    pub disabled_types: Vec<ChannelType>,
+    /// Checked in order; the first matching rule decides
+    #[serde(default)]
+    pub rules: Vec<ChannelRule>,
+    /// Applies to channels that no rule matches
+    #[serde(default)]
+    pub default: ChannelAccess,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelRule {
+    pub access: ChannelAccess,
+    /// Full channel name, where `*` matches any run of characters
+    pub pattern: String,
+    /// Only apply the rule to these channel types; empty means every type
+    #[serde(default)]
+    pub channel_types: Vec<ChannelType>,
+}
+
+impl ChannelRule {
+    fn matches(&self, channel: &str, channel_type: ChannelType) -> bool {
+        (self.channel_types.is_empty() || self.channel_types.contains(&channel_type))
+            && wildcard_match(&self.pattern, channel)
+    }
+}
+
+impl ChannelPolicy {
+    /// Reject channels the policy does not allow
+    pub fn check(&self, channel: &str) -> Result<()> {
+        let channel_type = ChannelType::from_name(channel);
+        if self.disabled_types.contains(&channel_type) {
+            return Err(Error::ChannelTypeNotAllowed(format!(
+                "{} channels are disabled for this app",
+                channel_type.as_str()
+            )));
+        }
+
+        let access = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(channel, channel_type))
+            .map_or(self.default, |rule| rule.access);
+        match access {
+            ChannelAccess::Allow => Ok(()),
+            ChannelAccess::Deny => Err(Error::ChannelNotAllowed(format!(
+                "{channel} is not allowed for this app"
+            ))),
+        }
+    }
+
+    /// Check the policy before it is stored
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.pattern.is_empty() {
+                return Err(format!("channel_policy.rules[{index}]: pattern is empty"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Match a channel name against a pattern where `*` matches any run of characters
+pub(crate) fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == name;
+    };
+    let Some(mut remaining) = name.strip_prefix(prefix) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let suffix = parts.pop().unwrap_or_default();
+    for part in parts {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(access: ChannelAccess, pattern: &str, channel_types: Vec<ChannelType>) -> ChannelRule {
+        ChannelRule {
+            access,
+            pattern: pattern.to_string(),
+            channel_types,
+        }
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("private-org-*", "private-org-42"));
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("*-updates", "presence-team-updates"));
+        assert!(wildcard_match("org-*-room-*", "org-1-room-2"));
+        assert!(!wildcard_match("org-*-room-*", "org-1-hall-2"));
+        assert!(!wildcard_match("private-org-*", "private-team-1"));
+        assert!(!wildcard_match("exact", "exact-not"));
+    }
+
+    #[test]
+    fn test_first_matching_rule_decides() {
+        let policy = ChannelPolicy {
+            rules: vec![
+                rule(ChannelAccess::Deny, "private-org-internal", vec![]),
+                rule(ChannelAccess::Allow, "private-org-*", vec![]),
+            ],
+            default: ChannelAccess::Deny,
+            ..Default::default()
+        };
+        assert!(policy.check("private-org-1").is_ok());
+        assert!(matches!(
+            policy.check("private-org-internal"),
+            Err(Error::ChannelNotAllowed(_))
+        ));
+        assert!(matches!(
+            policy.check("private-team-1"),
+            Err(Error::ChannelNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_rules_can_target_channel_types() {
+        let policy = ChannelPolicy {
+            rules: vec![rule(ChannelAccess::Deny, "*", vec![ChannelType::Presence])],
+            ..Default::default()
+        };
+        assert!(policy.check("private-chat").is_ok());
+        assert!(policy.check("presence-chat").is_err());
+    }
+
+    #[test]
+    fn test_disabled_types() {
+        let policy: ChannelPolicy =
+            serde_json::from_value(serde_json::json!({ "disabled_types": ["public"] })).unwrap();
+        assert!(matches!(
+            policy.check("news"),
+            Err(Error::ChannelTypeNotAllowed(_))
+        ));
+        assert!(policy.check("private-news").is_ok());
+    }
+}
@@ -1,3 +1,11 @@
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::AuthorizerConfig;
+use crate::app::channel_limits::ChannelLimitRule;
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::ip_filter::IpFilterConfig;
+use crate::app::jwt_auth::JwtAuthConfig;
+use crate::quota::AppQuotas;
+use crate::token::Token;
 use crate::webhook::types::Webhook;
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +46,144 @@ pub struct App {
     pub enable_watchlist_events: Option<bool>,
     #[serde(default, deserialize_with = "deserialize_and_validate_origins")]
     pub allowed_origins: Option<Vec<String>>,
+    /// Secrets accepted in addition to `secret`, e.g. the previous one during a rotation
+    #[serde(default)]
+    pub credentials: Option<Vec<AppCredential>>,
+    /// Restricts which channels may be used
+    #[serde(default)]
+    pub channel_policy: Option<ChannelPolicy>,
+    /// Limits for channels matching a pattern, overriding the app-wide ones
+    #[serde(default)]
+    pub channel_limits: Option<Vec<ChannelLimitRule>>,
+    /// Daily usage budgets; usage is only tracked for apps that have them
+    #[serde(default)]
+    pub quotas: Option<AppQuotas>,
+    /// Signed JWTs accepted for channel and user authentication besides `key:signature`
+    #[serde(default)]
+    pub jwt_auth: Option<JwtAuthConfig>,
+    /// Timestamp window and replay protection of HTTP API signatures
+    #[serde(default)]
+    pub api_auth: Option<ApiAuthConfig>,
+    /// IP ranges allowed to connect and to call the HTTP API
+    #[serde(default)]
+    pub ip_filter: Option<IpFilterConfig>,
+    /// Endpoint deciding on private and presence subscriptions sent without a signature
+    #[serde(default)]
+    pub authorizer: Option<AuthorizerConfig>,
+}
+
+/// Whether an additional key/secret pair is still accepted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialState {
+    /// Accepted for every signature, like the primary key and secret
+    #[default]
+    Active,
+    /// Still accepted while backends move to a newer pair
+    VerifyOnly,
+    /// Never accepted; kept for the record
+    Revoked,
+}
+
+/// A key/secret pair accepted alongside the primary `key` and `secret`. An empty key
+/// stands for the app key, so only the secret differs.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct AppCredential {
+    #[serde(default)]
+    pub key: String,
+    pub secret: String,
+    #[serde(default)]
+    pub state: CredentialState,
+    /// Unix timestamp in seconds after which the pair is no longer accepted
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl AppCredential {
+    /// Whether signatures made with this pair are accepted at `now` (unix seconds)
+    pub fn is_accepted_at(&self, now: u64) -> bool {
+        self.state != CredentialState::Revoked && self.expires_at.is_none_or(|at| now < at)
+    }
+}
+
+impl App {
+    /// Additional credentials currently accepted for signature verification
+    fn accepted_credentials(&self) -> impl Iterator<Item = &AppCredential> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.credentials
+            .iter()
+            .flatten()
+            .filter(move |credential| credential.is_accepted_at(now))
+    }
+
+    /// Additional secrets currently accepted for signature verification
+    pub fn accepted_credential_secrets(&self) -> Vec<String> {
+        self.accepted_credentials()
+            .map(|credential| credential.secret.clone())
+            .collect()
+    }
+
+    /// Key a credential is used with
+    fn credential_key<'a>(&'a self, credential: &'a AppCredential) -> &'a str {
+        if credential.key.is_empty() {
+            &self.key
+        } else {
+            &credential.key
+        }
+    }
+
+    /// The app key and the keys of all credentials, accepted or not, without duplicates
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys = vec![self.key.as_str()];
+        for credential in self.credentials.iter().flatten() {
+            let key = self.credential_key(credential);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+
+    /// Whether `key` is the app key or the key of an accepted credential
+    pub fn accepts_key(&self, key: &str) -> bool {
+        self.key == key
+            || self
+                .accepted_credentials()
+                .any(|credential| self.credential_key(credential) == key)
+    }
+
+    /// Reject channels this app's channel policy does not allow
+    pub fn check_channel_policy(&self, channel: &str) -> crate::error::Result<()> {
+        self.channel_policy
+            .as_ref()
+            .map_or(Ok(()), |policy| policy.check(channel))
+    }
+
+    /// Token that signs with the primary secret and verifies with any accepted secret
+    pub fn token(&self) -> Token {
+        Token::new(self.key.clone(), self.secret.clone())
+            .with_verification_secrets(self.accepted_credential_secrets())
+    }
+
+    /// Token that verifies only with the accepted secrets paired with `key`, or None
+    /// when the app does not accept the key
+    pub fn token_for_key(&self, key: &str) -> Option<Token> {
+        let mut secrets: Vec<String> = self
+            .accepted_credentials()
+            .filter(|credential| self.credential_key(credential) == key)
+            .map(|credential| credential.secret.clone())
+            .collect();
+        if self.key == key {
+            secrets.insert(0, self.secret.clone());
+        }
+        let (secret, verification_secrets) = secrets.split_first()?;
+        Token::new(key.to_string(), secret.clone())
+            .with_verification_secrets(verification_secrets.to_vec())
+            .into()
+    }
 }
 
 // Helper functions to deserialize numbers from strings or numbers
@@ -99,10 +245,19 @@ fn deserialize_optional_number_from_string<'de, D>(deserializer: D) -> Result<Op
 where
     D: serde::Deserializer<'de>,
 {
+    // Accept plain numbers too: cached apps are re-read from their own JSON serialization,
+    // and YAML/TOML app files write limits as numbers
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u32),
+        String(String),
+    }
+
     use serde::de::Error;
-    let value = Option::<String>::deserialize(deserializer)?;
-    match value {
-        Some(s) => Ok(Some(s.parse::<u32>().map_err(D::Error::custom)?)),
+    match Option::<NumberOrString>::deserialize(deserializer)? {
+        Some(NumberOrString::Number(n)) => Ok(Some(n)),
+        Some(NumberOrString::String(s)) => Ok(Some(s.parse::<u32>().map_err(D::Error::custom)?)),
         None => Ok(None),
     }
 }
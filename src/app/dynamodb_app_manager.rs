@@ -1,7 +1,14 @@
 // src/app/dynamodb_manager.rs
-use super::config::App;
+use super::config::{App, AppCredential};
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::AuthorizerConfig;
+use crate::app::channel_limits::ChannelLimitRule;
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::ip_filter::IpFilterConfig;
+use crate::app::jwt_auth::JwtAuthConfig;
 use crate::app::manager::AppManager;
 use crate::error::{Error, Result};
+use crate::quota::AppQuotas;
 use crate::webhook::types::Webhook;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -175,6 +182,102 @@ impl DynamoDbAppManager {
                 } else {
                     None
                 },
+                credentials: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("credentials")
+                {
+                    serde_json::from_str::<Vec<AppCredential>>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse credentials JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                channel_policy: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("channel_policy")
+                {
+                    serde_json::from_str::<ChannelPolicy>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse channel_policy JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                channel_limits: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("channel_limits")
+                {
+                    serde_json::from_str::<Vec<ChannelLimitRule>>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse channel_limits JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                quotas: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("quotas")
+                {
+                    serde_json::from_str::<AppQuotas>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse quotas JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                jwt_auth: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("jwt_auth")
+                {
+                    serde_json::from_str::<JwtAuthConfig>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse jwt_auth JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                api_auth: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("api_auth")
+                {
+                    serde_json::from_str::<ApiAuthConfig>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse api_auth JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                ip_filter: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("ip_filter")
+                {
+                    serde_json::from_str::<IpFilterConfig>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse ip_filter JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
+                authorizer: if let Some(aws_sdk_dynamodb::types::AttributeValue::S(json_str)) =
+                    map.get("authorizer")
+                {
+                    serde_json::from_str::<AuthorizerConfig>(json_str)
+                        .map_err(|e| {
+                            tracing::warn!("Failed to parse authorizer JSON: {}", e);
+                            e
+                        })
+                        .ok()
+                } else {
+                    None
+                },
             })
         } else {
             Err(Error::Internal("Invalid DynamoDB item format".to_string()))
@@ -315,15 +418,87 @@ impl DynamoDbAppManager {
             );
         }
 
+        if let Some(credentials) = &app.credentials {
+            let json_str = serde_json::to_string(credentials)
+                .expect("Failed to serialize credentials to JSON. This indicates a bug.");
+            item.insert(
+                "credentials".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(policy) = &app.channel_policy {
+            let json_str = serde_json::to_string(policy)
+                .expect("Failed to serialize channel_policy to JSON. This indicates a bug.");
+            item.insert(
+                "channel_policy".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(value) = &app.channel_limits {
+            let json_str = serde_json::to_string(value)
+                .expect("Failed to serialize channel_limits to JSON. This indicates a bug.");
+            item.insert(
+                "channel_limits".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(value) = &app.quotas {
+            let json_str = serde_json::to_string(value)
+                .expect("Failed to serialize quotas to JSON. This indicates a bug.");
+            item.insert(
+                "quotas".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(value) = &app.jwt_auth {
+            let json_str = serde_json::to_string(value)
+                .expect("Failed to serialize jwt_auth to JSON. This indicates a bug.");
+            item.insert(
+                "jwt_auth".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(value) = &app.api_auth {
+            let json_str = serde_json::to_string(value)
+                .expect("Failed to serialize api_auth to JSON. This indicates a bug.");
+            item.insert(
+                "api_auth".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(value) = &app.ip_filter {
+            let json_str = serde_json::to_string(value)
+                .expect("Failed to serialize ip_filter to JSON. This indicates a bug.");
+            item.insert(
+                "ip_filter".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
+        if let Some(value) = &app.authorizer {
+            let json_str = serde_json::to_string(value)
+                .expect("Failed to serialize authorizer to JSON. This indicates a bug.");
+            item.insert(
+                "authorizer".to_string(),
+                aws_sdk_dynamodb::types::AttributeValue::S(json_str),
+            );
+        }
+
         item
     }
 
-    /// Check if the DynamoDB table exists
-    async fn table_exists(&self) -> Result<bool> {
+    /// Check if a DynamoDB table exists
+    async fn table_exists(&self, table_name: &str) -> Result<bool> {
         let result = self
             .client
             .describe_table()
-            .table_name(&self.config.table_name)
+            .table_name(table_name)
             .send()
             .await;
 
@@ -332,7 +507,7 @@ impl DynamoDbAppManager {
 
     /// Create the DynamoDB table if it doesn't exist
     async fn ensure_table_exists(&self) -> Result<()> {
-        if self.table_exists().await? {
+        if self.table_exists(&self.config.table_name).await? {
             return Ok(());
         }
 
@@ -399,7 +574,11 @@ impl DynamoDbAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to create DynamoDB table: {e}")))?;
 
-        // Wait for table to be created
+        self.wait_for_table(&self.config.table_name).await
+    }
+
+    /// Wait for a newly created table to become active
+    async fn wait_for_table(&self, table_name: &str) -> Result<()> {
         let mut retries = 0;
         while retries < 10 {
             // Wait a bit before checking
@@ -408,7 +587,7 @@ impl DynamoDbAppManager {
             let table_status = self
                 .client
                 .describe_table()
-                .table_name(&self.config.table_name)
+                .table_name(table_name)
                 .send()
                 .await;
 
@@ -423,9 +602,132 @@ impl DynamoDbAppManager {
             retries += 1;
         }
 
-        Err(Error::Internal(
-            "Timeout waiting for DynamoDB table to be created".to_string(),
-        ))
+        Err(Error::Internal(format!(
+            "Timeout waiting for DynamoDB table {table_name} to be created"
+        )))
+    }
+
+    fn credential_keys_table(&self) -> String {
+        format!("{}_credential_keys", self.config.table_name)
+    }
+
+    /// Create the credential key lookup table if it doesn't exist and fill it from the
+    /// existing apps. A GSI cannot index the keys inside the credentials attribute.
+    async fn ensure_credential_keys_table_exists(&self) -> Result<()> {
+        let table_name = self.credential_keys_table();
+        if self.table_exists(&table_name).await? {
+            return Ok(());
+        }
+
+        self.client
+            .create_table()
+            .table_name(&table_name)
+            .key_schema(
+                aws_sdk_dynamodb::types::KeySchemaElement::builder()
+                    .attribute_name("key")
+                    .key_type(aws_sdk_dynamodb::types::KeyType::Hash)
+                    .build()
+                    .unwrap(),
+            )
+            .attribute_definitions(
+                aws_sdk_dynamodb::types::AttributeDefinition::builder()
+                    .attribute_name("key")
+                    .attribute_type(aws_sdk_dynamodb::types::ScalarAttributeType::S)
+                    .build()
+                    .unwrap(),
+            )
+            .provisioned_throughput(
+                aws_sdk_dynamodb::types::ProvisionedThroughput::builder()
+                    .read_capacity_units(5)
+                    .write_capacity_units(5)
+                    .build()
+                    .unwrap(),
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to create DynamoDB credential key table: {e}"
+                ))
+            })?;
+        self.wait_for_table(&table_name).await?;
+
+        for app in self.get_apps().await? {
+            self.sync_credential_keys(&app, None).await?;
+        }
+        Ok(())
+    }
+
+    /// Point the app's credential keys at it in the lookup table and drop the keys it
+    /// no longer has. The app key itself is found through the KeyIndex GSI.
+    async fn sync_credential_keys(&self, app: &App, previous: Option<&App>) -> Result<()> {
+        let keys = app.keys();
+        for key in keys.iter().filter(|key| **key != app.key) {
+            self.client
+                .put_item()
+                .table_name(self.credential_keys_table())
+                .item(
+                    "key",
+                    aws_sdk_dynamodb::types::AttributeValue::S(key.to_string()),
+                )
+                .item(
+                    "app_id",
+                    aws_sdk_dynamodb::types::AttributeValue::S(app.id.clone()),
+                )
+                .send()
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to store credential key: {e}")))?;
+        }
+
+        let removed: Vec<&str> = previous
+            .map(App::keys)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|key| !keys.contains(key))
+            .collect();
+        self.remove_credential_keys(&removed).await
+    }
+
+    async fn remove_credential_keys(&self, keys: &[&str]) -> Result<()> {
+        for key in keys {
+            self.client
+                .delete_item()
+                .table_name(self.credential_keys_table())
+                .key(
+                    "key",
+                    aws_sdk_dynamodb::types::AttributeValue::S(key.to_string()),
+                )
+                .send()
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to remove credential key: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Look a credential key up in the lookup table. Entries are only hints, so the
+    /// app must still accept the key.
+    async fn find_by_credential_key(&self, key: &str) -> Result<Option<App>> {
+        let response = self
+            .client
+            .get_item()
+            .table_name(self.credential_keys_table())
+            .key(
+                "key",
+                aws_sdk_dynamodb::types::AttributeValue::S(key.to_string()),
+            )
+            .send()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up credential key: {e}")))?;
+
+        let Some(aws_sdk_dynamodb::types::AttributeValue::S(app_id)) =
+            response.item().and_then(|item| item.get("app_id"))
+        else {
+            return Ok(None);
+        };
+        Ok(self
+            .get_app_internal(app_id)
+            .await?
+            .filter(|app| app.accepts_key(key)))
     }
 
     /// Get an app from DynamoDB
@@ -456,8 +758,9 @@ impl DynamoDbAppManager {
 #[async_trait]
 impl AppManager for DynamoDbAppManager {
     async fn init(&self) -> Result<()> {
-        // Create the table if it doesn't exist
-        self.ensure_table_exists().await
+        // Create the tables if they don't exist
+        self.ensure_table_exists().await?;
+        self.ensure_credential_keys_table_exists().await
     }
 
     async fn create_app(&self, config: App) -> Result<()> {
@@ -473,10 +776,12 @@ impl AppManager for DynamoDbAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to insert app into DynamoDB: {e}")))?;
 
-        Ok(())
+        self.sync_credential_keys(&config, None).await
     }
 
     async fn update_app(&self, config: App) -> Result<()> {
+        let previous = self.get_app_internal(&config.id).await?;
+
         // Convert App to DynamoDB item
         let item = self.app_to_item(&config);
 
@@ -489,10 +794,12 @@ impl AppManager for DynamoDbAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to update app in DynamoDB: {e}")))?;
 
-        Ok(())
+        self.sync_credential_keys(&config, previous.as_ref()).await
     }
 
     async fn delete_app(&self, app_id: &str) -> Result<()> {
+        let previous = self.get_app_internal(app_id).await?;
+
         // Remove item from DynamoDB
         self.client
             .delete_item()
@@ -505,7 +812,10 @@ impl AppManager for DynamoDbAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to delete app from DynamoDB: {e}")))?;
 
-        Ok(())
+        match previous {
+            Some(previous) => self.remove_credential_keys(&previous.keys()).await,
+            None => Ok(()),
+        }
     }
 
     async fn get_apps(&self) -> Result<Vec<App>> {
@@ -555,7 +865,7 @@ impl AppManager for DynamoDbAppManager {
             return Ok(Some(app));
         }
 
-        Ok(None)
+        self.find_by_credential_key(key).await
     }
 
     async fn find_by_id(&self, app_id: &str) -> Result<Option<App>> {
@@ -573,6 +883,7 @@ impl AppManager for DynamoDbAppManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::config::CredentialState;
 
     // Helper to create a test config for DynamoDB Local
     fn get_test_config(table_name: &str) -> DynamoDbConfig {
@@ -612,6 +923,14 @@ mod tests {
             webhooks: None,
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         }
     }
 
@@ -675,6 +994,49 @@ mod tests {
         manager.delete_app("dynamo_test2").await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_find_by_credential_key() {
+        // Skip test if DynamoDB Local is not available
+        if !is_dynamodb_available().await {
+            eprintln!("Skipping test: DynamoDB Local not available");
+            return;
+        }
+
+        let config = get_test_config("sockudo_credential_keys_test");
+        let manager = DynamoDbAppManager::new(config).await.unwrap();
+        manager.init().await.unwrap();
+
+        let mut app = create_test_app("credential_test");
+        app.credentials = Some(vec![AppCredential {
+            key: "credential_test_previous".to_string(),
+            secret: "previous_secret".to_string(),
+            state: CredentialState::VerifyOnly,
+            expires_at: None,
+        }]);
+        manager.create_app(app.clone()).await.unwrap();
+
+        let found = manager
+            .find_by_key("credential_test_previous")
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().id, "credential_test");
+        assert!(manager.find_by_key("unknown_key").await.unwrap().is_none());
+
+        // Revoking the credential stops the lookup
+        app.credentials.as_mut().unwrap()[0].state = CredentialState::Revoked;
+        manager.update_app(app).await.unwrap();
+        assert!(
+            manager
+                .find_by_key("credential_test_previous")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Cleanup
+        manager.delete_app("credential_test").await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_allowed_origins() {
         // Skip test if DynamoDB Local is not available
@@ -0,0 +1,159 @@
+// src/app/encrypted_app_manager.rs
+use crate::app::config::App;
+use crate::app::manager::{AppChange, AppManager};
+use crate::app::migrations::MigrationStatus;
+use crate::app::secret_cipher::SecretCipher;
+use crate::error::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::info;
+
+/// Decorator that encrypts app secrets before they reach the inner manager and decrypts
+/// them on the way out. It sits outside the app cache so cached apps stay encrypted too.
+pub struct EncryptedAppManager {
+    inner: Arc<dyn AppManager + Send + Sync>,
+    cipher: Arc<SecretCipher>,
+}
+
+impl EncryptedAppManager {
+    pub fn new(inner: Arc<dyn AppManager + Send + Sync>, cipher: Arc<SecretCipher>) -> Self {
+        Self { inner, cipher }
+    }
+
+    /// Rewrite every stored app whose secrets are plaintext or encrypted under an older
+    /// master key. With `dry_run` nothing is written. Returns the IDs of affected apps.
+    pub async fn reencrypt_all(&self, dry_run: bool) -> Result<Vec<String>> {
+        let mut affected = Vec::new();
+        for app in self.inner.get_apps().await? {
+            if !self.cipher.app_needs_reencryption(&app) {
+                continue;
+            }
+            let app_id = app.id.clone();
+            if !dry_run {
+                self.inner.update_app(self.cipher.encrypt_app(app)?).await?;
+                info!(app_id = %app_id, "Re-encrypted app secrets");
+            }
+            affected.push(app_id);
+        }
+        Ok(affected)
+    }
+
+    fn decrypt(&self, app: Option<App>) -> Result<Option<App>> {
+        app.map(|app| self.cipher.decrypt_app(app)).transpose()
+    }
+}
+
+#[async_trait]
+impl AppManager for EncryptedAppManager {
+    async fn init(&self) -> Result<()> {
+        self.inner.init().await
+    }
+
+    async fn create_app(&self, config: App) -> Result<()> {
+        self.inner
+            .create_app(self.cipher.encrypt_app(config)?)
+            .await
+    }
+
+    async fn update_app(&self, config: App) -> Result<()> {
+        self.inner
+            .update_app(self.cipher.encrypt_app(config)?)
+            .await
+    }
+
+    async fn delete_app(&self, app_id: &str) -> Result<()> {
+        self.inner.delete_app(app_id).await
+    }
+
+    async fn get_apps(&self) -> Result<Vec<App>> {
+        self.inner
+            .get_apps()
+            .await?
+            .into_iter()
+            .map(|app| self.cipher.decrypt_app(app))
+            .collect()
+    }
+
+    async fn find_by_key(&self, key: &str) -> Result<Option<App>> {
+        self.decrypt(self.inner.find_by_key(key).await?)
+    }
+
+    async fn find_by_id(&self, app_id: &str) -> Result<Option<App>> {
+        self.decrypt(self.inner.find_by_id(app_id).await?)
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        self.inner.check_health().await
+    }
+
+    async fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        self.inner.migrate(dry_run).await
+    }
+
+    async fn invalidate_cache(&self, app_id: &str) -> Result<()> {
+        self.inner.invalidate_cache(app_id).await
+    }
+
+    /// Changes are passed on as reported by the inner manager; subscribers only look at
+    /// IDs and flags, never at secrets
+    fn subscribe_changes(&self) -> Option<broadcast::Receiver<AppChange>> {
+        self.inner.subscribe_changes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::memory_app_manager::MemoryAppManager;
+
+    const KEY: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn create_test_app(id: &str) -> App {
+        App {
+            id: id.to_string(),
+            key: format!("{id}_key"),
+            secret: format!("{id}_secret"),
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_stored_encrypted() {
+        let inner = Arc::new(MemoryAppManager::new());
+        let cipher = Arc::new(SecretCipher::new(&[("k1".into(), KEY.into())], None).unwrap());
+        let manager = EncryptedAppManager::new(inner.clone(), cipher);
+
+        manager.create_app(create_test_app("app1")).await.unwrap();
+
+        let stored = inner.find_by_id("app1").await.unwrap().unwrap();
+        assert!(SecretCipher::is_encrypted(&stored.secret));
+        let app = manager.find_by_key("app1_key").await.unwrap().unwrap();
+        assert_eq!(app.secret, "app1_secret");
+    }
+
+    #[tokio::test]
+    async fn test_reencrypt_all() {
+        let inner = Arc::new(MemoryAppManager::new());
+        inner.create_app(create_test_app("legacy")).await.unwrap();
+        let cipher = Arc::new(SecretCipher::new(&[("k1".into(), KEY.into())], None).unwrap());
+        let manager = EncryptedAppManager::new(inner.clone(), cipher);
+
+        // Plaintext rows stay readable before the migration
+        let app = manager.find_by_id("legacy").await.unwrap().unwrap();
+        assert_eq!(app.secret, "legacy_secret");
+
+        assert_eq!(manager.reencrypt_all(true).await.unwrap(), vec!["legacy"]);
+        let stored = inner.find_by_id("legacy").await.unwrap().unwrap();
+        assert!(!SecretCipher::is_encrypted(&stored.secret));
+
+        assert_eq!(manager.reencrypt_all(false).await.unwrap(), vec!["legacy"]);
+        let stored = inner.find_by_id("legacy").await.unwrap().unwrap();
+        assert!(SecretCipher::is_encrypted(&stored.secret));
+        assert!(manager.reencrypt_all(false).await.unwrap().is_empty());
+
+        let app = manager.find_by_id("legacy").await.unwrap().unwrap();
+        assert_eq!(app.secret, "legacy_secret");
+    }
+}
@@ -2,6 +2,8 @@
 use crate::app::cached_app_manager::CachedAppManager;
 #[cfg(feature = "dynamodb")]
 use crate::app::dynamodb_app_manager::{DynamoDbAppManager, DynamoDbConfig};
+use crate::app::encrypted_app_manager::EncryptedAppManager;
+use crate::app::file_app_manager::FileAppManager;
 use crate::app::manager::AppManager;
 use crate::app::memory_app_manager::MemoryAppManager;
 #[cfg(feature = "mysql")]
@@ -10,8 +12,11 @@ use crate::app::mysql_app_manager::MySQLAppManager;
 use crate::app::pg_app_manager::PgSQLAppManager;
 #[cfg(feature = "scylladb")]
 use crate::app::scylla_app_manager::{ScyllaDbAppManager, ScyllaDbConfig};
+use crate::app::secret_cipher::SecretCipher;
+#[cfg(feature = "sqlite")]
+use crate::app::sqlite_app_manager::SqliteAppManager;
 use crate::cache::manager::CacheManager;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::options::{AppManagerConfig, AppManagerDriver, DatabaseConfig, DatabasePooling};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -20,34 +25,100 @@ use tracing::{info, warn};
 pub struct AppManagerFactory;
 
 impl AppManagerFactory {
-    #[allow(unused_variables)]
     pub async fn create(
         config: &AppManagerConfig,
         db_config: &DatabaseConfig,
         pooling: &DatabasePooling,
         cache_manager: Arc<Mutex<dyn CacheManager + Send + Sync>>,
+    ) -> Result<Arc<dyn AppManager + Send + Sync>> {
+        let inner = Self::create_store(config, db_config, pooling, true).await?;
+
+        let manager = if config.cache.enabled {
+            Arc::new(CachedAppManager::new(
+                inner,
+                cache_manager,
+                config.cache.clone(),
+            )) as Arc<dyn AppManager + Send + Sync>
+        } else {
+            inner
+        };
+
+        // Outside the cache, so cached apps hold encrypted secrets as well
+        if config.secret_encryption.enabled {
+            let cipher = SecretCipher::from_config(&config.secret_encryption)?;
+            info!(
+                "App secrets are encrypted with master key '{}'",
+                cipher.primary_key_id()
+            );
+            return Ok(Arc::new(EncryptedAppManager::new(
+                manager,
+                Arc::new(cipher),
+            )));
+        }
+
+        Ok(manager)
+    }
+
+    /// Apply pending schema migrations right after a store is opened, before anything
+    /// else (like the PostgreSQL change trigger) relies on its tables
+    #[cfg(any(
+        feature = "mysql",
+        feature = "postgres",
+        feature = "sqlite",
+        feature = "scylladb"
+    ))]
+    async fn migrated<M: AppManager>(manager: M, config: &AppManagerConfig) -> Result<M> {
+        if config.migrations.run_on_startup {
+            manager.migrate(false).await?;
+        }
+        Ok(manager)
+    }
+
+    /// The memory manager standing in for a store that could not be opened, or an error
+    /// when the caller needs the configured store itself
+    fn memory_fallback(
+        reason: String,
+        allow_memory_fallback: bool,
+    ) -> Result<Arc<dyn AppManager + Send + Sync>> {
+        if !allow_memory_fallback {
+            return Err(Error::Internal(reason));
+        }
+        warn!("{}, falling back to memory manager", reason);
+        Ok(Arc::new(MemoryAppManager::new()))
+    }
+
+    /// Create the app store for the configured driver, without caching or encryption.
+    /// Stores that cannot be opened fall back to memory if `allow_memory_fallback` is set
+    /// and are errors otherwise; failed migrations are always errors.
+    #[allow(unused_variables)]
+    pub async fn create_store(
+        config: &AppManagerConfig,
+        db_config: &DatabaseConfig,
+        pooling: &DatabasePooling,
+        allow_memory_fallback: bool,
     ) -> Result<Arc<dyn AppManager + Send + Sync>> {
         info!(
             "{}",
             format!("Initializing AppManager with driver: {:?}", config.driver)
         );
-        let inner: Arc<dyn AppManager + Send + Sync> = match config.driver {
+        Ok(match config.driver {
             // Match on the enum
             #[cfg(feature = "mysql")]
             AppManagerDriver::Mysql => {
                 let mysql_db_config = db_config.mysql.clone();
                 match MySQLAppManager::new(mysql_db_config, pooling.clone()).await {
-                    Ok(manager) => Arc::new(manager),
-                    Err(e) => {
-                        warn!(
-                            "{}",
-                            format!(
-                                "Failed to initialize MySQL app manager: {}, falling back to memory manager",
-                                e
-                            )
-                        );
-                        Arc::new(MemoryAppManager::new())
+                    Ok(manager) => {
+                        let mut manager = Self::migrated(manager, config).await?;
+                        let interval_ms = config.invalidation.mysql_poll_interval_ms;
+                        if interval_ms > 0 {
+                            manager.poll_for_changes(std::time::Duration::from_millis(interval_ms));
+                        }
+                        Arc::new(manager)
                     }
+                    Err(e) => Self::memory_fallback(
+                        format!("Failed to initialize MySQL app manager: {e}"),
+                        allow_memory_fallback,
+                    )?,
                 }
             }
             #[cfg(feature = "dynamodb")]
@@ -65,33 +136,32 @@ impl AppManagerFactory {
                 };
                 match DynamoDbAppManager::new(dynamo_app_config).await {
                     Ok(manager) => Arc::new(manager),
-                    Err(e) => {
-                        warn!(
-                            "{}",
-                            format!(
-                                "Failed to initialize DynamoDB app manager: {}, falling back to memory manager",
-                                e
-                            )
-                        );
-                        Arc::new(MemoryAppManager::new())
-                    }
+                    Err(e) => Self::memory_fallback(
+                        format!("Failed to initialize DynamoDB app manager: {e}"),
+                        allow_memory_fallback,
+                    )?,
                 }
             }
             #[cfg(feature = "postgres")]
             AppManagerDriver::PgSql => {
                 let pgsql_db_config = db_config.postgres.clone();
                 match PgSQLAppManager::new(pgsql_db_config, pooling.clone()).await {
-                    Ok(manager) => Arc::new(manager),
-                    Err(e) => {
-                        warn!(
-                            "{}",
-                            format!(
-                                "Failed to initialize PgSQL app manager: {}, falling back to memory manager",
-                                e
-                            )
-                        );
-                        Arc::new(MemoryAppManager::new())
+                    Ok(manager) => {
+                        let mut manager = Self::migrated(manager, config).await?;
+                        if config.invalidation.postgres_notify
+                            && let Err(e) = manager.listen_for_changes().await
+                        {
+                            warn!(
+                                "{}",
+                                format!("App changes will not be picked up from PostgreSQL: {}", e)
+                            );
+                        }
+                        Arc::new(manager)
                     }
+                    Err(e) => Self::memory_fallback(
+                        format!("Failed to initialize PgSQL app manager: {e}"),
+                        allow_memory_fallback,
+                    )?,
                 }
             }
             #[cfg(feature = "scylladb")]
@@ -108,66 +178,69 @@ impl AppManagerFactory {
                     replication_factor: scylla_settings.replication_factor,
                 };
                 match ScyllaDbAppManager::new(scylla_config).await {
-                    Ok(manager) => Arc::new(manager),
-                    Err(e) => {
+                    Ok(manager) => Arc::new(Self::migrated(manager, config).await?),
+                    Err(e) => Self::memory_fallback(
+                        format!("Failed to initialize ScyllaDB app manager: {e}"),
+                        allow_memory_fallback,
+                    )?,
+                }
+            }
+            #[cfg(feature = "sqlite")]
+            AppManagerDriver::Sqlite => {
+                match SqliteAppManager::new(db_config.sqlite.clone(), pooling.clone()).await {
+                    Ok(manager) => Arc::new(Self::migrated(manager, config).await?),
+                    Err(e) => Self::memory_fallback(
+                        format!("Failed to initialize SQLite app manager: {e}"),
+                        allow_memory_fallback,
+                    )?,
+                }
+            }
+            AppManagerDriver::File => match FileAppManager::new(config.file.clone()) {
+                Ok(manager) => {
+                    let manager = Arc::new(manager);
+                    if let Err(e) = manager.start_watching() {
                         warn!(
                             "{}",
-                            format!(
-                                "Failed to initialize ScyllaDB app manager: {}, falling back to memory manager",
-                                e
-                            )
+                            format!("App file changes will not be reloaded: {}", e)
                         );
-                        Arc::new(MemoryAppManager::new())
                     }
+                    manager
                 }
-            }
+                Err(e) => Self::memory_fallback(
+                    format!("Failed to initialize file app manager: {e}"),
+                    allow_memory_fallback,
+                )?,
+            },
             AppManagerDriver::Memory => {
                 // Handle unknown as Memory or make it an error
                 info!("{}", "Using memory app manager.".to_string());
                 Arc::new(MemoryAppManager::new())
             }
             #[cfg(not(feature = "mysql"))]
-            AppManagerDriver::Mysql => {
-                warn!(
-                    "{}",
-                    "MySQL app manager requested but not compiled in. Falling back to memory manager."
-                );
-                Arc::new(MemoryAppManager::new())
-            }
+            AppManagerDriver::Mysql => Self::memory_fallback(
+                "MySQL app manager requested but not compiled in".to_string(),
+                allow_memory_fallback,
+            )?,
             #[cfg(not(feature = "dynamodb"))]
-            AppManagerDriver::Dynamodb => {
-                warn!(
-                    "{}",
-                    "DynamoDB app manager requested but not compiled in. Falling back to memory manager."
-                );
-                Arc::new(MemoryAppManager::new())
-            }
+            AppManagerDriver::Dynamodb => Self::memory_fallback(
+                "DynamoDB app manager requested but not compiled in".to_string(),
+                allow_memory_fallback,
+            )?,
             #[cfg(not(feature = "postgres"))]
-            AppManagerDriver::PgSql => {
-                warn!(
-                    "{}",
-                    "PostgreSQL app manager requested but not compiled in. Falling back to memory manager."
-                );
-                Arc::new(MemoryAppManager::new())
-            }
+            AppManagerDriver::PgSql => Self::memory_fallback(
+                "PostgreSQL app manager requested but not compiled in".to_string(),
+                allow_memory_fallback,
+            )?,
             #[cfg(not(feature = "scylladb"))]
-            AppManagerDriver::ScyllaDb => {
-                warn!(
-                    "{}",
-                    "ScyllaDB app manager requested but not compiled in. Falling back to memory manager."
-                );
-                Arc::new(MemoryAppManager::new())
-            }
-        };
-
-        if config.cache.enabled {
-            Ok(Arc::new(CachedAppManager::new(
-                inner,
-                cache_manager,
-                config.cache.clone(),
-            )) as Arc<dyn AppManager + Send + Sync>)
-        } else {
-            Ok(inner)
-        }
+            AppManagerDriver::ScyllaDb => Self::memory_fallback(
+                "ScyllaDB app manager requested but not compiled in".to_string(),
+                allow_memory_fallback,
+            )?,
+            #[cfg(not(feature = "sqlite"))]
+            AppManagerDriver::Sqlite => Self::memory_fallback(
+                "SQLite app manager requested but not compiled in".to_string(),
+                allow_memory_fallback,
+            )?,
+        })
     }
 }
@@ -0,0 +1,351 @@
+// src/app/file_app_manager.rs
+use super::config::App;
+use crate::app::admin::validate_app;
+use crate::app::manager::{AppChange, AppManager};
+use crate::error::{Error, Result};
+use crate::options::FileAppManagerConfig;
+use async_trait::async_trait;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, error, info, warn};
+
+const APP_FILE_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "toml"];
+
+/// Apps currently served, indexed by id and key
+#[derive(Default)]
+struct AppSet {
+    by_id: HashMap<String, App>,
+    id_by_key: HashMap<String, String>,
+}
+
+impl AppSet {
+    fn new(apps: Vec<App>) -> Self {
+        let id_by_key = apps
+            .iter()
+            .flat_map(|app| {
+                app.keys()
+                    .into_iter()
+                    .map(|key| (key.to_string(), app.id.clone()))
+            })
+            .collect();
+        let by_id = apps.into_iter().map(|app| (app.id.clone(), app)).collect();
+        Self { by_id, id_by_key }
+    }
+}
+
+/// Read-only AppManager backed by a JSON, YAML or TOML file, or a directory of them.
+///
+/// Each file holds either a list of apps or an object with an `apps` list. When watching
+/// is enabled the source is reloaded after changes; a reload that fails to parse or
+/// validate is rejected as a whole and the previous apps stay in place.
+pub struct FileAppManager {
+    config: FileAppManagerConfig,
+    apps: RwLock<Arc<AppSet>>,
+    changes: broadcast::Sender<AppChange>,
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl FileAppManager {
+    /// Load the apps once; fails if the source cannot be read or is invalid
+    pub fn new(config: FileAppManagerConfig) -> Result<Self> {
+        let apps = load_apps(Path::new(&config.path))?;
+        info!("Loaded {} apps from {}", apps.len(), config.path);
+
+        let (changes, _) = broadcast::channel(256);
+        Ok(Self {
+            config,
+            apps: RwLock::new(Arc::new(AppSet::new(apps))),
+            changes,
+            watcher: Mutex::new(None),
+        })
+    }
+
+    fn snapshot(&self) -> Arc<AppSet> {
+        self.apps
+            .read()
+            .map(|apps| apps.clone())
+            .unwrap_or_default()
+    }
+
+    /// Re-read the source and swap in the new apps if they are valid.
+    /// Returns what changed; subscribers are notified of the same changes.
+    pub fn reload(&self) -> Result<Vec<AppChange>> {
+        let apps = load_apps(Path::new(&self.config.path))?;
+        let next = Arc::new(AppSet::new(apps));
+
+        let previous = {
+            let mut current = self
+                .apps
+                .write()
+                .map_err(|_| Error::Internal("App file state lock poisoned".to_string()))?;
+            std::mem::replace(&mut *current, next.clone())
+        };
+
+        let changes = diff_apps(&previous, &next);
+        if changes.is_empty() {
+            debug!("Reloaded {} without app changes", self.config.path);
+        } else {
+            info!(
+                "Reloaded {}: {} apps, {} changed",
+                self.config.path,
+                next.by_id.len(),
+                changes.len()
+            );
+        }
+        for change in &changes {
+            // Sending only fails when nobody is subscribed
+            let _ = self.changes.send(change.clone());
+        }
+        Ok(changes)
+    }
+
+    /// Watch the source path and reload after it changes, if watching is enabled.
+    /// Takes the shared handle so the reload task can hold a weak reference.
+    pub fn start_watching(self: &Arc<Self>) -> Result<()> {
+        if !self.config.watch {
+            return Ok(());
+        }
+
+        let path = PathBuf::from(&self.config.path);
+        // Editors often replace files instead of writing in place, so watch the parent
+        // directory of a single file and filter events down to that file
+        let (watch_path, file_filter) = if path.is_dir() {
+            (path.clone(), None)
+        } else {
+            let parent = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from("."));
+            (parent, path.file_name().map(|name| name.to_os_string()))
+        };
+
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel::<()>();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event) => {
+                    let relevant =
+                        event.kind.is_create() || event.kind.is_modify() || event.kind.is_remove();
+                    let matches_file = file_filter.as_ref().is_none_or(|name| {
+                        event
+                            .paths
+                            .iter()
+                            .any(|path| path.file_name() == Some(name.as_os_str()))
+                    });
+                    if relevant && matches_file {
+                        let _ = event_tx.send(());
+                    }
+                }
+                Err(e) => warn!("App file watcher error: {}", e),
+            })
+            .map_err(|e| Error::ConfigFile(format!("Failed to create app file watcher: {e}")))?;
+        watcher
+            .watch(&watch_path, RecursiveMode::NonRecursive)
+            .map_err(|e| {
+                Error::ConfigFile(format!(
+                    "Failed to watch {} for app changes: {e}",
+                    watch_path.display()
+                ))
+            })?;
+
+        if let Ok(mut slot) = self.watcher.lock() {
+            *slot = Some(watcher);
+        }
+
+        let manager = Arc::downgrade(self);
+        let debounce = Duration::from_millis(self.config.debounce_ms);
+        tokio::spawn(async move {
+            while event_rx.recv().await.is_some() {
+                // Let bursts of events (editor temp files, multi-file copies) settle
+                tokio::time::sleep(debounce).await;
+                while event_rx.try_recv().is_ok() {}
+
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                if let Err(e) = manager.reload() {
+                    error!(
+                        "Rejected app reload from {}, keeping previous apps: {}",
+                        manager.config.path, e
+                    );
+                }
+            }
+        });
+
+        info!("Watching {} for app changes", self.config.path);
+        Ok(())
+    }
+
+    fn read_only_error(&self) -> Error {
+        Error::Config(format!(
+            "File app manager is read-only; edit {} instead",
+            self.config.path
+        ))
+    }
+}
+
+/// Read and validate every app from a file or directory
+pub fn load_apps(path: &Path) -> Result<Vec<App>> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+            .map_err(|e| {
+                Error::ConfigFile(format!("Failed to read directory {}: {e}", path.display()))
+            })?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.is_file() && app_file_format(file).is_some())
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut apps = Vec::new();
+    for file in &files {
+        apps.extend(load_app_file(file)?);
+    }
+
+    let mut ids = HashSet::new();
+    let mut keys = HashSet::new();
+    for app in &apps {
+        validate_app(app).map_err(|e| Error::ConfigFile(format!("App {}: {e}", app.id)))?;
+        if !ids.insert(app.id.as_str()) {
+            return Err(Error::ConfigFile(format!("Duplicate app id {}", app.id)));
+        }
+        for key in app.keys() {
+            if !keys.insert(key) {
+                return Err(Error::ConfigFile(format!("Duplicate app key {key}")));
+            }
+        }
+    }
+
+    Ok(apps)
+}
+
+fn app_file_format(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    APP_FILE_EXTENSIONS
+        .iter()
+        .find(|known| **known == extension)
+        .copied()
+}
+
+fn load_app_file(path: &Path) -> Result<Vec<App>> {
+    let format = app_file_format(path).ok_or_else(|| {
+        Error::ConfigFile(format!(
+            "Unsupported app file {}: expected one of {}",
+            path.display(),
+            APP_FILE_EXTENSIONS.join(", ")
+        ))
+    })?;
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::ConfigFile(format!("Failed to read {}: {e}", path.display())))?;
+
+    let document: Value = match format {
+        "json" => serde_json::from_str(&content).map_err(|e| e.to_string()),
+        "yaml" | "yml" => serde_norway::from_str(&content).map_err(|e| e.to_string()),
+        _ => toml::from_str(&content).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| Error::ConfigFile(format!("Failed to parse {}: {e}", path.display())))?;
+
+    let entries = match document {
+        Value::Array(entries) => entries,
+        Value::Object(mut object) => match object.remove("apps") {
+            Some(Value::Array(entries)) => entries,
+            _ => {
+                return Err(Error::ConfigFile(format!(
+                    "{} must contain a list of apps or an `apps` list",
+                    path.display()
+                )));
+            }
+        },
+        Value::Null => Vec::new(),
+        _ => {
+            return Err(Error::ConfigFile(format!(
+                "{} must contain a list of apps or an `apps` list",
+                path.display()
+            )));
+        }
+    };
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            serde_json::from_value::<App>(entry)
+                .map_err(|e| Error::ConfigFile(format!("{} app #{index}: {e}", path.display())))
+        })
+        .collect()
+}
+
+fn diff_apps(previous: &AppSet, next: &AppSet) -> Vec<AppChange> {
+    let mut changes = Vec::new();
+    for (id, app) in &next.by_id {
+        match previous.by_id.get(id) {
+            None => changes.push(AppChange::Created(app.clone())),
+            Some(old) if serde_json::to_value(old).ok() != serde_json::to_value(app).ok() => {
+                changes.push(AppChange::Updated {
+                    previous: Box::new(old.clone()),
+                    current: Box::new(app.clone()),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for (id, app) in &previous.by_id {
+        if !next.by_id.contains_key(id) {
+            changes.push(AppChange::Deleted(app.clone()));
+        }
+    }
+    changes
+}
+
+#[async_trait]
+impl AppManager for FileAppManager {
+    async fn init(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_app(&self, _config: App) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn update_app(&self, _config: App) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn delete_app(&self, _app_id: &str) -> Result<()> {
+        Err(self.read_only_error())
+    }
+
+    async fn get_apps(&self) -> Result<Vec<App>> {
+        Ok(self.snapshot().by_id.values().cloned().collect())
+    }
+
+    async fn find_by_key(&self, key: &str) -> Result<Option<App>> {
+        let apps = self.snapshot();
+        Ok(apps
+            .id_by_key
+            .get(key)
+            .and_then(|id| apps.by_id.get(id))
+            .filter(|app| app.accepts_key(key))
+            .cloned())
+    }
+
+    async fn find_by_id(&self, app_id: &str) -> Result<Option<App>> {
+        Ok(self.snapshot().by_id.get(app_id).cloned())
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn subscribe_changes(&self) -> Option<broadcast::Receiver<AppChange>> {
+        Some(self.changes.subscribe())
+    }
+}
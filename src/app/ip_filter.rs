@@ -0,0 +1,114 @@
+// src/app/ip_filter.rs
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// IP ranges allowed to reach an app, separately for client connections and the HTTP API
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IpFilterConfig {
+    /// Rules for WebSocket client connections
+    #[serde(default)]
+    pub websocket: IpRules,
+    /// Rules for HTTP API requests signed with the app key
+    #[serde(default)]
+    pub http_api: IpRules,
+}
+
+/// CIDR ranges (or single addresses) to allow and deny. A matching `deny` entry always
+/// rejects; a non-empty `allow` list rejects every address it does not match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IpRules {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl IpRules {
+    /// Whether there are no rules, so every client is allowed
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Whether the address may connect. Entries that do not parse never match.
+    pub fn allows(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        let matches = |entries: &[String]| {
+            entries
+                .iter()
+                .filter_map(|entry| parse_range(entry))
+                .any(|range| range.contains(&ip))
+        };
+        !matches(&self.deny) && (self.allow.is_empty() || matches(&self.allow))
+    }
+
+    /// Like `allows`, for a client whose address may be unknown; only an allow list
+    /// rejects those
+    pub fn allows_client(&self, ip: Option<IpAddr>) -> bool {
+        ip.map_or(self.allow.is_empty(), |ip| self.allows(ip))
+    }
+}
+
+fn parse_range(entry: &str) -> Option<IpNet> {
+    let entry = entry.trim();
+    entry
+        .parse::<IpNet>()
+        .ok()
+        .or_else(|| entry.parse::<IpAddr>().ok().map(IpNet::from))
+}
+
+pub fn validate_config(config: &IpFilterConfig) -> std::result::Result<(), String> {
+    for (access, rules) in [
+        ("websocket", &config.websocket),
+        ("http_api", &config.http_api),
+    ] {
+        for (list, entries) in [("allow", &rules.allow), ("deny", &rules.deny)] {
+            if let Some(entry) = entries.iter().find(|entry| parse_range(entry).is_none()) {
+                return Err(format!(
+                    "ip_filter.{access}.{list}: '{entry}' is not an IP address or CIDR range"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(allow: &[&str], deny: &[&str]) -> IpRules {
+        IpRules {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_deny_wins_and_allow_is_exclusive() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+
+        assert!(rules(&[], &[]).allows(ip("203.0.113.7")));
+        assert!(!rules(&[], &["203.0.113.0/24"]).allows(ip("203.0.113.7")));
+
+        let office = rules(&["10.0.0.0/8", "2001:db8::/32"], &["10.0.0.13"]);
+        assert!(office.allows(ip("10.1.2.3")));
+        assert!(office.allows(ip("2001:db8::1")));
+        assert!(office.allows(ip("::ffff:10.1.2.3")));
+        assert!(!office.allows(ip("10.0.0.13")));
+        assert!(!office.allows(ip("192.168.1.1")));
+    }
+
+    #[test]
+    fn test_validate_rejects_unparsable_entries() {
+        let mut config = IpFilterConfig {
+            websocket: rules(&["10.0.0.0/8", "127.0.0.1"], &[]),
+            http_api: IpRules::default(),
+        };
+        assert!(validate_config(&config).is_ok());
+
+        config.http_api.deny.push("10.0.0.0/33".to_string());
+        let err = validate_config(&config).unwrap_err();
+        assert!(err.contains("ip_filter.http_api.deny"));
+    }
+}
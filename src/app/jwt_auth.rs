@@ -0,0 +1,446 @@
+// src/app/jwt_auth.rs
+use crate::app::channel_policy::wildcard_match;
+use crate::app::config::App;
+use crate::error::{Error, Result};
+use crate::websocket::SocketId;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use dashmap::DashMap;
+use ring::signature::{
+    ED25519, RSA_PKCS1_2048_8192_SHA256, RsaPublicKeyComponents, UnparsedPublicKey,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// How long the keys of a JWKS file are used before the file is checked for changes
+const JWKS_RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Keys of a JWKS file, the modification time they were read at and when it was last checked
+type CachedJwks = (SystemTime, Instant, Arc<Vec<Jwk>>);
+
+/// Keys of JWKS files by path
+static JWKS_FILES: LazyLock<DashMap<String, CachedJwks>> = LazyLock::new(DashMap::new);
+
+/// Signed JWTs an app accepts in place of `key:signature` auth strings.
+///
+/// Tokens are signed with HS256 using the app secret, or with RS256 / EdDSA using one of
+/// the configured public keys.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JwtAuthConfig {
+    /// Accept HS256 tokens signed with the app secret
+    #[serde(default = "default_allow_hs256")]
+    pub allow_hs256: bool,
+    /// Public keys for RS256 and EdDSA tokens
+    #[serde(default)]
+    pub keys: Vec<Jwk>,
+    /// Path of a JWKS file with more public keys; it is read again when it changes
+    #[serde(default)]
+    pub jwks_file: Option<String>,
+}
+
+fn default_allow_hs256() -> bool {
+    true
+}
+
+/// Public key in JWK format: `RSA` keys with `n` and `e`, or `OKP` keys with
+/// `crv: "Ed25519"` and `x`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default)]
+    pub kid: Option<String>,
+    #[serde(default)]
+    pub crv: Option<String>,
+    #[serde(default)]
+    pub n: Option<String>,
+    #[serde(default)]
+    pub e: Option<String>,
+    #[serde(default)]
+    pub x: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+/// Claims of a channel or user authentication token
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JwtClaims {
+    /// The socket the token was issued for
+    pub socket_id: String,
+    /// Expiry as a unix timestamp
+    pub exp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<u64>,
+    /// Channels the token may subscribe to, where `*` matches any run of characters
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// The user the token was issued to; required for presence channels and signin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    /// Presence member info and signin `user_info` of the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_info: Option<Value>,
+}
+
+/// Whether an auth string is a JWT rather than `key:signature`
+pub fn is_jwt(auth: &str) -> bool {
+    !auth.contains(':') && auth.split('.').count() == 3
+}
+
+/// Check the signature and validity of `token` and that it was issued for `socket_id`
+pub fn verify(
+    app: &App,
+    config: &JwtAuthConfig,
+    token: &str,
+    socket_id: &SocketId,
+) -> Result<JwtClaims> {
+    let mut parts = token.split('.');
+    let (Some(header_segment), Some(payload), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::Auth("Malformed JWT".into()));
+    };
+
+    let header: JwtHeader = decode_json(header_segment)?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| Error::Auth("Malformed JWT signature".into()))?;
+    let signing_input = &token[..header_segment.len() + 1 + payload.len()];
+
+    let signature_valid = match header.alg.as_str() {
+        "HS256" if config.allow_hs256 => {
+            app.token().verify(signing_input, &hex::encode(&signature))
+        }
+        "RS256" | "EdDSA" => public_keys(config)?
+            .iter()
+            .filter(|key| header.kid.is_none() || key.kid == header.kid)
+            .any(|key| verify_with_key(key, &header.alg, signing_input, &signature)),
+        alg => {
+            return Err(Error::Auth(format!(
+                "JWT algorithm {alg} is not accepted for this app"
+            )));
+        }
+    };
+    if !signature_valid {
+        return Err(Error::Auth("Invalid JWT signature".into()));
+    }
+
+    let claims: JwtClaims = decode_json(payload)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    if claims.exp <= now {
+        return Err(Error::Auth("JWT has expired".into()));
+    }
+    if claims.nbf.is_some_and(|nbf| nbf > now) {
+        return Err(Error::Auth("JWT is not valid yet".into()));
+    }
+    if claims.socket_id != socket_id.0 {
+        return Err(Error::Auth("JWT was issued for another socket".into()));
+    }
+
+    Ok(claims)
+}
+
+/// Expiry of a token that `verify` has accepted
+pub fn expires_at(token: &str) -> Option<u64> {
+    let payload = token.split('.').nth(1)?;
+    decode_json::<JwtClaims>(payload)
+        .ok()
+        .map(|claims| claims.exp)
+}
+
+impl JwtClaims {
+    pub fn allows_channel(&self, channel: &str) -> bool {
+        self.channels
+            .iter()
+            .any(|pattern| wildcard_match(pattern, channel))
+    }
+
+    /// Check that presence `channel_data` describes the user of the token
+    pub fn check_presence_member(&self, channel_data: Option<&str>) -> Result<()> {
+        let channel_data: Value = channel_data
+            .and_then(|data| serde_json::from_str(data).ok())
+            .ok_or_else(|| Error::Auth("Invalid channel_data for presence channel".into()))?;
+        self.check_user(&channel_data, "user_id")
+    }
+
+    /// Check that signin `user_data` describes the user of the token
+    pub fn check_signin_user(&self, user_data: &str) -> Result<()> {
+        let user_data: Value = serde_json::from_str(user_data)
+            .map_err(|e| Error::Auth(format!("Invalid user_data JSON: {e}")))?;
+        self.check_user(&user_data, "id")
+    }
+
+    fn check_user(&self, data: &Value, id_field: &str) -> Result<()> {
+        let sub = self
+            .sub
+            .as_deref()
+            .ok_or_else(|| Error::Auth("JWT does not name a user".into()))?;
+        let user_id = match data.get(id_field) {
+            Some(Value::String(id)) => id.clone(),
+            Some(Value::Number(id)) => id.to_string(),
+            _ => return Err(Error::Auth(format!("Missing '{id_field}' field"))),
+        };
+        if user_id != sub {
+            return Err(Error::Auth("JWT was issued for another user".into()));
+        }
+        // user_info is only trusted when the token carries it, as HMAC auth signs it
+        let user_info = data.get("user_info").filter(|info| !info.is_null());
+        if user_info != self.user_info.as_ref() {
+            return Err(Error::Auth("user_info does not match the JWT".into()));
+        }
+        Ok(())
+    }
+}
+
+fn decode_json<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| Error::Auth("Malformed JWT".into()))?;
+    serde_json::from_slice(&bytes).map_err(|e| Error::Auth(format!("Malformed JWT: {e}")))
+}
+
+fn verify_with_key(key: &Jwk, alg: &str, message: &str, signature: &[u8]) -> bool {
+    let decode = |value: &Option<String>| {
+        value
+            .as_deref()
+            .and_then(|value| URL_SAFE_NO_PAD.decode(value).ok())
+    };
+
+    match (alg, key.kty.as_str()) {
+        ("RS256", "RSA") => {
+            let (Some(n), Some(e)) = (decode(&key.n), decode(&key.e)) else {
+                return false;
+            };
+            RsaPublicKeyComponents { n, e }
+                .verify(&RSA_PKCS1_2048_8192_SHA256, message.as_bytes(), signature)
+                .is_ok()
+        }
+        ("EdDSA", "OKP") if key.crv.as_deref() == Some("Ed25519") => {
+            let Some(x) = decode(&key.x) else {
+                return false;
+            };
+            UnparsedPublicKey::new(&ED25519, x)
+                .verify(message.as_bytes(), signature)
+                .is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// The configured keys followed by those of the JWKS file
+fn public_keys(config: &JwtAuthConfig) -> Result<Vec<Jwk>> {
+    let mut keys = config.keys.clone();
+    if let Some(ref path) = config.jwks_file {
+        keys.extend(read_jwks_file(path)?.iter().cloned());
+    }
+    Ok(keys)
+}
+
+fn read_jwks_file(path: &str) -> Result<Arc<Vec<Jwk>>> {
+    let unavailable = |e: String| {
+        warn!("Failed to read JWKS file {}: {}", path, e);
+        Error::Auth("JWT signing keys are unavailable".into())
+    };
+
+    if let Some(cached) = JWKS_FILES.get(path)
+        && cached.1.elapsed() < JWKS_RECHECK_INTERVAL
+    {
+        return Ok(cached.2.clone());
+    }
+
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| unavailable(e.to_string()))?;
+    if let Some(mut cached) = JWKS_FILES.get_mut(path)
+        && cached.0 == modified
+    {
+        cached.1 = Instant::now();
+        return Ok(cached.2.clone());
+    }
+
+    let contents = std::fs::read(path).map_err(|e| unavailable(e.to_string()))?;
+    let set: JwkSet = serde_json::from_slice(&contents).map_err(|e| unavailable(e.to_string()))?;
+    let keys = Arc::new(set.keys);
+    JWKS_FILES.insert(path.to_string(), (modified, Instant::now(), keys.clone()));
+    Ok(keys)
+}
+
+/// Sign claims as an HS256 token with the app secret, the way an app backend issues them
+pub fn sign_hs256(app: &App, claims: &impl Serialize) -> String {
+    let input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#),
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).unwrap_or_default())
+    );
+    let signature = hex::decode(app.token().sign(&input)).unwrap_or_default();
+    format!("{input}.{}", URL_SAFE_NO_PAD.encode(signature))
+}
+
+/// Check the configuration before it is stored
+pub fn validate_config(config: &JwtAuthConfig) -> std::result::Result<(), String> {
+    for (index, key) in config.keys.iter().enumerate() {
+        let complete = match key.kty.as_str() {
+            "RSA" => key.n.is_some() && key.e.is_some(),
+            "OKP" => key.crv.as_deref() == Some("Ed25519") && key.x.is_some(),
+            _ => false,
+        };
+        if !complete {
+            return Err(format!(
+                "jwt_auth.keys[{index}]: expected an RSA key with n and e or an Ed25519 OKP key with x"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn encode(value: &impl Serialize) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(value).unwrap())
+    }
+
+    fn claims(exp_in: i64) -> JwtClaims {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        JwtClaims {
+            socket_id: "123.456".to_string(),
+            exp: (now + exp_in) as u64,
+            nbf: None,
+            channels: vec!["private-orders-*".to_string()],
+            sub: Some("42".to_string()),
+            user_info: Some(serde_json::json!({"name": "Ada"})),
+        }
+    }
+
+    fn app() -> App {
+        App {
+            id: "app".to_string(),
+            key: "key".to_string(),
+            secret: "secret".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn config() -> JwtAuthConfig {
+        serde_json::from_str("{}").unwrap()
+    }
+
+    #[test]
+    fn test_hs256_tokens_are_bound_to_socket_and_expiry() {
+        let app = app();
+        let socket_id = SocketId("123.456".to_string());
+        let token = sign_hs256(&app, &claims(60));
+        assert!(is_jwt(&token));
+
+        let verified = verify(&app, &config(), &token, &socket_id).unwrap();
+        assert!(verified.allows_channel("private-orders-7"));
+        assert!(!verified.allows_channel("private-invoices-7"));
+        assert!(
+            verified
+                .check_presence_member(Some(r#"{"user_id":"42","user_info":{"name":"Ada"}}"#))
+                .is_ok()
+        );
+        assert!(
+            verified
+                .check_signin_user(r#"{"id":"43","user_info":{"name":"Ada"}}"#)
+                .is_err()
+        );
+
+        let mut without_user_info = claims(60);
+        without_user_info.user_info = None;
+        let verified = verify(
+            &app,
+            &config(),
+            &sign_hs256(&app, &without_user_info),
+            &socket_id,
+        )
+        .unwrap();
+        assert!(verified.check_signin_user(r#"{"id":"42"}"#).is_ok());
+        assert!(
+            verified
+                .check_signin_user(r#"{"id":"42","user_info":null}"#)
+                .is_ok()
+        );
+        assert!(
+            verified
+                .check_signin_user(r#"{"id":"42","user_info":{"name":"Ada"}}"#)
+                .is_err()
+        );
+
+        let other_socket = SocketId("123.457".to_string());
+        assert!(verify(&app, &config(), &token, &other_socket).is_err());
+
+        let expired = sign_hs256(&app, &claims(-1));
+        assert!(verify(&app, &config(), &expired, &socket_id).is_err());
+
+        let mut hs256_disabled = config();
+        hs256_disabled.allow_hs256 = false;
+        assert!(verify(&app, &hs256_disabled, &token, &socket_id).is_err());
+    }
+
+    #[test]
+    fn test_eddsa_tokens_are_verified_with_public_keys() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let mut config = config();
+        config.keys.push(Jwk {
+            kty: "OKP".to_string(),
+            kid: Some("signing-1".to_string()),
+            crv: Some("Ed25519".to_string()),
+            n: None,
+            e: None,
+            x: Some(URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref())),
+        });
+        assert!(validate_config(&config).is_ok());
+
+        let input = format!(
+            "{}.{}",
+            encode(&serde_json::json!({"alg": "EdDSA", "kid": "signing-1"})),
+            encode(&claims(60))
+        );
+        let signature = URL_SAFE_NO_PAD.encode(key_pair.sign(input.as_bytes()).as_ref());
+        let token = format!("{input}.{signature}");
+        let socket_id = SocketId("123.456".to_string());
+        assert!(verify(&app(), &config, &token, &socket_id).is_ok());
+
+        let tampered = format!("{input}x.{signature}");
+        assert!(verify(&app(), &config, &tampered, &socket_id).is_err());
+    }
+
+    #[test]
+    fn test_jwks_file_is_not_checked_again_within_the_interval() {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        std::fs::write(
+            path,
+            r#"{"keys": [{"kty": "OKP", "crv": "Ed25519", "x": "AA"}]}"#,
+        )
+        .unwrap();
+
+        let keys = read_jwks_file(path).unwrap();
+        assert_eq!(keys.len(), 1);
+        std::fs::remove_file(path).unwrap();
+        assert!(Arc::ptr_eq(&keys, &read_jwks_file(path).unwrap()));
+    }
+}
@@ -1,7 +1,24 @@
 // src/app/traits.rs
 use crate::app::config::App;
+use crate::app::migrations::MigrationStatus;
 use crate::error::Result;
 use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+/// An app changed outside of the AppManager write methods, e.g. because its source was reloaded
+#[derive(Debug, Clone)]
+pub enum AppChange {
+    Created(App),
+    Updated {
+        previous: Box<App>,
+        current: Box<App>,
+    },
+    Deleted(App),
+    /// The app may have changed in the backing store; cached copies must be re-read
+    Invalidated {
+        app_id: String,
+    },
+}
 
 /// Trait defining operations that all AppManager implementations must support
 #[async_trait]
@@ -29,4 +46,21 @@ pub trait AppManager: Send + Sync + 'static {
 
     /// Health check for the app manager
     async fn check_health(&self) -> Result<()>;
+
+    /// Drop any locally cached copy of an app so the next lookup reads the backing store
+    async fn invalidate_cache(&self, _app_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Apply pending schema migrations of the backing store, or only report them with
+    /// `dry_run`. Stores without a schema have nothing to migrate.
+    async fn migrate(&self, _dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        Ok(Vec::new())
+    }
+
+    /// Subscribe to apps changing underneath this manager.
+    /// Managers whose apps only change through their own write methods return None.
+    fn subscribe_changes(&self) -> Option<broadcast::Receiver<AppChange>> {
+        None
+    }
 }
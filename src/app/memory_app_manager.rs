@@ -57,7 +57,7 @@ impl AppManager for MemoryAppManager {
         let app = self
             .apps
             .iter()
-            .find(|app| app.key == key)
+            .find(|app| app.accepts_key(key))
             .map(|app| app.clone());
         Ok(app)
     }
@@ -0,0 +1,243 @@
+// src/app/migrations.rs
+use crate::error::Result;
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use std::fmt;
+use tracing::{info, warn};
+
+/// One versioned change to an app store's schema. Versions are applied in ascending
+/// order and recorded in a history table next to the apps table.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Recorded in the history table before this run
+    AlreadyApplied,
+    /// Applied by this run
+    Applied,
+    /// Not applied yet; only reported by dry runs
+    Pending,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub description: &'static str,
+    pub state: MigrationState,
+}
+
+impl fmt::Display for MigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            MigrationState::AlreadyApplied => "already applied",
+            MigrationState::Applied => "applied",
+            MigrationState::Pending => "pending",
+        };
+        write!(f, "{:03} {} ({})", self.version, self.description, state)
+    }
+}
+
+/// Cross-node lock held while migrations run. Dropping it without `release` may leave
+/// the lock held by a pooled connection, so the runner always releases it.
+pub struct MigrationLock {
+    release: Option<BoxFuture<'static, Result<()>>>,
+}
+
+impl MigrationLock {
+    /// Lock released by running `release`
+    pub fn new(release: BoxFuture<'static, Result<()>>) -> Self {
+        Self {
+            release: Some(release),
+        }
+    }
+
+    /// For stores whose history table rejects a second record of a version
+    pub fn none() -> Self {
+        Self { release: None }
+    }
+
+    pub async fn release(mut self) {
+        if let Some(release) = self.release.take()
+            && let Err(e) = release.await
+        {
+            warn!("Failed to release the app store migration lock: {}", e);
+        }
+    }
+}
+
+/// Schema operations an app store provides to the migration runner
+#[async_trait]
+pub trait SchemaMigrations: Send + Sync {
+    /// Every migration of this store, in ascending version order
+    fn migrations(&self) -> &'static [Migration];
+
+    /// Versions recorded in the history table; empty if the table does not exist yet
+    async fn applied_versions(&self) -> Result<Vec<u32>>;
+
+    /// Create the history table if it doesn't exist
+    async fn ensure_history_table(&self) -> Result<()>;
+
+    /// Wait for the store's migration lock, so nodes starting together migrate one at a
+    /// time and each sees what the previous one applied
+    async fn lock_migrations(&self) -> Result<MigrationLock>;
+
+    /// Apply one migration and record it in the history table. Migrations must be
+    /// idempotent, because databases set up before versioning already have some of them.
+    async fn apply_migration(&self, migration: &Migration) -> Result<()>;
+}
+
+/// Apply all pending migrations of a store in order, under the store's migration lock.
+/// With `dry_run` nothing is written and pending migrations are reported as such.
+pub async fn run_migrations<S: SchemaMigrations + ?Sized>(
+    store: &S,
+    dry_run: bool,
+) -> Result<Vec<MigrationStatus>> {
+    if dry_run {
+        return apply_pending(store, true).await;
+    }
+    let lock = store.lock_migrations().await?;
+    let report = async {
+        store.ensure_history_table().await?;
+        apply_pending(store, false).await
+    }
+    .await;
+    lock.release().await;
+    report
+}
+
+async fn apply_pending<S: SchemaMigrations + ?Sized>(
+    store: &S,
+    dry_run: bool,
+) -> Result<Vec<MigrationStatus>> {
+    // Read under the lock, so versions another node just applied are skipped
+    let applied = store.applied_versions().await?;
+    let mut report = Vec::with_capacity(store.migrations().len());
+    for migration in store.migrations() {
+        let state = if applied.contains(&migration.version) {
+            MigrationState::AlreadyApplied
+        } else if dry_run {
+            MigrationState::Pending
+        } else {
+            store.apply_migration(migration).await?;
+            info!(
+                "Applied app store migration {:03}: {}",
+                migration.version, migration.description
+            );
+            MigrationState::Applied
+        };
+        report.push(MigrationStatus {
+            version: migration.version,
+            description: migration.description,
+            state,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    const MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            description: "create table",
+        },
+        Migration {
+            version: 2,
+            description: "add column",
+        },
+    ];
+
+    #[derive(Default)]
+    struct TestStore {
+        history: Mutex<Option<Vec<u32>>>,
+        lock: Arc<tokio::sync::Mutex<()>>,
+    }
+
+    #[async_trait]
+    impl SchemaMigrations for TestStore {
+        fn migrations(&self) -> &'static [Migration] {
+            MIGRATIONS
+        }
+
+        async fn applied_versions(&self) -> Result<Vec<u32>> {
+            Ok(self.history.lock().unwrap().clone().unwrap_or_default())
+        }
+
+        async fn ensure_history_table(&self) -> Result<()> {
+            self.history.lock().unwrap().get_or_insert_with(Vec::new);
+            Ok(())
+        }
+
+        async fn lock_migrations(&self) -> Result<MigrationLock> {
+            let guard = self.lock.clone().lock_owned().await;
+            Ok(MigrationLock::new(Box::pin(async move {
+                drop(guard);
+                Ok(())
+            })))
+        }
+
+        async fn apply_migration(&self, migration: &Migration) -> Result<()> {
+            // Let another runner in, as a database round trip would
+            tokio::task::yield_now().await;
+            let mut history = self.history.lock().unwrap();
+            let history = history.as_mut().unwrap();
+            if history.contains(&migration.version) {
+                return Err(crate::error::Error::Internal(format!(
+                    "Migration {} recorded twice",
+                    migration.version
+                )));
+            }
+            history.push(migration.version);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_writes_nothing() {
+        let store = TestStore::default();
+        let report = run_migrations(&store, true).await.unwrap();
+        assert!(report.iter().all(|s| s.state == MigrationState::Pending));
+        assert!(store.history.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_only_pending_migrations_are_applied() {
+        let store = TestStore {
+            history: Mutex::new(Some(vec![1])),
+            ..Default::default()
+        };
+        let report = run_migrations(&store, false).await.unwrap();
+        assert_eq!(report[0].state, MigrationState::AlreadyApplied);
+        assert_eq!(report[1].state, MigrationState::Applied);
+        assert_eq!(*store.history.lock().unwrap(), Some(vec![1, 2]));
+
+        let report = run_migrations(&store, false).await.unwrap();
+        assert!(
+            report
+                .iter()
+                .all(|s| s.state == MigrationState::AlreadyApplied)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_runs_apply_each_migration_once() {
+        let store = TestStore::default();
+        let (first, second) =
+            tokio::join!(run_migrations(&store, false), run_migrations(&store, false));
+        let applied = first
+            .unwrap()
+            .into_iter()
+            .chain(second.unwrap())
+            .filter(|s| s.state == MigrationState::Applied)
+            .count();
+        assert_eq!(applied, 2);
+        assert_eq!(*store.history.lock().unwrap(), Some(vec![1, 2]));
+    }
+}
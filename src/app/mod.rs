@@ -1,14 +1,26 @@
+pub mod admin;
 pub mod auth;
+pub mod authorizer;
 pub mod cached_app_manager;
+pub mod channel_limits;
+pub mod channel_policy;
 pub mod config;
 #[cfg(feature = "dynamodb")]
 pub mod dynamodb_app_manager;
+pub mod encrypted_app_manager;
 pub mod factory;
+pub mod file_app_manager;
+pub mod ip_filter;
+pub mod jwt_auth;
 pub mod manager;
 pub mod memory_app_manager;
+pub mod migrations;
 #[cfg(feature = "mysql")]
 pub mod mysql_app_manager;
 #[cfg(feature = "postgres")]
 pub mod pg_app_manager;
 #[cfg(feature = "scylladb")]
 pub mod scylla_app_manager;
+pub mod secret_cipher;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_app_manager;
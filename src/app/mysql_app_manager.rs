@@ -1,14 +1,25 @@
-use super::config::App;
-use crate::app::manager::AppManager;
+use super::config::{App, AppCredential};
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::AuthorizerConfig;
+use crate::app::channel_limits::ChannelLimitRule;
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::ip_filter::IpFilterConfig;
+use crate::app::jwt_auth::JwtAuthConfig;
+use crate::app::manager::{AppChange, AppManager};
+use crate::app::migrations::{
+    Migration, MigrationLock, MigrationStatus, SchemaMigrations, run_migrations,
+};
 use crate::error::{Error, Result};
+use crate::quota::AppQuotas;
 
 use crate::options::{DatabaseConnection, DatabasePooling};
-use crate::token::Token;
 use crate::webhook::types::Webhook;
 use crate::websocket::SocketId;
 use async_trait::async_trait;
 use sqlx::{MySqlPool, mysql::MySqlPoolOptions};
+use std::collections::HashMap;
 use std::time::Duration;
+use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
 /// Configuration for MySQL App Manager
@@ -16,6 +27,7 @@ use tracing::{debug, error, info, warn};
 pub struct MySQLAppManager {
     config: DatabaseConnection,
     pool: MySqlPool,
+    changes: Option<broadcast::Sender<AppChange>>,
 }
 
 impl MySQLAppManager {
@@ -53,11 +65,109 @@ impl MySQLAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to connect to MySQL: {e}")))?;
 
-        let manager = Self { config, pool };
+        Ok(Self {
+            config,
+            pool,
+            changes: None,
+        })
+    }
+
+    /// Poll the `updated_at` column (microsecond precision) for apps changed, added or
+    /// removed outside this process (e.g. by another node or directly in the database) and
+    /// report them through `subscribe_changes`
+    pub fn poll_for_changes(&mut self, interval: Duration) {
+        let (sender, _) = broadcast::channel(256);
+        self.changes = Some(sender.clone());
+
+        let pool = self.pool.clone();
+        let query = format!(
+            "SELECT id, CAST(updated_at AS CHAR) FROM `{}`",
+            self.config.table_name
+        );
+        info!(
+            "Polling MySQL table '{}' for app changes every {:?}",
+            self.config.table_name, interval
+        );
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut known: Option<HashMap<String, Option<String>>> = None;
+
+            loop {
+                ticker.tick().await;
+                let rows: Vec<(String, Option<String>)> =
+                    match sqlx::query_as(&query).fetch_all(&pool).await {
+                        Ok(rows) => rows,
+                        Err(e) => {
+                            warn!("Failed to poll MySQL for app changes: {}", e);
+                            continue;
+                        }
+                    };
+                let current: HashMap<String, Option<String>> = rows.into_iter().collect();
+
+                // The first poll only records the baseline
+                if let Some(previous) = &known {
+                    let changed = current
+                        .iter()
+                        .filter(|(id, updated_at)| previous.get(*id) != Some(*updated_at))
+                        .map(|(id, _)| id)
+                        .chain(previous.keys().filter(|id| !current.contains_key(*id)));
+                    for app_id in changed {
+                        debug!("App {} changed in MySQL", app_id);
+                        let _ = sender.send(AppChange::Invalidated {
+                            app_id: app_id.clone(),
+                        });
+                    }
+                }
+                known = Some(current);
+            }
+        });
+    }
+
+    /// Second precision hides a second change made within the same second from
+    /// `poll_for_changes`
+    async fn widen_updated_at_precision(&self) -> Result<()> {
+        let query = format!(
+            "ALTER TABLE `{}` MODIFY COLUMN updated_at TIMESTAMP(6) \
+             DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)",
+            self.config.table_name
+        );
+        sqlx::query(&query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to widen updated_at precision: {e}")))?;
+        Ok(())
+    }
+
+    /// Encrypted secrets do not fit the VARCHAR(255) column older versions created
+    async fn widen_secret_column(&self) -> Result<()> {
+        let check_query = format!(
+            r#"SELECT DATA_TYPE FROM INFORMATION_SCHEMA.COLUMNS
+               WHERE TABLE_SCHEMA = DATABASE()
+               AND TABLE_NAME = '{}'
+               AND COLUMN_NAME = 'secret'"#,
+            self.config.table_name
+        );
+
+        let data_type: Option<(String,)> = sqlx::query_as(&check_query)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to check secret column type: {e}")))?;
 
-        manager.ensure_table_exists().await?;
+        if data_type.is_some_and(|(data_type,)| data_type.eq_ignore_ascii_case("varchar")) {
+            let alter_query = format!(
+                r#"ALTER TABLE `{}` MODIFY secret TEXT NOT NULL"#,
+                self.config.table_name
+            );
+            sqlx::query(&alter_query)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to widen secret column: {e}")))?;
+            info!("Widened secret column of '{}'", self.config.table_name);
+        }
 
-        Ok(manager)
+        Ok(())
     }
 
     /// Helper function to add a column if it doesn't exist
@@ -118,8 +228,12 @@ impl MySQLAppManager {
         Ok(())
     }
 
-    /// Create the applications table if it doesn't exist
-    async fn ensure_table_exists(&self) -> Result<()> {
+    fn history_table(&self) -> String {
+        format!("{}_schema_migrations", self.config.table_name)
+    }
+
+    /// Create the applications table with the columns of the first schema version
+    async fn create_table(&self) -> Result<()> {
         // Use a constant query (avoid format!) for security
         const CREATE_TABLE_QUERY: &str = r#"
             CREATE TABLE IF NOT EXISTS `applications` (
@@ -140,9 +254,6 @@ impl MySQLAppManager {
                 max_event_payload_in_kb INT UNSIGNED NULL,
                 max_event_batch_size INT UNSIGNED NULL,
                 enable_user_authentication BOOLEAN NULL,
-                enable_watchlist_events BOOLEAN NULL,
-                webhooks JSON NULL,
-                allowed_origins JSON NULL,
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
             ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
@@ -160,19 +271,6 @@ impl MySQLAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to create MySQL table: {e}")))?;
 
-        // Add migrations for new columns if they don't exist
-        // MySQL doesn't support IF NOT EXISTS for columns, so we need to check first
-        let columns_to_add = vec![
-            ("allowed_origins", "JSON NULL"),
-            ("enable_watchlist_events", "BOOLEAN NULL"),
-            ("webhooks", "JSON NULL"),
-        ];
-
-        for (column_name, column_type) in columns_to_add {
-            self.add_column_if_not_exists(column_name, column_type)
-                .await?;
-        }
-
         info!(
             "{}",
             format!("Ensured table '{}' exists", self.config.table_name)
@@ -203,7 +301,15 @@ impl MySQLAppManager {
                 enable_user_authentication,
                 enable_watchlist_events,
                 webhooks,
-                allowed_origins
+                allowed_origins,
+                credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer
             FROM `{}` WHERE id = ?"#,
             self.config.table_name
         );
@@ -244,14 +350,24 @@ impl MySQLAppManager {
                 enable_user_authentication,
                 enable_watchlist_events,
                 webhooks,
-                allowed_origins
-            FROM `{}` WHERE `key` = ?"#,
+                allowed_origins,
+                credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer
+            FROM `{}`
+            WHERE `key` = ? OR JSON_CONTAINS(credentials, JSON_OBJECT('key', ?))"#,
             self.config.table_name
         );
 
-        let app_result = sqlx::query_as::<_, AppRow>(&query)
+        let rows = sqlx::query_as::<_, AppRow>(&query)
             .bind(key)
-            .fetch_optional(&self.pool)
+            .bind(key)
+            .fetch_all(&self.pool)
             .await
             .map_err(|e| {
                 error!(
@@ -261,7 +377,11 @@ impl MySQLAppManager {
                 Error::Internal(format!("Failed to fetch app from MySQL: {e}"))
             })?;
 
-        Ok(app_result.map(|row| row.into_app()))
+        // Credential keys only count while the credential is accepted
+        Ok(rows
+            .into_iter()
+            .map(|row| row.into_app())
+            .find(|app| app.accepts_key(key)))
     }
 
     /// Register a new app in the database
@@ -277,8 +397,15 @@ impl MySQLAppManager {
                 max_presence_member_size_in_kb, max_channel_name_length,
                 max_event_channels_at_once, max_event_name_length,
                 max_event_payload_in_kb, max_event_batch_size, enable_user_authentication,
-                enable_watchlist_events, webhooks, allowed_origins
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                enable_watchlist_events, webhooks, allowed_origins, credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
             self.config.table_name
         );
 
@@ -303,6 +430,14 @@ impl MySQLAppManager {
             .bind(app.enable_watchlist_events)
             .bind(sqlx::types::Json(&app.webhooks))
             .bind(sqlx::types::Json(&app.allowed_origins))
+            .bind(sqlx::types::Json(&app.credentials))
+            .bind(sqlx::types::Json(&app.channel_policy))
+            .bind(sqlx::types::Json(&app.channel_limits))
+            .bind(sqlx::types::Json(&app.quotas))
+            .bind(sqlx::types::Json(&app.jwt_auth))
+            .bind(sqlx::types::Json(&app.api_auth))
+            .bind(sqlx::types::Json(&app.ip_filter))
+            .bind(sqlx::types::Json(&app.authorizer))
             .execute(&self.pool)
             .await
             .map_err(|e| {
@@ -329,7 +464,8 @@ impl MySQLAppManager {
                 max_presence_member_size_in_kb = ?, max_channel_name_length = ?,
                 max_event_channels_at_once = ?, max_event_name_length = ?,
                 max_event_payload_in_kb = ?, max_event_batch_size = ?, enable_user_authentication = ?,
-                enable_watchlist_events = ?, webhooks = ?, allowed_origins = ?
+                enable_watchlist_events = ?, webhooks = ?, allowed_origins = ?, credentials = ?,
+                channel_policy = ?, channel_limits = ?, quotas = ?, jwt_auth = ?, api_auth = ?, ip_filter = ?, authorizer = ?
                 WHERE id = ?"#,
             self.config.table_name
         );
@@ -354,6 +490,14 @@ impl MySQLAppManager {
             .bind(app.enable_watchlist_events)
             .bind(sqlx::types::Json(&app.webhooks))
             .bind(sqlx::types::Json(&app.allowed_origins))
+            .bind(sqlx::types::Json(&app.credentials))
+            .bind(sqlx::types::Json(&app.channel_policy))
+            .bind(sqlx::types::Json(&app.channel_limits))
+            .bind(sqlx::types::Json(&app.quotas))
+            .bind(sqlx::types::Json(&app.jwt_auth))
+            .bind(sqlx::types::Json(&app.api_auth))
+            .bind(sqlx::types::Json(&app.ip_filter))
+            .bind(sqlx::types::Json(&app.authorizer))
             .bind(&app.id)
             .execute(&self.pool)
             .await
@@ -419,7 +563,15 @@ impl MySQLAppManager {
             enable_user_authentication,
             enable_watchlist_events,
             webhooks,
-            allowed_origins
+            allowed_origins,
+            credentials,
+            channel_policy,
+            channel_limits,
+            quotas,
+            jwt_auth,
+            api_auth,
+            ip_filter,
+            authorizer
         FROM `{}`"#,
             self.config.table_name // Ensure config.table_name is safely handled
         );
@@ -446,7 +598,7 @@ impl MySQLAppManager {
         Ok(self.find_by_id(app_id).await?.is_some())
     }
 
-    /// Validate a signature against an app's secret
+    /// Validate a signature against the app's accepted secrets
     pub async fn validate_signature(
         &self,
         app_id: &str,
@@ -458,10 +610,7 @@ impl MySQLAppManager {
             .await?
             .ok_or_else(|| Error::InvalidAppKey)?;
 
-        let token = Token::new(app.key.clone(), app.secret.clone());
-        let expected = token.sign(body);
-
-        Ok(signature == expected)
+        Ok(app.token().verify(body, signature))
     }
 
     /// Validate if a channel name is valid for an app
@@ -520,7 +669,7 @@ impl MySQLAppManager {
         let string_to_sign = format!("{socket_id}::user::{signature}");
 
         // Generate token
-        let token = Token::new(app.key.clone(), app.secret.clone());
+        let token = app.token();
 
         // Verify
         Ok(token.verify(&string_to_sign, &signature))
@@ -552,6 +701,22 @@ struct AppRow {
     webhooks: Option<Vec<Webhook>>,
     #[sqlx(json(nullable))]
     allowed_origins: Option<Vec<String>>,
+    #[sqlx(json(nullable))]
+    credentials: Option<Vec<AppCredential>>,
+    #[sqlx(json(nullable))]
+    channel_policy: Option<ChannelPolicy>,
+    #[sqlx(json(nullable))]
+    channel_limits: Option<Vec<ChannelLimitRule>>,
+    #[sqlx(json(nullable))]
+    quotas: Option<AppQuotas>,
+    #[sqlx(json(nullable))]
+    jwt_auth: Option<JwtAuthConfig>,
+    #[sqlx(json(nullable))]
+    api_auth: Option<ApiAuthConfig>,
+    #[sqlx(json(nullable))]
+    ip_filter: Option<IpFilterConfig>,
+    #[sqlx(json(nullable))]
+    authorizer: Option<AuthorizerConfig>,
 }
 
 impl AppRow {
@@ -578,7 +743,215 @@ impl AppRow {
             webhooks: self.webhooks,
             enable_watchlist_events: self.enable_watchlist_events,
             allowed_origins: self.allowed_origins,
+            credentials: self.credentials,
+            channel_policy: self.channel_policy,
+            channel_limits: self.channel_limits,
+            quotas: self.quotas,
+            jwt_auth: self.jwt_auth,
+            api_auth: self.api_auth,
+            ip_filter: self.ip_filter,
+            authorizer: self.authorizer,
+        }
+    }
+}
+
+/// How long a node waits for another one to finish migrating
+const MIGRATION_LOCK_TIMEOUT_SECS: i64 = 300;
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create applications table",
+    },
+    Migration {
+        version: 2,
+        description: "add allowed_origins column",
+    },
+    Migration {
+        version: 3,
+        description: "add enable_watchlist_events column",
+    },
+    Migration {
+        version: 4,
+        description: "add webhooks column",
+    },
+    Migration {
+        version: 5,
+        description: "add credentials column",
+    },
+    Migration {
+        version: 6,
+        description: "widen secret column to TEXT",
+    },
+    Migration {
+        version: 7,
+        description: "add channel_policy column",
+    },
+    Migration {
+        version: 8,
+        description: "add channel_limits column",
+    },
+    Migration {
+        version: 9,
+        description: "add quotas column",
+    },
+    Migration {
+        version: 10,
+        description: "add jwt_auth column",
+    },
+    Migration {
+        version: 11,
+        description: "add api_auth column",
+    },
+    Migration {
+        version: 12,
+        description: "add ip_filter column",
+    },
+    Migration {
+        version: 13,
+        description: "add authorizer column",
+    },
+    Migration {
+        version: 14,
+        description: "store updated_at with microsecond precision",
+    },
+];
+
+#[async_trait]
+impl SchemaMigrations for MySQLAppManager {
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    async fn applied_versions(&self) -> Result<Vec<u32>> {
+        let history_table = self.history_table();
+        let exists: Option<(String,)> = sqlx::query_as(
+            r#"SELECT TABLE_NAME FROM INFORMATION_SCHEMA.TABLES
+               WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?"#,
+        )
+        .bind(&history_table)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to look up {history_table}: {e}")))?;
+        if exists.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let versions: Vec<(u32,)> =
+            sqlx::query_as(&format!("SELECT version FROM `{history_table}`"))
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to read {history_table}: {e}")))?;
+        Ok(versions.into_iter().map(|(v,)| v).collect())
+    }
+
+    async fn ensure_history_table(&self) -> Result<()> {
+        let query = format!(
+            r#"CREATE TABLE IF NOT EXISTS `{}` (
+                version INT UNSIGNED PRIMARY KEY,
+                description VARCHAR(255) NOT NULL,
+                applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci"#,
+            self.history_table()
+        );
+        sqlx::query(&query).execute(&self.pool).await.map_err(|e| {
+            Error::Internal(format!("Failed to create migration history table: {e}"))
+        })?;
+        Ok(())
+    }
+
+    async fn lock_migrations(&self) -> Result<MigrationLock> {
+        // GET_LOCK belongs to the session, so the connection is kept until the release
+        let lock_name = self.history_table();
+        let mut connection = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to take the migration lock: {e}")))?;
+        let (acquired,): (Option<i64>,) = sqlx::query_as("SELECT GET_LOCK(?, ?)")
+            .bind(&lock_name)
+            .bind(MIGRATION_LOCK_TIMEOUT_SECS)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to take the migration lock: {e}")))?;
+        if acquired != Some(1) {
+            return Err(Error::Internal(format!(
+                "Timed out after {MIGRATION_LOCK_TIMEOUT_SECS}s waiting for the migration lock {lock_name}"
+            )));
         }
+        Ok(MigrationLock::new(Box::pin(async move {
+            sqlx::query("SELECT RELEASE_LOCK(?)")
+                .bind(lock_name)
+                .execute(&mut *connection)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to release migration lock: {e}")))?;
+            Ok(())
+        })))
+    }
+
+    async fn apply_migration(&self, migration: &Migration) -> Result<()> {
+        match migration.version {
+            1 => self.create_table().await?,
+            2 => {
+                self.add_column_if_not_exists("allowed_origins", "JSON NULL")
+                    .await?
+            }
+            3 => {
+                self.add_column_if_not_exists("enable_watchlist_events", "BOOLEAN NULL")
+                    .await?
+            }
+            4 => {
+                self.add_column_if_not_exists("webhooks", "JSON NULL")
+                    .await?
+            }
+            5 => {
+                self.add_column_if_not_exists("credentials", "JSON NULL")
+                    .await?
+            }
+            6 => self.widen_secret_column().await?,
+            7 => {
+                self.add_column_if_not_exists("channel_policy", "JSON NULL")
+                    .await?
+            }
+            8 => {
+                self.add_column_if_not_exists("channel_limits", "JSON NULL")
+                    .await?
+            }
+            9 => self.add_column_if_not_exists("quotas", "JSON NULL").await?,
+            10 => {
+                self.add_column_if_not_exists("jwt_auth", "JSON NULL")
+                    .await?
+            }
+            11 => {
+                self.add_column_if_not_exists("api_auth", "JSON NULL")
+                    .await?
+            }
+            12 => {
+                self.add_column_if_not_exists("ip_filter", "JSON NULL")
+                    .await?
+            }
+            13 => {
+                self.add_column_if_not_exists("authorizer", "JSON NULL")
+                    .await?
+            }
+            14 => self.widen_updated_at_precision().await?,
+            version => {
+                return Err(Error::Internal(format!(
+                    "Unknown MySQL migration {version}"
+                )));
+            }
+        }
+
+        sqlx::query(&format!(
+            "INSERT INTO `{}` (version, description) VALUES (?, ?)",
+            self.history_table()
+        ))
+        .bind(migration.version)
+        .bind(migration.description)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to record migration: {e}")))?;
+        Ok(())
     }
 }
 
@@ -624,6 +997,10 @@ impl AppManager for MySQLAppManager {
         self.find_by_key(key).await
     }
 
+    async fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        run_migrations(self, dry_run).await
+    }
+
     async fn check_health(&self) -> Result<()> {
         sqlx::query("SELECT 1")
             .fetch_one(&self.pool)
@@ -633,6 +1010,10 @@ impl AppManager for MySQLAppManager {
             })?;
         Ok(())
     }
+
+    fn subscribe_changes(&self) -> Option<broadcast::Receiver<AppChange>> {
+        self.changes.as_ref().map(broadcast::Sender::subscribe)
+    }
 }
 
 // Make the MySQLAppManager clonable for use in async contexts
@@ -641,6 +1022,7 @@ impl Clone for MySQLAppManager {
         Self {
             config: self.config.clone(),
             pool: self.pool.clone(),
+            changes: self.changes.clone(),
         }
     }
 }
@@ -672,6 +1054,14 @@ mod tests {
             webhooks: None,
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         }
     }
 
@@ -711,6 +1101,7 @@ mod tests {
         let manager = MySQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Test registering an app
         let test_app = create_test_app("test1");
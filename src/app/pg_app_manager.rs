@@ -1,20 +1,31 @@
-use super::config::App;
-use crate::app::manager::AppManager;
+use super::config::{App, AppCredential};
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::AuthorizerConfig;
+use crate::app::channel_limits::ChannelLimitRule;
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::ip_filter::IpFilterConfig;
+use crate::app::jwt_auth::JwtAuthConfig;
+use crate::app::manager::{AppChange, AppManager};
+use crate::app::migrations::{
+    Migration, MigrationLock, MigrationStatus, SchemaMigrations, run_migrations,
+};
 use crate::error::{Error, Result};
 use crate::options::{DatabaseConnection, DatabasePooling};
-use crate::token::Token;
+use crate::quota::AppQuotas;
 use crate::webhook::types::Webhook;
 use crate::websocket::SocketId;
 use async_trait::async_trait;
 use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgListener, PgPoolOptions};
 use std::time::Duration;
-use tracing::{debug, error, info};
+use tokio::sync::broadcast;
+use tracing::{debug, error, info, warn};
 
 /// PostgreSQL-based implementation of the AppManager
 pub struct PgSQLAppManager {
     config: DatabaseConnection,
     pool: PgPool,
+    changes: Option<broadcast::Sender<AppChange>>,
 }
 
 impl PgSQLAppManager {
@@ -49,16 +60,135 @@ impl PgSQLAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to connect to PostgreSQL: {e}")))?;
 
-        let manager = Self { config, pool };
+        Ok(Self {
+            config,
+            pool,
+            changes: None,
+        })
+    }
+
+    /// Names of the trigger function and NOTIFY channel of the apps table. Table names
+    /// may be schema-qualified, so plain identifiers are derived from them.
+    fn change_notification_names(&self) -> (String, String) {
+        let base_name: String = self
+            .config
+            .table_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        (
+            format!("{base_name}_notify_change"),
+            format!("{base_name}_changes"),
+        )
+    }
+
+    /// Listen for the NOTIFY that the trigger installed by a migration sends for every
+    /// change to the apps table, so changes made by other nodes or directly in the
+    /// database are reported through `subscribe_changes`
+    pub async fn listen_for_changes(&mut self) -> Result<()> {
+        let (_, channel) = self.change_notification_names();
+        if !self
+            .applied_versions()
+            .await?
+            .contains(&CHANGE_TRIGGER_MIGRATION)
+        {
+            return Err(Error::Internal(
+                "the app change trigger is not installed; run `sockudo migrate`".to_string(),
+            ));
+        }
+
+        let mut listener = PgListener::connect_with(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to connect app change listener: {e}")))?;
+        listener
+            .listen(&channel)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to LISTEN on {channel}: {e}")))?;
+
+        let (sender, _) = broadcast::channel(256);
+        self.changes = Some(sender.clone());
+        info!(
+            "Listening for app changes on PostgreSQL channel {}",
+            channel
+        );
 
-        manager.ensure_table_exists().await?;
+        tokio::spawn(async move {
+            loop {
+                match listener.recv().await {
+                    Ok(notification) => {
+                        debug!("App {} changed in PostgreSQL", notification.payload());
+                        let _ = sender.send(AppChange::Invalidated {
+                            app_id: notification.payload().to_string(),
+                        });
+                    }
+                    Err(e) => {
+                        // The listener reconnects on the next recv; changes made while it
+                        // was disconnected are only picked up once the cache TTL expires
+                        warn!("PostgreSQL app change listener error: {}", e);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        });
 
-        Ok(manager)
+        Ok(())
+    }
+
+    fn history_table(&self) -> String {
+        format!("{}_schema_migrations", self.config.table_name)
     }
 
-    /// Create the applications table if it doesn't exist
-    async fn ensure_table_exists(&self) -> Result<()> {
-        // PostgreSQL table creation query
+    /// Send a NOTIFY with the app id for every change to the apps table
+    async fn create_change_trigger(&self) -> Result<()> {
+        let (function_name, channel) = self.change_notification_names();
+        let create_function_query = format!(
+            r#"
+            CREATE OR REPLACE FUNCTION {function_name}() RETURNS trigger AS $$
+            BEGIN
+                IF TG_OP = 'DELETE' THEN
+                    PERFORM pg_notify('{channel}', OLD.id);
+                ELSE
+                    PERFORM pg_notify('{channel}', NEW.id);
+                END IF;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql
+        "#
+        );
+        sqlx::query(&create_function_query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to create {function_name}: {e}")))?;
+
+        let (exists,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM pg_trigger WHERE tgname = $1 AND tgrelid = to_regclass($2))",
+        )
+        .bind(&function_name)
+        .bind(&self.config.table_name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to look up trigger {function_name}: {e}")))?;
+        if exists {
+            debug!("Trigger '{}' already exists", function_name);
+            return Ok(());
+        }
+
+        let create_trigger_query = format!(
+            "CREATE TRIGGER {function_name} AFTER INSERT OR UPDATE OR DELETE ON {} \
+             FOR EACH ROW EXECUTE FUNCTION {function_name}()",
+            self.config.table_name
+        );
+        sqlx::query(&create_trigger_query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!("Failed to create trigger {function_name}: {e}"))
+            })?;
+        Ok(())
+    }
+
+    /// Create the applications table with the columns of the first schema version
+    async fn create_table(&self) -> Result<()> {
         let create_table_query = format!(
             r#"
             CREATE TABLE IF NOT EXISTS {} (
@@ -80,8 +210,6 @@ impl PgSQLAppManager {
                 max_event_batch_size INTEGER,
                 enable_user_authentication BOOLEAN,
                 enable_watchlist_events BOOLEAN,
-                webhooks JSONB,
-                allowed_origins JSONB,
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
             )
@@ -94,28 +222,51 @@ impl PgSQLAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to create PostgreSQL table: {e}")))?;
 
-        // Add migrations for columns that may not exist
-        // PostgreSQL supports ADD COLUMN IF NOT EXISTS
-        let columns_to_add = vec![("allowed_origins", "JSONB"), ("webhooks", "JSONB")];
+        info!("Ensured table '{}' exists", self.config.table_name);
+        Ok(())
+    }
 
-        for (column_name, column_type) in columns_to_add {
-            let add_column_query = format!(
-                r#"ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {}"#,
-                self.config.table_name, column_name, column_type
-            );
+    async fn add_column_if_not_exists(&self, column_name: &str, column_type: &str) -> Result<()> {
+        let add_column_query = format!(
+            r#"ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {}"#,
+            self.config.table_name, column_name, column_type
+        );
 
-            sqlx::query(&add_column_query)
+        sqlx::query(&add_column_query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to add column '{}' to table '{}': {}",
+                    column_name, self.config.table_name, e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Encrypted secrets do not fit the VARCHAR(255) column older versions created
+    async fn widen_secret_column(&self) -> Result<()> {
+        let secret_type: Option<(String,)> = sqlx::query_as(
+            "SELECT format_type(atttypid, atttypmod) FROM pg_attribute \
+             WHERE attrelid = $1::regclass AND attname = 'secret'",
+        )
+        .bind(&self.config.table_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to check secret column type: {e}")))?;
+
+        if secret_type.is_some_and(|(secret_type,)| secret_type != "text") {
+            let alter_query = format!(
+                r#"ALTER TABLE {} ALTER COLUMN secret TYPE TEXT"#,
+                self.config.table_name
+            );
+            sqlx::query(&alter_query)
                 .execute(&self.pool)
                 .await
-                .map_err(|e| {
-                    Error::Internal(format!(
-                        "Failed to add column '{}' to table '{}': {}",
-                        column_name, self.config.table_name, e
-                    ))
-                })?;
+                .map_err(|e| Error::Internal(format!("Failed to widen secret column: {e}")))?;
+            info!("Widened secret column of '{}'", self.config.table_name);
         }
 
-        info!("Ensured table '{}' exists", self.config.table_name);
         Ok(())
     }
 
@@ -141,7 +292,15 @@ impl PgSQLAppManager {
                 enable_user_authentication,
                 enable_watchlist_events,
                 webhooks,
-                allowed_origins
+                allowed_origins,
+                credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer
             FROM {} WHERE id = $1"#,
             self.config.table_name
         );
@@ -180,21 +339,35 @@ impl PgSQLAppManager {
                 enable_user_authentication,
                 enable_watchlist_events,
                 webhooks,
-                allowed_origins
-            FROM {} WHERE key = $1"#,
+                allowed_origins,
+                credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer
+            FROM {}
+            WHERE key = $1
+                OR credentials @> jsonb_build_array(jsonb_build_object('key', $1::text))"#,
             self.config.table_name
         );
 
-        let app_result = sqlx::query_as::<_, AppRow>(&query)
+        let rows = sqlx::query_as::<_, AppRow>(&query)
             .bind(key)
-            .fetch_optional(&self.pool)
+            .fetch_all(&self.pool)
             .await
             .map_err(|e| {
                 error!("Database error fetching app by key {}: {}", key, e);
                 Error::Internal(format!("Failed to fetch app from PostgreSQL: {e}"))
             })?;
 
-        Ok(app_result.map(|row| row.into_app()))
+        // Credential keys only count while the credential is accepted
+        Ok(rows
+            .into_iter()
+            .map(|row| row.into_app())
+            .find(|app| app.accepts_key(key)))
     }
 
     /// Register a new app in the database
@@ -210,8 +383,15 @@ impl PgSQLAppManager {
                 max_presence_member_size_in_kb, max_channel_name_length,
                 max_event_channels_at_once, max_event_name_length,
                 max_event_payload_in_kb, max_event_batch_size, enable_user_authentication,
-                enable_watchlist_events, webhooks, allowed_origins
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)"#,
+                enable_watchlist_events, webhooks, allowed_origins, credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28)"#,
             self.config.table_name
         );
 
@@ -236,6 +416,14 @@ impl PgSQLAppManager {
             .bind(app.enable_watchlist_events)
             .bind(sqlx::types::Json(&app.webhooks))
             .bind(sqlx::types::Json(&app.allowed_origins))
+            .bind(sqlx::types::Json(&app.credentials))
+            .bind(sqlx::types::Json(&app.channel_policy))
+            .bind(sqlx::types::Json(&app.channel_limits))
+            .bind(sqlx::types::Json(&app.quotas))
+            .bind(sqlx::types::Json(&app.jwt_auth))
+            .bind(sqlx::types::Json(&app.api_auth))
+            .bind(sqlx::types::Json(&app.ip_filter))
+            .bind(sqlx::types::Json(&app.authorizer))
             .execute(&self.pool)
             .await
             .map_err(|e| {
@@ -260,9 +448,10 @@ impl PgSQLAppManager {
                 max_event_channels_at_once = $12, max_event_name_length = $13,
                 max_event_payload_in_kb = $14, max_event_batch_size = $15,
                 enable_user_authentication = $16, enable_watchlist_events = $17,
-                webhooks = $18, allowed_origins = $19,
+                webhooks = $18, allowed_origins = $19, credentials = $20,
+                channel_policy = $21, channel_limits = $22, quotas = $23, jwt_auth = $24, api_auth = $25, ip_filter = $26, authorizer = $27,
                 updated_at = CURRENT_TIMESTAMP
-                WHERE id = $20"#,
+                WHERE id = $28"#,
             self.config.table_name
         );
 
@@ -286,6 +475,14 @@ impl PgSQLAppManager {
             .bind(app.enable_watchlist_events)
             .bind(sqlx::types::Json(&app.webhooks))
             .bind(sqlx::types::Json(&app.allowed_origins))
+            .bind(sqlx::types::Json(&app.credentials))
+            .bind(sqlx::types::Json(&app.channel_policy))
+            .bind(sqlx::types::Json(&app.channel_limits))
+            .bind(sqlx::types::Json(&app.quotas))
+            .bind(sqlx::types::Json(&app.jwt_auth))
+            .bind(sqlx::types::Json(&app.api_auth))
+            .bind(sqlx::types::Json(&app.ip_filter))
+            .bind(sqlx::types::Json(&app.authorizer))
             .bind(&app.id)
             .execute(&self.pool)
             .await
@@ -345,7 +542,15 @@ impl PgSQLAppManager {
             enable_user_authentication,
             enable_watchlist_events,
             webhooks,
-            allowed_origins
+            allowed_origins,
+            credentials,
+            channel_policy,
+            channel_limits,
+            quotas,
+            jwt_auth,
+            api_auth,
+            ip_filter,
+            authorizer
         FROM {}"#,
             self.config.table_name
         );
@@ -371,7 +576,7 @@ impl PgSQLAppManager {
         Ok(self.find_by_id(app_id).await?.is_some())
     }
 
-    /// Validate a signature against an app's secret
+    /// Validate a signature against the app's accepted secrets
     pub async fn validate_signature(
         &self,
         app_id: &str,
@@ -383,10 +588,7 @@ impl PgSQLAppManager {
             .await?
             .ok_or_else(|| Error::InvalidAppKey)?;
 
-        let token = Token::new(app.key.clone(), app.secret.clone());
-        let expected = token.sign(body);
-
-        Ok(signature == expected)
+        Ok(app.token().verify(body, signature))
     }
 
     /// Validate if a channel name is valid for an app
@@ -445,7 +647,7 @@ impl PgSQLAppManager {
         let string_to_sign = format!("{socket_id}::user::{signature}");
 
         // Generate token
-        let token = Token::new(app.key.clone(), app.secret.clone());
+        let token = app.token();
 
         // Verify
         Ok(token.verify(&string_to_sign, &signature))
@@ -477,6 +679,22 @@ struct AppRow {
     webhooks: Option<Vec<Webhook>>,
     #[sqlx(json(nullable))]
     allowed_origins: Option<Vec<String>>,
+    #[sqlx(json(nullable))]
+    credentials: Option<Vec<AppCredential>>,
+    #[sqlx(json(nullable))]
+    channel_policy: Option<ChannelPolicy>,
+    #[sqlx(json(nullable))]
+    channel_limits: Option<Vec<ChannelLimitRule>>,
+    #[sqlx(json(nullable))]
+    quotas: Option<AppQuotas>,
+    #[sqlx(json(nullable))]
+    jwt_auth: Option<JwtAuthConfig>,
+    #[sqlx(json(nullable))]
+    api_auth: Option<ApiAuthConfig>,
+    #[sqlx(json(nullable))]
+    ip_filter: Option<IpFilterConfig>,
+    #[sqlx(json(nullable))]
+    authorizer: Option<AuthorizerConfig>,
 }
 
 impl AppRow {
@@ -505,10 +723,185 @@ impl AppRow {
             webhooks: self.webhooks,
             enable_watchlist_events: self.enable_watchlist_events,
             allowed_origins: self.allowed_origins,
+            credentials: self.credentials,
+            channel_policy: self.channel_policy,
+            channel_limits: self.channel_limits,
+            quotas: self.quotas,
+            jwt_auth: self.jwt_auth,
+            api_auth: self.api_auth,
+            ip_filter: self.ip_filter,
+            authorizer: self.authorizer,
         }
     }
 }
 
+/// Installs the trigger `listen_for_changes` depends on
+const CHANGE_TRIGGER_MIGRATION: u32 = 13;
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create applications table",
+    },
+    Migration {
+        version: 2,
+        description: "add allowed_origins column",
+    },
+    Migration {
+        version: 3,
+        description: "add webhooks column",
+    },
+    Migration {
+        version: 4,
+        description: "add credentials column",
+    },
+    Migration {
+        version: 5,
+        description: "widen secret column to TEXT",
+    },
+    Migration {
+        version: 6,
+        description: "add channel_policy column",
+    },
+    Migration {
+        version: 7,
+        description: "add channel_limits column",
+    },
+    Migration {
+        version: 8,
+        description: "add quotas column",
+    },
+    Migration {
+        version: 9,
+        description: "add jwt_auth column",
+    },
+    Migration {
+        version: 10,
+        description: "add api_auth column",
+    },
+    Migration {
+        version: 11,
+        description: "add ip_filter column",
+    },
+    Migration {
+        version: 12,
+        description: "add authorizer column",
+    },
+    Migration {
+        version: CHANGE_TRIGGER_MIGRATION,
+        description: "add app change notification trigger",
+    },
+];
+
+#[async_trait]
+impl SchemaMigrations for PgSQLAppManager {
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    async fn applied_versions(&self) -> Result<Vec<u32>> {
+        let history_table = self.history_table();
+        let (exists,): (bool,) = sqlx::query_as("SELECT to_regclass($1) IS NOT NULL")
+            .bind(&history_table)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up {history_table}: {e}")))?;
+        if !exists {
+            return Ok(Vec::new());
+        }
+
+        let versions: Vec<(i32,)> = sqlx::query_as(&format!("SELECT version FROM {history_table}"))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to read {history_table}: {e}")))?;
+        Ok(versions.into_iter().map(|(v,)| v as u32).collect())
+    }
+
+    async fn ensure_history_table(&self) -> Result<()> {
+        let query = format!(
+            r#"CREATE TABLE IF NOT EXISTS {} (
+                version INTEGER PRIMARY KEY,
+                description VARCHAR(255) NOT NULL,
+                applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )"#,
+            self.history_table()
+        );
+        sqlx::query(&query).execute(&self.pool).await.map_err(|e| {
+            Error::Internal(format!("Failed to create migration history table: {e}"))
+        })?;
+        Ok(())
+    }
+
+    async fn lock_migrations(&self) -> Result<MigrationLock> {
+        // Advisory locks belong to the session, so the connection is kept until the release
+        let lock_name = self.history_table();
+        let mut connection = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to take the migration lock: {e}")))?;
+        sqlx::query("SELECT pg_advisory_lock(hashtext($1))")
+            .bind(&lock_name)
+            .execute(&mut *connection)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to take the migration lock: {e}")))?;
+        Ok(MigrationLock::new(Box::pin(async move {
+            sqlx::query("SELECT pg_advisory_unlock(hashtext($1))")
+                .bind(lock_name)
+                .execute(&mut *connection)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to release migration lock: {e}")))?;
+            Ok(())
+        })))
+    }
+
+    async fn apply_migration(&self, migration: &Migration) -> Result<()> {
+        match migration.version {
+            1 => self.create_table().await?,
+            2 => {
+                self.add_column_if_not_exists("allowed_origins", "JSONB")
+                    .await?
+            }
+            3 => self.add_column_if_not_exists("webhooks", "JSONB").await?,
+            4 => {
+                self.add_column_if_not_exists("credentials", "JSONB")
+                    .await?
+            }
+            5 => self.widen_secret_column().await?,
+            6 => {
+                self.add_column_if_not_exists("channel_policy", "JSONB")
+                    .await?
+            }
+            7 => {
+                self.add_column_if_not_exists("channel_limits", "JSONB")
+                    .await?
+            }
+            8 => self.add_column_if_not_exists("quotas", "JSONB").await?,
+            9 => self.add_column_if_not_exists("jwt_auth", "JSONB").await?,
+            10 => self.add_column_if_not_exists("api_auth", "JSONB").await?,
+            11 => self.add_column_if_not_exists("ip_filter", "JSONB").await?,
+            12 => self.add_column_if_not_exists("authorizer", "JSONB").await?,
+            CHANGE_TRIGGER_MIGRATION => self.create_change_trigger().await?,
+            version => {
+                return Err(Error::Internal(format!(
+                    "Unknown PostgreSQL migration {version}"
+                )));
+            }
+        }
+
+        sqlx::query(&format!(
+            "INSERT INTO {} (version, description) VALUES ($1, $2)",
+            self.history_table()
+        ))
+        .bind(migration.version as i32)
+        .bind(migration.description)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to record migration: {e}")))?;
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl AppManager for PgSQLAppManager {
     async fn init(&self) -> Result<()> {
@@ -540,6 +933,10 @@ impl AppManager for PgSQLAppManager {
         self.find_by_key(key).await
     }
 
+    async fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        run_migrations(self, dry_run).await
+    }
+
     async fn check_health(&self) -> Result<()> {
         sqlx::query("SELECT 1")
             .fetch_one(&self.pool)
@@ -551,6 +948,10 @@ impl AppManager for PgSQLAppManager {
             })?;
         Ok(())
     }
+
+    fn subscribe_changes(&self) -> Option<broadcast::Receiver<AppChange>> {
+        self.changes.as_ref().map(broadcast::Sender::subscribe)
+    }
 }
 
 impl Clone for PgSQLAppManager {
@@ -558,6 +959,7 @@ impl Clone for PgSQLAppManager {
         Self {
             config: self.config.clone(),
             pool: self.pool.clone(),
+            changes: self.changes.clone(),
         }
     }
 }
@@ -602,6 +1004,14 @@ mod tests {
             webhooks: None,
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         }
     }
 
@@ -614,6 +1024,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Test registering an app
         let test_app = create_test_app("test1");
@@ -652,6 +1063,31 @@ mod tests {
         manager.delete_app("test2").await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_changes_are_notified_by_the_migrated_trigger() {
+        let config = get_test_db_config("apps_notify_test").await;
+        let mut manager = PgSQLAppManager::new(config, DatabasePooling::default())
+            .await
+            .unwrap();
+        manager.migrate(false).await.unwrap();
+        // Migrating again leaves the trigger in place
+        manager.create_change_trigger().await.unwrap();
+        manager.listen_for_changes().await.unwrap();
+        let mut changes = manager.subscribe_changes().unwrap();
+
+        manager
+            .create_app(create_test_app("notify1"))
+            .await
+            .unwrap();
+        let change = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(change, AppChange::Invalidated { app_id } if app_id == "notify1"));
+
+        manager.delete_app("notify1").await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_webhooks_serialization() {
         let config = get_test_db_config("apps_webhooks_test").await;
@@ -659,6 +1095,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with webhooks
         let webhook = Webhook {
@@ -697,6 +1134,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with multiple webhooks
         let webhook1 = Webhook {
@@ -742,6 +1180,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Test with watchlist enabled
         let mut app1 = create_test_app("watchlist_enabled");
@@ -787,6 +1226,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Test with allowed origins
         let mut app = create_test_app("origins_test");
@@ -816,6 +1256,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app without webhooks
         let app = create_test_app("update_webhooks");
@@ -857,6 +1298,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with webhooks
         let webhook = Webhook {
@@ -889,6 +1331,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create multiple apps with different webhook configurations
         let webhook1 = Webhook {
@@ -937,6 +1380,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Health check should succeed
         let result = manager.check_health().await;
@@ -950,6 +1394,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Try to delete non-existent app
         let result = manager.delete_app("nonexistent").await;
@@ -963,6 +1408,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Try to update non-existent app
         let app = create_test_app("nonexistent");
@@ -977,6 +1423,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with all optional fields as None
         let mut app = create_test_app("null_test");
@@ -1007,6 +1454,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with empty webhooks array
         let mut app = create_test_app("empty_webhooks");
@@ -1029,6 +1477,7 @@ mod tests {
         let manager = PgSQLAppManager::new(config, DatabasePooling::default())
             .await
             .unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with Lambda webhook
         let webhook = Webhook {
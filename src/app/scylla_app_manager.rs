@@ -1,6 +1,16 @@
-use super::config::App;
+use super::config::{App, AppCredential};
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::AuthorizerConfig;
+use crate::app::channel_limits::ChannelLimitRule;
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::ip_filter::IpFilterConfig;
+use crate::app::jwt_auth::JwtAuthConfig;
 use crate::app::manager::AppManager;
+use crate::app::migrations::{
+    Migration, MigrationLock, MigrationStatus, SchemaMigrations, run_migrations,
+};
 use crate::error::{Error, Result};
+use crate::quota::AppQuotas;
 use crate::webhook::types::Webhook;
 use async_trait::async_trait;
 use futures::TryStreamExt;
@@ -9,6 +19,7 @@ use scylla::client::session_builder::SessionBuilder;
 use scylla::statement::prepared::PreparedStatement;
 use scylla::{DeserializeRow, SerializeRow};
 use std::sync::Arc;
+use tokio::sync::OnceCell;
 use tracing::{debug, error, info};
 
 /// Configuration for ScyllaDB App Manager
@@ -41,13 +52,19 @@ impl Default for ScyllaDbConfig {
 pub struct ScyllaDbAppManager {
     config: ScyllaDbConfig,
     session: Arc<Session>,
-    insert_stmt: Arc<PreparedStatement>,
-    update_stmt: Arc<PreparedStatement>,
-    delete_stmt: Arc<PreparedStatement>,
+    /// Prepared on first use, because the table may only exist once migrations ran
+    statements: OnceCell<PreparedStatements>,
+}
+
+struct PreparedStatements {
+    insert: PreparedStatement,
+    update: PreparedStatement,
+    delete: PreparedStatement,
 }
 
 impl ScyllaDbAppManager {
-    /// Create a new ScyllaDB-based AppManager with the provided configuration
+    /// Create a new ScyllaDB-based AppManager with the provided configuration.
+    /// The keyspace and table are created by `migrate`.
     pub async fn new(config: ScyllaDbConfig) -> Result<Self> {
         info!(
             "Initializing ScyllaDB AppManager with nodes: {:?}",
@@ -67,12 +84,21 @@ impl ScyllaDbAppManager {
             .await
             .map_err(|e| Error::Internal(format!("Failed to connect to ScyllaDB cluster: {e}")))?;
 
-        let session = Arc::new(session);
+        Ok(Self {
+            config,
+            session: Arc::new(session),
+            statements: OnceCell::new(),
+        })
+    }
 
-        // Ensure keyspace and table exist before preparing statements
-        Self::ensure_keyspace_and_table(&session, &config).await?;
+    async fn statements(&self) -> Result<&PreparedStatements> {
+        self.statements
+            .get_or_try_init(|| self.prepare_statements())
+            .await
+    }
 
-        // Prepare statements once for reuse
+    async fn prepare_statements(&self) -> Result<PreparedStatements> {
+        let config = &self.config;
         let insert_query = format!(
             r#"INSERT INTO {}.{} (
                 id, key, secret, max_connections, enable_client_messages, enabled,
@@ -82,9 +108,10 @@ impl ScyllaDbAppManager {
                 max_event_channels_at_once, max_event_name_length,
                 max_event_payload_in_kb, max_event_batch_size,
                 enable_user_authentication, enable_watchlist_events,
-                webhooks, allowed_origins,
+                webhooks, allowed_origins, credentials,
+                channel_policy, channel_limits, quotas, jwt_auth, api_auth, ip_filter, authorizer,
                 created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, toTimestamp(now()), toTimestamp(now()))"#,
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, toTimestamp(now()), toTimestamp(now()))"#,
             config.keyspace, config.table_name
         );
 
@@ -97,7 +124,8 @@ impl ScyllaDbAppManager {
                 max_event_channels_at_once = ?, max_event_name_length = ?,
                 max_event_payload_in_kb = ?, max_event_batch_size = ?,
                 enable_user_authentication = ?, enable_watchlist_events = ?,
-                webhooks = ?, allowed_origins = ?,
+                webhooks = ?, allowed_origins = ?, credentials = ?,
+                channel_policy = ?, channel_limits = ?, quotas = ?, jwt_auth = ?, api_auth = ?, ip_filter = ?, authorizer = ?,
                 updated_at = toTimestamp(now())
             WHERE id = ?"#,
             config.keyspace, config.table_name
@@ -108,50 +136,158 @@ impl ScyllaDbAppManager {
             config.keyspace, config.table_name
         );
 
-        let insert_stmt = session
+        let insert = self
+            .session
             .prepare(insert_query)
             .await
             .map_err(|e| Error::Internal(format!("Failed to prepare insert statement: {e}")))?;
 
-        let update_stmt = session
+        let update = self
+            .session
             .prepare(update_query)
             .await
             .map_err(|e| Error::Internal(format!("Failed to prepare update statement: {e}")))?;
 
-        let delete_stmt = session
+        let delete = self
+            .session
             .prepare(delete_query)
             .await
             .map_err(|e| Error::Internal(format!("Failed to prepare delete statement: {e}")))?;
 
-        Ok(Self {
-            config,
-            session,
-            insert_stmt: Arc::new(insert_stmt),
-            update_stmt: Arc::new(update_stmt),
-            delete_stmt: Arc::new(delete_stmt),
+        Ok(PreparedStatements {
+            insert,
+            update,
+            delete,
         })
     }
 
-    /// Create the keyspace and applications table if they don't exist
-    async fn ensure_keyspace_and_table(
-        session: &Arc<Session>,
-        config: &ScyllaDbConfig,
-    ) -> Result<()> {
-        // Create keyspace if it doesn't exist
-        let create_keyspace_query = format!(
-            r#"CREATE KEYSPACE IF NOT EXISTS {}
-               WITH replication = {{'class': '{}', 'replication_factor': {}}}"#,
-            config.keyspace, config.replication_class, config.replication_factor
+    fn history_table(&self) -> String {
+        format!("{}_schema_migrations", self.config.table_name)
+    }
+
+    fn credential_keys_table(&self) -> String {
+        format!("{}_credential_keys", self.config.table_name)
+    }
+
+    /// Point the app's credential keys at it in the lookup table and drop the keys it
+    /// no longer has. The app key itself is found through the index on `key`.
+    async fn sync_credential_keys(&self, app: &App, previous: Option<&App>) -> Result<()> {
+        let keys = app.keys();
+        let table = self.credential_keys_table();
+
+        let insert_query = format!(
+            "INSERT INTO {}.{} (key, app_id) VALUES (?, ?)",
+            self.config.keyspace, table
         );
+        for key in keys.iter().filter(|key| **key != app.key) {
+            self.session
+                .query_unpaged(insert_query.clone(), (*key, &app.id))
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to store credential key: {e}")))?;
+        }
+
+        let removed: Vec<&str> = previous
+            .map(App::keys)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|key| !keys.contains(key))
+            .collect();
+        self.remove_credential_keys(&removed).await
+    }
+
+    async fn remove_credential_keys(&self, keys: &[&str]) -> Result<()> {
+        let delete_query = format!(
+            "DELETE FROM {}.{} WHERE key = ?",
+            self.config.keyspace,
+            self.credential_keys_table()
+        );
+        for key in keys {
+            self.session
+                .query_unpaged(delete_query.clone(), (*key,))
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to remove credential key: {e}")))?;
+        }
+        Ok(())
+    }
 
-        session
-            .query_unpaged(create_keyspace_query, &[])
+    /// Look a credential key up in the lookup table. Entries are only hints, so the
+    /// app must still accept the key.
+    async fn find_by_credential_key(&self, key: &str) -> Result<Option<App>> {
+        let query = format!(
+            "SELECT app_id FROM {}.{} WHERE key = ?",
+            self.config.keyspace,
+            self.credential_keys_table()
+        );
+        let mut rows = self
+            .session
+            .query_iter(query, (key,))
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create keyspace: {e}")))?;
+            .map_err(|e| Error::Internal(format!("Failed to look up credential key: {e}")))?
+            .rows_stream::<(String,)>()
+            .map_err(|e| Error::Internal(format!("Failed to create rows stream: {e}")))?;
 
-        info!("Ensured keyspace '{}' exists", config.keyspace);
+        let Some((app_id,)) = rows
+            .try_next()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up credential key: {e}")))?
+        else {
+            return Ok(None);
+        };
+        Ok(self
+            .find_by_id(&app_id)
+            .await?
+            .filter(|app| app.accepts_key(key)))
+    }
 
-        // Create table if it doesn't exist
+    /// Create the credential key lookup table and fill it from the existing apps
+    async fn create_credential_keys_table(&self) -> Result<()> {
+        let create_table_query = format!(
+            r#"CREATE TABLE IF NOT EXISTS {}.{} (
+                key text PRIMARY KEY,
+                app_id text
+            )"#,
+            self.config.keyspace,
+            self.credential_keys_table()
+        );
+        self.execute_schema_query(create_table_query, "create credential key table")
+            .await?;
+
+        for app in self.get_apps().await? {
+            self.sync_credential_keys(&app, None).await?;
+        }
+        Ok(())
+    }
+
+    async fn table_exists(&self, table_name: &str) -> Result<bool> {
+        let mut rows = self
+            .session
+            .query_iter(
+                "SELECT table_name FROM system_schema.tables \
+                 WHERE keyspace_name = ? AND table_name = ?",
+                (&self.config.keyspace, table_name),
+            )
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up table {table_name}: {e}")))?
+            .rows_stream::<(String,)>()
+            .map_err(|e| Error::Internal(format!("Failed to create rows stream: {e}")))?;
+
+        Ok(rows
+            .try_next()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up table {table_name}: {e}")))?
+            .is_some())
+    }
+
+    async fn execute_schema_query(&self, query: String, action: &str) -> Result<()> {
+        self.session
+            .query_unpaged(query, &[])
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to {action}: {e}")))?;
+        Ok(())
+    }
+
+    /// Create the applications table with the columns of the first schema version
+    async fn create_table(&self) -> Result<()> {
         let create_table_query = format!(
             r#"CREATE TABLE IF NOT EXISTS {}.{} (
                 id text PRIMARY KEY,
@@ -177,30 +313,206 @@ impl ScyllaDbAppManager {
                 created_at timestamp,
                 updated_at timestamp
             )"#,
-            config.keyspace, config.table_name
+            self.config.keyspace, self.config.table_name
         );
-
-        session
-            .query_unpaged(create_table_query, &[])
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to create table: {e}")))?;
+        self.execute_schema_query(create_table_query, "create table")
+            .await?;
 
         info!(
             "Ensured table '{}.{}' exists",
-            config.keyspace, config.table_name
+            self.config.keyspace, self.config.table_name
         );
+        Ok(())
+    }
 
-        // Create secondary index on key for lookups
-        let create_index_query = format!(
-            r#"CREATE INDEX IF NOT EXISTS ON {}.{} (key)"#,
-            config.keyspace, config.table_name
+    /// CQL has no ADD IF NOT EXISTS, so look the column up first
+    async fn add_column_if_not_exists(&self, column_name: &str, column_type: &str) -> Result<()> {
+        let mut rows = self
+            .session
+            .query_iter(
+                "SELECT column_name FROM system_schema.columns \
+                 WHERE keyspace_name = ? AND table_name = ? AND column_name = ?",
+                (&self.config.keyspace, &self.config.table_name, column_name),
+            )
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up column {column_name}: {e}")))?
+            .rows_stream::<(String,)>()
+            .map_err(|e| Error::Internal(format!("Failed to create rows stream: {e}")))?;
+        let exists = rows
+            .try_next()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to look up column {column_name}: {e}")))?
+            .is_some();
+        if exists {
+            debug!("Column '{}' already exists", column_name);
+            return Ok(());
+        }
+
+        let add_column_query = format!(
+            r#"ALTER TABLE {}.{} ADD {} {}"#,
+            self.config.keyspace, self.config.table_name, column_name, column_type
         );
+        self.execute_schema_query(add_column_query, &format!("add column '{column_name}'"))
+            .await
+    }
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create applications table",
+    },
+    Migration {
+        version: 2,
+        description: "create index on key",
+    },
+    Migration {
+        version: 3,
+        description: "add credentials column",
+    },
+    Migration {
+        version: 4,
+        description: "add channel_policy column",
+    },
+    Migration {
+        version: 5,
+        description: "add channel_limits column",
+    },
+    Migration {
+        version: 6,
+        description: "add quotas column",
+    },
+    Migration {
+        version: 7,
+        description: "add jwt_auth column",
+    },
+    Migration {
+        version: 8,
+        description: "add api_auth column",
+    },
+    Migration {
+        version: 9,
+        description: "add ip_filter column",
+    },
+    Migration {
+        version: 10,
+        description: "add authorizer column",
+    },
+    Migration {
+        version: 11,
+        description: "create credential key lookup table",
+    },
+];
+
+#[async_trait]
+impl SchemaMigrations for ScyllaDbAppManager {
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
 
-        session
-            .query_unpaged(create_index_query, &[])
+    async fn applied_versions(&self) -> Result<Vec<u32>> {
+        let history_table = self.history_table();
+        if !self.table_exists(&history_table).await? {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            "SELECT version FROM {}.{}",
+            self.config.keyspace, history_table
+        );
+        let mut rows = self
+            .session
+            .query_iter(query, &[])
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create index on key: {e}")))?;
+            .map_err(|e| Error::Internal(format!("Failed to read {history_table}: {e}")))?
+            .rows_stream::<(i32,)>()
+            .map_err(|e| Error::Internal(format!("Failed to create rows stream: {e}")))?;
 
+        let mut versions = Vec::new();
+        while let Some((version,)) = rows
+            .try_next()
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to read {history_table}: {e}")))?
+        {
+            versions.push(version as u32);
+        }
+        Ok(versions)
+    }
+
+    async fn ensure_history_table(&self) -> Result<()> {
+        let create_keyspace_query = format!(
+            r#"CREATE KEYSPACE IF NOT EXISTS {}
+               WITH replication = {{'class': '{}', 'replication_factor': {}}}"#,
+            self.config.keyspace, self.config.replication_class, self.config.replication_factor
+        );
+        self.execute_schema_query(create_keyspace_query, "create keyspace")
+            .await?;
+        info!("Ensured keyspace '{}' exists", self.config.keyspace);
+
+        let create_history_query = format!(
+            r#"CREATE TABLE IF NOT EXISTS {}.{} (
+                version int PRIMARY KEY,
+                description text,
+                applied_at timestamp
+            )"#,
+            self.config.keyspace,
+            self.history_table()
+        );
+        self.execute_schema_query(create_history_query, "create migration history table")
+            .await
+    }
+
+    /// Schema changes are idempotent and recording a version again overwrites its row
+    async fn lock_migrations(&self) -> Result<MigrationLock> {
+        Ok(MigrationLock::none())
+    }
+
+    async fn apply_migration(&self, migration: &Migration) -> Result<()> {
+        match migration.version {
+            1 => self.create_table().await?,
+            2 => {
+                // Secondary index on key for lookups
+                let create_index_query = format!(
+                    r#"CREATE INDEX IF NOT EXISTS ON {}.{} (key)"#,
+                    self.config.keyspace, self.config.table_name
+                );
+                self.execute_schema_query(create_index_query, "create index on key")
+                    .await?
+            }
+            3 => self.add_column_if_not_exists("credentials", "text").await?,
+            4 => {
+                self.add_column_if_not_exists("channel_policy", "text")
+                    .await?
+            }
+            5 => {
+                self.add_column_if_not_exists("channel_limits", "text")
+                    .await?
+            }
+            6 => self.add_column_if_not_exists("quotas", "text").await?,
+            7 => self.add_column_if_not_exists("jwt_auth", "text").await?,
+            8 => self.add_column_if_not_exists("api_auth", "text").await?,
+            9 => self.add_column_if_not_exists("ip_filter", "text").await?,
+            10 => self.add_column_if_not_exists("authorizer", "text").await?,
+            11 => self.create_credential_keys_table().await?,
+            version => {
+                return Err(Error::Internal(format!(
+                    "Unknown ScyllaDB migration {version}"
+                )));
+            }
+        }
+
+        let record_query = format!(
+            "INSERT INTO {}.{} (version, description, applied_at) VALUES (?, ?, toTimestamp(now()))",
+            self.config.keyspace,
+            self.history_table()
+        );
+        self.session
+            .query_unpaged(
+                record_query,
+                (migration.version as i32, migration.description),
+            )
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to record migration: {e}")))?;
         Ok(())
     }
 }
@@ -228,6 +540,14 @@ struct AppRow {
     enable_watchlist_events: Option<bool>,
     webhooks: Option<String>,
     allowed_origins: Option<String>,
+    credentials: Option<String>,
+    channel_policy: Option<String>,
+    channel_limits: Option<String>,
+    quotas: Option<String>,
+    jwt_auth: Option<String>,
+    api_auth: Option<String>,
+    ip_filter: Option<String>,
+    authorizer: Option<String>,
 }
 
 /// Struct for UPDATE (SET fields first, then id for WHERE)
@@ -252,6 +572,14 @@ struct UpdateRow {
     enable_watchlist_events: Option<bool>,
     webhooks: Option<String>,
     allowed_origins: Option<String>,
+    credentials: Option<String>,
+    channel_policy: Option<String>,
+    channel_limits: Option<String>,
+    quotas: Option<String>,
+    jwt_auth: Option<String>,
+    api_auth: Option<String>,
+    ip_filter: Option<String>,
+    authorizer: Option<String>,
     id: String,
 }
 
@@ -276,6 +604,80 @@ impl UpdateRow {
             })
             .transpose()?;
 
+        let credentials = app
+            .credentials
+            .as_ref()
+            .map(|c| {
+                serde_json::to_string(c)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize credentials: {}", e)))
+            })
+            .transpose()?;
+
+        let channel_policy = app
+            .channel_policy
+            .as_ref()
+            .map(|p| {
+                serde_json::to_string(p).map_err(|e| {
+                    Error::Internal(format!("Failed to serialize channel_policy: {}", e))
+                })
+            })
+            .transpose()?;
+
+        let channel_limits = app
+            .channel_limits
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v).map_err(|e| {
+                    Error::Internal(format!("Failed to serialize channel_limits: {}", e))
+                })
+            })
+            .transpose()?;
+
+        let quotas = app
+            .quotas
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize quotas: {}", e)))
+            })
+            .transpose()?;
+
+        let jwt_auth = app
+            .jwt_auth
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize jwt_auth: {}", e)))
+            })
+            .transpose()?;
+
+        let api_auth = app
+            .api_auth
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize api_auth: {}", e)))
+            })
+            .transpose()?;
+
+        let ip_filter = app
+            .ip_filter
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize ip_filter: {}", e)))
+            })
+            .transpose()?;
+
+        let authorizer = app
+            .authorizer
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize authorizer: {}", e)))
+            })
+            .transpose()?;
+
         Ok(Self {
             key: app.key.clone(),
             secret: app.secret.clone(),
@@ -298,6 +700,14 @@ impl UpdateRow {
             enable_watchlist_events: app.enable_watchlist_events,
             webhooks,
             allowed_origins,
+            credentials,
+            channel_policy,
+            channel_limits,
+            quotas,
+            jwt_auth,
+            api_auth,
+            ip_filter,
+            authorizer,
             id: app.id.clone(),
         })
     }
@@ -324,6 +734,80 @@ impl AppRow {
             })
             .transpose()?;
 
+        let credentials = app
+            .credentials
+            .as_ref()
+            .map(|c| {
+                serde_json::to_string(c)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize credentials: {}", e)))
+            })
+            .transpose()?;
+
+        let channel_policy = app
+            .channel_policy
+            .as_ref()
+            .map(|p| {
+                serde_json::to_string(p).map_err(|e| {
+                    Error::Internal(format!("Failed to serialize channel_policy: {}", e))
+                })
+            })
+            .transpose()?;
+
+        let channel_limits = app
+            .channel_limits
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v).map_err(|e| {
+                    Error::Internal(format!("Failed to serialize channel_limits: {}", e))
+                })
+            })
+            .transpose()?;
+
+        let quotas = app
+            .quotas
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize quotas: {}", e)))
+            })
+            .transpose()?;
+
+        let jwt_auth = app
+            .jwt_auth
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize jwt_auth: {}", e)))
+            })
+            .transpose()?;
+
+        let api_auth = app
+            .api_auth
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize api_auth: {}", e)))
+            })
+            .transpose()?;
+
+        let ip_filter = app
+            .ip_filter
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize ip_filter: {}", e)))
+            })
+            .transpose()?;
+
+        let authorizer = app
+            .authorizer
+            .as_ref()
+            .map(|v| {
+                serde_json::to_string(v)
+                    .map_err(|e| Error::Internal(format!("Failed to serialize authorizer: {}", e)))
+            })
+            .transpose()?;
+
         Ok(Self {
             id: app.id.clone(),
             key: app.key.clone(),
@@ -347,6 +831,14 @@ impl AppRow {
             enable_watchlist_events: app.enable_watchlist_events,
             webhooks,
             allowed_origins,
+            credentials,
+            channel_policy,
+            channel_limits,
+            quotas,
+            jwt_auth,
+            api_auth,
+            ip_filter,
+            authorizer,
         })
     }
 
@@ -389,6 +881,72 @@ impl AppRow {
                     })
                     .ok()
             }),
+            credentials: self.credentials.and_then(|json| {
+                serde_json::from_str::<Vec<AppCredential>>(&json)
+                    .map_err(|e| {
+                        error!(
+                            "Failed to deserialize credentials for app {}: {}",
+                            self.id, e
+                        )
+                    })
+                    .ok()
+            }),
+            channel_policy: self.channel_policy.and_then(|json| {
+                serde_json::from_str::<ChannelPolicy>(&json)
+                    .map_err(|e| {
+                        error!(
+                            "Failed to deserialize channel_policy for app {}: {}",
+                            self.id, e
+                        )
+                    })
+                    .ok()
+            }),
+            channel_limits: self.channel_limits.and_then(|json| {
+                serde_json::from_str::<Vec<ChannelLimitRule>>(&json)
+                    .map_err(|e| {
+                        error!(
+                            "Failed to deserialize channel_limits for app {}: {}",
+                            self.id, e
+                        )
+                    })
+                    .ok()
+            }),
+            quotas: self.quotas.and_then(|json| {
+                serde_json::from_str::<AppQuotas>(&json)
+                    .map_err(|e| error!("Failed to deserialize quotas for app {}: {}", self.id, e))
+                    .ok()
+            }),
+            jwt_auth: self.jwt_auth.and_then(|json| {
+                serde_json::from_str::<JwtAuthConfig>(&json)
+                    .map_err(|e| {
+                        error!("Failed to deserialize jwt_auth for app {}: {}", self.id, e)
+                    })
+                    .ok()
+            }),
+            api_auth: self.api_auth.and_then(|json| {
+                serde_json::from_str::<ApiAuthConfig>(&json)
+                    .map_err(|e| {
+                        error!("Failed to deserialize api_auth for app {}: {}", self.id, e)
+                    })
+                    .ok()
+            }),
+            ip_filter: self.ip_filter.and_then(|json| {
+                serde_json::from_str::<IpFilterConfig>(&json)
+                    .map_err(|e| {
+                        error!("Failed to deserialize ip_filter for app {}: {}", self.id, e)
+                    })
+                    .ok()
+            }),
+            authorizer: self.authorizer.and_then(|json| {
+                serde_json::from_str::<AuthorizerConfig>(&json)
+                    .map_err(|e| {
+                        error!(
+                            "Failed to deserialize authorizer for app {}: {}",
+                            self.id, e
+                        )
+                    })
+                    .ok()
+            }),
         }
     }
 }
@@ -404,40 +962,45 @@ impl AppManager for ScyllaDbAppManager {
         let values = AppRow::from_app(&app)?;
 
         self.session
-            .execute_unpaged(&self.insert_stmt, values)
+            .execute_unpaged(&self.statements().await?.insert, values)
             .await
             .map_err(|e| {
                 error!("Database error creating app {}: {}", app.id, e);
                 Error::Internal(format!("Failed to insert app into ScyllaDB: {e}"))
             })?;
 
-        Ok(())
+        self.sync_credential_keys(&app, None).await
     }
 
     async fn update_app(&self, app: App) -> Result<()> {
+        let previous = self.find_by_id(&app.id).await?;
         let values = UpdateRow::from_app(&app)?;
 
         self.session
-            .execute_unpaged(&self.update_stmt, values)
+            .execute_unpaged(&self.statements().await?.update, values)
             .await
             .map_err(|e| {
                 error!("Database error updating app {}: {}", app.id, e);
                 Error::Internal(format!("Failed to update app in ScyllaDB: {e}"))
             })?;
 
-        Ok(())
+        self.sync_credential_keys(&app, previous.as_ref()).await
     }
 
     async fn delete_app(&self, app_id: &str) -> Result<()> {
+        let previous = self.find_by_id(app_id).await?;
         self.session
-            .execute_unpaged(&self.delete_stmt, (app_id,))
+            .execute_unpaged(&self.statements().await?.delete, (app_id,))
             .await
             .map_err(|e| {
                 error!("Database error deleting app {}: {}", app_id, e);
                 Error::Internal(format!("Failed to delete app from ScyllaDB: {e}"))
             })?;
 
-        Ok(())
+        match previous {
+            Some(previous) => self.remove_credential_keys(&previous.keys()).await,
+            None => Ok(()),
+        }
     }
 
     async fn get_apps(&self) -> Result<Vec<App>> {
@@ -449,7 +1012,8 @@ impl AppManager for ScyllaDbAppManager {
                 max_event_channels_at_once, max_event_name_length,
                 max_event_payload_in_kb, max_event_batch_size,
                 enable_user_authentication, enable_watchlist_events,
-                webhooks, allowed_origins
+                webhooks, allowed_origins, credentials,
+                channel_policy, channel_limits, quotas, jwt_auth, api_auth, ip_filter, authorizer
             FROM {}.{}"#,
             self.config.keyspace, self.config.table_name
         );
@@ -486,7 +1050,8 @@ impl AppManager for ScyllaDbAppManager {
                 max_event_channels_at_once, max_event_name_length,
                 max_event_payload_in_kb, max_event_batch_size,
                 enable_user_authentication, enable_watchlist_events,
-                webhooks, allowed_origins
+                webhooks, allowed_origins, credentials,
+                channel_policy, channel_limits, quotas, jwt_auth, api_auth, ip_filter, authorizer
             FROM {}.{} WHERE key = ?"#,
             self.config.keyspace, self.config.table_name
         );
@@ -510,7 +1075,7 @@ impl AppManager for ScyllaDbAppManager {
             let app = app_row.into_app();
             Ok(Some(app))
         } else {
-            Ok(None)
+            self.find_by_credential_key(key).await
         }
     }
 
@@ -525,7 +1090,8 @@ impl AppManager for ScyllaDbAppManager {
                 max_event_channels_at_once, max_event_name_length,
                 max_event_payload_in_kb, max_event_batch_size,
                 enable_user_authentication, enable_watchlist_events,
-                webhooks, allowed_origins
+                webhooks, allowed_origins, credentials,
+                channel_policy, channel_limits, quotas, jwt_auth, api_auth, ip_filter, authorizer
             FROM {}.{} WHERE id = ?"#,
             self.config.keyspace, self.config.table_name
         );
@@ -553,6 +1119,10 @@ impl AppManager for ScyllaDbAppManager {
         }
     }
 
+    async fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        run_migrations(self, dry_run).await
+    }
+
     async fn check_health(&self) -> Result<()> {
         // Simple health check: query system tables
         let query = "SELECT key FROM system.local WHERE key = 'local'";
@@ -567,6 +1137,7 @@ impl AppManager for ScyllaDbAppManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::config::CredentialState;
     use std::env;
 
     fn create_test_config() -> ScyllaDbConfig {
@@ -619,6 +1190,14 @@ mod tests {
             webhooks: None,
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         }
     }
 
@@ -673,6 +1252,14 @@ mod tests {
                 "https://example.com".to_string(),
                 "https://app.example.com".to_string(),
             ]),
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         }
     }
 
@@ -680,6 +1267,7 @@ mod tests {
     async fn test_create_and_find_app() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         let app = create_test_app("test_app_create");
         manager.create_app(app.clone()).await.unwrap();
@@ -699,6 +1287,7 @@ mod tests {
     async fn test_create_app_with_webhooks() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         let app = create_test_app_with_webhooks("test_app_webhooks");
         manager.create_app(app.clone()).await.unwrap();
@@ -738,6 +1327,7 @@ mod tests {
     async fn test_update_app() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create initial app
         let mut app = create_test_app("test_app_update");
@@ -782,6 +1372,7 @@ mod tests {
     async fn test_find_by_key() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         let app = create_test_app("test_app_key");
         manager.create_app(app.clone()).await.unwrap();
@@ -795,10 +1386,47 @@ mod tests {
         manager.delete_app("test_app_key").await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_find_by_credential_key() {
+        let config = create_test_config();
+        let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
+
+        let mut app = create_test_app("test_credential_key");
+        app.credentials = Some(vec![AppCredential {
+            key: "test_credential_key_previous".to_string(),
+            secret: "previous_secret".to_string(),
+            state: CredentialState::VerifyOnly,
+            expires_at: None,
+        }]);
+        manager.create_app(app.clone()).await.unwrap();
+
+        let found = manager
+            .find_by_key("test_credential_key_previous")
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().id, "test_credential_key");
+        assert!(manager.find_by_key("unknown_key").await.unwrap().is_none());
+
+        // Revoking the credential stops the lookup
+        app.credentials.as_mut().unwrap()[0].state = CredentialState::Revoked;
+        manager.update_app(app).await.unwrap();
+        assert!(
+            manager
+                .find_by_key("test_credential_key_previous")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        manager.delete_app("test_credential_key").await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_get_apps() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create multiple apps
         let app1 = create_test_app("test_get_apps_1");
@@ -829,6 +1457,7 @@ mod tests {
     async fn test_delete_app() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         let app = create_test_app("test_app_delete");
         manager.create_app(app.clone()).await.unwrap();
@@ -849,6 +1478,7 @@ mod tests {
     async fn test_update_webhooks_to_none() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         // Create app with webhooks
         let mut app = create_test_app_with_webhooks("test_app_webhooks_none");
@@ -873,6 +1503,7 @@ mod tests {
     async fn test_health_check() {
         let config = create_test_config();
         let manager = ScyllaDbAppManager::new(config).await.unwrap();
+        manager.migrate(false).await.unwrap();
 
         let result = manager.check_health().await;
         assert!(result.is_ok());
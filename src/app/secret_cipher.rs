@@ -0,0 +1,317 @@
+// src/app/secret_cipher.rs
+use crate::app::config::App;
+use crate::error::{Error, Result};
+use crate::options::SecretEncryptionConfig;
+use rand::Rng;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use std::collections::HashMap;
+
+/// Prefix of encrypted secrets; values without it are treated as plaintext
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+
+/// Envelope encryption for app secrets.
+///
+/// Every secret is encrypted with its own random data key, and the data key is encrypted
+/// with a master key. Stored values look like `enc:v1:<key id>:<wrapped data key>:<secret>`,
+/// so secrets written under an older master key stay readable while that key is configured.
+pub struct SecretCipher {
+    master_keys: HashMap<String, LessSafeKey>,
+    primary_key_id: String,
+}
+
+impl SecretCipher {
+    /// Build a cipher from `<key id>:<64 hex characters>` entries. The first key (or the
+    /// one named by `primary_key_id`) encrypts; every key can decrypt.
+    pub fn new(keys: &[(String, String)], primary_key_id: Option<&str>) -> Result<Self> {
+        let mut master_keys = HashMap::new();
+        for (key_id, hex_key) in keys {
+            if key_id.is_empty() || key_id.contains(':') {
+                return Err(Error::Config(format!(
+                    "Invalid secret encryption key id '{key_id}'"
+                )));
+            }
+            let bytes = hex::decode(hex_key).map_err(|e| {
+                Error::Config(format!("Secret encryption key '{key_id}' is not hex: {e}"))
+            })?;
+            if bytes.len() != KEY_LEN {
+                return Err(Error::Config(format!(
+                    "Secret encryption key '{key_id}' must be {KEY_LEN} bytes ({} hex characters)",
+                    KEY_LEN * 2
+                )));
+            }
+            master_keys.insert(key_id.clone(), Self::aead_key(&bytes));
+        }
+
+        let primary_key_id = match primary_key_id {
+            Some(id) => id.to_string(),
+            None => keys
+                .first()
+                .map(|(id, _)| id.clone())
+                .ok_or_else(|| Error::Config("No secret encryption keys configured".into()))?,
+        };
+        if !master_keys.contains_key(&primary_key_id) {
+            return Err(Error::Config(format!(
+                "Primary secret encryption key '{primary_key_id}' is not configured"
+            )));
+        }
+
+        Ok(Self {
+            master_keys,
+            primary_key_id,
+        })
+    }
+
+    /// Load the master keys named in the configuration, from the key file and/or the
+    /// inline key list
+    pub fn from_config(config: &SecretEncryptionConfig) -> Result<Self> {
+        let mut keys = Vec::new();
+        if let Some(path) = &config.keys_file {
+            let contents = std::fs::read_to_string(path).map_err(|e| {
+                Error::Config(format!(
+                    "Failed to read secret encryption keys from {path}: {e}"
+                ))
+            })?;
+            keys.extend(parse_keys(&contents)?);
+        }
+        if let Some(inline) = &config.keys {
+            keys.extend(parse_keys(inline)?);
+        }
+        Self::new(&keys, config.primary_key_id.as_deref())
+    }
+
+    fn aead_key(bytes: &[u8]) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&AES_256_GCM, bytes).expect("key length is checked"))
+    }
+
+    pub fn primary_key_id(&self) -> &str {
+        &self.primary_key_id
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENCRYPTED_PREFIX)
+    }
+
+    /// Whether a stored value is plaintext or encrypted under a non-primary master key
+    pub fn needs_reencryption(&self, value: &str) -> bool {
+        match value.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(rest) => rest.split(':').next() != Some(self.primary_key_id.as_str()),
+            None => true,
+        }
+    }
+
+    /// Encrypt a secret under the primary master key
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let mut data_key = [0u8; KEY_LEN];
+        rand::rng().fill(&mut data_key);
+
+        let master_key = &self.master_keys[&self.primary_key_id];
+        let wrapped_key = seal(master_key, self.primary_key_id.as_bytes(), &data_key)?;
+        let ciphertext = seal(&Self::aead_key(&data_key), &[], plaintext.as_bytes())?;
+
+        Ok(format!(
+            "{ENCRYPTED_PREFIX}{}:{}:{}",
+            self.primary_key_id,
+            hex::encode(wrapped_key),
+            hex::encode(ciphertext)
+        ))
+    }
+
+    /// Decrypt a stored secret. Plaintext values are returned unchanged, so rows written
+    /// before encryption was enabled keep working until they are re-encrypted.
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let Some(rest) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let mut parts = rest.splitn(3, ':');
+        let (Some(key_id), Some(wrapped_key), Some(ciphertext)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(Error::Internal("Malformed encrypted app secret".into()));
+        };
+
+        let master_key = self.master_keys.get(key_id).ok_or_else(|| {
+            Error::Internal(format!(
+                "App secret is encrypted with unknown master key '{key_id}'"
+            ))
+        })?;
+        let data_key = open(master_key, key_id.as_bytes(), wrapped_key)?;
+        if data_key.len() != KEY_LEN {
+            return Err(Error::Internal("Malformed encrypted app secret".into()));
+        }
+        let plaintext = open(&Self::aead_key(&data_key), &[], ciphertext)?;
+
+        String::from_utf8(plaintext)
+            .map_err(|_| Error::Internal("Decrypted app secret is not valid UTF-8".into()))
+    }
+
+    /// Encrypt the app's secrets for storage. Values that are already encrypted under the
+    /// primary key are kept.
+    pub fn encrypt_app(&self, mut app: App) -> Result<App> {
+        if self.needs_reencryption(&app.secret) {
+            app.secret = self.encrypt(&self.decrypt(&app.secret)?)?;
+        }
+        for credential in app.credentials.iter_mut().flatten() {
+            if self.needs_reencryption(&credential.secret) {
+                credential.secret = self.encrypt(&self.decrypt(&credential.secret)?)?;
+            }
+        }
+        Ok(app)
+    }
+
+    /// Decrypt the app's secrets after reading it from storage
+    pub fn decrypt_app(&self, mut app: App) -> Result<App> {
+        app.secret = self.decrypt(&app.secret)?;
+        for credential in app.credentials.iter_mut().flatten() {
+            credential.secret = self.decrypt(&credential.secret)?;
+        }
+        Ok(app)
+    }
+
+    /// Whether any of the stored app's secrets should be rewritten
+    pub fn app_needs_reencryption(&self, app: &App) -> bool {
+        self.needs_reencryption(&app.secret)
+            || app
+                .credentials
+                .iter()
+                .flatten()
+                .any(|credential| self.needs_reencryption(&credential.secret))
+    }
+}
+
+/// Parse `<key id>:<hex key>` entries separated by newlines or commas. Blank lines and
+/// lines starting with `#` are ignored.
+pub fn parse_keys(input: &str) -> Result<Vec<(String, String)>> {
+    input
+        .split(['\n', ','])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && !entry.starts_with('#'))
+        .map(|entry| {
+            entry
+                .split_once(':')
+                .map(|(id, key)| (id.trim().to_string(), key.trim().to_string()))
+                .ok_or_else(|| {
+                    Error::Config("Secret encryption keys must be given as <key id>:<hex>".into())
+                })
+        })
+        .collect()
+}
+
+fn seal(key: &LessSafeKey, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill(&mut nonce);
+
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(aad),
+        &mut in_out,
+    )
+    .map_err(|_| Error::Internal("Failed to encrypt app secret".into()))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&in_out);
+    Ok(sealed)
+}
+
+fn open(key: &LessSafeKey, aad: &[u8], sealed_hex: &str) -> Result<Vec<u8>> {
+    let sealed = hex::decode(sealed_hex)
+        .map_err(|_| Error::Internal("Malformed encrypted app secret".into()))?;
+    if sealed.len() < NONCE_LEN {
+        return Err(Error::Internal("Malformed encrypted app secret".into()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| Error::Internal("Malformed encrypted app secret".into()))?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(aad), &mut in_out)
+        .map_err(|_| Error::Internal("Failed to decrypt app secret".into()))?;
+    Ok(plaintext.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::{AppCredential, CredentialState};
+
+    const KEY_1: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+    const KEY_2: &str = "0202020202020202020202020202020202020202020202020202020202020202";
+
+    fn cipher(keys: &[(&str, &str)]) -> SecretCipher {
+        let keys: Vec<(String, String)> = keys
+            .iter()
+            .map(|(id, key)| (id.to_string(), key.to_string()))
+            .collect();
+        SecretCipher::new(&keys, None).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_and_plaintext_passthrough() {
+        let cipher = cipher(&[("k1", KEY_1)]);
+
+        let encrypted = cipher.encrypt("app-secret").unwrap();
+        assert!(encrypted.starts_with("enc:v1:k1:"));
+        assert!(!encrypted.contains("app-secret"));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "app-secret");
+        // Each encryption uses a fresh data key and nonce
+        assert_ne!(cipher.encrypt("app-secret").unwrap(), encrypted);
+
+        assert_eq!(cipher.decrypt("legacy-secret").unwrap(), "legacy-secret");
+        assert!(cipher.needs_reencryption("legacy-secret"));
+        assert!(!cipher.needs_reencryption(&encrypted));
+    }
+
+    #[test]
+    fn test_master_key_rotation() {
+        let old = cipher(&[("k1", KEY_1)]);
+        let rotated = cipher(&[("k2", KEY_2), ("k1", KEY_1)]);
+
+        let encrypted = old.encrypt("app-secret").unwrap();
+        assert_eq!(rotated.decrypt(&encrypted).unwrap(), "app-secret");
+        assert!(rotated.needs_reencryption(&encrypted));
+
+        let app = App {
+            secret: encrypted,
+            credentials: Some(vec![AppCredential {
+                key: String::new(),
+                secret: "previous".to_string(),
+                state: CredentialState::VerifyOnly,
+                expires_at: None,
+            }]),
+            ..Default::default()
+        };
+        assert!(rotated.app_needs_reencryption(&app));
+        let stored = rotated.encrypt_app(app).unwrap();
+        assert!(!rotated.app_needs_reencryption(&stored));
+        assert!(old.decrypt(&stored.secret).is_err());
+
+        let app = rotated.decrypt_app(stored).unwrap();
+        assert_eq!(app.secret, "app-secret");
+        assert_eq!(app.credentials.unwrap()[0].secret, "previous");
+    }
+
+    #[test]
+    fn test_tampered_or_unknown_key_fails() {
+        let cipher_1 = cipher(&[("k1", KEY_1)]);
+        let encrypted = cipher_1.encrypt("app-secret").unwrap();
+
+        let mut tampered = encrypted.clone();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == '0' { '1' } else { '0' });
+        assert!(cipher_1.decrypt(&tampered).is_err());
+
+        assert!(cipher(&[("k2", KEY_2)]).decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        let keys = parse_keys(&format!("# current\nk2:{KEY_2}\n\nk1:{KEY_1}")).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].0, "k2");
+        assert!(parse_keys("no-separator").is_err());
+        assert!(SecretCipher::new(&[("k1".into(), "abcd".into())], None).is_err());
+        assert!(SecretCipher::new(&[("k1".into(), KEY_1.into())], Some("k3")).is_err());
+    }
+}
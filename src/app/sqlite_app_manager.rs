@@ -0,0 +1,851 @@
+use super::config::{App, AppCredential};
+use crate::app::auth::ApiAuthConfig;
+use crate::app::authorizer::AuthorizerConfig;
+use crate::app::channel_limits::ChannelLimitRule;
+use crate::app::channel_policy::ChannelPolicy;
+use crate::app::ip_filter::IpFilterConfig;
+use crate::app::jwt_auth::JwtAuthConfig;
+use crate::app::manager::AppManager;
+use crate::app::migrations::{
+    Migration, MigrationLock, MigrationStatus, SchemaMigrations, run_migrations,
+};
+use crate::error::{Error, Result};
+use crate::options::{DatabasePooling, SqliteSettings};
+use crate::quota::AppQuotas;
+use crate::webhook::types::Webhook;
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+const IN_MEMORY_PATH: &str = ":memory:";
+
+const APP_COLUMNS: &str = r#"id, "key", secret, max_connections,
+                enable_client_messages, enabled,
+                max_backend_events_per_second,
+                max_client_events_per_second,
+                max_read_requests_per_second,
+                max_presence_members_per_channel,
+                max_presence_member_size_in_kb,
+                max_channel_name_length,
+                max_event_channels_at_once,
+                max_event_name_length,
+                max_event_payload_in_kb,
+                max_event_batch_size,
+                enable_user_authentication,
+                enable_watchlist_events,
+                webhooks,
+                allowed_origins,
+                credentials,
+                channel_policy,
+                channel_limits,
+                quotas,
+                jwt_auth,
+                api_auth,
+                ip_filter,
+                authorizer"#;
+
+/// SQLite-based implementation of the AppManager, for single-node deployments that
+/// need apps to survive restarts without running a database server
+pub struct SqliteAppManager {
+    config: SqliteSettings,
+    pool: SqlitePool,
+}
+
+impl SqliteAppManager {
+    /// Open (or create) the database file. The apps table is created by `migrate`.
+    pub async fn new(config: SqliteSettings, pooling: DatabasePooling) -> Result<Self> {
+        info!(
+            "Initializing SQLite AppManager with database {}",
+            config.path
+        );
+
+        let in_memory = config.path == IN_MEMORY_PATH;
+        let connect_options = if in_memory {
+            SqliteConnectOptions::from_str("sqlite::memory:")
+                .map_err(|e| Error::Config(format!("Invalid SQLite path: {e}")))?
+        } else {
+            SqliteConnectOptions::new()
+                .filename(&config.path)
+                .create_if_missing(true)
+                .journal_mode(SqliteJournalMode::Wal)
+        }
+        .busy_timeout(Duration::from_secs(5));
+
+        // Every connection to `:memory:` opens its own empty database, so keep exactly one
+        // connection alive for the lifetime of the pool
+        let opts = if in_memory {
+            SqlitePoolOptions::new()
+                .min_connections(1)
+                .max_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+        } else if pooling.enabled {
+            SqlitePoolOptions::new()
+                .min_connections(pooling.min)
+                .max_connections(pooling.max)
+                .idle_timeout(Duration::from_secs(180))
+        } else {
+            SqlitePoolOptions::new().idle_timeout(Duration::from_secs(180))
+        };
+        let pool = opts
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_with(connect_options)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to open SQLite database: {e}")))?;
+
+        Ok(Self { config, pool })
+    }
+
+    fn history_table(&self) -> String {
+        format!("{}_schema_migrations", self.config.table_name)
+    }
+
+    /// Add a column unless it exists. SQLite has no ADD COLUMN IF NOT EXISTS, so check
+    /// the table info first.
+    async fn add_column_if_not_exists(&self, column_name: &str, column_type: &str) -> Result<()> {
+        let existing_columns: Vec<(String,)> =
+            sqlx::query_as("SELECT name FROM pragma_table_info(?)")
+                .bind(&self.config.table_name)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| {
+                    Error::Internal(format!(
+                        "Failed to read columns of table '{}': {}",
+                        self.config.table_name, e
+                    ))
+                })?;
+
+        if existing_columns.iter().any(|(name,)| name == column_name) {
+            return Ok(());
+        }
+
+        let add_column_query = format!(
+            r#"ALTER TABLE "{}" ADD COLUMN {} {}"#,
+            self.config.table_name, column_name, column_type
+        );
+
+        sqlx::query(&add_column_query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to add column '{}' to table '{}': {}",
+                    column_name, self.config.table_name, e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Create the applications table with the columns of the first schema version
+    async fn create_table(&self) -> Result<()> {
+        // Same columns as the PostgreSQL schema; JSON columns are stored as TEXT
+        let create_table_query = format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS "{}" (
+                id VARCHAR(255) PRIMARY KEY,
+                "key" VARCHAR(255) UNIQUE NOT NULL,
+                secret VARCHAR(255) NOT NULL,
+                max_connections INTEGER NOT NULL,
+                enable_client_messages BOOLEAN NOT NULL DEFAULT FALSE,
+                enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                max_backend_events_per_second INTEGER,
+                max_client_events_per_second INTEGER NOT NULL,
+                max_read_requests_per_second INTEGER,
+                max_presence_members_per_channel INTEGER,
+                max_presence_member_size_in_kb INTEGER,
+                max_channel_name_length INTEGER,
+                max_event_channels_at_once INTEGER,
+                max_event_name_length INTEGER,
+                max_event_payload_in_kb INTEGER,
+                max_event_batch_size INTEGER,
+                enable_user_authentication BOOLEAN,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )
+        "#,
+            self.config.table_name
+        );
+
+        sqlx::query(&create_table_query)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to create SQLite table: {e}")))?;
+        Ok(())
+    }
+
+    /// Get an app by ID from database
+    pub async fn find_by_id(&self, app_id: &str) -> Result<Option<App>> {
+        debug!("Fetching app {} from database", app_id);
+
+        let query = format!(
+            r#"SELECT {} FROM "{}" WHERE id = ?"#,
+            APP_COLUMNS, self.config.table_name
+        );
+
+        let app_result = sqlx::query_as::<_, AppRow>(&query)
+            .bind(app_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database error fetching app {}: {}", app_id, e);
+                Error::Internal(format!("Failed to fetch app from SQLite: {e}"))
+            })?;
+
+        Ok(app_result.map(|row| row.into_app()))
+    }
+
+    /// Get an app by key from database
+    pub async fn find_by_key(&self, key: &str) -> Result<Option<App>> {
+        debug!("Fetching app by key {} from database", key);
+
+        let query = format!(
+            r#"SELECT {} FROM "{}"
+            WHERE "key" = ?
+                OR EXISTS (SELECT 1 FROM json_each("credentials") WHERE json_extract(value, '$.key') = ?)"#,
+            APP_COLUMNS, self.config.table_name
+        );
+
+        let rows = sqlx::query_as::<_, AppRow>(&query)
+            .bind(key)
+            .bind(key)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database error fetching app by key {}: {}", key, e);
+                Error::Internal(format!("Failed to fetch app from SQLite: {e}"))
+            })?;
+
+        // Credential keys only count while the credential is accepted
+        Ok(rows
+            .into_iter()
+            .map(|row| row.into_app())
+            .find(|app| app.accepts_key(key)))
+    }
+
+    /// Register a new app in the database
+    pub async fn create_app(&self, app: App) -> Result<()> {
+        info!("Registering new app: {}", app.id);
+
+        let query = format!(
+            r#"INSERT INTO "{}" ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            self.config.table_name, APP_COLUMNS
+        );
+
+        sqlx::query(&query)
+            .bind(&app.id)
+            .bind(&app.key)
+            .bind(&app.secret)
+            .bind(app.max_connections as i64)
+            .bind(app.enable_client_messages)
+            .bind(app.enabled)
+            .bind(app.max_backend_events_per_second.map(|v| v as i64))
+            .bind(app.max_client_events_per_second as i64)
+            .bind(app.max_read_requests_per_second.map(|v| v as i64))
+            .bind(app.max_presence_members_per_channel.map(|v| v as i64))
+            .bind(app.max_presence_member_size_in_kb.map(|v| v as i64))
+            .bind(app.max_channel_name_length.map(|v| v as i64))
+            .bind(app.max_event_channels_at_once.map(|v| v as i64))
+            .bind(app.max_event_name_length.map(|v| v as i64))
+            .bind(app.max_event_payload_in_kb.map(|v| v as i64))
+            .bind(app.max_event_batch_size.map(|v| v as i64))
+            .bind(app.enable_user_authentication)
+            .bind(app.enable_watchlist_events)
+            .bind(app.webhooks.as_ref().map(sqlx::types::Json))
+            .bind(app.allowed_origins.as_ref().map(sqlx::types::Json))
+            .bind(app.credentials.as_ref().map(sqlx::types::Json))
+            .bind(app.channel_policy.as_ref().map(sqlx::types::Json))
+            .bind(app.channel_limits.as_ref().map(sqlx::types::Json))
+            .bind(app.quotas.as_ref().map(sqlx::types::Json))
+            .bind(app.jwt_auth.as_ref().map(sqlx::types::Json))
+            .bind(app.api_auth.as_ref().map(sqlx::types::Json))
+            .bind(app.ip_filter.as_ref().map(sqlx::types::Json))
+            .bind(app.authorizer.as_ref().map(sqlx::types::Json))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database error registering app {}: {}", app.id, e);
+                Error::Internal(format!("Failed to insert app into SQLite: {e}"))
+            })?;
+
+        Ok(())
+    }
+
+    /// Update an existing app in the database
+    pub async fn update_app(&self, app: App) -> Result<()> {
+        info!("Updating app: {}", app.id);
+
+        let query = format!(
+            r#"UPDATE "{}" SET
+                "key" = ?, secret = ?, max_connections = ?, enable_client_messages = ?, enabled = ?,
+                max_backend_events_per_second = ?, max_client_events_per_second = ?,
+                max_read_requests_per_second = ?, max_presence_members_per_channel = ?,
+                max_presence_member_size_in_kb = ?, max_channel_name_length = ?,
+                max_event_channels_at_once = ?, max_event_name_length = ?,
+                max_event_payload_in_kb = ?, max_event_batch_size = ?,
+                enable_user_authentication = ?, enable_watchlist_events = ?,
+                webhooks = ?, allowed_origins = ?, credentials = ?, channel_policy = ?,
+                channel_limits = ?,
+                quotas = ?,
+                jwt_auth = ?,
+                api_auth = ?,
+                ip_filter = ?,
+                authorizer = ?,
+                updated_at = CURRENT_TIMESTAMP
+                WHERE id = ?"#,
+            self.config.table_name
+        );
+
+        let result = sqlx::query(&query)
+            .bind(&app.key)
+            .bind(&app.secret)
+            .bind(app.max_connections as i64)
+            .bind(app.enable_client_messages)
+            .bind(app.enabled)
+            .bind(app.max_backend_events_per_second.map(|v| v as i64))
+            .bind(app.max_client_events_per_second as i64)
+            .bind(app.max_read_requests_per_second.map(|v| v as i64))
+            .bind(app.max_presence_members_per_channel.map(|v| v as i64))
+            .bind(app.max_presence_member_size_in_kb.map(|v| v as i64))
+            .bind(app.max_channel_name_length.map(|v| v as i64))
+            .bind(app.max_event_channels_at_once.map(|v| v as i64))
+            .bind(app.max_event_name_length.map(|v| v as i64))
+            .bind(app.max_event_payload_in_kb.map(|v| v as i64))
+            .bind(app.max_event_batch_size.map(|v| v as i64))
+            .bind(app.enable_user_authentication)
+            .bind(app.enable_watchlist_events)
+            .bind(app.webhooks.as_ref().map(sqlx::types::Json))
+            .bind(app.allowed_origins.as_ref().map(sqlx::types::Json))
+            .bind(app.credentials.as_ref().map(sqlx::types::Json))
+            .bind(app.channel_policy.as_ref().map(sqlx::types::Json))
+            .bind(app.channel_limits.as_ref().map(sqlx::types::Json))
+            .bind(app.quotas.as_ref().map(sqlx::types::Json))
+            .bind(app.jwt_auth.as_ref().map(sqlx::types::Json))
+            .bind(app.api_auth.as_ref().map(sqlx::types::Json))
+            .bind(app.ip_filter.as_ref().map(sqlx::types::Json))
+            .bind(app.authorizer.as_ref().map(sqlx::types::Json))
+            .bind(&app.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database error updating app {}: {}", app.id, e);
+                Error::Internal(format!("Failed to update app in SQLite: {e}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(Error::InvalidAppKey);
+        }
+
+        Ok(())
+    }
+
+    /// Remove an app from the database
+    pub async fn delete_app(&self, app_id: &str) -> Result<()> {
+        info!("Removing app: {}", app_id);
+
+        let query = format!(r#"DELETE FROM "{}" WHERE id = ?"#, self.config.table_name);
+
+        let result = sqlx::query(&query)
+            .bind(app_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database error removing app {}: {}", app_id, e);
+                Error::Internal(format!("Failed to delete app from SQLite: {e}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(Error::InvalidAppKey);
+        }
+
+        Ok(())
+    }
+
+    /// Get all apps from the database
+    pub async fn get_apps(&self) -> Result<Vec<App>> {
+        info!("Fetching all apps from database");
+
+        let query = format!(
+            r#"SELECT {} FROM "{}""#,
+            APP_COLUMNS, self.config.table_name
+        );
+
+        let app_rows = sqlx::query_as::<_, AppRow>(&query)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database error fetching all apps: {}", e);
+                Error::Internal(format!("Failed to fetch apps from SQLite: {e}"))
+            })?;
+
+        let apps: Vec<App> = app_rows.into_iter().map(|row| row.into_app()).collect();
+
+        debug!("Fetched {} apps from database", apps.len());
+
+        Ok(apps)
+    }
+}
+
+/// Row struct for SQLx query results
+#[derive(sqlx::FromRow)]
+struct AppRow {
+    id: String,
+    key: String,
+    secret: String,
+    max_connections: i64,
+    enable_client_messages: bool,
+    enabled: bool,
+    max_backend_events_per_second: Option<i64>,
+    max_client_events_per_second: i64,
+    max_read_requests_per_second: Option<i64>,
+    max_presence_members_per_channel: Option<i64>,
+    max_presence_member_size_in_kb: Option<i64>,
+    max_channel_name_length: Option<i64>,
+    max_event_channels_at_once: Option<i64>,
+    max_event_name_length: Option<i64>,
+    max_event_payload_in_kb: Option<i64>,
+    max_event_batch_size: Option<i64>,
+    enable_user_authentication: Option<bool>,
+    enable_watchlist_events: Option<bool>,
+    #[sqlx(json(nullable))]
+    webhooks: Option<Vec<Webhook>>,
+    #[sqlx(json(nullable))]
+    allowed_origins: Option<Vec<String>>,
+    #[sqlx(json(nullable))]
+    credentials: Option<Vec<AppCredential>>,
+    #[sqlx(json(nullable))]
+    channel_policy: Option<ChannelPolicy>,
+    #[sqlx(json(nullable))]
+    channel_limits: Option<Vec<ChannelLimitRule>>,
+    #[sqlx(json(nullable))]
+    quotas: Option<AppQuotas>,
+    #[sqlx(json(nullable))]
+    jwt_auth: Option<JwtAuthConfig>,
+    #[sqlx(json(nullable))]
+    api_auth: Option<ApiAuthConfig>,
+    #[sqlx(json(nullable))]
+    ip_filter: Option<IpFilterConfig>,
+    #[sqlx(json(nullable))]
+    authorizer: Option<AuthorizerConfig>,
+}
+
+impl AppRow {
+    /// Convert database row to App struct
+    fn into_app(self) -> App {
+        App {
+            id: self.id,
+            key: self.key,
+            secret: self.secret,
+            max_connections: self.max_connections as u32,
+            enable_client_messages: self.enable_client_messages,
+            enabled: self.enabled,
+            max_backend_events_per_second: self.max_backend_events_per_second.map(|v| v as u32),
+            max_client_events_per_second: self.max_client_events_per_second as u32,
+            max_read_requests_per_second: self.max_read_requests_per_second.map(|v| v as u32),
+            max_presence_members_per_channel: self
+                .max_presence_members_per_channel
+                .map(|v| v as u32),
+            max_presence_member_size_in_kb: self.max_presence_member_size_in_kb.map(|v| v as u32),
+            max_channel_name_length: self.max_channel_name_length.map(|v| v as u32),
+            max_event_channels_at_once: self.max_event_channels_at_once.map(|v| v as u32),
+            max_event_name_length: self.max_event_name_length.map(|v| v as u32),
+            max_event_payload_in_kb: self.max_event_payload_in_kb.map(|v| v as u32),
+            max_event_batch_size: self.max_event_batch_size.map(|v| v as u32),
+            enable_user_authentication: self.enable_user_authentication,
+            webhooks: self.webhooks,
+            enable_watchlist_events: self.enable_watchlist_events,
+            allowed_origins: self.allowed_origins,
+            credentials: self.credentials,
+            channel_policy: self.channel_policy,
+            channel_limits: self.channel_limits,
+            quotas: self.quotas,
+            jwt_auth: self.jwt_auth,
+            api_auth: self.api_auth,
+            ip_filter: self.ip_filter,
+            authorizer: self.authorizer,
+        }
+    }
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create applications table",
+    },
+    Migration {
+        version: 2,
+        description: "add allowed_origins column",
+    },
+    Migration {
+        version: 3,
+        description: "add enable_watchlist_events column",
+    },
+    Migration {
+        version: 4,
+        description: "add webhooks column",
+    },
+    Migration {
+        version: 5,
+        description: "add credentials column",
+    },
+    Migration {
+        version: 6,
+        description: "add channel_policy column",
+    },
+    Migration {
+        version: 7,
+        description: "add channel_limits column",
+    },
+    Migration {
+        version: 8,
+        description: "add quotas column",
+    },
+    Migration {
+        version: 9,
+        description: "add jwt_auth column",
+    },
+    Migration {
+        version: 10,
+        description: "add api_auth column",
+    },
+    Migration {
+        version: 11,
+        description: "add ip_filter column",
+    },
+    Migration {
+        version: 12,
+        description: "add authorizer column",
+    },
+];
+
+#[async_trait]
+impl SchemaMigrations for SqliteAppManager {
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    async fn applied_versions(&self) -> Result<Vec<u32>> {
+        let history_table = self.history_table();
+        let exists: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+                .bind(&history_table)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to look up {history_table}: {e}")))?;
+        if exists.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let versions: Vec<(i64,)> =
+            sqlx::query_as(&format!(r#"SELECT version FROM "{history_table}""#))
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to read {history_table}: {e}")))?;
+        Ok(versions.into_iter().map(|(v,)| v as u32).collect())
+    }
+
+    async fn ensure_history_table(&self) -> Result<()> {
+        let query = format!(
+            r#"CREATE TABLE IF NOT EXISTS "{}" (
+                version INTEGER PRIMARY KEY,
+                description TEXT NOT NULL,
+                applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )"#,
+            self.history_table()
+        );
+        sqlx::query(&query).execute(&self.pool).await.map_err(|e| {
+            Error::Internal(format!("Failed to create migration history table: {e}"))
+        })?;
+        Ok(())
+    }
+
+    /// SQLite serializes writers to the file itself; a version recorded by another
+    /// process in the meantime is ignored
+    async fn lock_migrations(&self) -> Result<MigrationLock> {
+        Ok(MigrationLock::none())
+    }
+
+    async fn apply_migration(&self, migration: &Migration) -> Result<()> {
+        match migration.version {
+            1 => self.create_table().await?,
+            2 => {
+                self.add_column_if_not_exists("allowed_origins", "TEXT")
+                    .await?
+            }
+            3 => {
+                self.add_column_if_not_exists("enable_watchlist_events", "BOOLEAN")
+                    .await?
+            }
+            4 => self.add_column_if_not_exists("webhooks", "TEXT").await?,
+            5 => self.add_column_if_not_exists("credentials", "TEXT").await?,
+            6 => {
+                self.add_column_if_not_exists("channel_policy", "TEXT")
+                    .await?
+            }
+            7 => {
+                self.add_column_if_not_exists("channel_limits", "TEXT")
+                    .await?
+            }
+            8 => self.add_column_if_not_exists("quotas", "TEXT").await?,
+            9 => self.add_column_if_not_exists("jwt_auth", "TEXT").await?,
+            10 => self.add_column_if_not_exists("api_auth", "TEXT").await?,
+            11 => self.add_column_if_not_exists("ip_filter", "TEXT").await?,
+            12 => self.add_column_if_not_exists("authorizer", "TEXT").await?,
+            version => {
+                return Err(Error::Internal(format!(
+                    "Unknown SQLite migration {version}"
+                )));
+            }
+        }
+
+        sqlx::query(&format!(
+            r#"INSERT OR IGNORE INTO "{}" (version, description) VALUES (?, ?)"#,
+            self.history_table()
+        ))
+        .bind(migration.version as i64)
+        .bind(migration.description)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to record migration: {e}")))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AppManager for SqliteAppManager {
+    async fn init(&self) -> Result<()> {
+        // Initialization is done in the constructor
+        Ok(())
+    }
+
+    async fn create_app(&self, config: App) -> Result<()> {
+        self.create_app(config).await
+    }
+
+    async fn update_app(&self, config: App) -> Result<()> {
+        self.update_app(config).await
+    }
+
+    async fn delete_app(&self, app_id: &str) -> Result<()> {
+        self.delete_app(app_id).await
+    }
+
+    async fn get_apps(&self) -> Result<Vec<App>> {
+        self.get_apps().await
+    }
+
+    async fn find_by_id(&self, app_id: &str) -> Result<Option<App>> {
+        self.find_by_id(app_id).await
+    }
+
+    async fn find_by_key(&self, key: &str) -> Result<Option<App>> {
+        self.find_by_key(key).await
+    }
+
+    async fn migrate(&self, dry_run: bool) -> Result<Vec<MigrationStatus>> {
+        run_migrations(self, dry_run).await
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        sqlx::query("SELECT 1")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("App manager SQLite connection failed: {e}")))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::CredentialState;
+    use crate::app::migrations::MigrationState;
+
+    async fn create_manager() -> SqliteAppManager {
+        let config = SqliteSettings {
+            path: IN_MEMORY_PATH.to_string(),
+            ..Default::default()
+        };
+        let manager = SqliteAppManager::new(config, DatabasePooling::default())
+            .await
+            .unwrap();
+        manager.migrate(false).await.unwrap();
+        manager
+    }
+
+    fn create_test_app(id: &str) -> App {
+        App {
+            id: id.to_string(),
+            key: format!("{id}_key"),
+            secret: format!("{id}_secret"),
+            max_connections: 100,
+            enable_client_messages: true,
+            enabled: true,
+            max_client_events_per_second: 100,
+            max_event_payload_in_kb: Some(100),
+            enable_user_authentication: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_app_manager() {
+        let manager = create_manager().await;
+
+        let test_app = create_test_app("test1");
+        manager.create_app(test_app.clone()).await.unwrap();
+
+        let app = manager.find_by_id("test1").await.unwrap().unwrap();
+        assert_eq!(app.key, "test1_key");
+        assert_eq!(app.max_event_payload_in_kb, Some(100));
+        assert_eq!(app.max_event_batch_size, None);
+        assert_eq!(app.enable_user_authentication, Some(true));
+
+        let app = manager.find_by_key("test1_key").await.unwrap().unwrap();
+        assert_eq!(app.id, "test1");
+
+        let mut updated_app = test_app.clone();
+        updated_app.max_connections = 200;
+        updated_app.enabled = false;
+        manager.update_app(updated_app).await.unwrap();
+
+        let app = manager.find_by_id("test1").await.unwrap().unwrap();
+        assert_eq!(app.max_connections, 200);
+        assert!(!app.enabled);
+
+        manager.create_app(create_test_app("test2")).await.unwrap();
+        assert_eq!(manager.get_apps().await.unwrap().len(), 2);
+
+        manager.delete_app("test1").await.unwrap();
+        assert!(manager.find_by_id("test1").await.unwrap().is_none());
+        assert!(manager.delete_app("test1").await.is_err());
+        assert!(
+            manager
+                .update_app(create_test_app("missing"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_columns_round_trip() {
+        let manager = create_manager().await;
+
+        let webhook = Webhook {
+            url: Some("https://example.com/webhook".parse().unwrap()),
+            lambda_function: None,
+            lambda: None,
+            event_types: vec!["channel_occupied".to_string()],
+            filter: None,
+            headers: None,
+        };
+        let mut app = create_test_app("json_test");
+        app.webhooks = Some(vec![webhook.clone()]);
+        app.allowed_origins = Some(vec!["https://*.example.com".to_string()]);
+        let credential = AppCredential {
+            key: "previous_key".to_string(),
+            secret: "previous_secret".to_string(),
+            state: CredentialState::VerifyOnly,
+            expires_at: Some(1_900_000_000),
+        };
+        app.credentials = Some(vec![credential.clone()]);
+        manager.create_app(app).await.unwrap();
+
+        let retrieved = manager.find_by_id("json_test").await.unwrap().unwrap();
+        let webhooks = retrieved.webhooks.unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(webhooks[0].url, webhook.url);
+        assert_eq!(
+            retrieved.allowed_origins,
+            Some(vec!["https://*.example.com".to_string()])
+        );
+        assert_eq!(retrieved.credentials, Some(vec![credential]));
+        let by_credential_key = manager.find_by_key("previous_key").await.unwrap().unwrap();
+        assert_eq!(by_credential_key.id, "json_test");
+        assert!(manager.find_by_key("unknown_key").await.unwrap().is_none());
+
+        let empty = create_test_app("no_json");
+        manager.create_app(empty).await.unwrap();
+        let retrieved = manager.find_by_id("no_json").await.unwrap().unwrap();
+        assert!(retrieved.webhooks.is_none());
+        assert!(retrieved.allowed_origins.is_none());
+        assert!(retrieved.credentials.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_key_is_rejected() {
+        let manager = create_manager().await;
+        manager.create_app(create_test_app("dup")).await.unwrap();
+
+        let mut other = create_test_app("other");
+        other.key = "dup_key".to_string();
+        assert!(manager.create_app(other).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apps_persist_in_database_file() {
+        let path = std::env::temp_dir().join(format!("sockudo-{}.db", uuid::Uuid::new_v4()));
+        let config = SqliteSettings {
+            path: path.to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+
+        let manager = SqliteAppManager::new(config.clone(), DatabasePooling::default())
+            .await
+            .unwrap();
+        manager.migrate(false).await.unwrap();
+        manager
+            .create_app(create_test_app("persisted"))
+            .await
+            .unwrap();
+        manager.pool.close().await;
+
+        let reopened = SqliteAppManager::new(config, DatabasePooling::default())
+            .await
+            .unwrap();
+        assert!(reopened.find_by_id("persisted").await.unwrap().is_some());
+        reopened.pool.close().await;
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrations_upgrade_unversioned_table() {
+        let config = SqliteSettings {
+            path: IN_MEMORY_PATH.to_string(),
+            ..Default::default()
+        };
+        let manager = SqliteAppManager::new(config, DatabasePooling::default())
+            .await
+            .unwrap();
+
+        // A table created before versioning, with some of the later columns already added
+        manager.create_table().await.unwrap();
+        manager
+            .add_column_if_not_exists("allowed_origins", "TEXT")
+            .await
+            .unwrap();
+
+        let report = manager.migrate(true).await.unwrap();
+        assert_eq!(report.len(), MIGRATIONS.len());
+        assert!(report.iter().all(|s| s.state == MigrationState::Pending));
+
+        let report = manager.migrate(false).await.unwrap();
+        assert!(report.iter().all(|s| s.state == MigrationState::Applied));
+        manager
+            .create_app(create_test_app("upgraded"))
+            .await
+            .unwrap();
+
+        let report = manager.migrate(false).await.unwrap();
+        assert!(
+            report
+                .iter()
+                .all(|s| s.state == MigrationState::AlreadyApplied)
+        );
+    }
+}
@@ -0,0 +1,198 @@
+// src/audit/mod.rs
+pub mod sink;
+
+use crate::options::AuditConfig;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sink::AuditSink;
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{error, warn};
+
+/// Events waiting to be written; further events are dropped until the sink catches up
+const AUDIT_BUFFER: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// HTTP API request with a missing or invalid signature
+    ApiAuthFailed,
+    /// Admin API request with a missing or invalid bearer token
+    AdminAuthFailed,
+    /// WebSocket connection from an origin the app does not allow
+    OriginRejected,
+    /// `terminate_connections` called for a user
+    UserConnectionsTerminated,
+    /// Socket closed by the server, e.g. after a fatal error or an expired sign-in
+    ConnectionClosed,
+    /// Every socket of an app closed because it was disabled or deleted
+    AppConnectionsClosed,
+    AppCreated,
+    AppUpdated,
+    AppDeleted,
+    AppSecretRotated,
+    /// IP address or user banned after repeated rate-limit violations or auth failures
+    ClientBanned,
+    /// Ban lifted through the admin API
+    BanLifted,
+}
+
+/// One audit record, written as a JSON line
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    pub timestamp_ms: u64,
+    pub event: AuditEventKind,
+    /// Node that recorded the event
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    /// Address of the client that caused the event, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+    #[serde(flatten)]
+    pub details: Map<String, Value>,
+}
+
+impl AuditEvent {
+    pub fn new(event: AuditEventKind, app_id: Option<&str>) -> Self {
+        Self {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            event,
+            node: String::new(),
+            app_id: app_id.map(str::to_string),
+            ip: None,
+            details: Map::new(),
+        }
+    }
+
+    pub fn ip(mut self, ip: Option<IpAddr>) -> Self {
+        self.ip = ip.map(|ip| ip.to_canonical());
+        self
+    }
+
+    pub fn detail(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+enum AuditMessage {
+    Event(AuditEvent),
+    /// Answered once every event queued before it has been written
+    Flush(oneshot::Sender<()>),
+}
+
+/// Hands audit events to a background writer so recording never waits on the sink
+pub struct AuditLog {
+    sender: mpsc::Sender<AuditMessage>,
+    node: String,
+    app_ids: HashSet<String>,
+}
+
+impl AuditLog {
+    pub fn new(config: &AuditConfig, node: String, mut sink: Box<dyn AuditSink>) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<AuditMessage>(AUDIT_BUFFER);
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                match message {
+                    AuditMessage::Event(event) => {
+                        if let Err(e) = sink.write(&event).await {
+                            error!("Failed to write audit event {:?}: {}", event.event, e);
+                        }
+                    }
+                    AuditMessage::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        Self {
+            sender,
+            node,
+            app_ids: config.app_ids.iter().cloned().collect(),
+        }
+    }
+
+    /// Whether events of the app are recorded
+    pub fn records(&self, app_id: Option<&str>) -> bool {
+        self.app_ids.is_empty() || app_id.is_none_or(|app_id| self.app_ids.contains(app_id))
+    }
+
+    pub fn record(&self, mut event: AuditEvent) {
+        if !self.records(event.app_id.as_deref()) {
+            return;
+        }
+        event.node.clone_from(&self.node);
+        if let Err(mpsc::error::TrySendError::Full(AuditMessage::Event(event))) =
+            self.sender.try_send(AuditMessage::Event(event))
+        {
+            warn!(
+                "Audit log is falling behind, dropped {:?} event",
+                event.event
+            );
+        }
+    }
+
+    /// Wait until every event recorded so far has been written
+    pub async fn flush(&self) {
+        let (done, written) = oneshot::channel();
+        if self.sender.send(AuditMessage::Flush(done)).await.is_ok() {
+            let _ = written.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    struct MemorySink(Arc<Mutex<Vec<Value>>>);
+
+    #[async_trait]
+    impl AuditSink for MemorySink {
+        async fn write(&mut self, event: &AuditEvent) -> crate::error::Result<()> {
+            self.0.lock().unwrap().push(serde_json::to_value(event)?);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_events_of_selected_apps() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let config = AuditConfig {
+            app_ids: vec!["app-1".to_string()],
+            ..Default::default()
+        };
+        let log = AuditLog::new(
+            &config,
+            "node-1".to_string(),
+            Box::new(MemorySink(written.clone())),
+        );
+
+        log.record(
+            AuditEvent::new(AuditEventKind::UserConnectionsTerminated, Some("app-1"))
+                .ip(Some("::ffff:10.0.0.1".parse().unwrap()))
+                .detail("user_id", "42"),
+        );
+        log.record(AuditEvent::new(AuditEventKind::AppDeleted, Some("app-2")));
+        log.record(AuditEvent::new(AuditEventKind::AdminAuthFailed, None));
+        log.flush().await;
+
+        let written = written.lock().unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(written[0]["event"], "user_connections_terminated");
+        assert_eq!(written[0]["node"], "node-1");
+        assert_eq!(written[0]["app_id"], "app-1");
+        assert_eq!(written[0]["ip"], "10.0.0.1");
+        assert_eq!(written[0]["user_id"], "42");
+        assert_eq!(written[1]["event"], "admin_auth_failed");
+        assert!(written[1].get("app_id").is_none());
+    }
+}
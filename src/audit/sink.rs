@@ -0,0 +1,195 @@
+// src/audit/sink.rs
+use super::AuditEvent;
+use crate::error::{Error, Result};
+use crate::options::{AuditConfig, AuditDriver, QueueDriver};
+use crate::queue::manager::QueueManager;
+use crate::webhook::types::{JobData, JobPayload};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+/// Destination of audit events; events are written one at a time, in order
+#[async_trait]
+pub trait AuditSink: Send {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()>;
+}
+
+/// Sink of the configured driver, `None` when auditing is disabled
+pub async fn create(
+    config: &AuditConfig,
+    queue_driver: &QueueDriver,
+    queue_manager: Option<Arc<QueueManager>>,
+) -> Result<Option<Box<dyn AuditSink>>> {
+    Ok(match config.driver {
+        AuditDriver::None => None,
+        AuditDriver::Stdout => Some(Box::new(StdoutSink)),
+        AuditDriver::File => Some(Box::new(
+            FileSink::open(
+                PathBuf::from(&config.path),
+                config.max_file_size_mb * 1024 * 1024,
+                config.max_files,
+            )
+            .await?,
+        )),
+        AuditDriver::Queue => {
+            // Nothing consumes the memory queue, so events would pile up forever
+            if *queue_driver == QueueDriver::Memory {
+                return Err(Error::Configuration(
+                    "The queue audit driver needs a queue driver other than memory".to_string(),
+                ));
+            }
+            let queue_manager = queue_manager.ok_or_else(|| {
+                Error::Configuration(
+                    "The queue audit driver needs a queue driver other than none".to_string(),
+                )
+            })?;
+            Some(Box::new(QueueSink {
+                queue_manager,
+                queue_name: config.queue_name.clone(),
+            }))
+        }
+    })
+}
+
+fn to_line(event: &AuditEvent) -> Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(event)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+pub struct StdoutSink;
+
+#[async_trait]
+impl AuditSink for StdoutSink {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()> {
+        let mut stdout = tokio::io::stdout();
+        stdout.write_all(&to_line(event)?).await?;
+        stdout.flush().await?;
+        Ok(())
+    }
+}
+
+/// Appends to a file, rotating it once it reaches `max_size` bytes
+pub struct FileSink {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: u32,
+}
+
+impl FileSink {
+    pub async fn open(path: PathBuf, max_size: u64, max_files: u32) -> Result<Self> {
+        let file = Self::append(&path).await?;
+        let size = file.metadata().await?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+            max_files,
+        })
+    }
+
+    async fn append(path: &Path) -> Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(|e| Error::ConfigFile(format!("Failed to open {}: {e}", path.display())))
+    }
+
+    fn rotated(&self, index: u32) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    /// Shift `<path>.N` to `<path>.N+1`, dropping the oldest, and start a new file
+    async fn rotate(&mut self) -> Result<()> {
+        if self.max_files == 0 {
+            tokio::fs::remove_file(&self.path).await?;
+        } else {
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated(index);
+                if tokio::fs::try_exists(&from).await? {
+                    tokio::fs::rename(&from, self.rotated(index + 1)).await?;
+                }
+            }
+            tokio::fs::rename(&self.path, self.rotated(1)).await?;
+        }
+        self.file = Self::append(&self.path).await?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditSink for FileSink {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()> {
+        let line = to_line(event)?;
+        if self.max_size > 0 && self.size > 0 && self.size + line.len() as u64 > self.max_size {
+            self.rotate().await?;
+        }
+        self.file.write_all(&line).await?;
+        // tokio hands writes to a background thread; wait until this one landed
+        self.file.flush().await?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// Pushes each event as the only entry of a job's `events`, for consumers outside Sockudo
+pub struct QueueSink {
+    queue_manager: Arc<QueueManager>,
+    queue_name: String,
+}
+
+#[async_trait]
+impl AuditSink for QueueSink {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()> {
+        let job = JobData {
+            app_key: String::new(),
+            app_id: event.app_id.clone().unwrap_or_default(),
+            app_secret: String::new(),
+            payload: JobPayload {
+                time_ms: event.timestamp_ms as i64,
+                events: vec![serde_json::to_value(event)?],
+            },
+            original_signature: String::new(),
+        };
+        self.queue_manager.add_to_queue(&self.queue_name, job).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditEventKind;
+
+    #[tokio::test]
+    async fn test_file_sink_rotates_and_keeps_max_files() {
+        let dir = std::env::temp_dir().join(format!("sockudo-audit-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.log");
+
+        let event = AuditEvent::new(AuditEventKind::AppCreated, Some("app-1"));
+        let line_len = to_line(&event).unwrap().len() as u64;
+        // Two events per file
+        let mut sink = FileSink::open(path.clone(), line_len * 2, 2).await.unwrap();
+        for _ in 0..7 {
+            sink.write(&event).await.unwrap();
+        }
+
+        let lines = |path: PathBuf| std::fs::read_to_string(path).unwrap().lines().count();
+        assert_eq!(lines(path.clone()), 1);
+        assert_eq!(lines(sink.rotated(1)), 2);
+        assert_eq!(lines(sink.rotated(2)), 2);
+        assert!(!sink.rotated(3).exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
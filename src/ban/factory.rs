@@ -0,0 +1,106 @@
+// src/ban/factory.rs
+use crate::ban::BanStore;
+use crate::ban::memory_store::MemoryBanStore;
+use crate::error::Result;
+use crate::options::{BanConfig, CacheDriver, RedisConnection};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub struct BanStoreFactory;
+
+impl BanStoreFactory {
+    #[cfg_attr(not(feature = "redis"), allow(unused_variables))]
+    pub async fn create(
+        config: &BanConfig,
+        global_redis_conn_details: &RedisConnection,
+    ) -> Result<Arc<dyn BanStore>> {
+        info!("Initializing ban store with driver: {:?}", config.driver);
+
+        match config.driver {
+            #[cfg(feature = "redis")]
+            CacheDriver::Redis => Self::create_redis_store(config, global_redis_conn_details).await,
+            #[cfg(feature = "redis-cluster")]
+            CacheDriver::RedisCluster => {
+                Self::create_redis_cluster_store(config, global_redis_conn_details).await
+            }
+            CacheDriver::Memory | CacheDriver::None => Ok(Arc::new(MemoryBanStore::new())),
+            #[allow(unreachable_patterns)]
+            _ => {
+                warn!(
+                    "Ban store driver {:?} is not compiled in. Falling back to memory.",
+                    config.driver
+                );
+                Ok(Arc::new(MemoryBanStore::new()))
+            }
+        }
+    }
+
+    #[cfg(feature = "redis")]
+    fn prefix(config: &BanConfig, global_redis_conn_details: &RedisConnection) -> String {
+        config
+            .redis
+            .prefix
+            .clone()
+            .unwrap_or_else(|| global_redis_conn_details.key_prefix.clone() + "ban:")
+    }
+
+    #[cfg(feature = "redis")]
+    async fn create_redis_store(
+        config: &BanConfig,
+        global_redis_conn_details: &RedisConnection,
+    ) -> Result<Arc<dyn BanStore>> {
+        use crate::ban::redis_store::RedisBanStore;
+        use crate::error::Error;
+
+        let redis_url = config.redis.url_override.clone().unwrap_or_else(|| {
+            format!(
+                "redis://{}:{}",
+                global_redis_conn_details.host, global_redis_conn_details.port
+            )
+        });
+        let client = redis::Client::open(redis_url.as_str())
+            .map_err(|e| Error::Redis(format!("Failed to create Redis client for bans: {e}")))?;
+        let connection = client
+            .get_connection_manager()
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to connect to Redis for bans: {e}")))?;
+
+        Ok(Arc::new(RedisBanStore::new(
+            connection,
+            Self::prefix(config, global_redis_conn_details),
+        )))
+    }
+
+    #[cfg(feature = "redis-cluster")]
+    async fn create_redis_cluster_store(
+        config: &BanConfig,
+        global_redis_conn_details: &RedisConnection,
+    ) -> Result<Arc<dyn BanStore>> {
+        use crate::ban::redis_store::RedisBanStore;
+        use crate::error::Error;
+
+        if global_redis_conn_details.cluster_nodes.is_empty() {
+            return Err(Error::Configuration(
+                "Bans: Redis cluster nodes not configured.".to_string(),
+            ));
+        }
+        let nodes: Vec<String> = global_redis_conn_details
+            .cluster_nodes
+            .iter()
+            .map(|node| format!("redis://{}:{}", node.host, node.port))
+            .collect();
+        let client = redis::cluster::ClusterClient::new(nodes).map_err(|e| {
+            Error::Redis(format!(
+                "Failed to create Redis cluster client for bans: {e}"
+            ))
+        })?;
+        let connection = client.get_async_connection().await.map_err(|e| {
+            Error::Redis(format!("Failed to connect to Redis cluster for bans: {e}"))
+        })?;
+
+        Ok(Arc::new(RedisBanStore::new(
+            connection,
+            Self::prefix(config, global_redis_conn_details),
+        )))
+    }
+}
@@ -0,0 +1,121 @@
+// src/ban/memory_store.rs
+use super::{Ban, BanStore, now};
+use crate::error::Result;
+use async_trait::async_trait;
+use moka::Expiry;
+use moka::future::Cache;
+use std::time::{Duration, Instant};
+
+/// Bans of this node only. Each ban is evicted when it expires, so bans of addresses
+/// that never come back do not pile up.
+pub struct MemoryBanStore {
+    bans: Cache<String, Ban, ahash::RandomState>,
+}
+
+/// Expires each ban at its `expires_at`
+struct BanExpiry;
+
+impl Expiry<String, Ban> for BanExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        ban: &Ban,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(Duration::from_secs(ban.remaining()))
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        ban: &Ban,
+        _updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(Duration::from_secs(ban.remaining()))
+    }
+}
+
+impl MemoryBanStore {
+    pub fn new() -> Self {
+        Self {
+            bans: Cache::builder()
+                .name("sockudo-bans")
+                .expire_after(BanExpiry)
+                .build_with_hasher(ahash::RandomState::new()),
+        }
+    }
+}
+
+impl Default for MemoryBanStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BanStore for MemoryBanStore {
+    async fn find(&self, keys: &[String]) -> Result<Option<Ban>> {
+        let now = now();
+        for key in keys {
+            // Expiry is tracked in whole seconds, so check the ban as well
+            if let Some(ban) = self.bans.get(key).await.filter(|ban| ban.is_active(now)) {
+                return Ok(Some(ban));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn insert(&self, key: &str, ban: &Ban) -> Result<()> {
+        self.bans.insert(key.to_string(), ban.clone()).await;
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool> {
+        Ok(self
+            .bans
+            .remove(key)
+            .await
+            .is_some_and(|ban| ban.is_active(now())))
+    }
+
+    async fn list(&self) -> Result<Vec<Ban>> {
+        let now = now();
+        let mut bans: Vec<Ban> = self
+            .bans
+            .iter()
+            .map(|(_, ban)| ban)
+            .filter(|ban| ban.is_active(now))
+            .collect();
+        bans.sort_by_key(|ban| ban.banned_at);
+        Ok(bans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ban::BanTarget;
+
+    fn ban(user_id: &str, expires_in: u64) -> Ban {
+        let banned_at = now();
+        Ban {
+            target: BanTarget::user("app-1", user_id),
+            reason: "auth_failure".to_string(),
+            banned_at,
+            expires_at: banned_at + expires_in,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_bans_are_evicted_without_listing() {
+        let store = MemoryBanStore::new();
+        store.insert("expired", &ban("1", 1)).await.unwrap();
+        store.insert("active", &ban("2", 60)).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(2100)).await;
+        store.bans.run_pending_tasks().await;
+        assert_eq!(store.bans.entry_count(), 1);
+        assert!(store.find(&["active".to_string()]).await.unwrap().is_some());
+    }
+}
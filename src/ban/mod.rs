@@ -0,0 +1,325 @@
+// src/ban/mod.rs
+pub mod factory;
+pub mod memory_store;
+#[cfg(feature = "redis")]
+pub mod redis_store;
+
+use crate::audit::{AuditEvent, AuditEventKind, AuditLog};
+use crate::error::Result;
+use crate::options::BanConfig;
+use crate::rate_limiter::RateLimiter;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+/// Who a ban applies to. Users are banned within one app; addresses within one app, or
+/// across all apps when `app_id` is unset.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BanTarget {
+    Ip {
+        ip: IpAddr,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        app_id: Option<String>,
+    },
+    User {
+        app_id: String,
+        user_id: String,
+    },
+}
+
+impl BanTarget {
+    /// An address in every app
+    pub fn ip(ip: IpAddr) -> Self {
+        Self::Ip {
+            ip: ip.to_canonical(),
+            app_id: None,
+        }
+    }
+
+    /// An address in one app
+    pub fn app_ip(app_id: &str, ip: IpAddr) -> Self {
+        Self::Ip {
+            ip: ip.to_canonical(),
+            app_id: Some(app_id.to_string()),
+        }
+    }
+
+    pub fn user(app_id: &str, user_id: &str) -> Self {
+        Self::User {
+            app_id: app_id.to_string(),
+            user_id: user_id.to_string(),
+        }
+    }
+
+    pub fn app_id(&self) -> Option<&str> {
+        match self {
+            Self::Ip { app_id, .. } => app_id.as_deref(),
+            Self::User { app_id, .. } => Some(app_id),
+        }
+    }
+
+    fn key(&self) -> String {
+        match self {
+            Self::Ip { ip, app_id: None } => format!("ip:{ip}"),
+            Self::Ip {
+                ip,
+                app_id: Some(app_id),
+            } => format!("app:{app_id}:ip:{ip}"),
+            Self::User { app_id, user_id } => format!("app:{app_id}:user:{user_id}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Ban {
+    #[serde(flatten)]
+    pub target: BanTarget,
+    /// Violation that triggered the ban, e.g. `rate_limit` or `auth_failure`
+    pub reason: String,
+    pub banned_at: u64,
+    pub expires_at: u64,
+}
+
+impl Ban {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.expires_at > now
+    }
+
+    /// Seconds until the ban lifts
+    pub fn remaining(&self) -> u64 {
+        self.expires_at.saturating_sub(now())
+    }
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Active bans by target key. Expired bans are never returned.
+#[async_trait]
+pub trait BanStore: Send + Sync {
+    /// First active ban stored under one of the keys
+    async fn find(&self, keys: &[String]) -> Result<Option<Ban>>;
+
+    /// Store a ban under the key until it expires, replacing any previous one
+    async fn insert(&self, key: &str, ban: &Ban) -> Result<()>;
+
+    /// Remove the ban under the key. Returns whether it was active.
+    async fn remove(&self, key: &str) -> Result<bool>;
+
+    /// Active bans, oldest first
+    async fn list(&self) -> Result<Vec<Ban>>;
+}
+
+/// Escalates repeated rate-limit violations and authentication failures into temporary
+/// bans. With a Redis store, bans apply across the cluster.
+pub struct BanManager {
+    config: BanConfig,
+    /// Counts violations per target within `config.window_seconds`
+    violations: Arc<dyn RateLimiter + Send + Sync>,
+    store: Arc<dyn BanStore>,
+    audit_log: Option<Arc<AuditLog>>,
+}
+
+impl BanManager {
+    pub fn new(
+        config: BanConfig,
+        violations: Arc<dyn RateLimiter + Send + Sync>,
+        store: Arc<dyn BanStore>,
+    ) -> Self {
+        Self {
+            config,
+            violations,
+            store,
+            audit_log: None,
+        }
+    }
+
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Active ban of the target. Store errors are logged and let the client through.
+    pub async fn find(&self, target: &BanTarget) -> Option<Ban> {
+        self.find_any(&[target.key()]).await
+    }
+
+    /// Active ban of an address, across all apps or in the given app
+    pub async fn find_ip(&self, app_id: Option<&str>, ip: IpAddr) -> Option<Ban> {
+        let mut keys = vec![BanTarget::ip(ip).key()];
+        if let Some(app_id) = app_id {
+            keys.push(BanTarget::app_ip(app_id, ip).key());
+        }
+        self.find_any(&keys).await
+    }
+
+    async fn find_any(&self, keys: &[String]) -> Option<Ban> {
+        match self.store.find(keys).await {
+            Ok(ban) => ban,
+            Err(e) => {
+                warn!("Failed to look up bans of {}: {}", keys.join(", "), e);
+                None
+            }
+        }
+    }
+
+    /// Count a violation of the target, banning it once it reaches `max_violations` within
+    /// the window. Returns the ban this violation triggered.
+    pub async fn record_violation(&self, target: &BanTarget, reason: &str) -> Option<Ban> {
+        let key = format!("violations:{}", target.key());
+        match self.violations.increment(&key).await {
+            Ok(result) if !result.allowed => {}
+            Ok(_) => return None,
+            Err(e) => {
+                warn!("Failed to count violation of {}: {}", target.key(), e);
+                return None;
+            }
+        }
+        if let Err(e) = self.violations.reset(&key).await {
+            warn!("Failed to reset violations of {}: {}", target.key(), e);
+        }
+
+        match self.ban(target.clone(), reason).await {
+            Ok(ban) => Some(ban),
+            Err(e) => {
+                warn!("Failed to ban {}: {}", target.key(), e);
+                None
+            }
+        }
+    }
+
+    /// Ban the target for `duration_seconds`
+    pub async fn ban(&self, target: BanTarget, reason: &str) -> Result<Ban> {
+        let banned_at = now();
+        let ban = Ban {
+            target,
+            reason: reason.to_string(),
+            banned_at,
+            expires_at: banned_at + self.config.duration_seconds,
+        };
+        self.store.insert(&ban.target.key(), &ban).await?;
+
+        info!(
+            "Banned {} for {}s after repeated {}",
+            ban.target.key(),
+            self.config.duration_seconds,
+            reason
+        );
+        if let Some(audit_log) = &self.audit_log {
+            let mut event = AuditEvent::new(AuditEventKind::ClientBanned, ban.target.app_id())
+                .detail("reason", reason)
+                .detail("expires_at", ban.expires_at);
+            event = match &ban.target {
+                BanTarget::Ip { ip, .. } => event.ip(Some(*ip)),
+                BanTarget::User { user_id, .. } => event.detail("user_id", user_id.as_str()),
+            };
+            audit_log.record(event);
+        }
+        Ok(ban)
+    }
+
+    /// Active bans, oldest first
+    pub async fn list(&self) -> Result<Vec<Ban>> {
+        self.store.list().await
+    }
+
+    /// Lift the ban of the target and forget its violations. Returns whether it was banned.
+    pub async fn lift(&self, target: &BanTarget) -> Result<bool> {
+        let banned = self.store.remove(&target.key()).await?;
+        self.violations
+            .reset(&format!("violations:{}", target.key()))
+            .await?;
+        Ok(banned)
+    }
+}
+
+pub fn validate_config(config: &BanConfig) -> std::result::Result<(), String> {
+    if config.max_violations < 2 {
+        return Err("bans.max_violations must be at least 2".to_string());
+    }
+    if config.window_seconds == 0 || config.duration_seconds == 0 {
+        return Err("bans.window_seconds and bans.duration_seconds must be positive".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ban::memory_store::MemoryBanStore;
+    use crate::rate_limiter::memory_limiter::MemoryRateLimiter;
+
+    fn ban_manager() -> BanManager {
+        let config = BanConfig {
+            enabled: true,
+            max_violations: 3,
+            window_seconds: 60,
+            duration_seconds: 120,
+            ..Default::default()
+        };
+        BanManager::new(
+            config,
+            Arc::new(MemoryRateLimiter::new(3, 60)),
+            Arc::new(MemoryBanStore::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_repeated_violations_ban_until_lifted() {
+        let bans = ban_manager();
+        let ip: IpAddr = "::ffff:203.0.113.9".parse().unwrap();
+        let app_ip = BanTarget::app_ip("app-1", ip);
+        let user = BanTarget::user("app-1", "42");
+
+        assert!(
+            bans.record_violation(&app_ip, "auth_failure")
+                .await
+                .is_none()
+        );
+        assert!(
+            bans.record_violation(&app_ip, "auth_failure")
+                .await
+                .is_none()
+        );
+        let ban = bans
+            .record_violation(&app_ip, "auth_failure")
+            .await
+            .unwrap();
+        assert_eq!(
+            ban.target,
+            BanTarget::app_ip("app-1", "203.0.113.9".parse().unwrap())
+        );
+        assert_eq!(ban.expires_at - ban.banned_at, 120);
+        assert_eq!(bans.find_ip(Some("app-1"), ip).await, Some(ban));
+        // Scoped to the app that saw the failures
+        assert!(bans.find_ip(Some("app-2"), ip).await.is_none());
+        assert!(bans.find_ip(None, ip).await.is_none());
+        assert!(bans.find(&user).await.is_none());
+
+        bans.ban(BanTarget::ip(ip), "rate_limit").await.unwrap();
+        assert!(bans.find_ip(Some("app-2"), ip).await.is_some());
+        bans.ban(user.clone(), "auth_failure").await.unwrap();
+        let listed = bans.list().await.unwrap();
+        assert_eq!(listed.len(), 3);
+        assert!(listed.iter().any(|ban| ban.target == user));
+
+        assert!(bans.lift(&app_ip).await.unwrap());
+        assert!(!bans.lift(&app_ip).await.unwrap());
+        assert!(bans.find(&app_ip).await.is_none());
+        assert_eq!(bans.list().await.unwrap().len(), 2);
+        // Violations before the ban do not count towards the next one
+        assert!(
+            bans.record_violation(&app_ip, "auth_failure")
+                .await
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// src/ban/redis_store.rs
+use super::{Ban, BanStore, now};
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use redis::aio::ConnectionLike;
+use redis::{Cmd, RedisResult, Value};
+
+/// Bans scanned per round trip when listing
+const SCAN_COUNT: usize = 100;
+
+/// Connections the ban store can walk its keys with
+#[async_trait]
+pub trait BanConnection: ConnectionLike + Clone + Send + Sync + 'static {
+    /// Send one page of a SCAN. Ban keys share the hash slot of `slot_key`, which is
+    /// where a cluster connection has to send it.
+    async fn scan_page(&mut self, scan: &Cmd, _slot_key: &str) -> RedisResult<Value> {
+        scan.query_async(self).await
+    }
+}
+
+impl BanConnection for redis::aio::ConnectionManager {}
+
+#[cfg(feature = "redis-cluster")]
+#[async_trait]
+impl BanConnection for redis::cluster_async::ClusterConnection {
+    async fn scan_page(&mut self, scan: &Cmd, slot_key: &str) -> RedisResult<Value> {
+        use redis::cluster_routing::{
+            Route, RoutingInfo, SingleNodeRoutingInfo, SlotAddr, get_slot,
+        };
+
+        let route = Route::new(get_slot(slot_key.as_bytes()), SlotAddr::Master);
+        self.route_command(
+            scan,
+            RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(route)),
+        )
+        .await
+    }
+}
+
+/// Bans shared by every node, one Redis key per ban that expires with it. Keys share a
+/// hash tag, so on a cluster they live in one slot and can be fetched and scanned together.
+pub struct RedisBanStore<C> {
+    connection: C,
+    /// `{<prefix>bans}:`, prepended to every target key
+    key_prefix: String,
+}
+
+impl<C: BanConnection> RedisBanStore<C> {
+    pub fn new(connection: C, prefix: String) -> Self {
+        Self {
+            connection,
+            key_prefix: format!("{{{prefix}bans}}:"),
+        }
+    }
+
+    fn ban_key(&self, key: &str) -> String {
+        format!("{}{key}", self.key_prefix)
+    }
+
+    /// Keys of all stored bans
+    async fn scan_keys(&self) -> Result<Vec<String>> {
+        let mut connection = self.connection.clone();
+        let pattern = format!("{}*", escape_glob(&self.key_prefix));
+        let mut keys = Vec::new();
+        let mut cursor = 0u64;
+        loop {
+            let mut scan = redis::cmd("SCAN");
+            scan.arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_COUNT);
+            let page = connection
+                .scan_page(&scan, &self.key_prefix)
+                .await
+                .map_err(|e| Error::Redis(format!("Failed to scan bans: {e}")))?;
+            let (next, page_keys): (u64, Vec<String>) = redis::from_redis_value(&page)
+                .map_err(|e| Error::Redis(format!("Failed to scan bans: {e}")))?;
+            keys.extend(page_keys);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+}
+
+/// Escape the characters SCAN's MATCH treats as a pattern
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn parse(value: Option<String>) -> Option<Ban> {
+    serde_json::from_str(&value?).ok()
+}
+
+#[async_trait]
+impl<C: BanConnection> BanStore for RedisBanStore<C> {
+    async fn find(&self, keys: &[String]) -> Result<Option<Ban>> {
+        let mut connection = self.connection.clone();
+        let ban_keys: Vec<String> = keys.iter().map(|key| self.ban_key(key)).collect();
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(&ban_keys)
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to look up bans: {e}")))?;
+        let now = now();
+        Ok(values
+            .into_iter()
+            .filter_map(parse)
+            .find(|ban| ban.is_active(now)))
+    }
+
+    async fn insert(&self, key: &str, ban: &Ban) -> Result<()> {
+        let mut connection = self.connection.clone();
+        redis::cmd("SET")
+            .arg(self.ban_key(key))
+            .arg(serde_json::to_string(ban)?)
+            .arg("EX")
+            .arg(ban.remaining().max(1))
+            .query_async::<()>(&mut connection)
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to store ban: {e}")))
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool> {
+        let mut connection = self.connection.clone();
+        let ban_key = self.ban_key(key);
+        let (value,): (Option<String>,) = redis::pipe()
+            .atomic()
+            .get(&ban_key)
+            .del(&ban_key)
+            .ignore()
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to remove ban: {e}")))?;
+        Ok(parse(value).is_some_and(|ban| ban.is_active(now())))
+    }
+
+    async fn list(&self) -> Result<Vec<Ban>> {
+        let keys = self.scan_keys().await?;
+        let mut connection = self.connection.clone();
+        let now = now();
+        let mut bans = Vec::new();
+        for chunk in keys.chunks(SCAN_COUNT) {
+            let values: Vec<Option<String>> = redis::cmd("MGET")
+                .arg(chunk)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| Error::Redis(format!("Failed to list bans: {e}")))?;
+            bans.extend(
+                values
+                    .into_iter()
+                    .filter_map(parse)
+                    .filter(|ban| ban.is_active(now)),
+            );
+        }
+        bans.sort_by_key(|ban| ban.banned_at);
+        Ok(bans)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 use std::time::Duration;
 
 // Cache Manager Interface trait
@@ -15,6 +16,16 @@ pub trait CacheManager: Send + Sync {
     /// Set or overwrite the value in the cache
     async fn set(&mut self, key: &str, value: &str, ttl_seconds: u64) -> Result<()>;
 
+    /// Set the value unless the key exists, in one atomic step, and tell whether it was set.
+    /// The future does not borrow the manager, so a shared manager's lock can be released
+    /// before awaiting it.
+    fn set_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        ttl_seconds: u64,
+    ) -> BoxFuture<'static, Result<bool>>;
+
     /// Remove a key from the cache
     async fn remove(&mut self, key: &str) -> Result<()>;
 
@@ -4,8 +4,10 @@ use crate::cache::manager::CacheManager;
 use crate::error::Result; // Assuming your project's Error/Result types
 use crate::options::MemoryCacheOptions; // Using the type-safe options
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use moka::Expiry;
 use moka::future::Cache;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 // std::sync::Arc and Mutex are not directly needed in this struct if CacheManager trait doesn't require them for self
 
 /// A Memory-based implementation of the CacheManager trait using Moka.
@@ -15,10 +17,27 @@ pub struct MemoryCacheManager {
     cache: Cache<String, String, ahash::RandomState>,
     /// Configuration options for this cache instance.
     options: MemoryCacheOptions,
+    /// Keys claimed with `set_if_absent`, each kept for its own TTL and never evicted for
+    /// capacity, so a claim cannot be forgotten before it expires.
+    claims: Cache<String, Duration, ahash::RandomState>,
     /// Prefix for all keys in this cache instance.
     prefix: String,
 }
 
+/// Expires each claim after the TTL stored as its value.
+struct ClaimExpiry;
+
+impl Expiry<String, Duration> for ClaimExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        ttl: &Duration,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(*ttl)
+    }
+}
+
 impl MemoryCacheManager {
     /// Creates a new Memory cache manager with Moka configuration.
     pub fn new(prefix: String, options: MemoryCacheOptions) -> Self {
@@ -37,8 +56,14 @@ impl MemoryCacheManager {
         }
         .build_with_hasher(ahash::RandomState::new());
 
+        let claims = Cache::builder()
+            .name(format!("sockudo-memory-claims-{prefix}").as_str())
+            .expire_after(ClaimExpiry)
+            .build_with_hasher(ahash::RandomState::new());
+
         Self {
             cache,
+            claims,
             options,
             prefix,
         }
@@ -85,10 +110,31 @@ impl CacheManager for MemoryCacheManager {
         Ok(())
     }
 
+    /// Claims are kept apart from `set` entries: the value is not stored, and the claim
+    /// lives for exactly `ttl_seconds` regardless of the cache's TTL and capacity.
+    fn set_if_absent(
+        &self,
+        key: &str,
+        _value: &str,
+        ttl_seconds: u64,
+    ) -> BoxFuture<'static, Result<bool>> {
+        let claims = self.claims.clone();
+        let prefixed_key = self.prefixed_key(key);
+        let ttl = Duration::from_secs(ttl_seconds.max(1));
+        Box::pin(async move {
+            let entry = claims
+                .entry(prefixed_key)
+                .or_insert_with(async move { ttl })
+                .await;
+            Ok(entry.is_fresh())
+        })
+    }
+
     async fn remove(&mut self, key: &str) -> Result<()> {
         let prefixed_key = self.prefixed_key(key);
         // Moka's invalidate does not return a value, it just removes the entry.
         self.cache.invalidate(&prefixed_key).await;
+        self.claims.invalidate(&prefixed_key).await;
         Ok(())
     }
 
@@ -96,6 +142,7 @@ impl CacheManager for MemoryCacheManager {
         // Moka's cache is in-memory and managed by RAII.
         // "Disconnecting" can mean clearing all entries.
         self.cache.invalidate_all();
+        self.claims.invalidate_all();
         Ok(())
     }
 
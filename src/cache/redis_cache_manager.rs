@@ -3,6 +3,7 @@
 use crate::cache::manager::CacheManager;
 use crate::error::{Error, Result};
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 use redis::{AsyncCommands, Client, aio::ConnectionManager};
 use std::time::Duration;
 
@@ -138,6 +139,27 @@ impl CacheManager for RedisCacheManager {
         Ok(())
     }
 
+    fn set_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        ttl_seconds: u64,
+    ) -> BoxFuture<'static, Result<bool>> {
+        let mut connection = self.connection.clone();
+        let mut command = redis::cmd("SET");
+        command.arg(self.prefixed_key(key)).arg(value).arg("NX");
+        if ttl_seconds > 0 {
+            command.arg("EX").arg(ttl_seconds);
+        }
+        Box::pin(async move {
+            let set: Option<String> = command
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| Error::Cache(format!("Redis set NX error: {e}")))?;
+            Ok(set.is_some())
+        })
+    }
+
     async fn remove(&mut self, key: &str) -> Result<()> {
         let deleted: i32 = self
             .connection
@@ -0,0 +1,107 @@
+// src/channel/encryption.rs
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::Value;
+
+pub const ENCRYPTED_CHANNEL_PREFIX: &str = "private-encrypted-";
+
+/// Length of a secretbox (XSalsa20-Poly1305) nonce
+const NONCE_LENGTH: usize = 24;
+/// Length of the Poly1305 tag every secretbox ciphertext starts with
+const MAC_LENGTH: usize = 16;
+
+pub fn is_encrypted_channel(channel_name: &str) -> bool {
+    channel_name.starts_with(ENCRYPTED_CHANNEL_PREFIX)
+}
+
+/// Check that event data for an end-to-end encrypted channel is a
+/// `{"nonce": ..., "ciphertext": ...}` envelope, given as an object or as its JSON string.
+/// Sockudo never holds the channel key, so the ciphertext itself cannot be verified.
+pub fn validate_envelope(data: &Value) -> Result<(), String> {
+    let parsed;
+    let envelope = match data {
+        Value::String(s) => {
+            parsed = serde_json::from_str::<Value>(s)
+                .map_err(|_| "data must be a JSON object with nonce and ciphertext".to_string())?;
+            &parsed
+        }
+        other => other,
+    };
+    let Some(envelope) = envelope.as_object() else {
+        return Err("data must be a JSON object with nonce and ciphertext".to_string());
+    };
+
+    let decode = |field: &str| {
+        envelope
+            .get(field)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("{field} must be a base64 string"))
+            .and_then(|value| {
+                STANDARD
+                    .decode(value)
+                    .map_err(|_| format!("{field} must be a base64 string"))
+            })
+    };
+    let nonce = decode("nonce")?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(format!(
+            "nonce must be {NONCE_LENGTH} bytes, got {}",
+            nonce.len()
+        ));
+    }
+    let ciphertext = decode("ciphertext")?;
+    if ciphertext.len() < MAC_LENGTH {
+        return Err(format!(
+            "ciphertext must be at least {MAC_LENGTH} bytes, got {}",
+            ciphertext.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_envelope_validation() {
+        let nonce = STANDARD.encode([7u8; NONCE_LENGTH]);
+        let ciphertext = STANDARD.encode([1u8; 40]);
+        let envelope = json!({ "nonce": nonce, "ciphertext": ciphertext });
+
+        assert!(validate_envelope(&envelope).is_ok());
+        assert!(validate_envelope(&Value::String(envelope.to_string())).is_ok());
+
+        let plaintext = json!("hello");
+        assert!(validate_envelope(&plaintext).is_err());
+        let short_nonce = json!({ "nonce": STANDARD.encode([7u8; 12]), "ciphertext": ciphertext });
+        assert!(
+            validate_envelope(&short_nonce)
+                .unwrap_err()
+                .contains("nonce")
+        );
+        let short_ciphertext = json!({ "nonce": nonce, "ciphertext": STANDARD.encode([1u8; 8]) });
+        assert!(
+            validate_envelope(&short_ciphertext)
+                .unwrap_err()
+                .contains("ciphertext")
+        );
+        let not_base64 = json!({ "nonce": nonce, "ciphertext": "not base64!" });
+        assert!(validate_envelope(&not_base64).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_channel_names() {
+        assert!(is_encrypted_channel("private-encrypted-chat"));
+        assert!(!is_encrypted_channel("private-chat"));
+        assert_eq!(
+            crate::channel::ChannelType::from_name("private-encrypted-chat"),
+            crate::channel::ChannelType::PrivateEncrypted
+        );
+        assert_eq!(
+            crate::channel::ChannelType::from_name("private-encryptedchat"),
+            crate::channel::ChannelType::Private
+        );
+    }
+}
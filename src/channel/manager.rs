@@ -1,10 +1,11 @@
 use super::PresenceMemberInfo;
 use super::types::ChannelType;
 use crate::adapter::ConnectionManager;
+use crate::app::auth::split_signature_expiry;
 use crate::app::config::App;
 use crate::error::Error;
 use crate::protocol::messages::{MessageData, PusherMessage};
-use crate::token::{Token, secure_compare};
+use crate::token::Token;
 use crate::websocket::SocketId;
 use moka::future::Cache;
 use serde::{Deserialize, Serialize};
@@ -284,8 +285,21 @@ impl ChannelManager {
         signature: &str,
         message: PusherMessage,
     ) -> bool {
-        let expected = Self::get_expected_signature(app_config, socket_id, message);
-        secure_compare(signature, &expected)
+        // Signatures are "<key>:<hmac>[:<expires_at>]"; the key may be the app key or
+        // the key of any non-revoked credential, signed with a secret paired with it
+        let Some((key, hmac)) = signature.split_once(':') else {
+            return false;
+        };
+        let Some((hmac, expires_at)) = split_signature_expiry(hmac) else {
+            return false;
+        };
+        let mut data = Self::get_data_to_sign_for_signature(socket_id, message);
+        if let Some(expires_at) = expires_at {
+            data = format!("{data}:{expires_at}");
+        }
+        app_config
+            .token_for_key(key)
+            .is_some_and(|token| token.verify(&data, hmac))
     }
 
     pub fn get_expected_signature(
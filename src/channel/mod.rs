@@ -1,3 +1,4 @@
+pub mod encryption;
 pub mod manager;
 pub mod types;
 
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ChannelType {
     Public,
     Private,
@@ -16,8 +17,11 @@ impl ChannelType {
             return Self::Cache;
         }
 
+        if crate::channel::encryption::is_encrypted_channel(channel_name) {
+            return Self::PrivateEncrypted;
+        }
+
         match channel_name.split_once('-') {
-            Some(("private", "encrypted")) => Self::PrivateEncrypted,
             Some(("private", _)) => Self::Private,
             Some(("presence", _)) => Self::Presence,
             _ => Self::Public,
@@ -33,10 +33,19 @@ pub enum Error {
     #[error("Origin not allowed")]
     OriginNotAllowed,
 
+    #[error("IP address not allowed")]
+    IpNotAllowed,
+
+    #[error("Application is over its daily connection-minute quota")]
+    ConnectionMinutesQuotaExceeded,
+
     // 4100-4199: Reconnect with backoff errors
     #[error("Over capacity")]
     OverCapacity,
 
+    #[error("Temporarily banned after repeated violations, retry later")]
+    TemporarilyBanned,
+
     // 4200-4299: Reconnect immediately errors
     #[error("Generic reconnect immediately")]
     ReconnectImmediately,
@@ -47,6 +56,9 @@ pub enum Error {
     #[error("Closed after inactivity")]
     InactivityTimeout,
 
+    #[error("Server is draining, reconnect immediately")]
+    ServerDraining,
+
     // 4300-4399: Other errors
     #[error("Client event rejected due to rate limit")]
     ClientEventRateLimit,
@@ -67,6 +79,15 @@ pub enum Error {
     #[error("Channel does not exist")]
     ChannelNotFound,
 
+    #[error("Channel type not allowed: {0}")]
+    ChannelTypeNotAllowed(String),
+
+    #[error("Channel not allowed: {0}")]
+    ChannelNotAllowed(String),
+
+    #[error("Application is over its daily message quota")]
+    MessageQuotaExceeded,
+
     // Authentication errors
     #[error("Authentication error: {0}")]
     Auth(String),
@@ -181,20 +202,27 @@ impl Error {
             Error::NoProtocolVersion => 4008,
             Error::Unauthorized => 4009,
             Error::OriginNotAllowed => 4009,
+            Error::IpNotAllowed => 4009,
+            Error::ConnectionMinutesQuotaExceeded => 4010,
 
             // 4100-4199: Reconnect with backoff
             Error::OverCapacity => 4100,
+            Error::TemporarilyBanned => 4101,
 
             // 4200-4299: Reconnect immediately
             Error::ReconnectImmediately => 4200,
             Error::PongNotReceived => 4201,
             Error::InactivityTimeout => 4202,
+            Error::ServerDraining => 4203,
 
             // 4300-4399: Other errors
             Error::ClientEventRateLimit => 4301,
             Error::WatchlistLimitExceeded => 4302,
 
             Error::Broadcast(_) => 4303,
+            Error::ChannelTypeNotAllowed(_) => 4304,
+            Error::ChannelNotAllowed(_) => 4305,
+            Error::MessageQuotaExceeded => 4306,
 
             // Map other errors to appropriate ranges
             Error::Channel(_)
@@ -227,6 +255,9 @@ impl Error {
                 | Error::NoProtocolVersion
                 | Error::Unauthorized
                 | Error::OriginNotAllowed
+                | Error::IpNotAllowed
+                | Error::TemporarilyBanned
+                | Error::ConnectionMinutesQuotaExceeded
                 | Error::Auth(_)
                 | Error::InvalidSignature
                 | Error::InvalidAppKey
@@ -237,9 +268,11 @@ impl Error {
         matches!(
             self,
             Error::OverCapacity
+                | Error::TemporarilyBanned
                 | Error::ReconnectImmediately
                 | Error::PongNotReceived
                 | Error::InactivityTimeout
+                | Error::ServerDraining
         )
     }
 }
@@ -267,6 +300,7 @@ pub enum HealthStatus {
     Degraded(Vec<String>), // Some issues but still functional
     Error(Vec<String>),    // Critical issues, not functional
     NotFound,              // App doesn't exist
+    Draining,              // Node is draining and should not receive new traffic
 }
 
 // src/error/macros.rs
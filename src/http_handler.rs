@@ -1,18 +1,30 @@
 use crate::adapter::ConnectionHandler;
+use crate::adapter::horizontal_adapter::{ClusterTopology, NodeStats};
+use crate::app::admin::{
+    AppChanges, DEFAULT_SECRET_GRACE_PERIOD, redacted_app, rotate_app_credentials, validate_app,
+};
+use crate::app::channel_limits::ChannelLimits;
 use crate::app::config::App; // To access app limits
+use crate::audit::{AuditEvent, AuditEventKind};
+use crate::ban::{BanManager, BanTarget};
 use crate::channel::ChannelManager;
+use crate::channel::encryption::{self, is_encrypted_channel};
 use crate::error::{HEALTH_CHECK_TIMEOUT_MS, HealthStatus};
+use crate::middleware::admin_auth_middleware;
 use crate::protocol::constants::EVENT_NAME_MAX_LENGTH as DEFAULT_EVENT_NAME_MAX_LENGTH;
 use crate::protocol::messages::{
     ApiMessageData, BatchPusherApiMessage, InfoQueryParser, MessageData, PusherApiMessage,
     PusherMessage,
 };
+use crate::quota::QuotaKind;
+use crate::rate_limiter::middleware::UntrustedClientAddress;
 use crate::utils::{self, validate_channel_name};
 use crate::websocket::SocketId;
 use axum::{
     Json,
-    extract::{Path, Query, RawQuery, State}, // Added RawQuery
-    http::{HeaderMap, HeaderValue, StatusCode, Uri, header}, // Added Uri
+    Router,
+    extract::{FromRequestParts, Path, Query, RawQuery, State}, // Added RawQuery
+    http::{HeaderMap, HeaderValue, StatusCode, Uri, header, request::Parts}, // Added Uri
     response::{IntoResponse, Response as AxumResponse},
 };
 use futures_util::future::join_all;
@@ -20,6 +32,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::{
     collections::HashMap, // Added BTreeMap
+    convert::Infallible,
+    net::IpAddr,
     sync::Arc,
     time::Duration,
 };
@@ -54,6 +68,14 @@ pub enum AppError {
     PayloadTooLarge(String),
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
 }
 
 impl IntoResponse for AppError {
@@ -87,6 +109,12 @@ impl IntoResponse for AppError {
                 (StatusCode::PAYLOAD_TOO_LARGE, json!({ "error": msg }))
             }
             AppError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, json!({ "error": msg })),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, json!({ "error": msg })),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, json!({ "error": msg })),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, json!({ "error": msg })),
+            AppError::QuotaExceeded(msg) => {
+                (StatusCode::TOO_MANY_REQUESTS, json!({ "error": msg }))
+            }
         };
         error!(error.message = %self, status_code = %status, "HTTP request failed");
         (status, Json(error_message)).into_response()
@@ -105,12 +133,41 @@ impl From<crate::error::Error> for AppError {
                 AppError::InvalidInput(format!("Invalid channel name: {s}"))
             }
             crate::error::Error::Channel(s) => AppError::InvalidInput(s),
+            crate::error::Error::ChannelTypeNotAllowed(_)
+            | crate::error::Error::ChannelNotAllowed(_) => AppError::Forbidden(err.to_string()),
+            crate::error::Error::MessageQuotaExceeded => AppError::QuotaExceeded(err.to_string()),
             crate::error::Error::Auth(s) => AppError::ApiAuthFailed(s),
             _ => AppError::InternalError(err.to_string()),
         }
     }
 }
 
+impl From<UntrustedClientAddress> for AppError {
+    fn from(err: UntrustedClientAddress) -> Self {
+        AppError::InvalidInput(err.to_string())
+    }
+}
+
+/// Address of the client making the request, recorded in audit events
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+impl FromRequestParts<Arc<ConnectionHandler>> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        handler: &Arc<ConnectionHandler>,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            handler
+                .trusted_client_ip(&parts.headers, &parts.extensions)
+                .ok()
+                .flatten(),
+        ))
+    }
+}
+
 // --- Query Parameter Structs ---
 
 // This struct is used by Axum to deserialize the known Pusher auth query parameters.
@@ -275,21 +332,6 @@ async fn process_single_event_parallel(
         )));
     }
 
-    // Check event payload size against app limits
-    if let Some(max_payload_kb) = app.max_event_payload_in_kb {
-        let value_for_size_calc = match &event_payload_data {
-            Some(ApiMessageData::String(s)) => json!(s),
-            Some(ApiMessageData::Json(j_val)) => j_val.clone(),
-            None => json!(null),
-        };
-        let payload_size_bytes = utils::data_to_bytes_flexible(vec![value_for_size_calc]);
-        if payload_size_bytes > (max_payload_kb as usize * 1024) {
-            return Err(AppError::PayloadTooLarge(format!(
-                "Event payload size ({payload_size_bytes} bytes) for event '{event_name_str}' exceeds limit ({max_payload_kb}KB)"
-            )));
-        }
-    }
-
     // Map the original socket ID string to SocketId type
     let mapped_socket_id: Option<SocketId> = original_socket_id_str.map(SocketId);
 
@@ -320,6 +362,55 @@ async fn process_single_event_parallel(
         }
     };
 
+    // Check event payload size against the limit of each target channel
+    let payload_limits: Vec<(&str, u32)> = target_channels
+        .iter()
+        .filter_map(|channel| {
+            ChannelLimits::resolve(app, channel)
+                .max_event_payload_in_kb
+                .map(|max_payload_kb| (channel.as_str(), max_payload_kb))
+        })
+        .collect();
+    if !payload_limits.is_empty() {
+        let value_for_size_calc = match &event_payload_data {
+            Some(ApiMessageData::String(s)) => json!(s),
+            Some(ApiMessageData::Json(j_val)) => j_val.clone(),
+            None => json!(null),
+        };
+        let payload_size_bytes = utils::data_to_bytes_flexible(vec![value_for_size_calc]);
+        for (channel, max_payload_kb) in payload_limits {
+            if payload_size_bytes > (max_payload_kb as usize * 1024) {
+                return Err(AppError::PayloadTooLarge(format!(
+                    "Event payload size ({payload_size_bytes} bytes) for event '{event_name_str}' exceeds limit ({max_payload_kb}KB) of channel '{channel}'"
+                )));
+            }
+        }
+    }
+
+    // Encrypted channels only carry end-to-end encrypted envelopes, each under the key of
+    // its own channel
+    if let Some(encrypted_channel) = target_channels
+        .iter()
+        .find(|channel| is_encrypted_channel(channel))
+    {
+        if target_channels.len() > 1 {
+            return Err(AppError::InvalidInput(
+                "Cannot trigger an event on multiple channels when one of them is encrypted"
+                    .to_string(),
+            ));
+        }
+        let data = match &event_payload_data {
+            Some(ApiMessageData::String(s)) => Value::String(s.clone()),
+            Some(ApiMessageData::Json(j_val)) => j_val.clone(),
+            None => Value::Null,
+        };
+        encryption::validate_envelope(&data).map_err(|e| {
+            AppError::InvalidInput(format!(
+                "Invalid payload for encrypted channel '{encrypted_channel}': {e}"
+            ))
+        })?;
+    }
+
     // Create a collection of futures, one for each channel to process.
     // These futures will be executed concurrently by `join_all`.
     let channel_processing_futures = target_channels.into_iter().map(|target_channel_str| {
@@ -341,6 +432,7 @@ async fn process_single_event_parallel(
             // Validate the channel name.
             // `app` is captured by reference from the outer scope.
             validate_channel_name(app, &target_channel_str).await?;
+            app.check_channel_policy(&target_channel_str)?;
 
             // Construct the message to be sent to this specific channel.
             let message_data = match payload_for_task {
@@ -511,6 +603,7 @@ pub async fn events(
         .find_by_id(app_id.as_str())
         .await?
         .ok_or_else(|| AppError::AppNotFound(app_id.clone()))?;
+    handler.check_quota(&app, QuotaKind::Messages).await?;
 
     let need_channel_info = event_payload.info.is_some();
 
@@ -573,6 +666,9 @@ pub async fn batch_events(
         .find_by_id(app_id.as_str())
         .await?
         .ok_or_else(|| AppError::AppNotFound(app_id.clone()))?;
+    handler
+        .check_quota(&app_config, QuotaKind::Messages)
+        .await?;
 
     // Validate batch size against app limits.
     if let Some(max_batch) = app_config.max_event_batch_size
@@ -874,6 +970,7 @@ pub async fn terminate_user_connections(
     Path((app_id, user_id)): Path<(String, String)>,
     Query(auth_q_params_struct): Query<EventQuery>, // Auth params for this POST endpoint
     State(handler): State<Arc<ConnectionHandler>>,
+    ClientIp(client_ip): ClientIp,
     uri: Uri,
     RawQuery(raw_query_str_option): RawQuery,
     // This endpoint typically does not have a body, but if it could, it would be:
@@ -890,6 +987,11 @@ pub async fn terminate_user_connections(
         .await
         .terminate_connection(&app_id, &user_id)
         .await?;
+    handler.audit(
+        AuditEvent::new(AuditEventKind::UserConnectionsTerminated, Some(&app_id))
+            .ip(client_ip)
+            .detail("user_id", user_id.as_str()),
+    );
 
     info!(
         "Successfully initiated termination for user_id: {}",
@@ -999,8 +1101,13 @@ pub async fn up(
     app_id: Option<Path<String>>,
     State(handler): State<Arc<ConnectionHandler>>,
 ) -> Result<impl IntoResponse, AppError> {
-    // First check app configuration based on whether app_id is provided
-    let (health_status, app_id_str) = if let Some(Path(app_id)) = app_id {
+    // A draining node reports not-ready so load balancers stop routing to it
+    let (health_status, app_id_str) = if handler.is_draining() {
+        let app_id_str = app_id
+            .map(|Path(id)| id)
+            .unwrap_or_else(|| "system".to_string());
+        (HealthStatus::Draining, app_id_str)
+    } else if let Some(Path(app_id)) = app_id {
         // Specific app health check
         tracing::Span::current().record("app_id", &app_id);
         debug!("Health check received for app_id: {}", app_id);
@@ -1096,6 +1203,9 @@ pub async fn up(
         HealthStatus::NotFound => {
             warn!("Health check for non-existent app_id: {}", app_id_str);
         }
+        HealthStatus::Draining => {
+            debug!("Health check for {} while node is draining", app_id_str);
+        }
     }
 
     // Return response maintaining backward compatibility
@@ -1105,6 +1215,7 @@ pub async fn up(
         HealthStatus::Degraded(_) => (StatusCode::OK, "DEGRADED", "DEGRADED"), // Non-critical issues - WebSocket still works
         HealthStatus::Error(_) => (StatusCode::SERVICE_UNAVAILABLE, "ERROR", "ERROR"), // Critical issues - WebSocket won't work
         HealthStatus::NotFound => (StatusCode::NOT_FOUND, "NOT_FOUND", "NOT_FOUND"),
+        HealthStatus::Draining => (StatusCode::SERVICE_UNAVAILABLE, "DRAINING", "DRAINING"),
     };
 
     // Record metrics if available (non-blocking)
@@ -1122,6 +1233,319 @@ pub async fn up(
     Ok(response_val)
 }
 
+/// GET /admin/cluster
+/// Lists cluster nodes with heartbeat age, load, presence registry sizes and the cleanup leader
+#[instrument(skip(handler), fields(service = "admin_cluster"))]
+pub async fn cluster_topology(
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<impl IntoResponse, AppError> {
+    let topology = {
+        let mut connection_manager = handler.connection_manager.lock().await;
+        match connection_manager.as_horizontal_adapter() {
+            Some(horizontal) => horizontal.get_cluster_topology().await,
+            None => {
+                let stats = connection_manager
+                    .get_namespaces()
+                    .await
+                    .ok()
+                    .map(|namespaces| NodeStats::from_namespaces(&namespaces));
+                ClusterTopology::single_node(connection_manager.get_node_id(), stats)
+            }
+        }
+    };
+
+    Ok((StatusCode::OK, Json(topology)))
+}
+
+/// GET /admin/apps
+#[instrument(skip(handler), fields(service = "admin_apps"))]
+pub async fn admin_list_apps(
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<impl IntoResponse, AppError> {
+    let apps = handler.app_manager.get_apps().await?;
+    let apps: Vec<Value> = apps.iter().map(redacted_app).collect();
+    Ok((StatusCode::OK, Json(json!({ "apps": apps }))))
+}
+
+/// GET /admin/apps/{app_id}
+#[instrument(skip(handler), fields(app_id = %app_id))]
+pub async fn admin_get_app(
+    Path(app_id): Path<String>,
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<impl IntoResponse, AppError> {
+    let app = handler
+        .app_manager
+        .find_by_id(&app_id)
+        .await?
+        .ok_or_else(|| AppError::AppNotFound(app_id.clone()))?;
+    Ok((StatusCode::OK, Json(redacted_app(&app))))
+}
+
+/// POST /admin/apps
+/// Creates an app, generating the id, key and secret when they are not given.
+/// The response is the only one that includes the secret.
+#[instrument(skip(handler, changes), fields(service = "admin_apps"))]
+pub async fn admin_create_app(
+    State(handler): State<Arc<ConnectionHandler>>,
+    ClientIp(client_ip): ClientIp,
+    Json(changes): Json<AppChanges>,
+) -> Result<impl IntoResponse, AppError> {
+    let app = changes.into_new_app().map_err(AppError::InvalidInput)?;
+    validate_app(&app).map_err(AppError::InvalidInput)?;
+
+    if handler.app_manager.find_by_id(&app.id).await?.is_some() {
+        return Err(AppError::Conflict(format!("App {} already exists", app.id)));
+    }
+    if handler.app_manager.find_by_key(&app.key).await?.is_some() {
+        return Err(AppError::Conflict(format!(
+            "An app with key {} already exists",
+            app.key
+        )));
+    }
+
+    handler.app_manager.create_app(app.clone()).await?;
+    info!(app_id = %app.id, "App created through the admin API");
+    handler.audit(AuditEvent::new(AuditEventKind::AppCreated, Some(&app.id)).ip(client_ip));
+    Ok((StatusCode::CREATED, Json(app)))
+}
+
+/// PATCH /admin/apps/{app_id}
+/// Updates the given fields; `{"enabled": false}` disables the app.
+#[instrument(skip(handler, changes), fields(app_id = %app_id))]
+pub async fn admin_update_app(
+    Path(app_id): Path<String>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    ClientIp(client_ip): ClientIp,
+    Json(changes): Json<AppChanges>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut app = handler
+        .app_manager
+        .find_by_id(&app_id)
+        .await?
+        .ok_or_else(|| AppError::AppNotFound(app_id.clone()))?;
+
+    let before = serde_json::to_value(&app)?;
+    changes.apply_to(&mut app).map_err(AppError::InvalidInput)?;
+    validate_app(&app).map_err(AppError::InvalidInput)?;
+    let fields = changed_fields(&before, &serde_json::to_value(&app)?);
+
+    handler.app_manager.update_app(app.clone()).await?;
+    handler.propagate_app_change(&app.id).await;
+    info!(app_id = %app.id, enabled = app.enabled, "App updated through the admin API");
+    handler.audit(
+        AuditEvent::new(AuditEventKind::AppUpdated, Some(&app.id))
+            .ip(client_ip)
+            .detail("fields", fields),
+    );
+    Ok((StatusCode::OK, Json(redacted_app(&app))))
+}
+
+/// Names of the top-level fields that differ between two serialized apps
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = after
+        .iter()
+        .filter(|(field, value)| before.get(*field) != Some(value))
+        .map(|(field, _)| field.clone())
+        .chain(
+            before
+                .keys()
+                .filter(|field| !after.contains_key(*field))
+                .cloned(),
+        )
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// GET /admin/apps/{app_id}/usage
+/// Today's consumption of the app's message and connection-minute quotas
+#[instrument(skip(handler), fields(app_id = %app_id))]
+pub async fn admin_get_app_usage(
+    Path(app_id): Path<String>,
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<impl IntoResponse, AppError> {
+    let app = handler
+        .app_manager
+        .find_by_id(&app_id)
+        .await?
+        .ok_or_else(|| AppError::AppNotFound(app_id.clone()))?;
+    let usage = handler.quota_manager().usage(&app).await?;
+    Ok((StatusCode::OK, Json(usage)))
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RotateSecretQuery {
+    /// Seconds the previous key/secret pair stays accepted; 0 revokes all previous pairs
+    pub grace_period: Option<u64>,
+    /// Generate a new app key along with the secret
+    pub rotate_key: Option<bool>,
+}
+
+/// POST /admin/apps/{app_id}/secret
+/// Replaces the app secret, and with `rotate_key=true` the key, with newly generated
+/// ones and returns them. The previous pair keeps verifying signatures until the grace
+/// period ends.
+#[instrument(skip(handler, query), fields(app_id = %app_id))]
+pub async fn admin_rotate_app_secret(
+    Path(app_id): Path<String>,
+    Query(query): Query<RotateSecretQuery>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    ClientIp(client_ip): ClientIp,
+) -> Result<impl IntoResponse, AppError> {
+    let mut app = handler
+        .app_manager
+        .find_by_id(&app_id)
+        .await?
+        .ok_or_else(|| AppError::AppNotFound(app_id.clone()))?;
+
+    let grace_period = query
+        .grace_period
+        .map_or(DEFAULT_SECRET_GRACE_PERIOD, Duration::from_secs);
+    let rotate_key = query.rotate_key.unwrap_or(false);
+    rotate_app_credentials(&mut app, grace_period, rotate_key);
+    handler.app_manager.update_app(app.clone()).await?;
+    handler.propagate_app_change(&app.id).await;
+    info!(
+        app_id = %app.id,
+        grace_period_secs = grace_period.as_secs(),
+        rotate_key,
+        "App secret rotated through the admin API"
+    );
+    handler.audit(
+        AuditEvent::new(AuditEventKind::AppSecretRotated, Some(&app.id))
+            .ip(client_ip)
+            .detail("grace_period_secs", grace_period.as_secs())
+            .detail("key", app.key.clone()),
+    );
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "id": app.id, "key": app.key, "secret": app.secret })),
+    ))
+}
+
+/// DELETE /admin/apps/{app_id}
+#[instrument(skip(handler), fields(app_id = %app_id))]
+pub async fn admin_delete_app(
+    Path(app_id): Path<String>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    ClientIp(client_ip): ClientIp,
+) -> Result<impl IntoResponse, AppError> {
+    if handler.app_manager.find_by_id(&app_id).await?.is_none() {
+        return Err(AppError::AppNotFound(app_id));
+    }
+
+    handler.app_manager.delete_app(&app_id).await?;
+    handler.propagate_app_change(&app_id).await;
+    info!(app_id = %app_id, "App deleted through the admin API");
+    handler.audit(AuditEvent::new(AuditEventKind::AppDeleted, Some(&app_id)).ip(client_ip));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /admin/bans
+/// Active temporary bans of IP addresses and users
+#[instrument(skip(handler), fields(service = "admin_bans"))]
+pub async fn admin_list_bans(
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<impl IntoResponse, AppError> {
+    let ban_manager = enabled_ban_manager(&handler)?;
+    let bans = ban_manager.list().await?;
+    Ok((StatusCode::OK, Json(json!({ "bans": bans }))))
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct LiftBanQuery {
+    pub ip: Option<IpAddr>,
+    pub app_id: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// DELETE /admin/bans?ip=...[&app_id=...] or ?app_id=...&user_id=...
+/// Lifts a ban before it expires and forgets the violations counted towards the next one
+#[instrument(skip(handler), fields(service = "admin_bans"))]
+pub async fn admin_lift_ban(
+    Query(query): Query<LiftBanQuery>,
+    State(handler): State<Arc<ConnectionHandler>>,
+    ClientIp(client_ip): ClientIp,
+) -> Result<impl IntoResponse, AppError> {
+    let ban_manager = enabled_ban_manager(&handler)?;
+    let target = match query {
+        LiftBanQuery {
+            ip: Some(ip),
+            app_id: None,
+            user_id: None,
+        } => BanTarget::ip(ip),
+        LiftBanQuery {
+            ip: Some(ip),
+            app_id: Some(app_id),
+            user_id: None,
+        } => BanTarget::app_ip(&app_id, ip),
+        LiftBanQuery {
+            ip: None,
+            app_id: Some(app_id),
+            user_id: Some(user_id),
+        } => BanTarget::user(&app_id, &user_id),
+        _ => {
+            return Err(AppError::InvalidInput(
+                "Give ip, optionally with app_id, or both app_id and user_id".to_string(),
+            ));
+        }
+    };
+
+    if !ban_manager.lift(&target).await? {
+        return Err(AppError::NotFound("No active ban matches".to_string()));
+    }
+    info!(?target, "Ban lifted through the admin API");
+    let mut event = AuditEvent::new(AuditEventKind::BanLifted, target.app_id()).ip(client_ip);
+    event = match &target {
+        BanTarget::Ip { ip, .. } => event.detail("banned_ip", ip.to_string()),
+        BanTarget::User { user_id, .. } => event.detail("user_id", user_id.as_str()),
+    };
+    handler.audit(event);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Bans are a configuration choice, so the routes answer 404 while they are disabled
+fn enabled_ban_manager(handler: &ConnectionHandler) -> Result<&Arc<BanManager>, AppError> {
+    handler.ban_manager().ok_or_else(|| {
+        AppError::NotFound("Temporary bans are not enabled, set bans.enabled".to_string())
+    })
+}
+
+/// POST /drain (metrics port)
+/// Puts the node into drain mode and starts disconnecting clients gradually
+#[instrument(skip(handler), fields(service = "drain"))]
+pub async fn drain(
+    State(handler): State<Arc<ConnectionHandler>>,
+) -> Result<impl IntoResponse, AppError> {
+    let started = handler.spawn_drain();
+    if started {
+        info!("Drain requested via HTTP endpoint");
+    } else {
+        debug!("Drain requested via HTTP endpoint but node is already draining");
+    }
+
+    let response_payload = json!({
+        "draining": true,
+        "started": started,
+        "window_seconds": handler.server_options().drain.window_seconds,
+    });
+    Ok((StatusCode::ACCEPTED, Json(response_payload)))
+}
+
+/// The drain route, which takes the admin bearer token like the admin API
+pub fn drain_routes(handler: Arc<ConnectionHandler>) -> Router<Arc<ConnectionHandler>> {
+    Router::new().route(
+        "/drain",
+        axum::routing::post(drain).route_layer(axum::middleware::from_fn_with_state(
+            handler,
+            admin_auth_middleware,
+        )),
+    )
+}
+
 /// GET /metrics (Prometheus format)
 #[instrument(skip(handler), fields(service = "metrics_exporter"))]
 pub async fn metrics(
@@ -1,17 +1,23 @@
 pub mod adapter;
 pub mod app;
+pub mod audit;
+pub mod ban;
 pub mod cache;
 pub mod channel;
 pub mod cleanup;
 pub mod error;
 pub mod http_handler;
 pub mod metrics;
+pub mod middleware;
+pub mod mtls;
 pub mod namespace;
 pub mod options;
 pub mod presence;
 pub mod protocol;
 pub mod queue;
+pub mod quota;
 pub mod rate_limiter;
+pub mod tls;
 pub mod token;
 pub mod utils;
 pub mod watchlist;
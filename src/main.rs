@@ -4,6 +4,8 @@
 
 mod adapter;
 mod app;
+mod audit;
+mod ban;
 mod cache;
 mod channel;
 pub mod cleanup;
@@ -11,12 +13,15 @@ mod error;
 mod http_handler;
 mod metrics;
 mod middleware;
+mod mtls;
 mod namespace;
 mod options;
 mod presence;
 mod protocol;
 mod queue;
+mod quota;
 mod rate_limiter;
+mod tls;
 mod token;
 pub mod utils;
 mod watchlist;
@@ -37,8 +42,8 @@ use axum::routing::{get, post};
 use axum::serve::IncomingStream;
 use axum::{BoxError, Router, ServiceExt, middleware as axum_middleware};
 use axum_extra::extract::Host;
-use axum_server::tls_rustls::RustlsConfig;
-use clap::Parser;
+use axum_server::tls_rustls::RustlsAcceptor;
+use clap::{Parser, Subcommand};
 use error::Error;
 use futures_util::future::join_all;
 use mimalloc::MiMalloc;
@@ -57,21 +62,31 @@ use tokio::sync::Mutex;
 
 // Updated factory imports
 use crate::adapter::factory::AdapterFactory;
+use crate::app::encrypted_app_manager::EncryptedAppManager;
 use crate::app::factory::AppManagerFactory;
+use crate::app::secret_cipher::SecretCipher;
 use crate::cache::factory::CacheManagerFactory;
 use crate::cleanup::{CleanupConfig, CleanupSender};
 use crate::error::Result;
 use crate::http_handler::{
-    batch_events, channel, channel_users, channels, events, metrics, terminate_user_connections,
-    up, usage,
+    admin_create_app, admin_delete_app, admin_get_app, admin_get_app_usage, admin_lift_ban,
+    admin_list_apps, admin_list_bans, admin_rotate_app_secret, admin_update_app, batch_events,
+    channel, channel_users, channels, cluster_topology, drain_routes, events, metrics,
+    terminate_user_connections, up, usage,
 };
 
+use crate::audit::AuditLog;
+use crate::ban::BanManager;
+use crate::ban::factory::BanStoreFactory;
 use crate::metrics::MetricsFactory;
-use crate::options::{AdapterDriver, QueueDriver, ServerOptions};
+use crate::options::{AdapterDriver, QueueDriver, RateLimit, RateLimiterConfig, ServerOptions};
 use crate::queue::manager::{QueueManager, QueueManagerFactory};
+use crate::quota::QuotaManager;
+use crate::quota::factory::UsageStoreFactory;
+use crate::quota::memory_store::MemoryUsageStore;
 use crate::rate_limiter::RateLimiter;
 use crate::rate_limiter::factory::RateLimiterFactory;
-use crate::rate_limiter::middleware::IpKeyExtractor;
+use crate::rate_limiter::middleware::{IpKeyExtractor, TrustedClientIp};
 use crate::webhook::integration::{BatchingConfig, WebhookConfig, WebhookIntegration};
 use crate::ws_handler::handle_ws_upgrade;
 use tower_http::cors::{AllowOrigin, CorsLayer};
@@ -99,7 +114,11 @@ use crate::cache::memory_cache_manager::MemoryCacheManager; // Import for fallba
 // MetricsInterface trait
 use crate::cleanup::multi_worker::MultiWorkerCleanupSystem;
 use crate::metrics::MetricsInterface;
-use crate::middleware::pusher_api_auth_middleware;
+use crate::middleware::{
+    admin_auth_middleware, partial_results_middleware, pusher_api_auth_middleware,
+};
+use crate::mtls::ClientCertAcceptor;
+use crate::tls::TlsReloader;
 use crate::websocket::WebSocketRef;
 
 #[cfg(unix)]
@@ -251,6 +270,82 @@ fn rewrite_request_uri(req: Request<axum::body::Body>) -> Request<axum::body::Bo
 struct Args {
     #[arg(short, long)]
     config: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Encrypt app secrets stored in plaintext or under an older master key, then exit
+    ReencryptSecrets {
+        /// Only list the apps that would be re-encrypted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Apply pending schema migrations of the app store, then exit
+    Migrate {
+        /// Only list the migrations that would be applied
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Bring the app store schema up to date (`migrate` command)
+async fn migrate_app_store(config: &ServerOptions, dry_run: bool) -> Result<()> {
+    // The command reports what it does, so don't apply anything while opening the store
+    let mut app_manager_config = config.app_manager.clone();
+    app_manager_config.migrations.run_on_startup = false;
+    let store = AppManagerFactory::create_store(
+        &app_manager_config,
+        &config.database,
+        &config.database_pooling,
+        false,
+    )
+    .await?;
+
+    let report = store.migrate(dry_run).await?;
+    if report.is_empty() {
+        info!(
+            "The {:?} app manager has no schema to migrate",
+            config.app_manager.driver
+        );
+    }
+    for status in &report {
+        info!("{}", status);
+    }
+    Ok(())
+}
+
+/// Rewrite stored app secrets with the primary master key (`reencrypt-secrets` command)
+async fn reencrypt_app_secrets(config: &ServerOptions, dry_run: bool) -> Result<()> {
+    if !config.app_manager.secret_encryption.enabled {
+        return Err(Error::Config(
+            "app_manager.secret_encryption must be enabled to re-encrypt secrets".to_string(),
+        ));
+    }
+    let cipher = SecretCipher::from_config(&config.app_manager.secret_encryption)?;
+    let store = AppManagerFactory::create_store(
+        &config.app_manager,
+        &config.database,
+        &config.database_pooling,
+        false,
+    )
+    .await?;
+    store.init().await?;
+
+    let manager = EncryptedAppManager::new(store, Arc::new(cipher));
+    let affected = manager.reencrypt_all(dry_run).await?;
+    if dry_run {
+        info!(
+            "{} apps would be re-encrypted: {}",
+            affected.len(),
+            affected.join(", ")
+        );
+    } else {
+        info!("Re-encrypted secrets of {} apps", affected.len());
+    }
+    Ok(())
 }
 
 impl SockudoServer {
@@ -304,7 +399,12 @@ impl SockudoServer {
             config.app_manager.driver, config.app_manager.cache.enabled
         );
 
-        let connection_manager = AdapterFactory::create(&config.adapter, &config.database).await?;
+        let connection_manager = AdapterFactory::create(
+            &config.adapter,
+            &config.database,
+            &config.instance.process_id,
+        )
+        .await?;
 
         info!(
             "Adapter initialized with driver: {:?}",
@@ -414,6 +514,29 @@ impl SockudoServer {
                     }
                 }
             }
+            QueueDriver::Nats => {
+                let mut nats_queue_config = config.queue.nats.clone();
+                if nats_queue_config.servers.is_empty() {
+                    // Reuse the adapter's NATS connection settings
+                    nats_queue_config.servers = config.adapter.nats.servers.clone();
+                    nats_queue_config.username = config.adapter.nats.username.clone();
+                    nats_queue_config.password = config.adapter.nats.password.clone();
+                    nats_queue_config.token = config.adapter.nats.token.clone();
+                }
+                match QueueManagerFactory::create_nats(nats_queue_config).await {
+                    Ok(queue_driver_impl) => {
+                        info!("Queue manager initialized with NATS driver");
+                        Some(Arc::new(QueueManager::new(queue_driver_impl)))
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Failed to initialize NATS queue manager: {}, queues will be disabled",
+                            e
+                        );
+                        None
+                    }
+                }
+            }
             QueueDriver::None => {
                 info!("Queue driver set to None, queue manager will be disabled.");
                 None
@@ -598,7 +721,23 @@ impl SockudoServer {
             cleanup_config,
         };
 
-        let handler = Arc::new(ConnectionHandler::new(
+        let usage_store = UsageStoreFactory::create(&config.quotas, &config.database.redis)
+            .await
+            .unwrap_or_else(|e| {
+                error!(
+                    "Failed to initialize quota usage store: {}. Counting usage in memory.",
+                    e
+                );
+                Arc::new(MemoryUsageStore::new())
+            });
+        let quota_manager = Arc::new(QuotaManager::new(usage_store, state.metrics.clone()));
+        tokio::spawn(
+            quota_manager
+                .clone()
+                .sample_connections(state.app_manager.clone(), state.connection_manager.clone()),
+        );
+
+        let mut handler = ConnectionHandler::new(
             state.app_manager.clone(),
             state.connection_manager.clone(),
             state.cache_manager.clone(),
@@ -606,7 +745,64 @@ impl SockudoServer {
             Some(webhook_integration), // Pass the (potentially disabled) webhook_integration
             config.clone(),
             state.cleanup_queue.clone(),
-        ));
+        )
+        .with_quota_manager(quota_manager);
+        let audit_log = match audit::sink::create(
+            &config.audit,
+            &config.queue.driver,
+            state.queue_manager.clone(),
+        )
+        .await?
+        {
+            Some(sink) => {
+                info!(
+                    "Audit log enabled with the {:?} driver",
+                    config.audit.driver
+                );
+                Some(Arc::new(AuditLog::new(
+                    &config.audit,
+                    config.instance.process_id.clone(),
+                    sink,
+                )))
+            }
+            None => None,
+        };
+        if let Some(audit_log) = &audit_log {
+            handler = handler.with_audit_log(audit_log.clone());
+        }
+
+        if config.bans.enabled {
+            if let Err(e) = ban::validate_config(&config.bans) {
+                error!("Invalid bans configuration: {}", e);
+                return Err(Error::Configuration(e));
+            }
+            // Violations are counted with the rate limiter's backend, so with Redis they
+            // add up across nodes
+            let violations_config = RateLimiterConfig {
+                enabled: true,
+                api_rate_limit: RateLimit {
+                    max_requests: config.bans.max_violations,
+                    window_seconds: config.bans.window_seconds,
+                    ..config.rate_limiter.api_rate_limit.clone()
+                },
+                ..config.rate_limiter.clone()
+            };
+            let violations =
+                RateLimiterFactory::create(&violations_config, &config.database.redis).await?;
+            let store = BanStoreFactory::create(&config.bans, &config.database.redis).await?;
+            let mut ban_manager = BanManager::new(config.bans.clone(), violations, store);
+            if let Some(audit_log) = &audit_log {
+                ban_manager = ban_manager.with_audit_log(audit_log.clone());
+            }
+            info!(
+                "Temporary bans enabled: {} violations within {}s ban for {}s",
+                config.bans.max_violations,
+                config.bans.window_seconds,
+                config.bans.duration_seconds
+            );
+            handler = handler.with_ban_manager(Arc::new(ban_manager));
+        }
+        let handler = Arc::new(handler);
 
         // Start dead node cleanup event processing loop (only runs if cluster health is enabled)
         if let Some(mut event_receiver) = dead_node_event_receiver {
@@ -622,6 +818,24 @@ impl SockudoServer {
             });
         }
 
+        // Apply app invalidations sent by other nodes
+        if config.app_manager.invalidation.cluster {
+            let invalidation_receiver = state
+                .connection_manager
+                .lock()
+                .await
+                .configure_app_invalidation_events()
+                .await;
+            if let Some(mut invalidation_receiver) = invalidation_receiver {
+                let handler_clone = handler.clone();
+                tokio::spawn(async move {
+                    while let Some(app_id) = invalidation_receiver.recv().await {
+                        handler_clone.handle_app_invalidation(&app_id).await;
+                    }
+                });
+            }
+        }
+
         // Set metrics for adapters
         if let Some(metrics_instance_arc) = &metrics {
             let mut connection_manager_guard = state.connection_manager.lock().await;
@@ -700,6 +914,9 @@ impl SockudoServer {
         info!("Server init sequence started.");
         // Initialize AppManager first as other components might depend on it
         self.state.app_manager.init().await?; // Assuming AppManager has an init method
+        if let Some(changes) = self.state.app_manager.subscribe_changes() {
+            self.handler.spawn_app_change_listener(changes);
+        }
 
         // Initialize ConnectionManager (Adapter)
         {
@@ -873,6 +1090,12 @@ impl SockudoServer {
                 if let Some(ref metrics) = self.state.metrics {
                     rate_limit_layer = rate_limit_layer.with_metrics(metrics.clone());
                 }
+                if let Some(ban_manager) = self.handler.ban_manager() {
+                    rate_limit_layer = rate_limit_layer.with_ban_manager(
+                        ban_manager.clone(),
+                        TrustedClientIp::from_config(&self.config.rate_limiter.api_rate_limit),
+                    );
+                }
 
                 Some(rate_limit_layer)
             } else {
@@ -940,6 +1163,8 @@ impl SockudoServer {
             .route("/usage", get(usage))
             .route("/up", get(up)) // General health check
             .route("/up/{appId}", get(up)) // App-specific health check
+            .merge(self.configure_admin_routes(false))
+            .layer(axum_middleware::from_fn(partial_results_middleware))
             .layer(DefaultBodyLimit::max(body_limit_bytes))
             .layer(cors); // Apply CORS layer
 
@@ -951,9 +1176,36 @@ impl SockudoServer {
         router.with_state(self.handler.clone()) // Pass the handler state to all routes
     }
 
+    /// Admin routes are only mounted when enabled, on whichever port `admin_api.on_metrics_port` selects.
+    fn configure_admin_routes(&self, metrics_port: bool) -> Router<Arc<ConnectionHandler>> {
+        let admin_config = &self.config.admin_api;
+        if !admin_config.enabled || admin_config.on_metrics_port != metrics_port {
+            return Router::new();
+        }
+
+        Router::new()
+            .route("/admin/cluster", get(cluster_topology))
+            .route("/admin/apps", get(admin_list_apps).post(admin_create_app))
+            .route(
+                "/admin/apps/{appId}",
+                get(admin_get_app)
+                    .patch(admin_update_app)
+                    .delete(admin_delete_app),
+            )
+            .route("/admin/apps/{appId}/secret", post(admin_rotate_app_secret))
+            .route("/admin/apps/{appId}/usage", get(admin_get_app_usage))
+            .route("/admin/bans", get(admin_list_bans).delete(admin_lift_ban))
+            .route_layer(axum_middleware::from_fn_with_state(
+                self.handler.clone(),
+                admin_auth_middleware,
+            ))
+    }
+
     fn configure_metrics_routes(&self) -> Router {
         Router::new()
             .route("/metrics", get(metrics))
+            .merge(drain_routes(self.handler.clone()))
+            .merge(self.configure_admin_routes(true))
             .with_state(self.handler.clone()) // Metrics endpoint also needs the handler for state
     }
 
@@ -985,6 +1237,31 @@ impl SockudoServer {
                     );
                 }
             }
+        } else if self.config.admin_api.enabled && self.config.admin_api.on_metrics_port {
+            warn!(
+                "Admin API is configured on the metrics port, but metrics are disabled. Admin API will not be available."
+            );
+        }
+
+        // SIGUSR1 puts the node into drain mode without stopping the process
+        #[cfg(unix)]
+        {
+            let handler = self.handler.clone();
+            tokio::spawn(async move {
+                let mut drain_signal =
+                    match signal::unix::signal(signal::unix::SignalKind::user_defined1()) {
+                        Ok(sig) => sig,
+                        Err(e) => {
+                            warn!("Failed to install SIGUSR1 drain handler: {}", e);
+                            return;
+                        }
+                    };
+                while drain_signal.recv().await.is_some() {
+                    if handler.spawn_drain() {
+                        info!("SIGUSR1 received, draining node");
+                    }
+                }
+            });
         }
 
         let http_router = self.configure_http_routes();
@@ -1119,7 +1396,29 @@ impl SockudoServer {
             && !self.config.ssl.key_path.is_empty()
         {
             info!("SSL is enabled, starting HTTPS server");
-            let tls_config = self.load_tls_config().await?;
+            let mut tls_reloader = self.tls_reloader()?;
+            let tls_config = tls_reloader.load(self.config.ssl.client_auth.required)?;
+
+            // Listener for backend services that always requires a client certificate
+            if let Some(internal_port) = self.config.ssl.client_auth.internal_port {
+                let internal_config = tls_reloader.load(true)?;
+                let internal_addr = SocketAddr::from((http_addr.ip(), internal_port));
+                let internal_router = router_with_middleware_ssl.clone();
+                info!(
+                    "Internal HTTPS server listening on https://{} (client certificates required)",
+                    internal_addr
+                );
+                tokio::spawn(async move {
+                    let acceptor = ClientCertAcceptor::new(RustlsAcceptor::new(internal_config));
+                    if let Err(e) = axum_server::bind(internal_addr)
+                        .acceptor(acceptor)
+                        .serve(internal_router.into_make_service_with_connect_info::<SocketAddr>())
+                        .await
+                    {
+                        error!("Internal HTTPS server error: {}", e);
+                    }
+                });
+            }
 
             // HTTP to HTTPS redirect server
             if self.config.ssl.redirect_http {
@@ -1166,10 +1465,14 @@ impl SockudoServer {
                 }
             }
 
+            // Renewed certificates are picked up by new handshakes without a restart
+            tls_reloader.spawn()?;
+
             // Main HTTPS server
             info!("HTTPS server listening on https://{}", http_addr);
             let running = &self.state.running;
-            let server = axum_server::bind_rustls(http_addr, tls_config);
+            let server = axum_server::bind(http_addr)
+                .acceptor(ClientCertAcceptor::new(RustlsAcceptor::new(tls_config)));
 
             tokio::select! {
                 result = server.serve(router_with_middleware_ssl.into_make_service_with_connect_info::<SocketAddr>()) => {
@@ -1211,7 +1514,7 @@ impl SockudoServer {
         Ok(())
     }
 
-    async fn load_tls_config(&self) -> Result<RustlsConfig> {
+    fn tls_reloader(&self) -> Result<TlsReloader> {
         let cert_path = std::path::PathBuf::from(&self.config.ssl.cert_path);
         let key_path = std::path::PathBuf::from(&self.config.ssl.key_path);
         if !cert_path.exists() {
@@ -1224,9 +1527,7 @@ impl SockudoServer {
                 "SSL key_path not found: {key_path:?}"
             )));
         }
-        RustlsConfig::from_pem_file(cert_path, key_path)
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to load TLS configuration: {e}")))
+        Ok(TlsReloader::new(self.config.ssl.clone()))
     }
 
     async fn shutdown_signal(&self) {
@@ -1258,6 +1559,12 @@ impl SockudoServer {
         info!("Stopping server...");
         self.state.running.store(false, Ordering::SeqCst); // Signal other tasks to stop
 
+        // Spread disconnects over the drain window instead of dropping everyone at once
+        if self.config.drain.on_shutdown && self.handler.begin_drain() {
+            let window = Duration::from_secs(self.config.drain.window_seconds);
+            self.handler.drain_connections(window).await;
+        }
+
         let mut connections_to_cleanup: Vec<(String, WebSocketRef)> = Vec::new();
 
         // --- Step 1: Collect all connection identifiers ---
@@ -1593,6 +1900,14 @@ async fn main() -> Result<()> {
             .unwrap_or("None".to_string())
     );
 
+    match args.command {
+        Some(Command::ReencryptSecrets { dry_run }) => {
+            return reencrypt_app_secrets(&config, dry_run).await;
+        }
+        Some(Command::Migrate { dry_run }) => return migrate_app_store(&config, dry_run).await,
+        None => {}
+    }
+
     // --- Rest of the application logic ---
     info!("Starting Sockudo server initialization process with resolved configuration...");
 
@@ -56,6 +56,12 @@ pub trait MetricsInterface: Send + Sync {
     /// Update the count of active channels
     fn update_active_channels(&self, app_id: &str, channel_type: &str, count: i64);
 
+    /// Update today's consumption of an app quota
+    fn update_quota_usage(&self, app_id: &str, quota: &str, used: u64);
+
+    /// Track a connection or API request rejected by the IP filter of an app
+    fn mark_ip_rejected(&self, app_id: &str, access: &str);
+
     /// Handle a new API message event being received and sent out
     fn mark_api_message(
         &self,
@@ -54,6 +54,8 @@ pub struct PrometheusMetricsDriver {
     channel_subscriptions_total: CounterVec,
     channel_unsubscriptions_total: CounterVec,
     active_channels: GaugeVec,
+    quota_usage: GaugeVec,
+    ip_rejections_total: CounterVec,
     broadcast_latency_ms: HistogramVec,
 }
 
@@ -262,6 +264,24 @@ impl PrometheusMetricsDriver {
         )
         .unwrap();
 
+        let quota_usage = register_gauge_vec!(
+            Opts::new(
+                format!("{prefix}quota_usage"),
+                "Usage of daily app quotas since midnight UTC"
+            ),
+            &["app_id", "port", "quota"]
+        )
+        .unwrap();
+
+        let ip_rejections_total = register_counter_vec!(
+            Opts::new(
+                format!("{prefix}ip_rejections_total"),
+                "Connections and API requests rejected by app IP filters"
+            ),
+            &["app_id", "port", "access"]
+        )
+        .unwrap();
+
         let broadcast_latency_ms = register_histogram_vec!(
             histogram_opts!(
                 format!("{prefix}broadcast_latency_ms"),
@@ -301,6 +321,8 @@ impl PrometheusMetricsDriver {
             channel_subscriptions_total,
             channel_unsubscriptions_total,
             active_channels,
+            quota_usage,
+            ip_rejections_total,
             broadcast_latency_ms,
         }
     }
@@ -452,6 +474,20 @@ impl MetricsInterface for PrometheusMetricsDriver {
         );
     }
 
+    fn update_quota_usage(&self, app_id: &str, quota: &str, used: u64) {
+        let tags = vec![app_id.to_string(), self.port.to_string(), quota.to_string()];
+        self.quota_usage.with_label_values(&tags).set(used as f64);
+    }
+
+    fn mark_ip_rejected(&self, app_id: &str, access: &str) {
+        let tags = vec![
+            app_id.to_string(),
+            self.port.to_string(),
+            access.to_string(),
+        ];
+        self.ip_rejections_total.with_label_values(&tags).inc();
+    }
+
     fn mark_api_message(
         &self,
         app_id: &str,
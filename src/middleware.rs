@@ -1,15 +1,26 @@
 use crate::adapter::ConnectionHandler;
+use crate::adapter::horizontal_adapter::collect_partial_results;
 use crate::app::auth::AuthValidator;
+use crate::app::config::App;
+use crate::audit::{AuditEvent, AuditEventKind};
+use crate::ban::BanTarget;
 use crate::http_handler::{AppError, EventQuery};
+use crate::mtls::{self, ClientCertificate};
+use crate::rate_limiter::middleware::UntrustedClientAddress;
+use crate::token::secure_compare;
 use axum::{
-    body::Body,     // HttpBody and collect are important for body handling
-    extract::State, // Using axum::extract::Request for the whole request
-    http::Request as HttpRequest,
+    body::Body,             // HttpBody and collect are important for body handling
+    extract::{Path, State}, // Using axum::extract::Request for the whole request
+    http::{HeaderValue, Request as HttpRequest},
     middleware::Next,
     response::Response,
 };
 use http_body_util::BodyExt;
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+    sync::Arc,
+};
 
 // Helper to extract query parameters for the signature
 fn get_params_for_signature(
@@ -39,6 +50,49 @@ fn get_params_for_signature(
     Ok(params_map)
 }
 
+/// Reject requests from addresses outside the HTTP API rules of the app's IP filter, and
+/// requests to filtered apps whose address cannot be resolved through the trusted proxies
+async fn check_ip_filter(
+    handler_state: &ConnectionHandler,
+    app: &App,
+    client_ip: &Result<Option<IpAddr>, UntrustedClientAddress>,
+) -> Result<(), AppError> {
+    let Some(ref ip_filter) = app.ip_filter else {
+        return Ok(());
+    };
+    if ip_filter.http_api.is_empty() {
+        return Ok(());
+    }
+    let client_ip = client_ip.clone()?;
+    if ip_filter.http_api.allows_client(client_ip) {
+        return Ok(());
+    }
+    tracing::warn!(
+        "Rejecting API request for app {} from {:?}",
+        app.id,
+        client_ip
+    );
+    if let Some(ref metrics) = handler_state.metrics {
+        metrics.lock().await.mark_ip_rejected(&app.id, "http_api");
+    }
+    Err(AppError::Forbidden("IP address not allowed".to_string()))
+}
+
+/// Count a failed API signature towards a ban of the caller's address in the app. Unknown
+/// keys have no app to scope the ban to and are left to the API rate limit.
+async fn escalate_auth_failure(
+    handler_state: &ConnectionHandler,
+    app: Option<&App>,
+    client_ip: Option<IpAddr>,
+) {
+    if let (Some(ban_manager), Some(app), Some(ip)) = (handler_state.ban_manager(), app, client_ip)
+    {
+        ban_manager
+            .record_violation(&BanTarget::app_ip(&app.id, ip), "auth_failure")
+            .await;
+    }
+}
+
 /// Axum middleware for Pusher API authentication.
 ///
 /// This middleware authenticates incoming requests based on the Pusher protocol,
@@ -46,10 +100,38 @@ fn get_params_for_signature(
 /// It requires the `ConnectionHandler` state to access the `AppManager` for app details.
 pub async fn pusher_api_auth_middleware(
     State(handler_state): State<Arc<ConnectionHandler>>, // Access to AppManager via ConnectionHandler
-    request: HttpRequest<Body>,                          // The incoming HTTP request
-    next: Next, // The next middleware or handler in the chain
+    Path(path_params): Path<HashMap<String, String>>,
+    request: HttpRequest<Body>, // The incoming HTTP request
+    next: Next,                 // The next middleware or handler in the chain
 ) -> Result<Response, AppError> {
     tracing::debug!("Entering Pusher API Auth Middleware");
+    let client_ip = handler_state.trusted_client_ip(request.headers(), request.extensions());
+    // Bans and audit events only use an address the client could not forge
+    let trusted_ip = client_ip.as_ref().ok().copied().flatten();
+
+    // Backend services may authenticate with a client certificate instead of signing
+    let certificate = request
+        .extensions()
+        .get::<Option<ClientCertificate>>()
+        .cloned()
+        .flatten();
+    if let Some(app_id) = path_params.get("appId")
+        && let Some(certificate) = certificate
+        && mtls::allows_app(
+            &handler_state.server_options().ssl.client_auth.identities,
+            &certificate,
+            app_id,
+        )
+        && let Some(app) = handler_state.app_manager().find_by_id(app_id).await?
+    {
+        check_ip_filter(&handler_state, &app, &client_ip).await?;
+        tracing::debug!(
+            "Client certificate {:?} authenticated API request for app {}",
+            certificate.subject,
+            app_id
+        );
+        return Ok(next.run(request).await);
+    }
 
     let uri = request.uri().clone();
     let query_str_option = uri.query();
@@ -74,6 +156,32 @@ pub async fn pusher_api_auth_middleware(
         ));
     };
 
+    // Reject addresses outside the app's IP filter before doing any signature work
+    let app = handler_state
+        .app_manager()
+        .find_by_key(&auth_q_params_struct.auth_key)
+        .await?;
+    if let Some(ref app) = app {
+        check_ip_filter(&handler_state, app, &client_ip).await?;
+    }
+    handler_state
+        .check_ip_ban(app.as_ref().map(|app| app.id.as_str()), trusted_ip)
+        .await
+        .map_err(|e| AppError::Forbidden(e.to_string()))?;
+    let audit_failure = |reason: &str| {
+        handler_state.audit(
+            AuditEvent::new(
+                AuditEventKind::ApiAuthFailed,
+                app.as_ref().map(|app| app.id.as_str()),
+            )
+            .ip(trusted_ip)
+            .detail("auth_key", auth_q_params_struct.auth_key.as_str())
+            .detail("method", method.as_str())
+            .detail("path", path.as_str())
+            .detail("reason", reason),
+        );
+    };
+
     // 2. Collect all query parameters (excluding auth_signature) for the signature string.
     let all_query_params_for_sig_map = get_params_for_signature(query_str_option)?;
 
@@ -93,7 +201,8 @@ pub async fn pusher_api_auth_middleware(
     tracing::debug!("Request body buffered, {} bytes", body_bytes.len());
 
     // 4. Perform the authentication using AuthValidator.
-    let auth_validator = AuthValidator::new(handler_state.app_manager().clone());
+    let auth_validator = AuthValidator::new(handler_state.app_manager().clone())
+        .with_replay_cache(handler_state.cache_manager.clone());
 
     // `validate_pusher_api_request` should return `Result<bool, AppError>` or `Result<(), AppError>`
     // If it returns `Result<bool, AppError>`:
@@ -119,6 +228,8 @@ pub async fn pusher_api_auth_middleware(
                 "Pusher API authentication failed (validator returned false) for path: {}",
                 path
             );
+            audit_failure("Invalid API signature");
+            escalate_auth_failure(&handler_state, app.as_ref(), trusted_ip).await;
             Err(AppError::ApiAuthFailed("Invalid API signature".to_string()))
         }
         Err(e) => {
@@ -128,11 +239,87 @@ pub async fn pusher_api_auth_middleware(
                 path,
                 e
             );
+            audit_failure(&e.to_string());
+            escalate_auth_failure(&handler_state, app.as_ref(), trusted_ip).await;
             Err(e.into())
         }
     }
 }
 
+/// Axum middleware for the admin API.
+///
+/// Requires an `Authorization: Bearer <token>` header matching `admin_api.token`.
+/// The admin API uses its own credential instead of per-app Pusher signatures because
+/// its routes are not scoped to a single app.
+pub async fn admin_auth_middleware(
+    State(handler_state): State<Arc<ConnectionHandler>>,
+    request: HttpRequest<Body>,
+    next: Next,
+) -> Result<Response, AppError> {
+    let expected_token = handler_state
+        .server_options()
+        .admin_api
+        .token
+        .as_deref()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| AppError::ApiAuthFailed("Admin API token is not configured".to_string()))?;
+
+    let provided_token = request
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    let reason = match provided_token {
+        Some(token) if secure_compare(token, expected_token) => {
+            return Ok(next.run(request).await);
+        }
+        Some(_) => {
+            tracing::warn!("Admin API request rejected: invalid bearer token");
+            "Invalid admin token"
+        }
+        None => "Missing admin bearer token",
+    };
+    handler_state.audit(
+        AuditEvent::new(AuditEventKind::AdminAuthFailed, None)
+            .ip(handler_state
+                .trusted_client_ip(request.headers(), request.extensions())
+                .ok()
+                .flatten())
+            .detail("method", request.method().as_str())
+            .detail("path", request.uri().path())
+            .detail("reason", reason),
+    );
+    Err(AppError::ApiAuthFailed(reason.to_string()))
+}
+
+/// Axum middleware that flags responses built from an incomplete cluster view.
+///
+/// When an aggregation request made while handling the request returns before every node
+/// answered (quorum reached or timeout), `X-Partial-Results: true` is set and
+/// `X-Missing-Nodes` lists the nodes that did not answer, when they are known.
+pub async fn partial_results_middleware(request: HttpRequest<Body>, next: Next) -> Response {
+    let (mut response, partial_results) = collect_partial_results(next.run(request)).await;
+
+    if partial_results.partial {
+        let headers = response.headers_mut();
+        headers.insert("X-Partial-Results", HeaderValue::from_static("true"));
+        let missing_nodes = partial_results
+            .missing_nodes
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join(",");
+        if !missing_nodes.is_empty()
+            && let Ok(value) = HeaderValue::from_str(&missing_nodes)
+        {
+            headers.insert("X-Missing-Nodes", value);
+        }
+    }
+
+    response
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
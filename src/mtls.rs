@@ -0,0 +1,175 @@
+// src/mtls.rs
+use crate::options::ClientCertIdentity;
+use axum::Extension;
+use axum::middleware::AddExtension;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::RustlsAcceptor;
+use futures_util::future::BoxFuture;
+use std::io;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower::Layer;
+use x509_parser::oid_registry::{
+    OID_X509_COMMON_NAME, OID_X509_COUNTRY_NAME, OID_X509_LOCALITY_NAME,
+    OID_X509_ORGANIZATION_NAME, OID_X509_ORGANIZATIONAL_UNIT, OID_X509_STATE_OR_PROVINCE_NAME, Oid,
+};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// Verified client certificate of the TLS connection a request came in on
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    /// Subject attributes in certificate order, e.g. `[("O", "Acme"), ("CN", "billing")]`
+    pub subject: Arc<[(String, String)]>,
+}
+
+/// TLS acceptor that hands the client certificate of each connection to its requests as
+/// an `Option<ClientCertificate>` extension
+#[derive(Clone)]
+pub struct ClientCertAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientCertAcceptor {
+    pub fn new(inner: RustlsAcceptor) -> Self {
+        Self { inner }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = <RustlsAcceptor as Accept<I, S>>::Stream;
+    type Service = AddExtension<S, Option<ClientCertificate>>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            // Only certificates that passed the verifier are presented here
+            let certificate = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|chain| chain.first())
+                .and_then(|cert| certificate_subject(cert))
+                .map(|subject| ClientCertificate {
+                    subject: subject.into(),
+                });
+            Ok((stream, Extension(certificate).layer(service)))
+        })
+    }
+}
+
+/// Whether one of the configured identities matching the certificate covers the app
+pub fn allows_app(
+    identities: &[ClientCertIdentity],
+    certificate: &ClientCertificate,
+    app_id: &str,
+) -> bool {
+    identities.iter().any(|identity| {
+        subject_matches(&identity.subject, &certificate.subject)
+            && identity
+                .app_ids
+                .iter()
+                .any(|allowed| allowed == "*" || allowed == app_id)
+    })
+}
+
+/// Every `key=value` pair of the pattern must be in the subject; attribute names are
+/// case-insensitive
+fn subject_matches(pattern: &str, subject: &[(String, String)]) -> bool {
+    let required: Vec<&str> = pattern
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    !required.is_empty()
+        && required.iter().all(|part| {
+            let Some((key, value)) = part.split_once('=') else {
+                return false;
+            };
+            subject
+                .iter()
+                .any(|(k, v)| k.eq_ignore_ascii_case(key.trim()) && v == value.trim())
+        })
+}
+
+/// Subject attributes kept from client certificates, with the names used in identities
+static SUBJECT_ATTRIBUTES: [(Oid<'static>, &str); 6] = [
+    (OID_X509_COMMON_NAME, "CN"),
+    (OID_X509_COUNTRY_NAME, "C"),
+    (OID_X509_LOCALITY_NAME, "L"),
+    (OID_X509_STATE_OR_PROVINCE_NAME, "ST"),
+    (OID_X509_ORGANIZATION_NAME, "O"),
+    (OID_X509_ORGANIZATIONAL_UNIT, "OU"),
+];
+
+/// Common subject attributes of a DER certificate; others are left out
+pub fn certificate_subject(der: &[u8]) -> Option<Vec<(String, String)>> {
+    let (_, certificate) = X509Certificate::from_der(der).ok()?;
+    let subject = certificate
+        .subject()
+        .iter_attributes()
+        .filter_map(|attribute| {
+            let (_, key) = SUBJECT_ATTRIBUTES
+                .iter()
+                .find(|(oid, _)| oid == attribute.attr_type())?;
+            let value = attribute.as_str().ok()?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect();
+    Some(subject)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::tests::CERT;
+    use rustls::pki_types::CertificateDer;
+    use rustls::pki_types::pem::PemObject;
+
+    fn identity(subject: &str, app_ids: &[&str]) -> ClientCertIdentity {
+        ClientCertIdentity {
+            subject: subject.to_string(),
+            app_ids: app_ids.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_subject_identities_map_to_apps() {
+        let der = CertificateDer::from_pem_slice(CERT.as_bytes()).unwrap();
+        let subject = certificate_subject(&der).unwrap();
+        assert_eq!(
+            subject,
+            vec![
+                ("C".to_string(), "US".to_string()),
+                ("O".to_string(), "Acme".to_string()),
+                ("CN".to_string(), "billing-service".to_string()),
+            ]
+        );
+
+        let certificate = ClientCertificate {
+            subject: subject.into(),
+        };
+        let identities = [
+            identity("CN=billing-service, o=Acme", &["billing"]),
+            identity("CN=ops", &["*"]),
+        ];
+        assert!(allows_app(&identities, &certificate, "billing"));
+        assert!(!allows_app(&identities, &certificate, "other"));
+        assert!(!allows_app(
+            &[identity("CN=billing-service,O=Other", &["*"])],
+            &certificate,
+            "billing"
+        ));
+        assert!(!allows_app(
+            &[identity("", &["*"])],
+            &certificate,
+            "billing"
+        ));
+    }
+}
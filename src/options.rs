@@ -134,6 +134,23 @@ impl Default for ScyllaDbSettings {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SqliteSettings {
+    /// Database file, created if missing. `:memory:` keeps everything in memory.
+    pub path: String,
+    pub table_name: String,
+}
+
+impl Default for SqliteSettings {
+    fn default() -> Self {
+        Self {
+            path: "sockudo.db".to_string(),
+            table_name: "applications".to_string(),
+        }
+    }
+}
+
 impl FromStr for AdapterDriver {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -147,6 +164,27 @@ impl FromStr for AdapterDriver {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisTransportMode {
+    /// Fire-and-forget pub/sub (messages are lost while a node is disconnected)
+    #[default]
+    PubSub,
+    /// Broadcasts are appended to a capped stream and replayed after reconnects
+    Streams,
+}
+
+impl FromStr for RedisTransportMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pubsub" | "pub-sub" => Ok(RedisTransportMode::PubSub),
+            "streams" | "stream" => Ok(RedisTransportMode::Streams),
+            _ => Err(format!("Unknown Redis transport mode: {s}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum AppManagerDriver {
@@ -156,6 +194,8 @@ pub enum AppManagerDriver {
     Dynamodb,
     PgSql,
     ScyllaDb,
+    File,
+    Sqlite,
 }
 impl FromStr for AppManagerDriver {
     type Err = String;
@@ -166,6 +206,8 @@ impl FromStr for AppManagerDriver {
             "dynamodb" => Ok(AppManagerDriver::Dynamodb),
             "pgsql" | "postgres" | "postgresql" => Ok(AppManagerDriver::PgSql),
             "scylladb" | "scylla" => Ok(AppManagerDriver::ScyllaDb),
+            "file" => Ok(AppManagerDriver::File),
+            "sqlite" => Ok(AppManagerDriver::Sqlite),
             _ => Err(format!("Unknown app manager driver: {s}")),
         }
     }
@@ -204,6 +246,7 @@ pub enum QueueDriver {
     #[serde(rename = "redis-cluster")] // Add this variant
     RedisCluster,
     Sqs,
+    Nats,
     None,
 }
 
@@ -215,6 +258,7 @@ impl FromStr for QueueDriver {
             "redis" => Ok(QueueDriver::Redis),
             "redis-cluster" => Ok(QueueDriver::RedisCluster), // Add this case
             "sqs" => Ok(QueueDriver::Sqs),
+            "nats" => Ok(QueueDriver::Nats),
             "none" => Ok(QueueDriver::None),
             _ => Err(format!("Unknown queue driver: {s}")),
         }
@@ -229,11 +273,35 @@ impl AsRef<str> for QueueDriver {
             QueueDriver::Redis => "redis",
             QueueDriver::RedisCluster => "redis-cluster", // Add this case
             QueueDriver::Sqs => "sqs",
+            QueueDriver::Nats => "nats",
             QueueDriver::None => "none",
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditDriver {
+    #[default]
+    None,
+    File,
+    Stdout,
+    Queue,
+}
+
+impl FromStr for AuditDriver {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(AuditDriver::None),
+            "file" => Ok(AuditDriver::File),
+            "stdout" => Ok(AuditDriver::Stdout),
+            "queue" => Ok(AuditDriver::Queue),
+            _ => Err(format!("Unknown audit driver: {s}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RedisClusterQueueConfig {
@@ -304,13 +372,17 @@ impl AsRef<str> for MetricsDriver {
 #[serde(default)]
 pub struct ServerOptions {
     pub adapter: AdapterConfig,
+    pub admin_api: AdminApiConfig,
     pub app_manager: AppManagerConfig,
+    pub audit: AuditConfig,
+    pub bans: BanConfig,
     pub cache: CacheConfig,
     pub channel_limits: ChannelLimits,
     pub cors: CorsConfig,
     pub database: DatabaseConfig,
     pub database_pooling: DatabasePooling,
     pub debug: bool,
+    pub drain: DrainConfig,
     pub event_limits: EventLimits,
     pub host: String,
     pub http_api: HttpApiConfig,
@@ -322,10 +394,13 @@ pub struct ServerOptions {
     pub path_prefix: String,
     pub presence: PresenceConfig,
     pub queue: QueueConfig,
+    pub quotas: QuotaConfig,
     pub rate_limiter: RateLimiterConfig,
     pub shutdown_grace_period: u64,
     pub ssl: SslConfig,
     pub user_authentication_timeout: u64,
+    /// Seconds before an expiring channel or user authorization lapses that the socket is warned
+    pub auth_expiry_warning: u64,
     pub webhooks: WebhooksConfig,
     pub websocket_max_payload_kb: u32,
     pub cleanup: crate::cleanup::CleanupConfig,
@@ -350,6 +425,16 @@ pub struct SqsQueueConfig {
     pub message_group_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AdminApiConfig {
+    pub enabled: bool,
+    /// Bearer token required on every admin request
+    pub token: Option<String>,
+    /// Serve the admin routes on the metrics port instead of the main port
+    pub on_metrics_port: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AdapterConfig {
@@ -360,6 +445,19 @@ pub struct AdapterConfig {
     #[serde(default = "default_buffer_multiplier_per_cpu")]
     pub buffer_multiplier_per_cpu: usize,
     pub cluster_health: ClusterHealthConfig,
+    pub requests: ClusterRequestConfig,
+}
+
+/// How cluster aggregation requests (channel lists, presence members, socket counts) wait for remote nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClusterRequestConfig {
+    /// Fraction of remote nodes that must answer before a request returns (0.0 < ratio <= 1.0)
+    pub quorum_ratio: f64,
+    /// Nodes whose average response time exceeds this are flagged as slow (0 disables tracking)
+    pub slow_node_threshold_ms: u64,
+    /// Stop waiting for nodes flagged as slow; their answers are still used if they arrive in time
+    pub skip_slow_nodes: bool,
 }
 
 fn default_buffer_multiplier_per_cpu() -> usize {
@@ -375,6 +473,17 @@ impl Default for AdapterConfig {
             nats: NatsAdapterConfig::default(),
             buffer_multiplier_per_cpu: default_buffer_multiplier_per_cpu(),
             cluster_health: ClusterHealthConfig::default(),
+            requests: ClusterRequestConfig::default(),
+        }
+    }
+}
+
+impl Default for ClusterRequestConfig {
+    fn default() -> Self {
+        Self {
+            quorum_ratio: 1.0,
+            slow_node_threshold_ms: 1000,
+            skip_slow_nodes: false,
         }
     }
 }
@@ -387,6 +496,9 @@ pub struct RedisAdapterConfig {
     pub redis_pub_options: HashMap<String, serde_json::Value>,
     pub redis_sub_options: HashMap<String, serde_json::Value>,
     pub cluster_mode: bool,
+    pub transport_mode: RedisTransportMode,
+    /// Approximate number of broadcasts kept in the stream (streams mode only)
+    pub stream_max_len: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -409,6 +521,33 @@ pub struct NatsAdapterConfig {
     pub token: Option<String>,
     pub connection_timeout_ms: u64,
     pub nodes_number: Option<u32>,
+    /// Publish broadcasts through a JetStream stream so nodes replay what they missed
+    pub jetstream: bool,
+    pub stream_max_messages: i64,
+    pub stream_max_age_seconds: u64,
+    /// Names this node's JetStream broadcast consumer, so a restarted node with the same ID
+    /// resumes where it stopped. Set from `instance.process_id`.
+    #[serde(skip)]
+    pub process_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NatsQueueConfig {
+    /// Falls back to the NATS adapter servers and credentials when empty
+    pub servers: Vec<String>,
+    pub prefix: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub token: Option<String>,
+    pub connection_timeout_ms: u64,
+    pub concurrency: u32,
+    /// Time a worker has to ack a job before JetStream redelivers it
+    pub ack_wait_seconds: u64,
+    /// Deliveries attempted before a job is given up on
+    pub max_deliver: i64,
+    /// Delay before a failed (nak'd) job is redelivered
+    pub nak_delay_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -416,7 +555,11 @@ pub struct NatsAdapterConfig {
 pub struct AppManagerConfig {
     pub driver: AppManagerDriver,
     pub array: ArrayConfig,
+    pub file: FileAppManagerConfig,
     pub cache: CacheSettings,
+    pub invalidation: AppInvalidationConfig,
+    pub secret_encryption: SecretEncryptionConfig,
+    pub migrations: AppMigrationsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -425,6 +568,52 @@ pub struct ArrayConfig {
     pub apps: Vec<App>,
 }
 
+/// Apps loaded from a JSON, YAML or TOML file, or from every such file in a directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileAppManagerConfig {
+    pub path: String,
+    /// Reload when the file or directory changes
+    pub watch: bool,
+    /// Wait this long after the last change before reloading, so editors finish writing
+    pub debounce_ms: u64,
+}
+
+/// How app changes made elsewhere reach this node's app cache and connected clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppInvalidationConfig {
+    /// Tell other nodes over the horizontal adapter when an app is changed through this node
+    pub cluster: bool,
+    /// Listen for PostgreSQL NOTIFY events sent by the trigger the app store migrations
+    /// install on the apps table
+    pub postgres_notify: bool,
+    /// Poll the MySQL apps table's `updated_at` column at this interval; 0 disables polling
+    pub mysql_poll_interval_ms: u64,
+}
+
+/// Schema migrations of the SQL and ScyllaDB app stores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppMigrationsConfig {
+    /// Apply pending migrations when the server starts; when disabled they are only
+    /// applied by the `migrate` command
+    pub run_on_startup: bool,
+}
+
+/// Envelope encryption of app secrets in the app store
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SecretEncryptionConfig {
+    pub enabled: bool,
+    /// File with one `<key id>:<64 hex characters>` master key per line
+    pub keys_file: Option<String>,
+    /// Master keys in the same format, separated by commas
+    pub keys: Option<String>,
+    /// Key that encrypts new secrets; defaults to the first key given
+    pub primary_key_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CacheSettings {
@@ -477,6 +666,7 @@ pub struct CorsConfig {
 pub struct DatabaseConfig {
     pub mysql: DatabaseConnection,
     pub postgres: DatabaseConnection,
+    pub sqlite: SqliteSettings,
     pub redis: RedisConnection,
     pub dynamodb: DynamoDbSettings,
     pub scylladb: ScyllaDbSettings,
@@ -706,6 +896,78 @@ pub struct DatabasePooling {
     pub max: u32,
 }
 
+/// Where audit events (authentication failures, forced disconnects, app changes) are
+/// written as JSON lines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    pub driver: AuditDriver,
+    /// File appended to by the `file` driver
+    pub path: String,
+    /// Size in megabytes at which the file is rotated; 0 never rotates
+    pub max_file_size_mb: u64,
+    /// Rotated files kept as `<path>.1` (newest) to `<path>.<max_files>`
+    pub max_files: u32,
+    /// Queue the `queue` driver pushes to, through the configured queue driver
+    pub queue_name: String,
+    /// Only record events of these apps; empty records every app. Events not tied to an
+    /// app, such as admin token failures, are always recorded.
+    pub app_ids: Vec<String>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            driver: AuditDriver::None,
+            path: "audit.log".to_string(),
+            max_file_size_mb: 100,
+            max_files: 5,
+            queue_name: "audit".to_string(),
+            app_ids: Vec::new(),
+        }
+    }
+}
+
+/// Temporary bans of clients that keep hitting rate limits or failing authentication.
+/// Violations are counted with the `rate_limiter` driver. Bans are kept with `driver`;
+/// with `memory` each node bans on its own, use Redis to share bans across a cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BanConfig {
+    pub enabled: bool,
+    /// Violations within `window_seconds` that get a client banned
+    pub max_violations: u32,
+    pub window_seconds: u64,
+    /// How long a ban lasts
+    pub duration_seconds: u64,
+    pub driver: CacheDriver,
+    pub redis: RedisConfig,
+}
+
+impl Default for BanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_violations: 20,
+            window_seconds: 60,
+            duration_seconds: 300,
+            driver: CacheDriver::Memory,
+            redis: RedisConfig {
+                prefix: Some("sockudo_ban:".to_string()),
+                url_override: None,
+                cluster_mode: false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrainConfig {
+    pub window_seconds: u64, // Spread drain disconnects evenly over this window
+    pub on_shutdown: bool, // Drain gradually on SIGTERM/Ctrl+C instead of closing everything at once
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct EventLimits {
@@ -771,6 +1033,7 @@ pub struct QueueConfig {
     pub redis: RedisQueueConfig,
     pub redis_cluster: RedisClusterQueueConfig, // Add this field
     pub sqs: SqsQueueConfig,
+    pub nats: NatsQueueConfig,
 }
 
 // Updated RedisQueueConfig for type safety
@@ -790,6 +1053,32 @@ pub struct RateLimit {
     pub window_seconds: u64,
     pub identifier: Option<String>,
     pub trust_hops: Option<u32>,
+    /// Let IP filters and bans read the client address from `X-Real-IP`. Only enable
+    /// behind a proxy that overwrites the header on every request; ignored when
+    /// `trust_hops` is set. Rate limiting reads the header either way.
+    pub trust_x_real_ip: Option<bool>,
+}
+
+/// Where the daily usage counters of apps with quotas are kept. With `memory` each node
+/// counts on its own; use Redis to enforce quotas across a cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaConfig {
+    pub driver: CacheDriver,
+    pub redis: RedisConfig,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            driver: CacheDriver::Memory,
+            redis: RedisConfig {
+                prefix: Some("sockudo_quota:".to_string()),
+                url_override: None,
+                cluster_mode: false,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -812,6 +1101,31 @@ pub struct SslConfig {
     pub ca_path: Option<String>,
     pub redirect_http: bool,
     pub http_port: Option<u16>,
+    /// Reload the certificate when its files change; SIGHUP always reloads it
+    pub watch: bool,
+    pub client_auth: ClientAuthConfig,
+}
+
+/// Client certificate (mutual TLS) authentication of HTTPS connections
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ClientAuthConfig {
+    /// CA bundle client certificates are verified against; mTLS is off without it
+    pub ca_path: Option<String>,
+    /// Refuse connections without a client certificate on the main listener
+    pub required: bool,
+    /// Port of an extra HTTPS listener that always requires a client certificate
+    pub internal_port: Option<u16>,
+    /// Certificate subjects allowed to call the HTTP API of apps without signing
+    pub identities: Vec<ClientCertIdentity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ClientCertIdentity {
+    /// Attributes the certificate subject must have, e.g. `CN=billing,O=Acme`
+    pub subject: String,
+    /// Apps the certificate may publish to and query; `*` allows all of them
+    pub app_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -851,13 +1165,17 @@ impl Default for ServerOptions {
     fn default() -> Self {
         Self {
             adapter: AdapterConfig::default(),
+            admin_api: AdminApiConfig::default(),
             app_manager: AppManagerConfig::default(),
+            audit: AuditConfig::default(),
+            bans: BanConfig::default(),
             cache: CacheConfig::default(),
             channel_limits: ChannelLimits::default(),
             cors: CorsConfig::default(),
             database: DatabaseConfig::default(),
             database_pooling: DatabasePooling::default(),
             debug: false,
+            drain: DrainConfig::default(),
             event_limits: EventLimits::default(),
             host: "0.0.0.0".to_string(),
             http_api: HttpApiConfig::default(),
@@ -869,10 +1187,12 @@ impl Default for ServerOptions {
             path_prefix: "/".to_string(),
             presence: PresenceConfig::default(),
             queue: QueueConfig::default(),
+            quotas: QuotaConfig::default(),
             rate_limiter: RateLimiterConfig::default(),
             shutdown_grace_period: 10,
             ssl: SslConfig::default(),
             user_authentication_timeout: 3600,
+            auth_expiry_warning: 60,
             webhooks: WebhooksConfig::default(),
             websocket_max_payload_kb: 64,
             cleanup: crate::cleanup::CleanupConfig::default(),
@@ -907,6 +1227,8 @@ impl Default for RedisAdapterConfig {
             redis_pub_options: HashMap::new(),
             redis_sub_options: HashMap::new(),
             cluster_mode: false,
+            transport_mode: RedisTransportMode::default(),
+            stream_max_len: 10_000,
         }
     }
 }
@@ -939,6 +1261,27 @@ impl Default for NatsAdapterConfig {
             token: None,
             connection_timeout_ms: 5000,
             nodes_number: None,
+            jetstream: false,
+            stream_max_messages: 10_000,
+            stream_max_age_seconds: 300,
+            process_id: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+impl Default for NatsQueueConfig {
+    fn default() -> Self {
+        Self {
+            servers: Vec::new(),
+            prefix: "sockudo_queue".to_string(),
+            username: None,
+            password: None,
+            token: None,
+            connection_timeout_ms: 5000,
+            concurrency: 5,
+            ack_wait_seconds: 30,
+            max_deliver: 5,
+            nak_delay_ms: 1000,
         }
     }
 }
@@ -1054,6 +1397,34 @@ impl Default for ClusterNode {
     }
 }
 
+impl Default for FileAppManagerConfig {
+    fn default() -> Self {
+        Self {
+            path: "config/apps.json".to_string(),
+            watch: true,
+            debounce_ms: 500,
+        }
+    }
+}
+
+impl Default for AppInvalidationConfig {
+    fn default() -> Self {
+        Self {
+            cluster: true,
+            postgres_notify: false,
+            mysql_poll_interval_ms: 0,
+        }
+    }
+}
+
+impl Default for AppMigrationsConfig {
+    fn default() -> Self {
+        Self {
+            run_on_startup: true,
+        }
+    }
+}
+
 impl Default for DatabasePooling {
     fn default() -> Self {
         Self {
@@ -1064,6 +1435,15 @@ impl Default for DatabasePooling {
     }
 }
 
+impl Default for DrainConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: 30,
+            on_shutdown: false,
+        }
+    }
+}
+
 impl Default for EventLimits {
     fn default() -> Self {
         Self {
@@ -1156,6 +1536,7 @@ impl Default for RateLimit {
             window_seconds: 60,
             identifier: Some("default".to_string()),
             trust_hops: Some(0),
+            trust_x_real_ip: Some(false),
         }
     }
 }
@@ -1170,12 +1551,14 @@ impl Default for RateLimiterConfig {
                 window_seconds: 60,
                 identifier: Some("api".to_string()),
                 trust_hops: Some(0),
+                trust_x_real_ip: Some(false),
             },
             websocket_rate_limit: RateLimit {
                 max_requests: 20,
                 window_seconds: 60,
                 identifier: Some("websocket_connect".to_string()),
                 trust_hops: Some(0),
+                trust_x_real_ip: Some(false),
             },
             redis: RedisConfig {
                 // Specific Redis settings if Redis is chosen as backend for rate limiting
@@ -1197,6 +1580,8 @@ impl Default for SslConfig {
             ca_path: None,
             redirect_http: false,
             http_port: Some(80),
+            watch: true,
+            client_auth: ClientAuthConfig::default(),
         }
     }
 }
@@ -1295,10 +1680,21 @@ impl ServerOptions {
         self.port = parse_env::<u16>("PORT", self.port);
         self.shutdown_grace_period =
             parse_env::<u64>("SHUTDOWN_GRACE_PERIOD", self.shutdown_grace_period);
+        self.drain.window_seconds =
+            parse_env::<u64>("DRAIN_WINDOW_SECONDS", self.drain.window_seconds);
+        self.drain.on_shutdown = parse_bool_env("DRAIN_ON_SHUTDOWN", self.drain.on_shutdown);
+        self.admin_api.enabled = parse_bool_env("ADMIN_API_ENABLED", self.admin_api.enabled);
+        if let Ok(token) = std::env::var("ADMIN_API_TOKEN") {
+            self.admin_api.token = Some(token);
+        }
+        self.admin_api.on_metrics_port =
+            parse_bool_env("ADMIN_API_ON_METRICS_PORT", self.admin_api.on_metrics_port);
         self.user_authentication_timeout = parse_env::<u64>(
             "USER_AUTHENTICATION_TIMEOUT",
             self.user_authentication_timeout,
         );
+        self.auth_expiry_warning =
+            parse_env::<u64>("AUTH_EXPIRY_WARNING", self.auth_expiry_warning);
         self.websocket_max_payload_kb =
             parse_env::<u32>("WEBSOCKET_MAX_PAYLOAD_KB", self.websocket_max_payload_kb);
         if let Ok(id) = std::env::var("INSTANCE_PROCESS_ID") {
@@ -1314,6 +1710,18 @@ impl ServerOptions {
             "ADAPTER_BUFFER_MULTIPLIER_PER_CPU",
             self.adapter.buffer_multiplier_per_cpu,
         );
+        self.adapter.requests.quorum_ratio = parse_env::<f64>(
+            "ADAPTER_REQUEST_QUORUM_RATIO",
+            self.adapter.requests.quorum_ratio,
+        );
+        self.adapter.requests.slow_node_threshold_ms = parse_env::<u64>(
+            "ADAPTER_SLOW_NODE_THRESHOLD_MS",
+            self.adapter.requests.slow_node_threshold_ms,
+        );
+        self.adapter.requests.skip_slow_nodes = parse_bool_env(
+            "ADAPTER_SKIP_SLOW_NODES",
+            self.adapter.requests.skip_slow_nodes,
+        );
         if let Ok(driver_str) = std::env::var("CACHE_DRIVER") {
             self.cache.driver = parse_driver_enum(driver_str, self.cache.driver.clone(), "Cache");
         }
@@ -1324,6 +1732,36 @@ impl ServerOptions {
             self.app_manager.driver =
                 parse_driver_enum(driver_str, self.app_manager.driver.clone(), "AppManager");
         }
+        if let Ok(driver_str) = std::env::var("QUOTAS_DRIVER") {
+            self.quotas.driver =
+                parse_driver_enum(driver_str, self.quotas.driver.clone(), "Quota usage store");
+        }
+        if let Ok(driver_str) = std::env::var("BANS_DRIVER") {
+            self.bans.driver = parse_driver_enum(driver_str, self.bans.driver.clone(), "Ban store");
+        }
+        if let Ok(driver_str) = std::env::var("AUDIT_DRIVER") {
+            self.audit.driver = parse_driver_enum(driver_str, self.audit.driver.clone(), "Audit");
+        }
+        if let Ok(path) = std::env::var("AUDIT_PATH") {
+            self.audit.path = path;
+        }
+        if let Ok(app_ids) = std::env::var("AUDIT_APP_IDS") {
+            self.audit.app_ids = app_ids
+                .split(',')
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect();
+        }
+        self.bans.enabled = parse_bool_env("BANS_ENABLED", self.bans.enabled);
+        if let Some(max_violations) = parse_env_optional::<u32>("BANS_MAX_VIOLATIONS") {
+            self.bans.max_violations = max_violations;
+        }
+        if let Some(window) = parse_env_optional::<u64>("BANS_WINDOW_SECONDS") {
+            self.bans.window_seconds = window;
+        }
+        if let Some(duration) = parse_env_optional::<u64>("BANS_DURATION_SECONDS") {
+            self.bans.duration_seconds = duration;
+        }
         if let Ok(driver_str) = std::env::var("RATE_LIMITER_DRIVER") {
             self.rate_limiter.driver = parse_driver_enum(
                 driver_str,
@@ -1383,6 +1821,14 @@ impl ServerOptions {
         }
         override_db_pool_settings(&mut self.database.postgres, "DATABASE_POSTGRES");
 
+        // --- Database: SQLite ---
+        if let Ok(path) = std::env::var("DATABASE_SQLITE_PATH") {
+            self.database.sqlite.path = path;
+        }
+        if let Ok(table) = std::env::var("DATABASE_SQLITE_TABLE_NAME") {
+            self.database.sqlite.table_name = table;
+        }
+
         // --- Database: DynamoDB ---
         if let Ok(region) = std::env::var("DATABASE_DYNAMODB_REGION") {
             self.database.dynamodb.region = region;
@@ -1426,6 +1872,15 @@ impl ServerOptions {
         if let Some(port) = parse_env_optional::<u16>("SSL_HTTP_PORT") {
             self.ssl.http_port = Some(port);
         }
+        self.ssl.watch = parse_bool_env("SSL_WATCH", self.ssl.watch);
+        if let Ok(val) = std::env::var("SSL_CLIENT_CA_PATH") {
+            self.ssl.client_auth.ca_path = Some(val);
+        }
+        self.ssl.client_auth.required =
+            parse_bool_env("SSL_CLIENT_CERT_REQUIRED", self.ssl.client_auth.required);
+        if let Some(port) = parse_env_optional::<u16>("SSL_INTERNAL_PORT") {
+            self.ssl.client_auth.internal_port = Some(port);
+        }
 
         // --- Unix Socket Configuration ---
         self.unix_socket.enabled = parse_bool_env("UNIX_SOCKET_ENABLED", self.unix_socket.enabled);
@@ -1486,6 +1941,13 @@ impl ServerOptions {
         if let Some(hops) = parse_env_optional::<u32>("RATE_LIMITER_API_TRUST_HOPS") {
             self.rate_limiter.api_rate_limit.trust_hops = Some(hops);
         }
+        self.rate_limiter.api_rate_limit.trust_x_real_ip = Some(parse_bool_env(
+            "RATE_LIMITER_API_TRUST_X_REAL_IP",
+            self.rate_limiter
+                .api_rate_limit
+                .trust_x_real_ip
+                .unwrap_or(false),
+        ));
         self.rate_limiter.websocket_rate_limit.max_requests = parse_env::<u32>(
             "RATE_LIMITER_WS_MAX_REQUESTS",
             self.rate_limiter.websocket_rate_limit.max_requests,
@@ -1497,6 +1959,9 @@ impl ServerOptions {
         if let Ok(prefix) = std::env::var("RATE_LIMITER_REDIS_PREFIX") {
             self.rate_limiter.redis.prefix = Some(prefix);
         }
+        if let Ok(prefix) = std::env::var("QUOTAS_REDIS_PREFIX") {
+            self.quotas.redis.prefix = Some(prefix);
+        }
 
         // --- Queue: Redis ---
         self.queue.redis.concurrency =
@@ -1526,12 +1991,43 @@ impl ServerOptions {
             self.queue.sqs.endpoint_url = Some(endpoint);
         }
 
+        // --- Queue: NATS ---
+        if let Ok(servers) = std::env::var("QUEUE_NATS_SERVERS") {
+            self.queue.nats.servers = servers.split(',').map(|s| s.trim().to_string()).collect();
+        }
+        if let Ok(prefix) = std::env::var("QUEUE_NATS_PREFIX") {
+            self.queue.nats.prefix = prefix;
+        }
+        self.queue.nats.concurrency =
+            parse_env::<u32>("QUEUE_NATS_CONCURRENCY", self.queue.nats.concurrency);
+        self.queue.nats.ack_wait_seconds = parse_env::<u64>(
+            "QUEUE_NATS_ACK_WAIT_SECONDS",
+            self.queue.nats.ack_wait_seconds,
+        );
+        self.queue.nats.max_deliver =
+            parse_env::<i64>("QUEUE_NATS_MAX_DELIVER", self.queue.nats.max_deliver);
+        self.queue.nats.nak_delay_ms =
+            parse_env::<u64>("QUEUE_NATS_NAK_DELAY_MS", self.queue.nats.nak_delay_ms);
+
         // --- Webhooks ---
         self.webhooks.batching.enabled =
             parse_bool_env("WEBHOOK_BATCHING_ENABLED", self.webhooks.batching.enabled);
         self.webhooks.batching.duration =
             parse_env::<u64>("WEBHOOK_BATCHING_DURATION", self.webhooks.batching.duration);
 
+        // --- Redis Adapter ---
+        if let Ok(mode) = std::env::var("REDIS_ADAPTER_TRANSPORT_MODE") {
+            self.adapter.redis.transport_mode = parse_driver_enum(
+                mode,
+                self.adapter.redis.transport_mode,
+                "Redis adapter transport mode",
+            );
+        }
+        self.adapter.redis.stream_max_len = parse_env::<usize>(
+            "REDIS_ADAPTER_STREAM_MAX_LEN",
+            self.adapter.redis.stream_max_len,
+        );
+
         // --- NATS Adapter ---
         if let Ok(servers) = std::env::var("NATS_SERVERS") {
             self.adapter.nats.servers = servers.split(',').map(|s| s.trim().to_string()).collect();
@@ -1553,6 +2049,15 @@ impl ServerOptions {
             "NATS_REQUEST_TIMEOUT_MS",
             self.adapter.nats.request_timeout_ms,
         );
+        self.adapter.nats.jetstream = parse_bool_env("NATS_JETSTREAM", self.adapter.nats.jetstream);
+        self.adapter.nats.stream_max_messages = parse_env::<i64>(
+            "NATS_STREAM_MAX_MESSAGES",
+            self.adapter.nats.stream_max_messages,
+        );
+        self.adapter.nats.stream_max_age_seconds = parse_env::<u64>(
+            "NATS_STREAM_MAX_AGE_SECONDS",
+            self.adapter.nats.stream_max_age_seconds,
+        );
 
         // --- CORS ---
         if let Ok(origins) = std::env::var("CORS_ORIGINS") {
@@ -1581,6 +2086,40 @@ impl ServerOptions {
             self.database.mysql.connection_pool_size = pool_size;
             self.database.postgres.connection_pool_size = pool_size;
         }
+        if let Ok(path) = std::env::var("APP_MANAGER_FILE_PATH") {
+            self.app_manager.file.path = path;
+        }
+        self.app_manager.file.watch =
+            parse_bool_env("APP_MANAGER_FILE_WATCH", self.app_manager.file.watch);
+        self.app_manager.invalidation.cluster = parse_bool_env(
+            "APP_MANAGER_INVALIDATION_CLUSTER",
+            self.app_manager.invalidation.cluster,
+        );
+        self.app_manager.invalidation.postgres_notify = parse_bool_env(
+            "APP_MANAGER_INVALIDATION_POSTGRES_NOTIFY",
+            self.app_manager.invalidation.postgres_notify,
+        );
+        self.app_manager.invalidation.mysql_poll_interval_ms = parse_env::<u64>(
+            "APP_MANAGER_INVALIDATION_MYSQL_POLL_MS",
+            self.app_manager.invalidation.mysql_poll_interval_ms,
+        );
+        self.app_manager.migrations.run_on_startup = parse_bool_env(
+            "APP_MANAGER_MIGRATIONS_RUN_ON_STARTUP",
+            self.app_manager.migrations.run_on_startup,
+        );
+        self.app_manager.secret_encryption.enabled = parse_bool_env(
+            "APP_MANAGER_SECRET_ENCRYPTION_ENABLED",
+            self.app_manager.secret_encryption.enabled,
+        );
+        if let Ok(path) = std::env::var("APP_MANAGER_SECRET_KEYS_FILE") {
+            self.app_manager.secret_encryption.keys_file = Some(path);
+        }
+        if let Ok(keys) = std::env::var("APP_MANAGER_SECRET_KEYS") {
+            self.app_manager.secret_encryption.keys = Some(keys);
+        }
+        if let Ok(key_id) = std::env::var("APP_MANAGER_SECRET_PRIMARY_KEY_ID") {
+            self.app_manager.secret_encryption.primary_key_id = Some(key_id);
+        }
         self.app_manager.cache.enabled =
             parse_bool_env("APP_MANAGER_CACHE_ENABLED", self.app_manager.cache.enabled);
         self.app_manager.cache.ttl =
@@ -1678,6 +2217,14 @@ impl ServerOptions {
                         None
                     }
                 },
+                credentials: None,
+                channel_policy: None,
+                channel_limits: None,
+                quotas: None,
+                jwt_auth: None,
+                api_auth: None,
+                ip_filter: None,
+                authorizer: None,
             };
 
             self.app_manager.array.apps.push(default_app);
@@ -1801,6 +2348,23 @@ impl ServerOptions {
             }
         }
 
+        if self.admin_api.enabled
+            && self
+                .admin_api
+                .token
+                .as_deref()
+                .is_none_or(|token| token.trim().is_empty())
+        {
+            return Err("admin_api.token must be set when the admin API is enabled".to_string());
+        }
+
+        let quorum_ratio = self.adapter.requests.quorum_ratio;
+        if !(quorum_ratio > 0.0 && quorum_ratio <= 1.0) {
+            return Err(format!(
+                "adapter.requests.quorum_ratio ({quorum_ratio}) must be greater than 0 and at most 1"
+            ));
+        }
+
         // Validate cleanup configuration if present
         if let Err(e) = self.cleanup.validate() {
             return Err(format!("Invalid cleanup configuration: {}", e));
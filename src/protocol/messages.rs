@@ -325,6 +325,20 @@ impl PusherMessage {
         }
     }
 
+    /// Warns that the authorization of `channel`, or of the signed-in user without a
+    /// channel, lapses at `expires_at` unless it is renewed
+    pub fn auth_expiring(channel: Option<String>, expires_at: u64) -> Self {
+        Self {
+            event: Some("pusher:auth_expiring".to_string()),
+            data: Some(MessageData::Json(json!({
+                "expires_at": expires_at
+            }))),
+            channel,
+            name: None,
+            user_id: None,
+        }
+    }
+
     pub fn signin_success(user_data: String) -> Self {
         Self {
             event: Some("pusher:signin_success".to_string()),
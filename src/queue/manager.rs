@@ -3,10 +3,14 @@
 
 use crate::error::Result;
 
+#[cfg(feature = "nats")]
+use crate::options::NatsQueueConfig;
 #[cfg(feature = "sqs")]
 use crate::options::SqsQueueConfig;
 use crate::queue::QueueInterface;
 use crate::queue::memory_queue_manager::MemoryQueueManager;
+#[cfg(feature = "nats")]
+use crate::queue::nats_queue_manager::NatsQueueManager;
 #[cfg(feature = "redis-cluster")]
 use crate::queue::redis_cluster_queue_manager::RedisClusterQueueManager;
 #[cfg(feature = "redis")]
@@ -124,6 +128,28 @@ impl QueueManagerFactory {
         manager.start_processing();
         Ok(Box::new(manager))
     }
+
+    #[cfg(feature = "nats")]
+    pub async fn create_nats(config: NatsQueueConfig) -> Result<Box<dyn QueueInterface>> {
+        info!(
+            "Creating NATS queue manager (Prefix: {}, Concurrency: {}, Max deliver: {})",
+            config.prefix, config.concurrency, config.max_deliver
+        );
+        debug!("NATS queue manager servers: {:?}", config.servers);
+        let manager = NatsQueueManager::new(config).await?;
+        Ok(Box::new(manager))
+    }
+
+    #[cfg(not(feature = "nats"))]
+    #[allow(unused_variables)]
+    pub async fn create_nats(
+        config: crate::options::NatsQueueConfig,
+    ) -> Result<Box<dyn QueueInterface>> {
+        warn!("NATS queue manager requested but not compiled in. Falling back to memory queue.");
+        let manager = MemoryQueueManager::new();
+        manager.start_processing();
+        Ok(Box::new(manager))
+    }
 }
 
 pub struct QueueManager {
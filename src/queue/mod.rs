@@ -10,6 +10,8 @@ use std::sync::Arc;
 
 pub mod manager;
 pub mod memory_queue_manager;
+#[cfg(feature = "nats")]
+pub mod nats_queue_manager;
 #[cfg(feature = "redis-cluster")]
 pub mod redis_cluster_queue_manager;
 #[cfg(feature = "redis")]
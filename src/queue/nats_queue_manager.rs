@@ -0,0 +1,224 @@
+use crate::adapter::transports::nats_transport::jetstream_name;
+use crate::error::{Error, Result};
+use crate::options::NatsQueueConfig;
+use crate::queue::{ArcJobProcessorFn, QueueInterface};
+use crate::webhook::sender::JobProcessorFnAsync;
+use crate::webhook::types::JobData;
+use async_nats::jetstream::{self, AckKind, consumer, stream};
+use async_nats::{Client as NatsClient, ConnectOptions as NatsOptions};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// JetStream-backed implementation of the QueueInterface.
+///
+/// Jobs are stored in a work-queue stream and pulled through one durable consumer per
+/// queue, shared by every node. Failed jobs are nak'd for redelivery until `max_deliver`
+/// attempts have been made, after which they are terminated.
+pub struct NatsQueueManager {
+    client: NatsClient,
+    jetstream: jetstream::Context,
+    stream: stream::Stream,
+    config: NatsQueueConfig,
+}
+
+impl NatsQueueManager {
+    /// Connects to NATS and makes sure the job stream exists.
+    pub async fn new(config: NatsQueueConfig) -> Result<Self> {
+        let mut nats_options = NatsOptions::new();
+        if let (Some(username), Some(password)) =
+            (config.username.as_deref(), config.password.as_deref())
+        {
+            nats_options =
+                nats_options.user_and_password(username.to_string(), password.to_string());
+        } else if let Some(token) = config.token.as_deref() {
+            nats_options = nats_options.token(token.to_string());
+        }
+        nats_options =
+            nats_options.connection_timeout(Duration::from_millis(config.connection_timeout_ms));
+
+        let client = nats_options
+            .connect(&config.servers)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect to NATS: {e}")))?;
+
+        let jetstream = jetstream::new(client.clone());
+        let stream_name = jetstream_name(&format!("{}_jobs", config.prefix));
+        let stream = jetstream
+            .get_or_create_stream(stream::Config {
+                name: stream_name.clone(),
+                subjects: vec![format!("{}.>", config.prefix)],
+                retention: stream::RetentionPolicy::WorkQueue,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| Error::Queue(format!("Failed to create JetStream job stream: {e}")))?;
+
+        info!(
+            "NATS queue manager using JetStream stream {} (max_deliver: {})",
+            stream_name, config.max_deliver
+        );
+
+        Ok(Self {
+            client,
+            jetstream,
+            stream,
+            config,
+        })
+    }
+
+    fn format_subject(&self, queue_name: &str) -> String {
+        format!("{}.{}", self.config.prefix, queue_name)
+    }
+}
+
+#[async_trait]
+impl QueueInterface for NatsQueueManager {
+    /// Publishes a job and waits for JetStream to acknowledge that it was stored.
+    async fn add_to_queue(&self, queue_name: &str, data: JobData) -> Result<()> {
+        let payload = serde_json::to_vec(&data)?;
+
+        self.jetstream
+            .publish(self.format_subject(queue_name), payload.into())
+            .await
+            .map_err(|e| Error::Queue(format!("NATS publish failed for queue {queue_name}: {e}")))?
+            .await
+            .map_err(|e| {
+                Error::Queue(format!(
+                    "NATS publish not acknowledged for queue {queue_name}: {e}"
+                ))
+            })?;
+
+        Ok(())
+    }
+
+    /// Creates (or attaches to) the durable consumer for a queue and starts worker tasks.
+    async fn process_queue(&self, queue_name: &str, callback: JobProcessorFnAsync) -> Result<()> {
+        let durable_name = jetstream_name(queue_name);
+        let consumer: consumer::PullConsumer = self
+            .stream
+            .get_or_create_consumer(
+                &durable_name,
+                consumer::pull::Config {
+                    durable_name: Some(durable_name.clone()),
+                    filter_subject: self.format_subject(queue_name),
+                    ack_policy: consumer::AckPolicy::Explicit,
+                    ack_wait: Duration::from_secs(self.config.ack_wait_seconds),
+                    max_deliver: self.config.max_deliver,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| {
+                Error::Queue(format!(
+                    "Failed to create NATS consumer for queue {queue_name}: {e}"
+                ))
+            })?;
+
+        let processor_arc: ArcJobProcessorFn = Arc::from(callback);
+        debug!(
+            "Registered processor and starting workers for NATS queue: {}",
+            queue_name
+        );
+
+        for i in 0..self.config.concurrency {
+            let worker_consumer = consumer.clone();
+            let worker_processor = processor_arc.clone();
+            let worker_queue_name = queue_name.to_string();
+            let max_deliver = self.config.max_deliver;
+            let nak_delay = Duration::from_millis(self.config.nak_delay_ms);
+
+            tokio::spawn(async move {
+                debug!(
+                    "Starting NATS queue worker {} for queue: {}",
+                    i, worker_queue_name
+                );
+
+                loop {
+                    let mut messages = match worker_consumer.messages().await {
+                        Ok(messages) => messages,
+                        Err(e) => {
+                            error!(
+                                "[Worker {}] Failed to pull from NATS queue {}: {}",
+                                i, worker_queue_name, e
+                            );
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                            continue;
+                        }
+                    };
+
+                    while let Some(message) = messages.next().await {
+                        let message = match message {
+                            Ok(message) => message,
+                            Err(e) => {
+                                warn!(
+                                    "[Worker {}] NATS queue {} stream error: {}",
+                                    i, worker_queue_name, e
+                                );
+                                break;
+                            }
+                        };
+
+                        let delivered = message.info().map(|info| info.delivered).unwrap_or(1);
+                        let ack = match serde_json::from_slice::<JobData>(&message.payload) {
+                            Ok(job_data) => match worker_processor(job_data).await {
+                                Ok(()) => AckKind::Ack,
+                                Err(e) if max_deliver > 0 && delivered >= max_deliver => {
+                                    error!(
+                                        "[Worker {}] Job on queue {} failed after {} deliveries, giving up: {}",
+                                        i, worker_queue_name, delivered, e
+                                    );
+                                    AckKind::Term
+                                }
+                                Err(e) => {
+                                    warn!(
+                                        "[Worker {}] Job on queue {} failed (delivery {}), will retry: {}",
+                                        i, worker_queue_name, delivered, e
+                                    );
+                                    AckKind::Nak(Some(nak_delay))
+                                }
+                            },
+                            Err(e) => {
+                                // Redelivering a payload that cannot be parsed will never succeed
+                                error!(
+                                    "[Worker {}] Error deserializing job data from NATS queue {}: {}",
+                                    i, worker_queue_name, e
+                                );
+                                AckKind::Term
+                            }
+                        };
+
+                        if let Err(e) = message.ack_with(ack).await {
+                            warn!(
+                                "[Worker {}] Failed to ack message on NATS queue {}: {}",
+                                i, worker_queue_name, e
+                            );
+                        }
+                    }
+
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        self.client
+            .drain()
+            .await
+            .map_err(|e| Error::Queue(format!("Failed to drain NATS queue connection: {e}")))
+    }
+
+    async fn check_health(&self) -> Result<()> {
+        match self.client.connection_state() {
+            async_nats::connection::State::Connected => Ok(()),
+            state => Err(Error::Queue(format!(
+                "Queue NATS connection is not healthy: {state:?}"
+            ))),
+        }
+    }
+}
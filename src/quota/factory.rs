@@ -0,0 +1,111 @@
+// src/quota/factory.rs
+use crate::error::Result;
+use crate::options::{CacheDriver, QuotaConfig, RedisConnection};
+use crate::quota::UsageStore;
+use crate::quota::memory_store::MemoryUsageStore;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub struct UsageStoreFactory;
+
+impl UsageStoreFactory {
+    #[cfg_attr(not(feature = "redis"), allow(unused_variables))]
+    pub async fn create(
+        config: &QuotaConfig,
+        global_redis_conn_details: &RedisConnection,
+    ) -> Result<Arc<dyn UsageStore>> {
+        info!(
+            "Initializing quota usage store with driver: {:?}",
+            config.driver
+        );
+
+        match config.driver {
+            #[cfg(feature = "redis")]
+            CacheDriver::Redis => Self::create_redis_store(config, global_redis_conn_details).await,
+            #[cfg(feature = "redis-cluster")]
+            CacheDriver::RedisCluster => {
+                Self::create_redis_cluster_store(config, global_redis_conn_details).await
+            }
+            CacheDriver::Memory | CacheDriver::None => Ok(Arc::new(MemoryUsageStore::new())),
+            #[allow(unreachable_patterns)]
+            _ => {
+                warn!(
+                    "Quota usage store driver {:?} is not compiled in. Falling back to memory.",
+                    config.driver
+                );
+                Ok(Arc::new(MemoryUsageStore::new()))
+            }
+        }
+    }
+
+    #[cfg(feature = "redis")]
+    fn prefix(config: &QuotaConfig, global_redis_conn_details: &RedisConnection) -> String {
+        config
+            .redis
+            .prefix
+            .clone()
+            .unwrap_or_else(|| global_redis_conn_details.key_prefix.clone() + "quota:")
+    }
+
+    #[cfg(feature = "redis")]
+    async fn create_redis_store(
+        config: &QuotaConfig,
+        global_redis_conn_details: &RedisConnection,
+    ) -> Result<Arc<dyn UsageStore>> {
+        use crate::error::Error;
+        use crate::quota::redis_store::RedisUsageStore;
+
+        let redis_url = config.redis.url_override.clone().unwrap_or_else(|| {
+            format!(
+                "redis://{}:{}",
+                global_redis_conn_details.host, global_redis_conn_details.port
+            )
+        });
+        let client = redis::Client::open(redis_url.as_str())
+            .map_err(|e| Error::Redis(format!("Failed to create Redis client for quotas: {e}")))?;
+        let connection = client
+            .get_connection_manager()
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to connect to Redis for quotas: {e}")))?;
+
+        Ok(Arc::new(RedisUsageStore::new(
+            connection,
+            Self::prefix(config, global_redis_conn_details),
+        )))
+    }
+
+    #[cfg(feature = "redis-cluster")]
+    async fn create_redis_cluster_store(
+        config: &QuotaConfig,
+        global_redis_conn_details: &RedisConnection,
+    ) -> Result<Arc<dyn UsageStore>> {
+        use crate::error::Error;
+        use crate::quota::redis_store::RedisUsageStore;
+
+        if global_redis_conn_details.cluster_nodes.is_empty() {
+            return Err(Error::Configuration(
+                "Quotas: Redis cluster nodes not configured.".to_string(),
+            ));
+        }
+        let nodes: Vec<String> = global_redis_conn_details
+            .cluster_nodes
+            .iter()
+            .map(|node| format!("redis://{}:{}", node.host, node.port))
+            .collect();
+        let client = redis::cluster::ClusterClient::new(nodes).map_err(|e| {
+            Error::Redis(format!(
+                "Failed to create Redis cluster client for quotas: {e}"
+            ))
+        })?;
+        let connection = client.get_async_connection().await.map_err(|e| {
+            Error::Redis(format!(
+                "Failed to connect to Redis cluster for quotas: {e}"
+            ))
+        })?;
+
+        Ok(Arc::new(RedisUsageStore::new(
+            connection,
+            Self::prefix(config, global_redis_conn_details),
+        )))
+    }
+}
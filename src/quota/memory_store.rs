@@ -0,0 +1,75 @@
+// src/quota/memory_store.rs
+use super::{QuotaKind, UsageStore};
+use crate::error::Result;
+use async_trait::async_trait;
+use dashmap::DashMap;
+
+/// Usage counters of this node only
+#[derive(Default)]
+pub struct MemoryUsageStore {
+    /// (day, usage) per app and kind; only the current day is kept
+    counters: DashMap<(String, QuotaKind), (u64, u64)>,
+}
+
+impl MemoryUsageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UsageStore for MemoryUsageStore {
+    async fn add(&self, app_id: &str, kind: QuotaKind, day: u64, amount: u64) -> Result<u64> {
+        let mut entry = self
+            .counters
+            .entry((app_id.to_string(), kind))
+            .or_insert((day, 0));
+        let (counter_day, used) = entry.value_mut();
+        if *counter_day != day {
+            *counter_day = day;
+            *used = 0;
+        }
+        *used = used.saturating_add(amount);
+        Ok(*used)
+    }
+
+    async fn get(&self, app_id: &str, kind: QuotaKind, day: u64) -> Result<u64> {
+        Ok(self
+            .counters
+            .get(&(app_id.to_string(), kind))
+            .filter(|entry| entry.0 == day)
+            .map_or(0, |entry| entry.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_counters_start_over_each_day() {
+        let store = MemoryUsageStore::new();
+        assert_eq!(
+            store.add("app", QuotaKind::Messages, 1, 5).await.unwrap(),
+            5
+        );
+        assert_eq!(
+            store.add("app", QuotaKind::Messages, 1, 2).await.unwrap(),
+            7
+        );
+        assert_eq!(store.get("app", QuotaKind::Messages, 1).await.unwrap(), 7);
+
+        assert_eq!(store.get("app", QuotaKind::Messages, 2).await.unwrap(), 0);
+        assert_eq!(
+            store.add("app", QuotaKind::Messages, 2, 1).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .get("app", QuotaKind::ConnectionMinutes, 2)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+}
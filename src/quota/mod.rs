@@ -0,0 +1,387 @@
+// src/quota/mod.rs
+pub mod factory;
+pub mod memory_store;
+#[cfg(feature = "redis")]
+pub mod redis_store;
+
+use crate::adapter::ConnectionManager;
+use crate::app::config::App;
+use crate::app::manager::AppManager;
+use crate::error::{Error, Result};
+use crate::metrics::MetricsInterface;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// How often local connections are sampled into connection minutes
+pub const CONNECTION_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a daily total read from or written to the store is trusted by quota checks.
+/// Usage recorded by other nodes shows up in checks after at most this long.
+const TOTAL_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Daily usage budgets of an app, reset at midnight UTC. Unset budgets are unlimited.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct AppQuotas {
+    /// Messages delivered to connections per day
+    #[serde(default)]
+    pub messages_per_day: Option<u64>,
+    /// Minutes of connection time per day
+    #[serde(default)]
+    pub connection_minutes_per_day: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotaKind {
+    Messages,
+    ConnectionMinutes,
+}
+
+impl QuotaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaKind::Messages => "messages",
+            QuotaKind::ConnectionMinutes => "connection_minutes",
+        }
+    }
+
+    fn limit(&self, quotas: &AppQuotas) -> Option<u64> {
+        match self {
+            QuotaKind::Messages => quotas.messages_per_day,
+            QuotaKind::ConnectionMinutes => quotas.connection_minutes_per_day,
+        }
+    }
+
+    fn exceeded(&self) -> Error {
+        match self {
+            QuotaKind::Messages => Error::MessageQuotaExceeded,
+            QuotaKind::ConnectionMinutes => Error::ConnectionMinutesQuotaExceeded,
+        }
+    }
+}
+
+/// Counters of usage per app, kind and day
+#[async_trait]
+pub trait UsageStore: Send + Sync {
+    /// Add `amount` to the usage of `day` and return the new total
+    async fn add(&self, app_id: &str, kind: QuotaKind, day: u64, amount: u64) -> Result<u64>;
+
+    /// Usage of `day`, 0 if nothing was recorded
+    async fn get(&self, app_id: &str, kind: QuotaKind, day: u64) -> Result<u64>;
+}
+
+/// Consumption of one quota
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub used: u64,
+    pub limit: Option<u64>,
+}
+
+/// Consumption of an app for the current day
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AppUsage {
+    pub app_id: String,
+    /// Unix timestamp at which the counters start over
+    pub resets_at: u64,
+    pub messages: QuotaUsage,
+    pub connection_minutes: QuotaUsage,
+}
+
+/// A daily total as last seen in the store
+#[derive(Clone, Copy)]
+struct CachedTotal {
+    day: u64,
+    used: u64,
+    seen_at: Instant,
+}
+
+/// Tracks usage of apps that have quotas and enforces them
+pub struct QuotaManager {
+    store: Arc<dyn UsageStore>,
+    metrics: Option<Arc<Mutex<dyn MetricsInterface + Send + Sync>>>,
+    /// Latest totals per app and kind, so checks do not hit the store for every message
+    totals: DashMap<(String, QuotaKind), CachedTotal>,
+}
+
+impl QuotaManager {
+    pub fn new(
+        store: Arc<dyn UsageStore>,
+        metrics: Option<Arc<Mutex<dyn MetricsInterface + Send + Sync>>>,
+    ) -> Self {
+        Self {
+            store,
+            metrics,
+            totals: DashMap::new(),
+        }
+    }
+
+    fn remember_total(&self, app_id: &str, kind: QuotaKind, day: u64, used: u64) {
+        self.totals.insert(
+            (app_id.to_string(), kind),
+            CachedTotal {
+                day,
+                used,
+                seen_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Today's total, from the local cache while it is fresh or else from the store
+    async fn current_total(&self, app_id: &str, kind: QuotaKind, day: u64) -> Result<u64> {
+        let cached = self
+            .totals
+            .get(&(app_id.to_string(), kind))
+            .map(|entry| *entry.value())
+            .filter(|total| total.day == day && total.seen_at.elapsed() < TOTAL_CACHE_TTL);
+        if let Some(total) = cached {
+            return Ok(total.used);
+        }
+
+        let used = self.store.get(app_id, kind, day).await?;
+        self.remember_total(app_id, kind, day, used);
+        Ok(used)
+    }
+
+    /// Reject the operation if the app has used up its quota of `kind` for today.
+    /// Errors of the usage store let the operation through.
+    pub async fn check(&self, app: &App, kind: QuotaKind) -> Result<()> {
+        let Some(limit) = app.quotas.as_ref().and_then(|q| kind.limit(q)) else {
+            return Ok(());
+        };
+
+        match self.current_total(&app.id, kind, current_day()).await {
+            Ok(used) if used >= limit => {
+                debug!(
+                    "App {} is over its {} quota ({}/{})",
+                    app.id,
+                    kind.as_str(),
+                    used,
+                    limit
+                );
+                Err(kind.exceeded())
+            }
+            Ok(_) => Ok(()),
+            Err(e) => {
+                warn!(
+                    "Failed to read {} usage of app {}: {}",
+                    kind.as_str(),
+                    app.id,
+                    e
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Count usage for apps that have quotas. Other apps are not tracked.
+    pub async fn record(&self, app: &App, kind: QuotaKind, amount: u64) {
+        if app.quotas.is_none() || amount == 0 {
+            return;
+        }
+
+        let day = current_day();
+        match self.store.add(&app.id, kind, day, amount).await {
+            Ok(used) => {
+                // The store returns the new total, which includes other nodes' usage
+                self.remember_total(&app.id, kind, day, used);
+                if let Some(ref metrics) = self.metrics {
+                    let metrics_locked = metrics.lock().await;
+                    metrics_locked.update_quota_usage(&app.id, kind.as_str(), used);
+                }
+            }
+            Err(e) => warn!(
+                "Failed to record {} usage of app {}: {}",
+                kind.as_str(),
+                app.id,
+                e
+            ),
+        }
+    }
+
+    pub async fn usage(&self, app: &App) -> Result<AppUsage> {
+        let day = current_day();
+        let quotas = app.quotas.unwrap_or_default();
+        Ok(AppUsage {
+            app_id: app.id.clone(),
+            resets_at: (day + 1) * SECONDS_PER_DAY,
+            messages: QuotaUsage {
+                used: self.store.get(&app.id, QuotaKind::Messages, day).await?,
+                limit: quotas.messages_per_day,
+            },
+            connection_minutes: QuotaUsage {
+                used: self
+                    .store
+                    .get(&app.id, QuotaKind::ConnectionMinutes, day)
+                    .await?,
+                limit: quotas.connection_minutes_per_day,
+            },
+        })
+    }
+
+    /// Add one connection minute per local connection of every app with quotas, once a
+    /// minute. Each node samples its own connections, so the counters add up across a
+    /// cluster when they are kept in Redis.
+    pub async fn sample_connections(
+        self: Arc<Self>,
+        app_manager: Arc<dyn AppManager + Send + Sync>,
+        connection_manager: Arc<Mutex<dyn ConnectionManager + Send + Sync>>,
+    ) {
+        let mut interval = tokio::time::interval(CONNECTION_SAMPLE_INTERVAL);
+        // The first tick completes immediately; skip it so a minute has passed
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+
+            let connections: Vec<(String, usize)> = {
+                let mut connection_manager = connection_manager.lock().await;
+                match connection_manager.get_namespaces().await {
+                    Ok(namespaces) => namespaces
+                        .iter()
+                        .map(|ns| (ns.key().clone(), ns.sockets.len()))
+                        .filter(|(_, count)| *count > 0)
+                        .collect(),
+                    Err(e) => {
+                        warn!("Failed to sample connections for quotas: {}", e);
+                        continue;
+                    }
+                }
+            };
+
+            for (app_id, count) in connections {
+                match app_manager.find_by_id(&app_id).await {
+                    Ok(Some(app)) => {
+                        self.record(&app, QuotaKind::ConnectionMinutes, count as u64)
+                            .await
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("Failed to load app {} for quotas: {}", app_id, e),
+                }
+            }
+        }
+    }
+}
+
+/// Days since the unix epoch (UTC)
+fn current_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECONDS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quota::memory_store::MemoryUsageStore;
+
+    fn app(quotas: Option<AppQuotas>) -> App {
+        App {
+            id: "app".to_string(),
+            quotas,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publishes_are_rejected_once_quota_is_used() {
+        let manager = QuotaManager::new(Arc::new(MemoryUsageStore::new()), None);
+        let app = app(Some(AppQuotas {
+            messages_per_day: Some(10),
+            connection_minutes_per_day: None,
+        }));
+
+        manager.record(&app, QuotaKind::Messages, 9).await;
+        assert!(manager.check(&app, QuotaKind::Messages).await.is_ok());
+
+        manager.record(&app, QuotaKind::Messages, 1).await;
+        assert!(matches!(
+            manager.check(&app, QuotaKind::Messages).await,
+            Err(Error::MessageQuotaExceeded)
+        ));
+        assert!(
+            manager
+                .check(&app, QuotaKind::ConnectionMinutes)
+                .await
+                .is_ok()
+        );
+
+        let usage = manager.usage(&app).await.unwrap();
+        assert_eq!(usage.messages.used, 10);
+        assert_eq!(usage.messages.limit, Some(10));
+        assert_eq!(usage.connection_minutes.used, 0);
+    }
+
+    /// Memory store that counts reads
+    #[derive(Default)]
+    struct CountingStore {
+        inner: MemoryUsageStore,
+        reads: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl UsageStore for CountingStore {
+        async fn add(&self, app_id: &str, kind: QuotaKind, day: u64, amount: u64) -> Result<u64> {
+            self.inner.add(app_id, kind, day, amount).await
+        }
+
+        async fn get(&self, app_id: &str, kind: QuotaKind, day: u64) -> Result<u64> {
+            self.reads
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.inner.get(app_id, kind, day).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checks_reuse_recent_totals() {
+        let store = Arc::new(CountingStore::default());
+        let manager = QuotaManager::new(store.clone(), None);
+        let app = app(Some(AppQuotas {
+            messages_per_day: Some(10),
+            connection_minutes_per_day: None,
+        }));
+        let reads = || store.reads.load(std::sync::atomic::Ordering::Relaxed);
+
+        // The first check reads the store, later ones use the cached total
+        for _ in 0..3 {
+            assert!(manager.check(&app, QuotaKind::Messages).await.is_ok());
+        }
+        assert_eq!(reads(), 1);
+
+        // Recording refreshes the cached total with what the store returned
+        manager.record(&app, QuotaKind::Messages, 10).await;
+        assert!(manager.check(&app, QuotaKind::Messages).await.is_err());
+        assert_eq!(reads(), 1);
+
+        // Usage recorded elsewhere is seen once the cached total is stale
+        store
+            .inner
+            .add(&app.id, QuotaKind::Messages, current_day(), 5)
+            .await
+            .unwrap();
+        tokio::time::sleep(TOTAL_CACHE_TTL).await;
+        assert_eq!(
+            manager
+                .current_total(&app.id, QuotaKind::Messages, current_day())
+                .await
+                .unwrap(),
+            15
+        );
+        assert_eq!(reads(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_apps_without_quotas_are_not_tracked() {
+        let manager = QuotaManager::new(Arc::new(MemoryUsageStore::new()), None);
+        let app = app(None);
+
+        manager.record(&app, QuotaKind::Messages, 100).await;
+        assert_eq!(manager.usage(&app).await.unwrap().messages.used, 0);
+    }
+}
@@ -0,0 +1,55 @@
+// src/quota/redis_store.rs
+use super::{QuotaKind, SECONDS_PER_DAY, UsageStore};
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use redis::aio::ConnectionLike;
+
+/// Usage counters shared by every node through Redis or Redis Cluster. Keys expire a
+/// day after the day they count.
+pub struct RedisUsageStore<C> {
+    connection: C,
+    prefix: String,
+}
+
+impl<C> RedisUsageStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    pub fn new(connection: C, prefix: String) -> Self {
+        Self { connection, prefix }
+    }
+
+    fn key(&self, app_id: &str, kind: QuotaKind, day: u64) -> String {
+        format!("{}{}:{}:{}", self.prefix, app_id, kind.as_str(), day)
+    }
+}
+
+#[async_trait]
+impl<C> UsageStore for RedisUsageStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    async fn add(&self, app_id: &str, kind: QuotaKind, day: u64, amount: u64) -> Result<u64> {
+        let key = self.key(app_id, kind, day);
+        let mut connection = self.connection.clone();
+        let (used,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(&key, amount)
+            .expire(&key, (2 * SECONDS_PER_DAY) as i64)
+            .ignore()
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to record quota usage: {e}")))?;
+        Ok(used)
+    }
+
+    async fn get(&self, app_id: &str, kind: QuotaKind, day: u64) -> Result<u64> {
+        let mut connection = self.connection.clone();
+        let used: Option<u64> = redis::cmd("GET")
+            .arg(self.key(app_id, kind, day))
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| Error::Redis(format!("Failed to read quota usage: {e}")))?;
+        Ok(used.unwrap_or(0))
+    }
+}
@@ -1,9 +1,11 @@
 // src/rate_limiter/middleware.rs
+use crate::ban::{BanManager, BanTarget};
+use crate::options::RateLimit;
 use crate::rate_limiter::{RateLimitResult, RateLimiter};
 use axum::{
     body::Body as AxumBody,
     extract::ConnectInfo,
-    http::{HeaderMap, HeaderName, HeaderValue, Request as AxumRequest, StatusCode},
+    http::{Extensions, HeaderMap, HeaderName, HeaderValue, Request as AxumRequest, StatusCode},
     response::{IntoResponse, Response as AxumResponse},
 };
 use futures_util::future::BoxFuture;
@@ -11,7 +13,7 @@ use hyper::Request as HyperRequest;
 use serde_json::json;
 use std::{
     fmt,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     sync::Arc,
     task::{Context, Poll},
 };
@@ -40,6 +42,18 @@ impl fmt::Display for RateLimitMiddlewareError {
 
 impl std::error::Error for RateLimitMiddlewareError {}
 
+/// The client address cannot be resolved from the peer address and trusted proxy headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UntrustedClientAddress(String);
+
+impl fmt::Display for UntrustedClientAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cannot determine the client address: {}", self.0)
+    }
+}
+
+impl std::error::Error for UntrustedClientAddress {}
+
 // Define header names as constants
 const HEADER_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
 const HEADER_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
@@ -70,6 +84,8 @@ pub struct RateLimitLayer<K> {
     options: RateLimitOptions,
     metrics: Option<Arc<tokio::sync::Mutex<dyn crate::metrics::MetricsInterface + Send + Sync>>>,
     config_name: String, // Track which rate limit config this is using
+    ban_manager: Option<Arc<BanManager>>,
+    trusted_client_ip: TrustedClientIp,
 }
 
 impl<K> RateLimitLayer<K>
@@ -92,6 +108,8 @@ where
             options,
             metrics: None,
             config_name: "unknown".to_string(),
+            ban_manager: None,
+            trusted_client_ip: TrustedClientIp::default(),
         }
     }
 
@@ -107,6 +125,18 @@ where
         self.metrics = Some(metrics);
         self
     }
+
+    /// Count rejections towards a ban of the client address, when it can be resolved
+    /// through the trusted proxies
+    pub fn with_ban_manager(
+        mut self,
+        ban_manager: Arc<BanManager>,
+        trusted_client_ip: TrustedClientIp,
+    ) -> Self {
+        self.ban_manager = Some(ban_manager);
+        self.trusted_client_ip = trusted_client_ip;
+        self
+    }
 }
 
 impl<S, K> Layer<S> for RateLimitLayer<K>
@@ -126,6 +156,8 @@ where
             options: self.options.clone(),
             metrics: self.metrics.clone(),
             config_name: self.config_name.clone(),
+            ban_manager: self.ban_manager.clone(),
+            trusted_client_ip: self.trusted_client_ip.clone(),
         }
     }
 }
@@ -138,6 +170,8 @@ pub struct RateLimitService<S, K> {
     options: RateLimitOptions,
     metrics: Option<Arc<tokio::sync::Mutex<dyn crate::metrics::MetricsInterface + Send + Sync>>>,
     config_name: String,
+    ban_manager: Option<Arc<BanManager>>,
+    trusted_client_ip: TrustedClientIp,
 }
 
 impl<S, K> Service<AxumRequest<AxumBody>> for RateLimitService<S, K>
@@ -162,6 +196,8 @@ where
         let options = self.options.clone();
         let metrics = self.metrics.clone();
         let config_name = self.config_name.clone();
+        let ban_manager = self.ban_manager.clone();
+        let trusted_client_ip = self.trusted_client_ip.clone();
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
@@ -244,6 +280,14 @@ where
                     );
                 }
 
+                if let Some(ban_manager) = ban_manager
+                    && let Ok(Some(ip)) = trusted_client_ip.resolve(req.headers(), req.extensions())
+                {
+                    ban_manager
+                        .record_violation(&BanTarget::ip(ip), "rate_limit")
+                        .await;
+                }
+
                 return Ok(rate_limit_error_response(Some(&rate_limit_result)));
             }
 
@@ -281,35 +325,40 @@ impl IpKeyExtractor {
     }
 
     fn get_ip<B>(&self, req: &HyperRequest<B>) -> Option<String> {
+        self.client_ip(req.headers(), req.extensions())
+            .map(|ip| ip.to_string())
+    }
+
+    /// Address of the client behind `trust_hops` trusted proxies, falling back to
+    /// `X-Real-IP` and then to the peer address of the connection
+    pub fn client_ip(&self, headers: &HeaderMap, extensions: &Extensions) -> Option<IpAddr> {
         if self.trust_hops > 0
-            && let Some(value) = req.headers().get("x-forwarded-for")
+            && let Some(value) = headers.get("x-forwarded-for")
             && let Ok(forwarded_str) = value.to_str()
         {
             let ips: Vec<&str> = forwarded_str.split(',').map(str::trim).collect();
             let client_ip_index = ips.len().saturating_sub(self.trust_hops);
             if let Some(ip_str) = ips.get(client_ip_index) {
-                if ip_str.parse::<std::net::IpAddr>().is_ok() {
-                    return Some(ip_str.to_string());
+                if let Ok(ip) = ip_str.parse::<IpAddr>() {
+                    return Some(ip);
                 }
             } else if let Some(ip_str) = ips.first()
-                && ip_str.parse::<std::net::IpAddr>().is_ok()
+                && let Ok(ip) = ip_str.parse::<IpAddr>()
             {
-                return Some(ip_str.to_string());
+                return Some(ip);
             }
         }
 
-        if let Some(value) = req.headers().get("x-real-ip")
+        if let Some(value) = headers.get("x-real-ip")
             && let Ok(real_ip_str) = value.to_str()
+            && let Ok(ip) = real_ip_str.trim().parse::<IpAddr>()
         {
-            let real_ip = real_ip_str.trim();
-            if real_ip.parse::<std::net::IpAddr>().is_ok() {
-                return Some(real_ip.to_string());
-            }
+            return Some(ip);
         }
 
-        req.extensions()
+        extensions
             .get::<ConnectInfo<SocketAddr>>()
-            .map(|ConnectInfo(addr)| addr.ip().to_string())
+            .map(|ConnectInfo(addr)| addr.ip())
     }
 }
 
@@ -331,6 +380,81 @@ impl KeyExtractor for IpKeyExtractor {
     }
 }
 
+/// Resolves client addresses for IP filters and bans, which must not be decided on an
+/// address the client chose. Unlike `IpKeyExtractor`, it never falls back to a header
+/// the trusted proxies did not set.
+#[derive(Clone, Debug, Default)]
+pub struct TrustedClientIp {
+    trust_hops: usize,
+    trust_x_real_ip: bool,
+}
+
+impl TrustedClientIp {
+    pub fn new(trust_hops: usize) -> Self {
+        Self {
+            trust_hops,
+            trust_x_real_ip: false,
+        }
+    }
+
+    /// Trust the proxies configured for the given rate limit
+    pub fn from_config(config: &RateLimit) -> Self {
+        Self::new(config.trust_hops.unwrap_or(0) as usize)
+            .with_trusted_x_real_ip(config.trust_x_real_ip.unwrap_or(false))
+    }
+
+    /// Take the address from `X-Real-IP`, which a trusted proxy in front of every
+    /// request overwrites
+    pub fn with_trusted_x_real_ip(mut self, trust_x_real_ip: bool) -> Self {
+        self.trust_x_real_ip = trust_x_real_ip;
+        self
+    }
+
+    /// Address of the client, read only from sources the client cannot forge: the entry
+    /// `trust_hops` trusted proxies appended to `X-Forwarded-For`, `X-Real-IP` when a
+    /// trusted proxy sets it, or else the peer address of the connection. Requests that
+    /// did not pass through the configured proxies are errors instead of falling back.
+    pub fn resolve(
+        &self,
+        headers: &HeaderMap,
+        extensions: &Extensions,
+    ) -> Result<Option<IpAddr>, UntrustedClientAddress> {
+        if self.trust_hops > 0 {
+            let forwarded: Vec<&str> = headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .map(str::trim)
+                .collect();
+            let Some(client_ip_index) = forwarded.len().checked_sub(self.trust_hops) else {
+                return Err(UntrustedClientAddress(format!(
+                    "X-Forwarded-For lists {} of {} trusted proxies",
+                    forwarded.len(),
+                    self.trust_hops
+                )));
+            };
+            return forwarded[client_ip_index]
+                .parse::<IpAddr>()
+                .map(Some)
+                .map_err(|_| UntrustedClientAddress("invalid X-Forwarded-For entry".to_string()));
+        }
+
+        if self.trust_x_real_ip {
+            return headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<IpAddr>().ok())
+                .map(Some)
+                .ok_or_else(|| UntrustedClientAddress("missing or invalid X-Real-IP".to_string()));
+        }
+
+        Ok(extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip()))
+    }
+}
+
 // --- Helper Functions ---
 
 fn rate_limit_error_response(result: Option<&RateLimitResult>) -> AxumResponse {
@@ -405,3 +529,87 @@ fn add_rate_limit_headers(
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &[(&str, &str)]) -> (HeaderMap, Extensions) {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        let mut extensions = Extensions::new();
+        extensions.insert(ConnectInfo(SocketAddr::from(([192, 0, 2, 1], 40000))));
+        (header_map, extensions)
+    }
+
+    fn peer() -> Option<IpAddr> {
+        Some("192.0.2.1".parse().unwrap())
+    }
+
+    #[test]
+    fn test_rate_limit_key_keeps_x_real_ip_fallback() {
+        let (headers, extensions) = request(&[("x-real-ip", "203.0.113.7")]);
+        assert_eq!(
+            IpKeyExtractor::new(0).client_ip(&headers, &extensions),
+            Some("203.0.113.7".parse().unwrap())
+        );
+
+        // Fewer entries than proxies still yields a key instead of rejecting the request
+        let (headers, extensions) = request(&[("x-forwarded-for", "203.0.113.8")]);
+        assert_eq!(
+            IpKeyExtractor::new(2).client_ip(&headers, &extensions),
+            Some("203.0.113.8".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_spoofed_headers_ignored_without_trusted_proxies() {
+        let (headers, extensions) = request(&[
+            ("x-real-ip", "203.0.113.7"),
+            ("x-forwarded-for", "203.0.113.8"),
+        ]);
+        let resolver = TrustedClientIp::new(0);
+        assert_eq!(resolver.resolve(&headers, &extensions), Ok(peer()));
+    }
+
+    #[test]
+    fn test_forwarded_for_resolves_behind_trusted_proxies() {
+        let (headers, extensions) =
+            request(&[("x-forwarded-for", "198.51.100.1, 203.0.113.7, 10.0.0.2")]);
+        assert_eq!(
+            TrustedClientIp::new(2).resolve(&headers, &extensions),
+            Ok(Some("203.0.113.7".parse().unwrap()))
+        );
+
+        // Fewer entries than proxies: the first one would be chosen by the client
+        let (headers, extensions) = request(&[("x-forwarded-for", "203.0.113.7")]);
+        assert!(
+            TrustedClientIp::new(2)
+                .resolve(&headers, &extensions)
+                .is_err()
+        );
+        let (headers, extensions) = request(&[("x-real-ip", "203.0.113.7")]);
+        assert!(
+            TrustedClientIp::new(1)
+                .resolve(&headers, &extensions)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_x_real_ip_read_only_when_trusted() {
+        let (headers, extensions) = request(&[("x-real-ip", "203.0.113.7")]);
+        let resolver = TrustedClientIp::new(0).with_trusted_x_real_ip(true);
+        assert_eq!(
+            resolver.resolve(&headers, &extensions),
+            Ok(Some("203.0.113.7".parse().unwrap()))
+        );
+        let (headers, extensions) = request(&[]);
+        assert!(resolver.resolve(&headers, &extensions).is_err());
+    }
+}
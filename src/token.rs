@@ -22,6 +22,8 @@ pub fn secure_compare(a: &str, b: &str) -> bool {
 pub struct Token {
     key: String,
     secret: String,
+    /// Further secrets accepted by `verify`, never used for signing
+    verification_secrets: Vec<String>,
 }
 
 impl Token {
@@ -32,7 +34,17 @@ impl Token {
     /// * `key` - The application key
     /// * `secret` - The application secret used for signing
     pub fn new(key: String, secret: String) -> Self {
-        Token { key, secret }
+        Token {
+            key,
+            secret,
+            verification_secrets: Vec::new(),
+        }
+    }
+
+    /// Also accept signatures made with any of the given secrets
+    pub fn with_verification_secrets(mut self, secrets: Vec<String>) -> Self {
+        self.verification_secrets = secrets;
+        self
     }
 
     /// Signs the input string using HMAC-SHA256 with the secret
@@ -63,18 +75,20 @@ impl Token {
     ///
     /// Returns true if the signatures match, false otherwise
     pub fn verify(&self, input: &str, signature: &str) -> bool {
-        // Create a new MAC instance and verify directly to avoid timing attacks
-        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
-            .expect("HMAC can take key of any size");
-
-        mac.update(input.as_bytes());
-
-        // Try to verify using the MAC's built-in verify method first
-        if let Ok(signature_bytes) = hex::decode(signature) {
-            mac.verify_slice(&signature_bytes).is_ok()
-        } else {
-            false
-        }
+        let Ok(signature_bytes) = hex::decode(signature) else {
+            return false;
+        };
+
+        // Check every secret so the time taken does not reveal which one matched
+        std::iter::once(&self.secret)
+            .chain(&self.verification_secrets)
+            .fold(false, |valid, secret| {
+                // Create a new MAC instance and verify directly to avoid timing attacks
+                let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+                    .expect("HMAC can take key of any size");
+                mac.update(input.as_bytes());
+                mac.verify_slice(&signature_bytes).is_ok() | valid
+            })
     }
 }
 
@@ -124,4 +138,17 @@ mod tests {
 
         assert!(!token2.verify(input, &signature));
     }
+
+    #[test]
+    fn test_token_verify_with_verification_secrets() {
+        let old = Token::new("test_key".to_string(), "old_secret".to_string());
+        let rotated = Token::new("test_key".to_string(), "new_secret".to_string())
+            .with_verification_secrets(vec!["old_secret".to_string()]);
+        let input = "test_input";
+
+        assert!(rotated.verify(input, &old.sign(input)));
+        assert!(rotated.verify(input, &rotated.sign(input)));
+        // Signing only ever uses the primary secret
+        assert!(!old.verify(input, &rotated.sign(input)));
+    }
 }
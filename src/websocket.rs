@@ -12,7 +12,7 @@ use hyper_util::rt::TokioIo;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error as StdError;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -76,6 +76,10 @@ pub struct UserInfo {
 pub struct ConnectionTimeouts {
     pub activity_timeout_handle: Option<JoinHandle<()>>,
     pub auth_timeout_handle: Option<JoinHandle<()>>,
+    /// Tasks that warn about and enforce the expiry of channel authorizations
+    pub channel_auth_expiry_handles: HashMap<String, JoinHandle<()>>,
+    /// Task that warns about and enforces the expiry of the user authentication
+    pub user_auth_expiry_handle: Option<JoinHandle<()>>,
 }
 
 impl Default for ConnectionTimeouts {
@@ -89,6 +93,8 @@ impl ConnectionTimeouts {
         Self {
             activity_timeout_handle: None,
             auth_timeout_handle: None,
+            channel_auth_expiry_handles: HashMap::new(),
+            user_auth_expiry_handle: None,
         }
     }
 
@@ -104,9 +110,25 @@ impl ConnectionTimeouts {
         }
     }
 
+    pub fn clear_channel_auth_expiry(&mut self, channel: &str) {
+        if let Some(handle) = self.channel_auth_expiry_handles.remove(channel) {
+            handle.abort();
+        }
+    }
+
+    pub fn clear_user_auth_expiry(&mut self) {
+        if let Some(handle) = self.user_auth_expiry_handle.take() {
+            handle.abort();
+        }
+    }
+
     pub fn clear_all(&mut self) {
         self.clear_activity_timeout();
         self.clear_auth_timeout();
+        for (_, handle) in self.channel_auth_expiry_handles.drain() {
+            handle.abort();
+        }
+        self.clear_user_auth_expiry();
     }
 }
 
@@ -134,6 +156,12 @@ pub struct ConnectionState {
     pub last_ping: Instant,
     pub presence: Option<HashMap<String, PresenceMemberInfo>>,
     pub user: Option<Value>,
+    /// Unix time at which the authorization of each expiring subscription lapses
+    pub auth_expirations: HashMap<String, u64>,
+    /// Unix time at which the user authentication lapses, if it expires
+    pub user_auth_expires_at: Option<u64>,
+    /// Upgrade request headers, kept only for apps with an authorizer
+    pub upgrade_headers: Option<BTreeMap<String, String>>,
     pub timeouts: ConnectionTimeouts,
     pub status: ConnectionStatus,
     pub disconnecting: bool,
@@ -156,6 +184,9 @@ impl ConnectionState {
             last_ping: Instant::now(),
             presence: None,
             user: None,
+            auth_expirations: HashMap::new(),
+            user_auth_expires_at: None,
+            upgrade_headers: None,
             timeouts: ConnectionTimeouts::new(),
             status: ConnectionStatus::Active,
             disconnecting: false,
@@ -172,6 +203,9 @@ impl ConnectionState {
             last_ping: Instant::now(),
             presence: None,
             user: None,
+            auth_expirations: HashMap::new(),
+            user_auth_expires_at: None,
+            upgrade_headers: None,
             timeouts: ConnectionTimeouts::new(),
             status: ConnectionStatus::Active,
             disconnecting: false,
@@ -191,9 +225,70 @@ impl ConnectionState {
     }
 
     pub fn remove_subscription(&mut self, channel: &str) -> bool {
+        self.auth_expirations.remove(channel);
+        self.timeouts.clear_channel_auth_expiry(channel);
         self.subscribed_channels.remove(channel)
     }
 
+    /// When the authorization of `channel`, or of the user without a channel, lapses
+    pub fn auth_expires_at(&self, channel: Option<&str>) -> Option<u64> {
+        match channel {
+            Some(channel) => self.auth_expirations.get(channel).copied(),
+            None => self.user_auth_expires_at,
+        }
+    }
+
+    /// Replace the expiry of the authorization of `channel`, or of the user without a
+    /// channel, along with the task enforcing it
+    pub fn set_auth_expiry(
+        &mut self,
+        channel: Option<&str>,
+        expires_at: Option<u64>,
+        handle: Option<JoinHandle<()>>,
+    ) {
+        match channel {
+            Some(channel) => {
+                self.timeouts.clear_channel_auth_expiry(channel);
+                match expires_at {
+                    Some(expires_at) => self
+                        .auth_expirations
+                        .insert(channel.to_string(), expires_at),
+                    None => self.auth_expirations.remove(channel),
+                };
+                if let Some(handle) = handle {
+                    self.timeouts
+                        .channel_auth_expiry_handles
+                        .insert(channel.to_string(), handle);
+                }
+            }
+            None => {
+                self.timeouts.clear_user_auth_expiry();
+                self.user_auth_expires_at = expires_at;
+                self.timeouts.user_auth_expiry_handle = handle;
+            }
+        }
+    }
+
+    /// Forget an authorization that lapsed at `expires_at`. Returns false if it was renewed
+    /// or removed in the meantime. The enforcing task is detached rather than aborted, as
+    /// it is the one calling this.
+    pub fn take_lapsed_auth(&mut self, channel: Option<&str>, expires_at: u64) -> bool {
+        if self.auth_expires_at(channel) != Some(expires_at) {
+            return false;
+        }
+        match channel {
+            Some(channel) => {
+                self.auth_expirations.remove(channel);
+                self.timeouts.channel_auth_expiry_handles.remove(channel);
+            }
+            None => {
+                self.user_auth_expires_at = None;
+                self.timeouts.user_auth_expiry_handle = None;
+            }
+        }
+        true
+    }
+
     pub fn update_ping(&mut self) {
         self.last_ping = Instant::now();
     }
@@ -727,6 +822,26 @@ mod tests {
         assert!(!state.is_subscribed("test-channel"));
     }
 
+    #[test]
+    fn test_auth_expiry_tracking() {
+        let mut state = ConnectionState::new();
+        state.add_subscription("private-orders".to_string());
+        state.set_auth_expiry(Some("private-orders"), Some(100), None);
+        state.set_auth_expiry(None, Some(200), None);
+        assert_eq!(state.auth_expires_at(Some("private-orders")), Some(100));
+        assert_eq!(state.auth_expires_at(None), Some(200));
+
+        // A renewed authorization is not taken by the check for the old expiry
+        state.set_auth_expiry(Some("private-orders"), Some(150), None);
+        assert!(!state.take_lapsed_auth(Some("private-orders"), 100));
+        assert!(state.take_lapsed_auth(Some("private-orders"), 150));
+        assert_eq!(state.auth_expires_at(Some("private-orders")), None);
+
+        state.remove_subscription("private-orders");
+        assert!(state.take_lapsed_auth(None, 200));
+        assert_eq!(state.auth_expires_at(None), None);
+    }
+
     #[test]
     fn test_socket_id_display() {
         let id = SocketId("123.456".to_string());
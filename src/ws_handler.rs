@@ -4,7 +4,7 @@
 use crate::adapter::ConnectionHandler;
 
 use axum::extract::{Path, Query, State};
-use axum::http::HeaderMap;
+use axum::http::{Extensions, HeaderMap};
 use axum::response::IntoResponse;
 use fastwebsockets::upgrade;
 use serde::Deserialize;
@@ -23,9 +23,19 @@ pub async fn handle_ws_upgrade(
     Path(app_key): Path<String>,
     Query(_params): Query<ConnectionQuery>,
     headers: HeaderMap,
+    extensions: Extensions,
     ws: upgrade::IncomingUpgrade,
     State(handler): State<Arc<ConnectionHandler>>,
 ) -> impl IntoResponse {
+    // A draining node must not pick up new clients; they will be routed elsewhere
+    if handler.is_draining() {
+        return (
+            http::StatusCode::SERVICE_UNAVAILABLE,
+            "Server is draining, try another node",
+        )
+            .into_response();
+    }
+
     let (response, fut) = match ws.upgrade() {
         Ok((response, fut)) => (response, fut),
         Err(e) => {
@@ -46,8 +56,13 @@ pub async fn handle_ws_upgrade(
         .and_then(|h| h.to_str().ok())
         .map(|s| s.to_string());
 
+    let client_ip = handler.trusted_client_ip(&headers, &extensions);
+
     tokio::task::spawn(async move {
-        if let Err(e) = handler.handle_socket(fut, app_key.clone(), origin).await {
+        if let Err(e) = handler
+            .handle_socket(fut, app_key.clone(), origin, client_ip, headers)
+            .await
+        {
             error!("Error handling socket: {e}");
             // Only track generic socket handling errors for cases not already tracked
             // Most specific errors (app_not_found, authentication_failed, etc.)
@@ -58,6 +73,7 @@ pub async fn handle_ws_upgrade(
                     crate::error::Error::ApplicationNotFound
                     | crate::error::Error::ApplicationDisabled
                     | crate::error::Error::OriginNotAllowed
+                    | crate::error::Error::IpNotAllowed
                     | crate::error::Error::Auth(_)
                     | crate::error::Error::InvalidMessageFormat(_)
                     | crate::error::Error::InvalidEventName(_) => {
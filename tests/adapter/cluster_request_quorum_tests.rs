@@ -0,0 +1,296 @@
+use sockudo::adapter::horizontal_adapter::{
+    HorizontalAdapter, RequestType, ResponseBody, collect_partial_results,
+};
+use sockudo::adapter::horizontal_adapter_base::HorizontalAdapterBase;
+use sockudo::error::Result;
+use sockudo::options::ClusterRequestConfig;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::horizontal_adapter_helpers::{MockConfig, MockNodeState, MockTransport};
+
+async fn create_adapter(
+    config: MockConfig,
+    request_config: ClusterRequestConfig,
+) -> Result<HorizontalAdapterBase<MockTransport>> {
+    let node_ids: Vec<String> = config
+        .node_states
+        .iter()
+        .map(|state| state.node_id.clone())
+        .collect();
+
+    let mut adapter = HorizontalAdapterBase::<MockTransport>::new(config).await?;
+    adapter.set_request_config(&request_config).await;
+    adapter.start_listeners().await?;
+    adapter
+        .with_discovered_nodes(node_ids.iter().map(String::as_str).collect())
+        .await
+}
+
+#[tokio::test]
+async fn test_quorum_returns_before_timeout_with_partial_result() -> Result<()> {
+    let adapter = create_adapter(
+        MockTransport::partial_failures(),
+        ClusterRequestConfig {
+            quorum_ratio: 0.5,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let start = Instant::now();
+    let response = adapter
+        .send_request(
+            "test-app",
+            RequestType::ChannelSockets,
+            Some("test-channel"),
+            None,
+            None,
+        )
+        .await?;
+
+    // node-1 answers after 10ms, which satisfies a quorum of one out of two
+    assert!(start.elapsed() < Duration::from_millis(400));
+    assert!(response.partial);
+    assert_eq!(response.socket_ids, vec!["socket-1"]);
+    assert!(
+        response
+            .missing_nodes
+            .iter()
+            .all(|node| node == "node-2" || node == "node-3")
+    );
+    assert!(response.missing_nodes.contains(&"node-2".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timeout_marks_missing_nodes_and_flags_them_slow() -> Result<()> {
+    let adapter = create_adapter(
+        MockTransport::partial_failures(),
+        ClusterRequestConfig {
+            slow_node_threshold_ms: 100,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let response = adapter
+        .send_request(
+            "test-app",
+            RequestType::ChannelSockets,
+            Some("test-channel"),
+            None,
+            None,
+        )
+        .await?;
+
+    assert!(response.partial);
+    assert_eq!(
+        response.missing_nodes,
+        vec!["node-2".to_string(), "node-3".to_string()]
+    );
+
+    let horizontal = adapter.horizontal.lock().await;
+    let slow_nodes = horizontal.get_slow_nodes().await;
+    assert!(slow_nodes.contains("node-2"));
+    assert!(slow_nodes.contains("node-3"));
+    assert!(!slow_nodes.contains("node-1"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_skip_slow_nodes_does_not_wait_for_them() -> Result<()> {
+    let config = MockConfig {
+        request_timeout_ms: 1000,
+        node_states: vec![
+            MockNodeState::new("node-1").with_sockets(vec!["socket-1"]),
+            MockNodeState::new("node-2")
+                .with_sockets(vec!["socket-2"])
+                .will_not_respond(),
+            MockNodeState::new("node-3")
+                .with_sockets(vec!["socket-3"])
+                .with_response_delay(800),
+        ],
+        ..Default::default()
+    };
+    let adapter = create_adapter(
+        config,
+        ClusterRequestConfig {
+            skip_slow_nodes: true,
+            slow_node_threshold_ms: 100,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    {
+        let horizontal = adapter.horizontal.lock().await;
+        horizontal
+            .record_node_latency("node-2", Duration::from_millis(900))
+            .await;
+    }
+
+    let start = Instant::now();
+    let response = adapter
+        .send_request("test-app", RequestType::Sockets, None, None, None)
+        .await?;
+
+    // node-2 is skipped, so node-1's answer is enough without waiting for node-3
+    assert!(start.elapsed() < Duration::from_millis(500));
+    assert!(response.partial);
+    assert!(response.missing_nodes.contains(&"node-2".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_latency_is_sampled_once_and_only_within_the_timeout() -> Result<()> {
+    let config = MockConfig {
+        request_timeout_ms: 300,
+        node_states: vec![
+            MockNodeState::new("node-1").with_sockets(vec!["socket-1"]),
+            MockNodeState::new("node-2")
+                .with_sockets(vec!["socket-2"])
+                .with_response_delay(100),
+            MockNodeState::new("node-3")
+                .with_sockets(vec!["socket-3"])
+                .with_response_delay(500),
+        ],
+        ..Default::default()
+    };
+    let adapter = create_adapter(
+        config,
+        ClusterRequestConfig {
+            quorum_ratio: 0.5,
+            slow_node_threshold_ms: 1000,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let response = adapter
+        .send_request("test-app", RequestType::Sockets, None, None, None)
+        .await?;
+    assert!(response.partial);
+
+    // node-2 answers after the quorum but within the timeout, node-3 after the timeout
+    tokio::time::sleep(Duration::from_millis(700)).await;
+    let horizontal = adapter.horizontal.lock().await;
+    let latencies = horizontal.node_latencies.read().await;
+    assert_eq!(latencies.get("node-1").map(|l| l.samples), Some(1));
+    assert_eq!(latencies.get("node-2").map(|l| l.samples), Some(1));
+    assert!(!latencies.contains_key("node-3"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_quorum_result_is_not_partial() -> Result<()> {
+    let adapter = create_adapter(MockConfig::default(), ClusterRequestConfig::default()).await?;
+
+    let (response, partial_results) = collect_partial_results(adapter.send_request(
+        "test-app",
+        RequestType::Sockets,
+        None,
+        None,
+        None,
+    ))
+    .await;
+    let response = response?;
+
+    assert!(!response.partial);
+    assert!(response.missing_nodes.is_empty());
+    assert!(!partial_results.partial);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_partial_results_are_collected_for_the_enclosing_scope() -> Result<()> {
+    let adapter = create_adapter(
+        MockTransport::partial_failures(),
+        ClusterRequestConfig::default(),
+    )
+    .await?;
+
+    let (response, partial_results) = collect_partial_results(adapter.send_request(
+        "test-app",
+        RequestType::ChannelSockets,
+        Some("test-channel"),
+        None,
+        None,
+    ))
+    .await;
+    response?;
+
+    assert!(partial_results.partial);
+    assert!(partial_results.missing_nodes.contains("node-2"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_horizontal_adapter_send_request_waits_for_quorum() -> Result<()> {
+    let mut horizontal = HorizontalAdapter::new();
+    horizontal.requests_timeout = 1000;
+    horizontal
+        .add_discovered_node_for_test("node-2".to_string())
+        .await;
+    let horizontal = Arc::new(horizontal);
+
+    // Answer as node-2 once the request is registered, as a transport would
+    let responder = horizontal.clone();
+    tokio::spawn(async move {
+        let request_id = loop {
+            if let Some(entry) = responder.pending_requests.iter().next() {
+                break entry.key().clone();
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        };
+        responder
+            .process_response(ResponseBody {
+                request_id,
+                node_id: "node-2".to_string(),
+                app_id: "test-app".to_string(),
+                members: HashMap::new(),
+                channels_with_sockets_count: HashMap::new(),
+                socket_ids: vec!["socket-2".to_string()],
+                sockets_count: 0,
+                exists: false,
+                channels: HashSet::new(),
+                members_count: 0,
+                partial: false,
+                missing_nodes: Vec::new(),
+            })
+            .await
+    });
+
+    let start = Instant::now();
+    let response = horizontal
+        .send_request(
+            "test-app",
+            RequestType::ChannelSockets,
+            Some("test-channel"),
+            None,
+            None,
+            2,
+        )
+        .await?;
+
+    assert!(start.elapsed() < Duration::from_millis(500));
+    assert!(!response.partial);
+    assert_eq!(response.socket_ids, vec!["socket-2"]);
+    assert!(horizontal.pending_requests.is_empty());
+
+    // A single node has nobody to ask
+    let response = horizontal
+        .send_request("test-app", RequestType::SocketsCount, None, None, None, 1)
+        .await?;
+    assert!(!response.partial);
+    assert!(horizontal.pending_requests.is_empty());
+
+    Ok(())
+}
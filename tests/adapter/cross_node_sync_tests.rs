@@ -28,6 +28,8 @@ async fn test_request_response_aggregation_logic() {
             exists: true,
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         },
         // Response 2: Node has 1 socket in channel
         ResponseBody {
@@ -41,6 +43,8 @@ async fn test_request_response_aggregation_logic() {
             exists: true,
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         },
     ];
 
@@ -111,6 +115,8 @@ async fn test_channel_members_aggregation_deduplication() {
         exists: false,
         channels: HashSet::new(),
         members_count: 2,
+        partial: false,
+        missing_nodes: Vec::new(),
     });
 
     // Node 2: Has user-1 (duplicate) and user-3 (unique)
@@ -141,6 +147,8 @@ async fn test_channel_members_aggregation_deduplication() {
         exists: false,
         channels: HashSet::new(),
         members_count: 2,
+        partial: false,
+        missing_nodes: Vec::new(),
     });
 
     // Test aggregation
@@ -196,6 +204,8 @@ async fn test_channels_with_socket_count_aggregation() {
         exists: false,
         channels: HashSet::new(),
         members_count: 0,
+        partial: false,
+        missing_nodes: Vec::new(),
     });
 
     // Node 2: Has channels A(3 sockets), C(1 socket)
@@ -214,6 +224,8 @@ async fn test_channels_with_socket_count_aggregation() {
         exists: false,
         channels: HashSet::new(),
         members_count: 0,
+        partial: false,
+        missing_nodes: Vec::new(),
     });
 
     // Test aggregation
@@ -268,6 +280,8 @@ async fn test_exists_flag_aggregation_logic() {
             exists: false, // All return false
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         });
     }
 
@@ -300,6 +314,8 @@ async fn test_exists_flag_aggregation_logic() {
             exists: false,
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         },
         ResponseBody {
             request_id: request_id.to_string(),
@@ -312,6 +328,8 @@ async fn test_exists_flag_aggregation_logic() {
             exists: true, // One returns true
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         },
     ];
 
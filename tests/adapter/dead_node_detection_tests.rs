@@ -1,6 +1,6 @@
 use crate::adapter::horizontal_adapter_helpers::{MockConfig, MockTransport};
 use sockudo::adapter::ConnectionManager;
-use sockudo::adapter::horizontal_adapter::{DeadNodeEvent, RequestType};
+use sockudo::adapter::horizontal_adapter::{DeadNodeEvent, NodeStats, RequestType};
 use sockudo::adapter::horizontal_adapter_base::HorizontalAdapterBase;
 use sockudo::options::ClusterHealthConfig;
 use std::time::Duration;
@@ -35,6 +35,10 @@ async fn test_heartbeat_tracking_updates_node_registry() {
         timestamp: Some(1234567890),
         dead_node_id: None,
         target_node_id: None,
+        stats: Some(NodeStats {
+            connections: 3,
+            channels: 2,
+        }),
     };
 
     // Process the heartbeat through the adapter's request handler
@@ -56,6 +60,8 @@ async fn test_heartbeat_tracking_updates_node_registry() {
             heartbeats.len() == 1,
             "Should have exactly 1 remote node tracked"
         );
+        let stats = horizontal.node_stats.read().await;
+        assert_eq!(stats.get("remote-node-1").map(|s| s.connections), Some(3));
     }
 }
 
@@ -383,6 +389,7 @@ async fn test_follower_cleanup_only_removes_registry_data() {
         timestamp: Some(1234567890),
         dead_node_id: Some(dead_node_id.to_string()),
         target_node_id: None,
+        stats: None,
     };
 
     // Process the dead node notification
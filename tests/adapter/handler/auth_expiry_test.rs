@@ -0,0 +1,130 @@
+use crate::mocks::connection_handler_mock::MockCacheManager;
+use axum::Router;
+use axum::routing::get;
+use bytes::Bytes;
+use fastwebsockets::{Frame, OpCode, Payload, WebSocket, handshake};
+use http_body_util::Empty;
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::TokioIo;
+use serde_json::{Value, json};
+use sockudo::adapter::handler::ConnectionHandler;
+use sockudo::adapter::local_adapter::LocalAdapter;
+use sockudo::app::config::App;
+use sockudo::app::manager::AppManager;
+use sockudo::app::memory_app_manager::MemoryAppManager;
+use sockudo::options::ServerOptions;
+use sockudo::ws_handler::handle_ws_upgrade;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+struct SpawnExecutor;
+
+impl<Fut> hyper::rt::Executor<Fut> for SpawnExecutor
+where
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    fn execute(&self, fut: Fut) {
+        tokio::task::spawn(fut);
+    }
+}
+
+fn app() -> App {
+    App {
+        id: "app-1".to_string(),
+        key: "key-1".to_string(),
+        secret: "secret-1".to_string(),
+        enabled: true,
+        max_connections: 100,
+        ..Default::default()
+    }
+}
+
+async fn serve() -> SocketAddr {
+    let app_manager = Arc::new(MemoryAppManager::new());
+    app_manager.create_app(app()).await.unwrap();
+    let handler = ConnectionHandler::new(
+        app_manager as Arc<dyn AppManager + Send + Sync>,
+        Arc::new(Mutex::new(LocalAdapter::new())),
+        Arc::new(Mutex::new(MockCacheManager::new())),
+        None,
+        None,
+        ServerOptions::default(),
+        None,
+    );
+    let router = Router::new()
+        .route("/app/{appKey}", get(handle_ws_upgrade))
+        .with_state(Arc::new(handler));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    });
+    addr
+}
+
+async fn read(ws: &mut WebSocket<TokioIo<Upgraded>>) -> Value {
+    let frame = ws.read_frame().await.unwrap();
+    assert_eq!(frame.opcode, OpCode::Text);
+    serde_json::from_slice(&frame.payload).unwrap()
+}
+
+async fn send(ws: &mut WebSocket<TokioIo<Upgraded>>, message: Value) {
+    let payload = serde_json::to_vec(&message).unwrap();
+    ws.write_frame(Frame::text(Payload::Owned(payload)))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_resubscribing_with_a_fresh_auth_renews_in_place() {
+    let addr = serve().await;
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let request = hyper::Request::builder()
+        .uri(format!("http://{addr}/app/key-1?protocol=7"))
+        .header("Host", addr.to_string())
+        .header("Upgrade", "websocket")
+        .header("Connection", "upgrade")
+        .header("Sec-WebSocket-Key", handshake::generate_key())
+        .header("Sec-WebSocket-Version", "13")
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+    let (mut ws, _) = handshake::client(&SpawnExecutor, request, stream)
+        .await
+        .unwrap();
+
+    let established = read(&mut ws).await;
+    assert_eq!(established["event"], "pusher:connection_established");
+    let data: Value = serde_json::from_str(established["data"].as_str().unwrap()).unwrap();
+    let socket_id = data["socket_id"].as_str().unwrap().to_string();
+    let subscribe = |expires_at: i64| {
+        let signature = app()
+            .token()
+            .sign(&format!("{socket_id}:private-orders:{expires_at}"));
+        json!({
+            "event": "pusher:subscribe",
+            "data": {
+                "channel": "private-orders",
+                "auth": format!("key-1:{signature}:{expires_at}"),
+            },
+        })
+    };
+
+    let expires_at = chrono::Utc::now().timestamp() + 3600;
+    send(&mut ws, subscribe(expires_at)).await;
+    let succeeded = read(&mut ws).await;
+    assert_eq!(succeeded["event"], "pusher_internal:subscription_succeeded");
+
+    // The renewal is silent, so the next message is the answer to the ping
+    send(&mut ws, subscribe(expires_at + 3600)).await;
+    send(&mut ws, json!({"event": "pusher:ping", "data": {}})).await;
+    assert_eq!(read(&mut ws).await["event"], "pusher:pong");
+}
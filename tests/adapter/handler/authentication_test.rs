@@ -1,7 +1,10 @@
 use crate::mocks::connection_handler_mock::create_test_connection_handler;
 use serde_json::json;
+use sockudo::adapter::handler::authentication::auth_expires_at;
 use sockudo::adapter::handler::types::SubscriptionRequest;
-use sockudo::app::config::App;
+use sockudo::app::authorizer::AuthorizerConfig;
+use sockudo::app::config::{App, AppCredential, CredentialState};
+use sockudo::app::jwt_auth::sign_hs256;
 use sockudo::error::Error;
 use sockudo::protocol::messages::{MessageData, PusherMessage};
 use sockudo::websocket::SocketId;
@@ -117,3 +120,251 @@ async fn test_verify_channel_authentication_presence_channel() {
         .await;
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn test_verify_channel_authentication_with_jwt() {
+    let (handler, _app_manager) = create_test_connection_handler();
+    let app_config = App {
+        secret: "test-app-secret".to_string(),
+        jwt_auth: Some(serde_json::from_value(json!({})).unwrap()),
+        ..Default::default()
+    };
+    let socket_id = SocketId::new();
+    let token = sign_hs256(
+        &app_config,
+        &json!({
+            "socket_id": socket_id.0,
+            "exp": chrono::Utc::now().timestamp() + 60,
+            "channels": ["presence-room-*"],
+            "sub": "123",
+            "user_info": {"name": "Test User"}
+        }),
+    );
+
+    let request = |channel: &str, user_id: &str| SubscriptionRequest {
+        channel: channel.to_string(),
+        auth: Some(token.clone()),
+        channel_data: Some(
+            json!({"user_id": user_id, "user_info": {"name": "Test User"}}).to_string(),
+        ),
+    };
+
+    let allowed = handler
+        .verify_channel_authentication(&app_config, &socket_id, &request("presence-room-1", "123"))
+        .await;
+    assert!(allowed.unwrap());
+
+    let other_channel = handler
+        .verify_channel_authentication(&app_config, &socket_id, &request("presence-lobby", "123"))
+        .await;
+    assert!(!other_channel.unwrap());
+
+    let other_user = handler
+        .verify_channel_authentication(&app_config, &socket_id, &request("presence-room-1", "124"))
+        .await;
+    assert!(!other_user.unwrap());
+
+    // Without jwt_auth the token is treated like any other invalid signature
+    let app_without_jwt = App {
+        jwt_auth: None,
+        api_auth: None,
+        ip_filter: None,
+        authorizer: None,
+        ..app_config.clone()
+    };
+    let disabled = handler
+        .verify_channel_authentication(
+            &app_without_jwt,
+            &socket_id,
+            &request("presence-room-1", "123"),
+        )
+        .await;
+    assert!(!disabled.unwrap());
+}
+
+#[tokio::test]
+async fn test_verify_channel_authentication_with_expiring_signature() {
+    let (handler, _app_manager) = create_test_connection_handler();
+    let app_config = App {
+        key: "test-app-key".to_string(),
+        secret: "test-app-secret".to_string(),
+        ..Default::default()
+    };
+    let socket_id = SocketId::new();
+    let signed = |expires_at: i64| {
+        let signature = app_config
+            .token()
+            .sign(&format!("{}:private-orders:{expires_at}", socket_id.0));
+        format!("test-app-key:{signature}:{expires_at}")
+    };
+    let request = |auth: String| SubscriptionRequest {
+        channel: "private-orders".to_string(),
+        auth: Some(auth),
+        channel_data: None,
+    };
+
+    let expires_at = chrono::Utc::now().timestamp() + 60;
+    let auth = signed(expires_at);
+    assert_eq!(auth_expires_at(&auth), Some(expires_at as u64));
+    let valid = handler
+        .verify_channel_authentication(&app_config, &socket_id, &request(auth))
+        .await;
+    assert!(valid.unwrap());
+
+    let expired = handler
+        .verify_channel_authentication(
+            &app_config,
+            &socket_id,
+            &request(signed(chrono::Utc::now().timestamp() - 1)),
+        )
+        .await;
+    assert!(!expired.unwrap());
+
+    assert_eq!(auth_expires_at("test-app-key:abcdef"), None);
+}
+
+#[tokio::test]
+async fn test_verify_channel_authentication_with_credential_key() {
+    let (handler, _app_manager) = create_test_connection_handler();
+    let credential = |key: &str, secret: &str, state| AppCredential {
+        key: key.to_string(),
+        secret: secret.to_string(),
+        state,
+        expires_at: None,
+    };
+    let app_config = App {
+        key: "test-app-key".to_string(),
+        secret: "test-app-secret".to_string(),
+        credentials: Some(vec![
+            credential(
+                "previous-key",
+                "previous-secret",
+                CredentialState::VerifyOnly,
+            ),
+            credential("revoked-key", "revoked-secret", CredentialState::Revoked),
+        ]),
+        ..Default::default()
+    };
+    let socket_id = SocketId::new();
+    let signed = |key: &str, secret: &str| {
+        let token = sockudo::token::Token::new(key.to_string(), secret.to_string());
+        let signature = token.sign(&format!("{}:private-orders", socket_id.0));
+        SubscriptionRequest {
+            channel: "private-orders".to_string(),
+            auth: Some(format!("{key}:{signature}")),
+            channel_data: None,
+        }
+    };
+
+    for (key, secret, accepted) in [
+        ("test-app-key", "test-app-secret", true),
+        ("previous-key", "previous-secret", true),
+        // A key only verifies with the secret it is paired with
+        ("previous-key", "test-app-secret", false),
+        ("test-app-key", "previous-secret", false),
+        ("revoked-key", "revoked-secret", false),
+    ] {
+        let valid = handler
+            .verify_channel_authentication(&app_config, &socket_id, &signed(key, secret))
+            .await
+            .unwrap();
+        assert_eq!(valid, accepted, "{key}/{secret}");
+    }
+}
+
+/// Authorizer that allows `private-allowed` and the presence channel, and verifies the
+/// request signature
+async fn spawn_authorizer(secret: &'static str) -> String {
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+
+    let route = post(move |headers: HeaderMap, body: String| async move {
+        let token = sockudo::token::Token::new("test-app-key".into(), secret.into());
+        let signature = headers["x-pusher-signature"].to_str().unwrap();
+        if !token.verify(&body, signature) {
+            return (StatusCode::UNAUTHORIZED, String::new());
+        }
+        let request: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let decision = match request["channel_name"].as_str().unwrap() {
+            "private-allowed" => json!({ "authorized": true }),
+            "presence-room" => json!({
+                "authorized": true,
+                "channel_data": json!({ "user_id": "42" }).to_string()
+            }),
+            _ => return (StatusCode::FORBIDDEN, String::new()),
+        };
+        (StatusCode::OK, decision.to_string())
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(axum::serve(listener, axum::Router::new().route("/auth", route)).into_future());
+    format!("http://{address}/auth")
+}
+
+#[tokio::test]
+async fn test_authorize_subscription_with_authorizer() {
+    let (handler, _app_manager) = create_test_connection_handler();
+    let app_config = App {
+        key: "test-app-key".to_string(),
+        secret: "test-app-secret".to_string(),
+        authorizer: Some(AuthorizerConfig {
+            url: spawn_authorizer("test-app-secret").await,
+            timeout_ms: None,
+            cache_ttl_seconds: None,
+        }),
+        ..Default::default()
+    };
+    let socket_id = SocketId::new();
+    let request = |channel: &str| SubscriptionRequest {
+        channel: channel.to_string(),
+        auth: None,
+        channel_data: Some(json!({ "user_id": "spoofed" }).to_string()),
+    };
+
+    // Missing signatures are left to the authorizer instead of failing
+    let unsigned = handler
+        .verify_channel_authentication(&app_config, &socket_id, &request("private-allowed"))
+        .await;
+    assert!(!unsigned.unwrap());
+
+    let mut allowed = request("private-allowed");
+    assert!(
+        handler
+            .authorize_subscription(&app_config, &socket_id, &mut allowed)
+            .await
+            .unwrap()
+    );
+
+    let mut presence = request("presence-room");
+    assert!(
+        handler
+            .authorize_subscription(&app_config, &socket_id, &mut presence)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        presence.channel_data,
+        Some(json!({ "user_id": "42" }).to_string())
+    );
+
+    let mut denied = request("private-denied");
+    assert!(
+        !handler
+            .authorize_subscription(&app_config, &socket_id, &mut denied)
+            .await
+            .unwrap()
+    );
+
+    // Requests signed with another secret are rejected by the authorizer
+    let wrong_secret = App {
+        secret: "other-secret".to_string(),
+        ..app_config.clone()
+    };
+    let mut forged = request("private-allowed");
+    assert!(
+        !handler
+            .authorize_subscription(&wrong_secret, &socket_id, &mut forged)
+            .await
+            .unwrap()
+    );
+}
@@ -0,0 +1,166 @@
+use crate::mocks::connection_handler_mock::MockCacheManager;
+use axum::Router;
+use axum::routing::get;
+use bytes::Bytes;
+use fastwebsockets::{OpCode, WebSocket, handshake};
+use http_body_util::Empty;
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use sockudo::adapter::handler::ConnectionHandler;
+use sockudo::adapter::local_adapter::LocalAdapter;
+use sockudo::app::config::App;
+use sockudo::app::manager::AppManager;
+use sockudo::app::memory_app_manager::MemoryAppManager;
+use sockudo::ban::memory_store::MemoryBanStore;
+use sockudo::ban::{BanManager, BanTarget};
+use sockudo::options::{BanConfig, ServerOptions};
+use sockudo::rate_limiter::memory_limiter::MemoryRateLimiter;
+use sockudo::ws_handler::handle_ws_upgrade;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+struct SpawnExecutor;
+
+impl<Fut> hyper::rt::Executor<Fut> for SpawnExecutor
+where
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    fn execute(&self, fut: Fut) {
+        tokio::task::spawn(fut);
+    }
+}
+
+async fn serve(handler: Arc<ConnectionHandler>) -> SocketAddr {
+    let router = Router::new()
+        .route("/app/{appKey}", get(handle_ws_upgrade))
+        .with_state(handler);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    });
+    addr
+}
+
+/// Open a connection and read the first message the server sends
+async fn connect(addr: SocketAddr, app_key: &str) -> (WebSocket<TokioIo<Upgraded>>, Value) {
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let request = hyper::Request::builder()
+        .uri(format!("http://{addr}/app/{app_key}?protocol=7"))
+        .header("Host", addr.to_string())
+        .header("Upgrade", "websocket")
+        .header("Connection", "upgrade")
+        .header("Sec-WebSocket-Key", handshake::generate_key())
+        .header("Sec-WebSocket-Version", "13")
+        // Ignored without a trusted proxy
+        .header("X-Real-IP", "198.51.100.7")
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+    let (mut ws, _) = handshake::client(&SpawnExecutor, request, stream)
+        .await
+        .unwrap();
+
+    let frame = ws.read_frame().await.unwrap();
+    assert_eq!(frame.opcode, OpCode::Text);
+    let message: Value = serde_json::from_slice(&frame.payload).unwrap();
+    (ws, message)
+}
+
+async fn handler_with_bans() -> (Arc<ConnectionHandler>, Arc<BanManager>) {
+    handler_with_bans_and_options(ServerOptions::default()).await
+}
+
+async fn handler_with_bans_and_options(
+    options: ServerOptions,
+) -> (Arc<ConnectionHandler>, Arc<BanManager>) {
+    let app_manager = Arc::new(MemoryAppManager::new());
+    app_manager
+        .create_app(App {
+            id: "app-1".to_string(),
+            key: "key-1".to_string(),
+            secret: "secret-1".to_string(),
+            enabled: true,
+            max_connections: 100,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let ban_manager = Arc::new(BanManager::new(
+        BanConfig {
+            enabled: true,
+            max_violations: 3,
+            ..Default::default()
+        },
+        Arc::new(MemoryRateLimiter::new(3, 60)),
+        Arc::new(MemoryBanStore::new()),
+    ));
+    let handler = ConnectionHandler::new(
+        app_manager as Arc<dyn AppManager + Send + Sync>,
+        Arc::new(Mutex::new(LocalAdapter::new())),
+        Arc::new(Mutex::new(MockCacheManager::new())),
+        None,
+        None,
+        options,
+        None,
+    )
+    .with_ban_manager(ban_manager.clone());
+    (Arc::new(handler), ban_manager)
+}
+
+#[tokio::test]
+async fn test_banned_address_is_rejected_with_4101() {
+    let (handler, ban_manager) = handler_with_bans().await;
+    let addr = serve(handler).await;
+    let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+
+    // Bans of the address in other apps do not apply
+    ban_manager
+        .ban(BanTarget::app_ip("app-2", localhost), "auth_failure")
+        .await
+        .unwrap();
+    let (_ws, message) = connect(addr, "key-1").await;
+    assert_eq!(message["event"], "pusher:connection_established");
+
+    ban_manager
+        .ban(BanTarget::app_ip("app-1", localhost), "auth_failure")
+        .await
+        .unwrap();
+    let (mut ws, message) = connect(addr, "key-1").await;
+    assert_eq!(message["event"], "pusher:error");
+    assert_eq!(message["data"]["code"], 4101);
+    let frame = ws.read_frame().await.unwrap();
+    assert_eq!(frame.opcode, OpCode::Close);
+    assert_eq!(
+        u16::from_be_bytes([frame.payload[0], frame.payload[1]]),
+        4101
+    );
+}
+
+#[tokio::test]
+async fn test_untrusted_address_connects_without_ip_ban_check() {
+    // Behind two proxies, but the request carries no X-Forwarded-For
+    let mut options = ServerOptions::default();
+    options.rate_limiter.api_rate_limit.trust_hops = Some(2);
+    let (handler, ban_manager) = handler_with_bans_and_options(options).await;
+    let addr = serve(handler).await;
+    ban_manager
+        .ban(
+            BanTarget::app_ip("app-1", "127.0.0.1".parse().unwrap()),
+            "auth_failure",
+        )
+        .await
+        .unwrap();
+
+    let (_ws, message) = connect(addr, "key-1").await;
+    assert_eq!(message["event"], "pusher:connection_established");
+}
@@ -1,3 +1,5 @@
+pub mod auth_expiry_test;
 pub mod authentication_test;
+pub mod ban_test;
 pub mod signin_test;
 pub mod validation_test;
@@ -1,7 +1,8 @@
 use serde_json::json;
 use sockudo::adapter::handler::types::SignInRequest;
 use sockudo::app::auth::AuthValidator;
-use sockudo::app::config::App;
+use sockudo::app::config::{App, AppCredential, CredentialState};
+use sockudo::app::jwt_auth::sign_hs256;
 use sockudo::token::Token;
 use sockudo::websocket::SocketId;
 use std::sync::Arc;
@@ -214,6 +215,62 @@ async fn test_verify_signin_authentication_without_prefix() {
     assert!(result.is_ok(), "Should succeed with signature-only format");
 }
 
+#[tokio::test]
+async fn test_verify_signin_authentication_with_credential_key() {
+    let socket_id = SocketId::new();
+    let mut app = create_test_app();
+    app.credentials = Some(vec![AppCredential {
+        key: "previous-key".to_string(),
+        secret: "previous-secret".to_string(),
+        state: CredentialState::VerifyOnly,
+        expires_at: None,
+    }]);
+    let handler = create_test_connection_handler_with_app_manager(MockAppManager::new());
+
+    let user_data = json!({"id": "user-123"}).to_string();
+    let string_to_sign = format!("{}::user::{}", socket_id.0, user_data);
+    let signin = |key: &str, secret: &str| SignInRequest {
+        user_data: user_data.clone(),
+        auth: format!(
+            "{key}:{}",
+            Token::new(key.to_string(), secret.to_string()).sign(&string_to_sign)
+        ),
+    };
+
+    // The credential key verifies with its own secret
+    let request = signin("previous-key", "previous-secret");
+    assert!(
+        handler
+            .verify_signin_authentication(&socket_id, &app, &request)
+            .await
+            .is_ok()
+    );
+
+    // Secrets paired with other keys are rejected, as are unknown keys
+    for request in [
+        signin("previous-key", "test-app-secret"),
+        signin("test-app-key", "previous-secret"),
+        signin("unknown-key", "test-app-secret"),
+    ] {
+        assert!(
+            handler
+                .verify_signin_authentication(&socket_id, &app, &request)
+                .await
+                .is_err()
+        );
+    }
+
+    // Revoked credentials no longer sign in
+    app.credentials.as_mut().unwrap()[0].state = CredentialState::Revoked;
+    let request = signin("previous-key", "previous-secret");
+    assert!(
+        handler
+            .verify_signin_authentication(&socket_id, &app, &request)
+            .await
+            .is_err()
+    );
+}
+
 #[tokio::test]
 async fn test_auth_validator_sign_in_token_generation() {
     let app = create_test_app();
@@ -259,6 +316,46 @@ async fn test_verify_signin_authentication_with_invalid_signature() {
     assert!(result.is_err(), "Should fail with invalid signature");
 }
 
+#[tokio::test]
+async fn test_verify_signin_authentication_with_jwt() {
+    let socket_id = SocketId::new();
+    let app = App {
+        jwt_auth: Some(serde_json::from_value(json!({})).unwrap()),
+        ..create_test_app()
+    };
+    let handler = create_test_connection_handler_with_app_manager(MockAppManager::new());
+
+    let jwt = sign_hs256(
+        &app,
+        &json!({
+            "socket_id": socket_id.0,
+            "exp": chrono::Utc::now().timestamp() + 60,
+            "sub": "user-123"
+        }),
+    );
+
+    let request = SignInRequest {
+        user_data: json!({"id": "user-123"}).to_string(),
+        auth: jwt.clone(),
+    };
+    let result = handler
+        .verify_signin_authentication(&socket_id, &app, &request)
+        .await;
+    assert!(result.is_ok(), "Should accept a JWT for the signed-in user");
+
+    let request = SignInRequest {
+        user_data: json!({"id": "user-456"}).to_string(),
+        auth: jwt,
+    };
+    let result = handler
+        .verify_signin_authentication(&socket_id, &app, &request)
+        .await;
+    assert!(
+        result.is_err(),
+        "Should reject a JWT issued for another user"
+    );
+}
+
 #[tokio::test]
 async fn test_auth_validator_with_different_user_data() {
     let socket_id = SocketId::new();
@@ -506,5 +603,13 @@ fn create_test_app() -> App {
         webhooks: Some(vec![]),
         enable_watchlist_events: None,
         allowed_origins: None,
+        credentials: None,
+        channel_policy: None,
+        channel_limits: None,
+        quotas: None,
+        jwt_auth: None,
+        api_auth: None,
+        ip_filter: None,
+        authorizer: None,
     }
 }
@@ -137,6 +137,27 @@ mod client_event_validation_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_client_event_rejected_on_encrypted_channel() {
+        let (handler, _app_manager) = create_test_connection_handler();
+        let app = setup_test_app();
+
+        let request = ClientEventRequest {
+            event: "client-typing".to_string(),
+            channel: "private-encrypted-channel".to_string(),
+            data: json!({"nonce": "abc", "ciphertext": "def"}),
+        };
+
+        let result = handler.validate_client_event(&app, &request).await;
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Client events are not supported on encrypted channels")
+        );
+    }
+
     #[tokio::test]
     async fn test_client_event_allowed_on_private_channel() {
         let (handler, _app_manager) = create_test_connection_handler();
@@ -310,3 +331,101 @@ mod client_event_validation_tests {
         assert!(handler.validate_client_event(&app, &request).await.is_ok());
     }
 }
+
+#[cfg(test)]
+mod channel_policy_validation_tests {
+    use crate::mocks::connection_handler_mock::create_test_connection_handler;
+    use serde_json::json;
+    use sockudo::adapter::handler::types::SubscriptionRequest;
+    use sockudo::app::config::App;
+
+    fn request(channel: &str) -> SubscriptionRequest {
+        SubscriptionRequest {
+            channel: channel.to_string(),
+            auth: Some("test-key:signature".to_string()),
+            channel_data: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscription_rejected_by_channel_policy() {
+        let (handler, _app_manager) = create_test_connection_handler();
+        let app = App {
+            id: "test-app".to_string(),
+            key: "test-key".to_string(),
+            secret: "test-secret".to_string(),
+            enabled: true,
+            channel_policy: Some(
+                serde_json::from_value(json!({
+                    "disabled_types": ["public"],
+                    "rules": [{ "access": "allow", "pattern": "private-org-*" }],
+                    "default": "deny"
+                }))
+                .unwrap(),
+            ),
+            ..Default::default()
+        };
+
+        assert!(
+            handler
+                .validate_subscription_request(&app, &request("private-org-1"))
+                .await
+                .is_ok()
+        );
+
+        let err = handler
+            .validate_subscription_request(&app, &request("private-team-1"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.close_code(), 4305);
+
+        let err = handler
+            .validate_subscription_request(&app, &request("news"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.close_code(), 4304);
+    }
+}
+
+#[cfg(test)]
+mod channel_limit_validation_tests {
+    use crate::mocks::connection_handler_mock::create_test_connection_handler;
+    use serde_json::json;
+    use sockudo::adapter::handler::types::ClientEventRequest;
+    use sockudo::app::channel_limits::ChannelLimitRule;
+    use sockudo::app::config::App;
+
+    #[tokio::test]
+    async fn test_client_event_payload_limit_follows_channel_rule() {
+        let (handler, _app_manager) = create_test_connection_handler();
+        let app = App {
+            id: "test-app".to_string(),
+            key: "test-key".to_string(),
+            secret: "test-secret".to_string(),
+            enabled: true,
+            enable_client_messages: true,
+            max_event_payload_in_kb: Some(1),
+            channel_limits: Some(vec![ChannelLimitRule {
+                pattern: "private-telemetry-*".to_string(),
+                max_event_payload_in_kb: Some(64),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        let data = json!({ "samples": "x".repeat(4096) });
+
+        let request = ClientEventRequest {
+            event: "client-sample".to_string(),
+            channel: "private-telemetry-eu".to_string(),
+            data: data.clone(),
+        };
+        assert!(handler.validate_client_event(&app, &request).await.is_ok());
+
+        let request = ClientEventRequest {
+            event: "client-sample".to_string(),
+            channel: "private-chat".to_string(),
+            data,
+        };
+        assert!(handler.validate_client_event(&app, &request).await.is_err());
+    }
+}
@@ -338,6 +338,8 @@ impl MockTransport {
                 exists: true,
                 channels: HashSet::new(),
                 members_count: 999_999_999,
+                partial: false,
+                missing_nodes: Vec::new(),
             };
         }
 
@@ -352,6 +354,8 @@ impl MockTransport {
             exists: false,
             channels: HashSet::new(),
             members_count: 0,
+            partial: false,
+            missing_nodes: Vec::new(),
         };
 
         match request.request_type {
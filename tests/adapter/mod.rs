@@ -48,3 +48,6 @@ mod local_adapter_fallback_tests;
 
 #[cfg(test)]
 mod single_node_optimization_tests;
+
+#[cfg(test)]
+mod cluster_request_quorum_tests;
@@ -38,6 +38,7 @@ async fn create_redis_adapter(node_id: &str, cluster_config: &ClusterHealthConfi
         prefix: format!("test_cluster_health_{}", node_id),
         request_timeout_ms: 5000,
         cluster_mode: false,
+        ..Default::default()
     };
 
     let mut adapter = RedisAdapter::new(options).await.unwrap();
@@ -0,0 +1,146 @@
+use sockudo::error::{Error, Result};
+use sockudo::options::NatsQueueConfig;
+use sockudo::queue::QueueInterface;
+use sockudo::queue::nats_queue_manager::NatsQueueManager;
+use sockudo::webhook::sender::JobProcessorFnAsync;
+use sockudo::webhook::types::{JobData, JobPayload};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::test_helpers::*;
+
+type Deliveries = Arc<Mutex<Vec<(Instant, JobData)>>>;
+
+fn queue_config() -> NatsQueueConfig {
+    let adapter = get_nats_config();
+    NatsQueueConfig {
+        servers: adapter.servers,
+        prefix: adapter.prefix,
+        connection_timeout_ms: 1000,
+        concurrency: 1,
+        ack_wait_seconds: 30,
+        max_deliver: 3,
+        nak_delay_ms: 200,
+        ..Default::default()
+    }
+}
+
+fn job(app_id: &str) -> JobData {
+    JobData {
+        app_key: "test_key".to_string(),
+        app_id: app_id.to_string(),
+        app_secret: "test_secret".to_string(),
+        payload: JobPayload {
+            time_ms: chrono::Utc::now().timestamp_millis(),
+            events: vec![],
+        },
+        original_signature: "test_signature".to_string(),
+    }
+}
+
+/// Processor that fails its first `failures` deliveries and records every delivery
+fn recording_processor(failures: usize) -> (JobProcessorFnAsync, Deliveries) {
+    let deliveries: Deliveries = Arc::new(Mutex::new(Vec::new()));
+    let recorded = deliveries.clone();
+    let processor: JobProcessorFnAsync = Box::new(move |job_data| {
+        let recorded = recorded.clone();
+        Box::pin(async move {
+            let mut deliveries = recorded.lock().unwrap();
+            deliveries.push((Instant::now(), job_data));
+            if deliveries.len() <= failures {
+                Err(Error::Other("processor failed".to_string()))
+            } else {
+                Ok(())
+            }
+        })
+    });
+    (processor, deliveries)
+}
+
+async fn wait_for_deliveries(deliveries: &Deliveries, count: usize, timeout_ms: u64) -> usize {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    loop {
+        let delivered = deliveries.lock().unwrap().len();
+        if delivered >= count || Instant::now() >= deadline {
+            return delivered;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_processed_job_is_acked_once() -> Result<()> {
+    let manager = NatsQueueManager::new(queue_config()).await?;
+    let (processor, deliveries) = recording_processor(0);
+    manager.process_queue("webhooks", processor).await?;
+
+    manager.add_to_queue("webhooks", job("acked")).await?;
+
+    assert_eq!(wait_for_deliveries(&deliveries, 1, 2000).await, 1);
+    // An acked job is never delivered again
+    assert_eq!(wait_for_deliveries(&deliveries, 2, 1000).await, 1);
+    assert_eq!(deliveries.lock().unwrap()[0].1.app_id, "acked");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_job_is_nakd_with_delay() -> Result<()> {
+    let config = NatsQueueConfig {
+        nak_delay_ms: 500,
+        ..queue_config()
+    };
+    let manager = NatsQueueManager::new(config).await?;
+    let (processor, deliveries) = recording_processor(1);
+    manager.process_queue("webhooks", processor).await?;
+
+    manager.add_to_queue("webhooks", job("nak")).await?;
+
+    assert_eq!(wait_for_deliveries(&deliveries, 2, 5000).await, 2);
+    let deliveries = deliveries.lock().unwrap();
+    let gap = deliveries[1].0 - deliveries[0].0;
+    // Redelivered after the nak delay, well before the ack wait would expire
+    assert!(
+        gap >= Duration::from_millis(450),
+        "redelivered after {gap:?}"
+    );
+    assert!(gap < Duration::from_secs(5), "redelivered after {gap:?}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_job_is_terminated_after_max_deliver() -> Result<()> {
+    let manager = NatsQueueManager::new(queue_config()).await?;
+    let (processor, deliveries) = recording_processor(usize::MAX);
+    manager.process_queue("webhooks", processor).await?;
+
+    manager.add_to_queue("webhooks", job("term")).await?;
+
+    assert_eq!(wait_for_deliveries(&deliveries, 3, 5000).await, 3);
+    // Terminated on the third failure instead of being nak'd again
+    assert_eq!(wait_for_deliveries(&deliveries, 4, 1500).await, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_processor_error_redelivers_the_same_job() -> Result<()> {
+    let manager = NatsQueueManager::new(queue_config()).await?;
+    let (processor, deliveries) = recording_processor(2);
+    manager.process_queue("webhooks", processor).await?;
+
+    manager.add_to_queue("webhooks", job("retried")).await?;
+
+    assert_eq!(wait_for_deliveries(&deliveries, 3, 5000).await, 3);
+    // Succeeds on the last allowed delivery and is not delivered again
+    assert_eq!(wait_for_deliveries(&deliveries, 4, 1000).await, 3);
+    let deliveries = deliveries.lock().unwrap();
+    assert!(
+        deliveries
+            .iter()
+            .all(|(_, job_data)| job_data.app_id == "retried")
+    );
+
+    Ok(())
+}
@@ -28,6 +28,7 @@ async fn test_nats_transport_new_with_invalid_url() {
         password: None,
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // Add a timeout to prevent test from hanging
@@ -69,6 +70,72 @@ async fn test_publish_broadcast() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_publish_broadcast_jetstream() -> Result<()> {
+    let config = NatsAdapterConfig {
+        jetstream: true,
+        stream_max_messages: 100,
+        ..get_nats_config()
+    };
+    let transport = NatsTransport::new(config.clone()).await?;
+
+    let collector = MessageCollector::new();
+    let handlers = create_test_handlers(collector.clone());
+    transport.start_listeners(handlers).await?;
+
+    // Give the ordered consumer time to be created
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+    let broadcast = create_test_broadcast("jetstream-event");
+    transport.publish_broadcast(&broadcast).await?;
+
+    let received = collector.wait_for_broadcast(1000).await;
+    assert!(received.is_some());
+    assert!(received.unwrap().message.contains("jetstream-event"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_jetstream_resumes_after_restart_with_same_process_id() -> Result<()> {
+    let config = NatsAdapterConfig {
+        jetstream: true,
+        stream_max_messages: 100,
+        process_id: "node-a".to_string(),
+        ..get_nats_config()
+    };
+
+    // A previous run of node-a created its consumer and stopped before this was published
+    let previous_run = NatsTransport::new(config.clone()).await?;
+    previous_run.broadcast_consumer().await?;
+    previous_run
+        .publish_broadcast(&create_test_broadcast("missed-event"))
+        .await?;
+
+    let restarted = NatsTransport::new(config.clone()).await?;
+    let collector = MessageCollector::new();
+    restarted
+        .start_listeners(create_test_handlers(collector.clone()))
+        .await?;
+
+    let received = collector.wait_for_broadcast(1000).await;
+    assert!(received.unwrap().message.contains("missed-event"));
+
+    // A node with another process ID starts at new broadcasts only
+    let other = NatsTransport::new(NatsAdapterConfig {
+        process_id: "node-b".to_string(),
+        ..config
+    })
+    .await?;
+    let other_collector = MessageCollector::new();
+    other
+        .start_listeners(create_test_handlers(other_collector.clone()))
+        .await?;
+    assert!(other_collector.wait_for_broadcast(300).await.is_none());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_publish_request() -> Result<()> {
     let config = get_nats_config();
@@ -200,6 +267,7 @@ async fn test_subject_names() -> Result<()> {
         password: None,
         token: None,
         nodes_number: Some(2),
+        ..Default::default()
     };
 
     let transport = NatsTransport::new(config.clone()).await?;
@@ -317,6 +385,7 @@ async fn test_nats_with_no_credentials() -> Result<()> {
         password: None,
         token: None,
         nodes_number: Some(2),
+        ..Default::default()
     };
 
     let transport = NatsTransport::new(config).await?;
@@ -337,6 +406,7 @@ async fn test_nats_with_username_but_no_password() -> Result<()> {
         password: None, // Missing password
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // Our code should not set credentials if both username AND password aren't provided
@@ -358,6 +428,7 @@ async fn test_nats_with_password_but_no_username() -> Result<()> {
         password: Some("testpass".to_string()),
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // Our code should not set credentials if both username AND password aren't provided
@@ -379,6 +450,7 @@ async fn test_nats_token_takes_precedence_over_username_password() -> Result<()>
         password: Some("pass".to_string()),
         token: Some("fake_token".to_string()), // Token should take precedence
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // This tests our conditional logic: token is checked first, so username/password are ignored
@@ -405,6 +477,7 @@ async fn test_nats_empty_string_credentials() -> Result<()> {
         password: Some("".to_string()), // Empty string
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // Our code should still try to set credentials with empty strings
@@ -427,6 +500,7 @@ async fn test_nats_connection_timeout() -> Result<()> {
         password: None,
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // This should fail quickly due to short connection timeout
@@ -448,6 +522,7 @@ async fn test_nats_config_edge_cases() -> Result<()> {
         password: None,
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     let transport = NatsTransport::new(config).await?;
@@ -463,6 +538,7 @@ async fn test_nats_config_edge_cases() -> Result<()> {
         password: None,
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     // This should still work - our code doesn't validate timeouts
@@ -479,6 +555,7 @@ async fn test_nats_config_edge_cases() -> Result<()> {
         password: None,
         token: None,
         nodes_number: Some(0), // Zero nodes
+        ..Default::default()
     };
 
     let transport = NatsTransport::new(config).await?;
@@ -502,6 +579,7 @@ async fn test_nats_invalid_config() {
         password: None,
         token: None,
         nodes_number: Some(1),
+        ..Default::default()
     };
 
     let result = NatsTransport::new(config).await;
@@ -526,6 +604,7 @@ async fn test_nats_invalid_config() {
             password: None,
             token: None,
             nodes_number: Some(1),
+            ..Default::default()
         };
 
         let result = tokio::time::timeout(
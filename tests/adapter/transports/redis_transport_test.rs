@@ -1,6 +1,7 @@
 use sockudo::adapter::horizontal_transport::HorizontalTransport;
 use sockudo::adapter::transports::{RedisAdapterConfig, RedisTransport};
 use sockudo::error::Result;
+use sockudo::options::RedisTransportMode;
 
 use super::test_helpers::*;
 
@@ -23,6 +24,7 @@ async fn test_redis_transport_new_with_invalid_url() {
         prefix: "test".to_string(),
         request_timeout_ms: 1000,
         cluster_mode: false,
+        ..Default::default()
     };
 
     // Add a timeout to prevent test from hanging
@@ -44,6 +46,7 @@ async fn test_redis_transport_config_edge_cases() -> Result<()> {
         prefix: "".to_string(), // Empty prefix
         request_timeout_ms: 1000,
         cluster_mode: false,
+        ..Default::default()
     };
 
     let transport = RedisTransport::new(config).await?;
@@ -55,6 +58,7 @@ async fn test_redis_transport_config_edge_cases() -> Result<()> {
         prefix: "test_short_timeout".to_string(),
         request_timeout_ms: 1, // 1ms timeout
         cluster_mode: false,
+        ..Default::default()
     };
 
     let transport = RedisTransport::new(config).await?;
@@ -66,6 +70,7 @@ async fn test_redis_transport_config_edge_cases() -> Result<()> {
         prefix: "test_zero_timeout".to_string(),
         request_timeout_ms: 0, // Zero timeout
         cluster_mode: false,
+        ..Default::default()
     };
 
     let transport = RedisTransport::new(config).await?;
@@ -92,6 +97,7 @@ async fn test_redis_transport_malformed_url() {
             prefix: "test".to_string(),
             request_timeout_ms: 1000,
             cluster_mode: false,
+            ..Default::default()
         };
 
         let result = tokio::time::timeout(
@@ -137,6 +143,66 @@ async fn test_publish_broadcast() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_publish_broadcast_streams_mode() -> Result<()> {
+    let config = RedisAdapterConfig {
+        transport_mode: RedisTransportMode::Streams,
+        stream_max_len: 100,
+        ..get_redis_config()
+    };
+    let transport = RedisTransport::new(config.clone()).await?;
+
+    let collector = MessageCollector::new();
+    let handlers = create_test_handlers(collector.clone());
+    transport.start_listeners(handlers).await?;
+
+    // Give the stream reader time to find the tail
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let broadcast = create_test_broadcast("stream-event");
+    transport.publish_broadcast(&broadcast).await?;
+
+    let received = collector.wait_for_broadcast(1000).await;
+    assert!(received.is_some());
+    assert!(received.unwrap().message.contains("stream-event"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_streams_mode_resumes_from_the_stored_position() -> Result<()> {
+    let config = RedisAdapterConfig {
+        transport_mode: RedisTransportMode::Streams,
+        process_id: "node-a".to_string(),
+        ..get_redis_config()
+    };
+    let transport = RedisTransport::new(config.clone()).await?;
+
+    // A previous run of node-a stopped before anything was published
+    let client = redis::Client::open(config.url.as_str()).unwrap();
+    let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+    let _: () = redis::AsyncCommands::set(
+        &mut conn,
+        format!("{}:#broadcast-stream:cursor:node-a", config.prefix),
+        "0-0",
+    )
+    .await
+    .unwrap();
+    transport
+        .publish_broadcast(&create_test_broadcast("missed-event"))
+        .await?;
+
+    let collector = MessageCollector::new();
+    transport
+        .start_listeners(create_test_handlers(collector.clone()))
+        .await?;
+
+    let received = collector.wait_for_broadcast(1000).await;
+    assert!(received.unwrap().message.contains("missed-event"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_publish_request() -> Result<()> {
     let config = get_redis_config();
@@ -269,6 +335,7 @@ async fn test_channel_names() -> Result<()> {
         prefix: "custom_prefix".to_string(),
         request_timeout_ms: 1000,
         cluster_mode: false,
+        ..Default::default()
     };
 
     let transport = RedisTransport::new(config.clone()).await?;
@@ -19,6 +19,7 @@ pub fn get_redis_config() -> RedisAdapterConfig {
         prefix: format!("test_{}", Uuid::new_v4().to_string().replace('-', "")),
         request_timeout_ms: 1000, // Reduced timeout
         cluster_mode: false,
+        ..Default::default()
     }
 }
 
@@ -52,6 +53,7 @@ pub fn get_nats_config() -> NatsAdapterConfig {
         password: None,
         token: None,
         nodes_number: Some(2),
+        ..Default::default()
     }
 }
 
@@ -84,6 +86,7 @@ pub fn create_test_request() -> RequestBody {
         timestamp: None,
         dead_node_id: None,
         target_node_id: None,
+        stats: None,
     }
 }
 
@@ -101,6 +104,8 @@ pub fn create_test_response(request_id: &str) -> ResponseBody {
         exists: false,
         channels: HashSet::new(),
         members_count: 0,
+        partial: false,
+        missing_nodes: Vec::new(),
     }
 }
 
@@ -256,6 +261,8 @@ pub fn create_test_handlers(
                     exists: false,
                     channels: HashSet::new(),
                     members_count: 0,
+                    partial: false,
+                    missing_nodes: Vec::new(),
                 })
             }) as BoxFuture<'static, sockudo::error::Result<ResponseBody>>
         }),
@@ -1,15 +1,19 @@
 use chrono::Utc;
 use sockudo::adapter::handler::types::SignInRequest;
-use sockudo::app::auth::AuthValidator;
-use sockudo::app::config::App;
+use sockudo::app::admin::{DEFAULT_SECRET_GRACE_PERIOD, rotate_app_credentials};
+use sockudo::app::auth::{ApiAuthConfig, AuthValidator};
+use sockudo::app::config::{App, AppCredential, CredentialState};
 use sockudo::app::manager::AppManager;
 use sockudo::app::memory_app_manager::MemoryAppManager;
+use sockudo::cache::memory_cache_manager::MemoryCacheManager;
 use sockudo::error::Error;
 use sockudo::http_handler::EventQuery;
+use sockudo::options::MemoryCacheOptions;
 use sockudo::token::Token;
 use sockudo::websocket::SocketId;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use tokio::sync::Mutex;
 
 mod mocks;
 use mocks::connection_handler_mock::{
@@ -39,6 +43,14 @@ async fn create_test_app_manager() -> Arc<dyn AppManager> {
         webhooks: Some(vec![]),
         enable_watchlist_events: None,
         allowed_origins: None,
+        credentials: None,
+        channel_policy: None,
+        channel_limits: None,
+        quotas: None,
+        jwt_auth: None,
+        api_auth: None,
+        ip_filter: None,
+        authorizer: None,
     };
     manager.create_app(app).await.unwrap();
     Arc::new(manager)
@@ -120,6 +132,14 @@ async fn test_validate_channel_auth_with_app_key_prefix() {
         webhooks: Some(vec![]),
         enable_watchlist_events: None,
         allowed_origins: None,
+        credentials: None,
+        channel_policy: None,
+        channel_limits: None,
+        quotas: None,
+        jwt_auth: None,
+        api_auth: None,
+        ip_filter: None,
+        authorizer: None,
     };
 
     // Create mock app manager and configure it
@@ -397,6 +417,176 @@ async fn test_api_auth_invalid_signature() {
     }
 }
 
+#[tokio::test]
+async fn test_api_auth_accepts_non_revoked_credential_secrets() {
+    let manager = MemoryAppManager::new();
+    let app = App {
+        id: "rotated-app-id".to_string(),
+        key: "rotated-app-key".to_string(),
+        secret: "new-secret".to_string(),
+        enabled: true,
+        credentials: Some(vec![
+            AppCredential {
+                key: String::new(),
+                secret: "previous-secret".to_string(),
+                state: CredentialState::VerifyOnly,
+                expires_at: None,
+            },
+            AppCredential {
+                key: String::new(),
+                secret: "leaked-secret".to_string(),
+                state: CredentialState::Revoked,
+                expires_at: None,
+            },
+            AppCredential {
+                key: String::new(),
+                secret: "expired-secret".to_string(),
+                state: CredentialState::Active,
+                expires_at: Some(1),
+            },
+        ]),
+        ..Default::default()
+    };
+    manager.create_app(app).await.unwrap();
+    let auth_validator = AuthValidator::new(Arc::new(manager));
+
+    let current_timestamp = Utc::now().timestamp().to_string();
+    let mut query_params = BTreeMap::new();
+    query_params.insert("auth_key".to_string(), "rotated-app-key".to_string());
+    query_params.insert("auth_timestamp".to_string(), current_timestamp.clone());
+    query_params.insert("auth_version".to_string(), "1.0".to_string());
+
+    for (secret, accepted) in [
+        ("new-secret", true),
+        ("previous-secret", true),
+        ("leaked-secret", false),
+        ("expired-secret", false),
+    ] {
+        let auth_query = EventQuery {
+            auth_key: "rotated-app-key".to_string(),
+            auth_timestamp: current_timestamp.clone(),
+            auth_version: "1.0".to_string(),
+            body_md5: "".to_string(),
+            auth_signature: generate_valid_signature(
+                "rotated-app-key",
+                secret,
+                "GET",
+                "/apps/rotated-app-id/channels",
+                &query_params,
+            ),
+        };
+
+        let result = auth_validator
+            .validate_pusher_api_request(
+                &auth_query,
+                "GET",
+                "/apps/rotated-app-id/channels",
+                &query_params,
+                None,
+            )
+            .await;
+        assert_eq!(result.is_ok(), accepted, "secret {secret}");
+    }
+}
+
+#[tokio::test]
+async fn test_api_auth_accepts_non_revoked_credential_keys() {
+    let credential = |key: &str, secret: &str, state| AppCredential {
+        key: key.to_string(),
+        secret: secret.to_string(),
+        state,
+        expires_at: None,
+    };
+    let manager = MemoryAppManager::new();
+    let app = App {
+        id: "rotated-app-id".to_string(),
+        key: "new-key".to_string(),
+        secret: "new-secret".to_string(),
+        enabled: true,
+        credentials: Some(vec![
+            credential(
+                "previous-key",
+                "previous-secret",
+                CredentialState::VerifyOnly,
+            ),
+            credential("leaked-key", "leaked-secret", CredentialState::Revoked),
+        ]),
+        ..Default::default()
+    };
+    manager.create_app(app).await.unwrap();
+    let auth_validator = AuthValidator::new(Arc::new(manager));
+
+    let current_timestamp = Utc::now().timestamp().to_string();
+    for (key, secret, accepted) in [
+        ("new-key", "new-secret", true),
+        ("previous-key", "previous-secret", true),
+        // Each key only verifies with its own secret
+        ("previous-key", "new-secret", false),
+        ("new-key", "previous-secret", false),
+        ("leaked-key", "leaked-secret", false),
+    ] {
+        let mut query_params = BTreeMap::new();
+        query_params.insert("auth_key".to_string(), key.to_string());
+        query_params.insert("auth_timestamp".to_string(), current_timestamp.clone());
+        query_params.insert("auth_version".to_string(), "1.0".to_string());
+        let auth_query = EventQuery {
+            auth_key: key.to_string(),
+            auth_timestamp: current_timestamp.clone(),
+            auth_version: "1.0".to_string(),
+            body_md5: "".to_string(),
+            auth_signature: generate_valid_signature(
+                key,
+                secret,
+                "GET",
+                "/apps/rotated-app-id/channels",
+                &query_params,
+            ),
+        };
+
+        let result = auth_validator
+            .validate_pusher_api_request(
+                &auth_query,
+                "GET",
+                "/apps/rotated-app-id/channels",
+                &query_params,
+                None,
+            )
+            .await;
+        assert_eq!(result.is_ok(), accepted, "{key}/{secret}");
+    }
+}
+
+#[test]
+fn test_signing_uses_app_secret_across_rotation() {
+    let mut app = App {
+        key: "old-key".to_string(),
+        secret: "old-secret".to_string(),
+        ..Default::default()
+    };
+    let signed_with = |key: &str, secret: &str| Token::new(key.to_string(), secret.into());
+
+    rotate_app_credentials(&mut app, DEFAULT_SECRET_GRACE_PERIOD, true);
+    assert_ne!(app.key, "old-key");
+    assert_ne!(app.secret, "old-secret");
+
+    // Sockudo signs with the new primary secret only
+    assert_eq!(
+        app.token().sign("input"),
+        signed_with(&app.key, &app.secret).sign("input")
+    );
+
+    // The previous pair keeps verifying under its own key during the grace period
+    assert!(app.accepts_key("old-key"));
+    let previous = app.token_for_key("old-key").unwrap();
+    assert!(previous.verify("input", &signed_with("old-key", "old-secret").sign("input")));
+    assert!(!previous.verify("input", &signed_with("old-key", &app.secret).sign("input")));
+
+    // Without a grace period the previous pair is revoked
+    rotate_app_credentials(&mut app, std::time::Duration::ZERO, false);
+    assert!(!app.accepts_key("old-key"));
+    assert!(app.token_for_key("old-key").is_none());
+}
+
 #[tokio::test]
 async fn test_api_auth_post_with_body_md5() {
     let app_manager = create_test_app_manager().await;
@@ -510,19 +700,37 @@ async fn test_sign_in_token_generation() {
         webhooks: Some(vec![]),
         enable_watchlist_events: None,
         allowed_origins: None,
+        credentials: None,
+        channel_policy: None,
+        channel_limits: None,
+        quotas: None,
+        jwt_auth: None,
+        api_auth: None,
+        ip_filter: None,
+        authorizer: None,
     };
 
     let signature =
         auth_validator.sign_in_token_for_user_data(socket_id, user_data, app_config.clone());
 
     // Verify the signature is valid
-    let is_valid =
-        auth_validator.sign_in_token_is_valid(socket_id, user_data, &signature, app_config.clone());
+    let is_valid = auth_validator.sign_in_token_is_valid(
+        socket_id,
+        "test-key",
+        user_data,
+        &signature,
+        app_config.clone(),
+    );
     assert!(is_valid);
 
     // Verify invalid signature fails
-    let is_invalid =
-        auth_validator.sign_in_token_is_valid(socket_id, user_data, "wrong-signature", app_config);
+    let is_invalid = auth_validator.sign_in_token_is_valid(
+        socket_id,
+        "test-key",
+        user_data,
+        "wrong-signature",
+        app_config,
+    );
     assert!(!is_invalid);
 }
 
@@ -853,3 +1061,216 @@ async fn test_api_auth_post_no_body_with_body_md5_should_fail() {
         _ => panic!("Expected Auth error for body_md5 with no body"),
     }
 }
+
+#[tokio::test]
+async fn test_sign_in_token_with_expiry() {
+    let auth_validator = AuthValidator::new(create_test_app_manager().await);
+    let socket_id = "12345.67890";
+    let user_data = r#"{"id":"user-1"}"#;
+    let app_config = App {
+        key: "test-key".to_string(),
+        secret: "test-secret".to_string(),
+        ..Default::default()
+    };
+    let token = Token::new(app_config.key.clone(), app_config.secret.clone());
+    let signed = |expires_at: i64| {
+        let signature = token.sign(&format!("{socket_id}::user::{user_data}:{expires_at}"));
+        format!("{signature}:{expires_at}")
+    };
+
+    let future = Utc::now().timestamp() + 60;
+    assert!(auth_validator.sign_in_token_is_valid(
+        socket_id,
+        "test-key",
+        user_data,
+        &signed(future),
+        app_config.clone()
+    ));
+
+    // The expiry is part of what is signed
+    let tampered = signed(future).replace(&future.to_string(), &(future + 3600).to_string());
+    assert!(!auth_validator.sign_in_token_is_valid(
+        socket_id,
+        "test-key",
+        user_data,
+        &tampered,
+        app_config.clone()
+    ));
+
+    let past = Utc::now().timestamp() - 1;
+    assert!(!auth_validator.sign_in_token_is_valid(
+        socket_id,
+        "test-key",
+        user_data,
+        &signed(past),
+        app_config
+    ));
+}
+
+async fn create_api_auth_validator(api_auth: ApiAuthConfig) -> AuthValidator {
+    create_api_auth_validator_with_cache(api_auth, MemoryCacheOptions::default()).await
+}
+
+async fn create_api_auth_validator_with_cache(
+    api_auth: ApiAuthConfig,
+    cache_options: MemoryCacheOptions,
+) -> AuthValidator {
+    let manager = MemoryAppManager::new();
+    manager
+        .create_app(App {
+            id: "test-app-id".to_string(),
+            key: "test-app-key".to_string(),
+            secret: "test-app-secret".to_string(),
+            enabled: true,
+            api_auth: Some(api_auth),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let cache = MemoryCacheManager::new("test".to_string(), cache_options);
+    AuthValidator::new(Arc::new(manager)).with_replay_cache(Arc::new(Mutex::new(cache)))
+}
+
+fn signed_api_query(timestamp: i64) -> (EventQuery, BTreeMap<String, String>) {
+    let mut query_params = BTreeMap::new();
+    query_params.insert("auth_key".to_string(), "test-app-key".to_string());
+    query_params.insert("auth_timestamp".to_string(), timestamp.to_string());
+    query_params.insert("auth_version".to_string(), "1.0".to_string());
+    let signature = generate_valid_signature(
+        "test-app-key",
+        "test-app-secret",
+        "GET",
+        "/apps/test-app-id/channels",
+        &query_params,
+    );
+    let auth_query = EventQuery {
+        auth_key: "test-app-key".to_string(),
+        auth_timestamp: timestamp.to_string(),
+        auth_version: "1.0".to_string(),
+        body_md5: "".to_string(),
+        auth_signature: signature,
+    };
+    (auth_query, query_params)
+}
+
+#[tokio::test]
+async fn test_api_auth_rejects_replayed_signature() {
+    let auth_validator = create_api_auth_validator(ApiAuthConfig {
+        timestamp_skew_seconds: None,
+        replay_protection: true,
+    })
+    .await;
+    let (auth_query, query_params) = signed_api_query(Utc::now().timestamp());
+
+    let validate = || {
+        auth_validator.validate_pusher_api_request(
+            &auth_query,
+            "GET",
+            "/apps/test-app-id/channels",
+            &query_params,
+            None,
+        )
+    };
+    assert!(validate().await.unwrap());
+    assert!(matches!(validate().await, Err(Error::Auth(_))));
+}
+
+#[tokio::test]
+async fn test_api_auth_rejects_replay_after_memory_cache_ttl() {
+    // Claims must outlive the cache-wide TTL and capacity for the whole timestamp window
+    let auth_validator = create_api_auth_validator_with_cache(
+        ApiAuthConfig {
+            timestamp_skew_seconds: None,
+            replay_protection: true,
+        },
+        MemoryCacheOptions {
+            ttl: 1,
+            cleanup_interval: 1,
+            max_capacity: 1,
+        },
+    )
+    .await;
+    let (auth_query, query_params) = signed_api_query(Utc::now().timestamp());
+
+    let validate = || {
+        auth_validator.validate_pusher_api_request(
+            &auth_query,
+            "GET",
+            "/apps/test-app-id/channels",
+            &query_params,
+            None,
+        )
+    };
+    assert!(validate().await.unwrap());
+    for timestamp in 1..4 {
+        let (other_query, other_params) = signed_api_query(Utc::now().timestamp() - timestamp);
+        auth_validator
+            .validate_pusher_api_request(
+                &other_query,
+                "GET",
+                "/apps/test-app-id/channels",
+                &other_params,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert!(matches!(validate().await, Err(Error::Auth(_))));
+}
+
+#[tokio::test]
+async fn test_api_auth_accepts_concurrent_replays_once() {
+    let auth_validator = create_api_auth_validator(ApiAuthConfig {
+        timestamp_skew_seconds: None,
+        replay_protection: true,
+    })
+    .await;
+    let (auth_query, query_params) = signed_api_query(Utc::now().timestamp());
+
+    let results = futures::future::join_all((0..16).map(|_| {
+        auth_validator.validate_pusher_api_request(
+            &auth_query,
+            "GET",
+            "/apps/test-app-id/channels",
+            &query_params,
+            None,
+        )
+    }))
+    .await;
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+}
+
+#[tokio::test]
+async fn test_api_auth_uses_app_timestamp_skew() {
+    let auth_validator = create_api_auth_validator(ApiAuthConfig {
+        timestamp_skew_seconds: Some(30),
+        replay_protection: false,
+    })
+    .await;
+
+    let (auth_query, query_params) = signed_api_query(Utc::now().timestamp() - 20);
+    let result = auth_validator
+        .validate_pusher_api_request(
+            &auth_query,
+            "GET",
+            "/apps/test-app-id/channels",
+            &query_params,
+            None,
+        )
+        .await;
+    assert!(result.unwrap());
+
+    // Within the default window of 600 seconds, but outside the app's
+    let (auth_query, query_params) = signed_api_query(Utc::now().timestamp() - 120);
+    let result = auth_validator
+        .validate_pusher_api_request(
+            &auth_query,
+            "GET",
+            "/apps/test-app-id/channels",
+            &query_params,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(Error::Auth(_))));
+}
@@ -58,6 +58,14 @@ mod tests {
             webhooks: Some(vec![]),
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         };
 
         app_manager.create_app(test_app).await.unwrap();
@@ -124,6 +132,14 @@ mod tests {
             webhooks: Some(vec![webhook_config]),
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         };
 
         app_manager.create_app(test_app).await.unwrap();
@@ -558,6 +574,14 @@ mod tests {
             webhooks: Some(vec![]),
             enable_watchlist_events: None,
             allowed_origins: None,
+            credentials: None,
+            channel_policy: None,
+            channel_limits: None,
+            quotas: None,
+            jwt_auth: None,
+            api_auth: None,
+            ip_filter: None,
+            authorizer: None,
         };
         app_manager.create_app(test_app).await.unwrap();
 
@@ -0,0 +1,248 @@
+use sockudo::app::cached_app_manager::CachedAppManager;
+use sockudo::app::factory::AppManagerFactory;
+use sockudo::app::file_app_manager::FileAppManager;
+use sockudo::app::manager::{AppChange, AppManager};
+use sockudo::cache::memory_cache_manager::MemoryCacheManager;
+use sockudo::options::{
+    AppManagerConfig, AppManagerDriver, CacheSettings, DatabaseConfig, DatabasePooling,
+    FileAppManagerConfig, MemoryCacheOptions,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sockudo-apps-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn config(path: &std::path::Path, watch: bool) -> FileAppManagerConfig {
+    FileAppManagerConfig {
+        path: path.to_string_lossy().into_owned(),
+        watch,
+        debounce_ms: 50,
+    }
+}
+
+const TWO_APPS: &str = r#"[
+    { "id": "app-1", "key": "key-1", "secret": "secret-1", "enabled": true, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10 },
+    { "id": "app-2", "key": "key-2", "secret": "secret-2", "enabled": true, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10 }
+]"#;
+
+#[tokio::test]
+async fn test_loads_json_file() {
+    let dir = temp_dir();
+    let path = dir.join("apps.json");
+    std::fs::write(&path, TWO_APPS).unwrap();
+
+    let manager = FileAppManager::new(config(&path, false)).unwrap();
+
+    assert_eq!(manager.get_apps().await.unwrap().len(), 2);
+    let app = manager.find_by_key("key-1").await.unwrap().unwrap();
+    assert_eq!(app.id, "app-1");
+    assert_eq!(app.max_connections, 100);
+    assert!(manager.find_by_id("missing").await.unwrap().is_none());
+    assert!(manager.delete_app("app-1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_loads_yaml_and_toml_from_directory() {
+    let dir = temp_dir();
+    std::fs::write(
+        dir.join("a.yaml"),
+        "apps:\n  - id: yaml-app\n    key: yaml-key\n    secret: yaml-secret\n    enabled: true\n    max_connections: 100\n    enable_client_messages: false\n    max_client_events_per_second: 10\n    max_event_payload_in_kb: 20\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("b.toml"),
+        "[[apps]]\nid = \"toml-app\"\nkey = \"toml-key\"\nsecret = \"toml-secret\"\nenabled = true\nmax_connections = 100\nenable_client_messages = false\nmax_client_events_per_second = 10\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("README.md"), "ignored").unwrap();
+
+    let manager = FileAppManager::new(config(&dir, false)).unwrap();
+
+    let yaml_app = manager.find_by_id("yaml-app").await.unwrap().unwrap();
+    assert_eq!(yaml_app.max_event_payload_in_kb, Some(20));
+    assert!(manager.find_by_key("toml-key").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_and_duplicate_apps() {
+    let dir = temp_dir();
+    let path = dir.join("apps.yaml");
+    let app = |id: &str, key: &str| {
+        format!(
+            "- id: {id}\n  key: {key}\n  secret: secret\n  enabled: true\n  max_connections: 100\n  enable_client_messages: false\n  max_client_events_per_second: 10\n"
+        )
+    };
+
+    std::fs::write(&path, app("bad/id", "key-1")).unwrap();
+    let error = FileAppManager::new(config(&path, false)).err().unwrap();
+    assert!(error.to_string().contains("id may only contain"));
+
+    std::fs::write(&path, app("app-1", "key-1") + &app("app-2", "key-1")).unwrap();
+    let error = FileAppManager::new(config(&path, false)).err().unwrap();
+    assert!(error.to_string().contains("Duplicate app key"));
+}
+
+#[tokio::test]
+async fn test_finds_apps_by_accepted_credential_keys() {
+    let dir = temp_dir();
+    let path = dir.join("apps.json");
+    let app = |credentials: &str| {
+        format!(
+            r#"[{{ "id": "app-1", "key": "key-1", "secret": "secret-1", "enabled": true, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10, "credentials": {credentials} }}]"#
+        )
+    };
+    std::fs::write(
+        &path,
+        app(r#"[
+            { "key": "previous-key", "secret": "previous-secret", "state": "verify_only" },
+            { "key": "leaked-key", "secret": "leaked-secret", "state": "revoked" }
+        ]"#),
+    )
+    .unwrap();
+
+    let manager = FileAppManager::new(config(&path, false)).unwrap();
+    let found = manager.find_by_key("previous-key").await.unwrap().unwrap();
+    assert_eq!(found.id, "app-1");
+    assert!(manager.find_by_key("leaked-key").await.unwrap().is_none());
+
+    // A credential key may not belong to another app
+    std::fs::write(
+        &path,
+        r#"[
+            { "id": "app-1", "key": "key-1", "secret": "secret-1", "enabled": true, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10, "credentials": [{ "key": "previous-key", "secret": "previous-secret" }] },
+            { "id": "app-2", "key": "previous-key", "secret": "secret-2", "enabled": true, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10 }
+        ]"#,
+    )
+    .unwrap();
+    let error = FileAppManager::new(config(&path, false)).err().unwrap();
+    assert!(error.to_string().contains("Duplicate app key previous-key"));
+}
+
+#[tokio::test]
+async fn test_reload_reports_changes_and_keeps_apps_on_error() {
+    let dir = temp_dir();
+    let path = dir.join("apps.json");
+    std::fs::write(&path, TWO_APPS).unwrap();
+    let manager = FileAppManager::new(config(&path, false)).unwrap();
+    let mut changes = manager.subscribe_changes().unwrap();
+
+    std::fs::write(
+        &path,
+        r#"[
+            { "id": "app-1", "key": "key-1", "secret": "secret-1", "enabled": false, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10 },
+            { "id": "app-3", "key": "key-3", "secret": "secret-3", "enabled": true, "max_connections": 100, "enable_client_messages": false, "max_client_events_per_second": 10 }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(manager.reload().unwrap().len(), 3);
+
+    let mut created = Vec::new();
+    let mut deleted = Vec::new();
+    let mut disabled = Vec::new();
+    for _ in 0..3 {
+        match changes.recv().await.unwrap() {
+            AppChange::Created(app) => created.push(app.id),
+            AppChange::Deleted(app) => deleted.push(app.id),
+            AppChange::Updated { previous, current } => {
+                assert!(previous.enabled && !current.enabled);
+                disabled.push(current.id);
+            }
+            AppChange::Invalidated { app_id } => panic!("unexpected invalidation of {app_id}"),
+        }
+    }
+    assert_eq!(created, vec!["app-3"]);
+    assert_eq!(deleted, vec!["app-2"]);
+    assert_eq!(disabled, vec!["app-1"]);
+
+    std::fs::write(&path, "[{ not json").unwrap();
+    assert!(manager.reload().is_err());
+    assert!(manager.find_by_id("app-3").await.unwrap().is_some());
+    assert!(manager.find_by_id("app-2").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_watch_reloads_after_file_change() {
+    let dir = temp_dir();
+    let path = dir.join("apps.yml");
+    std::fs::write(
+        &path,
+        "- id: app-1\n  key: key-1\n  secret: secret-1\n  enabled: true\n  max_connections: 100\n  enable_client_messages: false\n  max_client_events_per_second: 10\n",
+    )
+    .unwrap();
+    let manager = Arc::new(FileAppManager::new(config(&path, true)).unwrap());
+    manager.start_watching().unwrap();
+    let mut changes = manager.subscribe_changes().unwrap();
+
+    std::fs::write(&path, "[]").unwrap();
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+        .await
+        .expect("reload after file change")
+        .unwrap();
+    assert!(matches!(change, AppChange::Deleted(app) if app.id == "app-1"));
+    assert!(manager.get_apps().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_cached_manager_invalidates_before_forwarding_changes() {
+    let dir = temp_dir();
+    let path = dir.join("apps.json");
+    std::fs::write(&path, TWO_APPS).unwrap();
+    let file_manager = Arc::new(FileAppManager::new(config(&path, false)).unwrap());
+    let cache = Arc::new(Mutex::new(MemoryCacheManager::new(
+        "test".to_string(),
+        MemoryCacheOptions::default(),
+    )));
+    let cached = CachedAppManager::new(
+        file_manager.clone(),
+        cache,
+        CacheSettings {
+            enabled: true,
+            ttl: 300,
+        },
+    );
+    cached.init().await.unwrap();
+    let mut changes = cached.subscribe_changes().unwrap();
+
+    assert!(cached.find_by_id("app-1").await.unwrap().unwrap().enabled);
+
+    std::fs::write(
+        &path,
+        TWO_APPS.replacen("\"enabled\": true", "\"enabled\": false", 1),
+    )
+    .unwrap();
+    file_manager.reload().unwrap();
+
+    let change = changes.recv().await.unwrap();
+    assert!(matches!(change, AppChange::Updated { ref current, .. } if current.id == "app-1"));
+    assert!(!cached.find_by_id("app-1").await.unwrap().unwrap().enabled);
+}
+
+#[tokio::test]
+async fn test_create_store_fails_without_memory_fallback() {
+    let app_manager_config = AppManagerConfig {
+        driver: AppManagerDriver::File,
+        file: config(&temp_dir().join("missing.json"), false),
+        ..Default::default()
+    };
+    let database = DatabaseConfig::default();
+    let pooling = DatabasePooling::default();
+    let create = |allow_memory_fallback| {
+        AppManagerFactory::create_store(
+            &app_manager_config,
+            &database,
+            &pooling,
+            allow_memory_fallback,
+        )
+    };
+
+    assert!(create(false).await.is_err());
+    let fallback = create(true).await.unwrap();
+    assert!(fallback.get_apps().await.unwrap().is_empty());
+}
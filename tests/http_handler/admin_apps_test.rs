@@ -0,0 +1,345 @@
+use crate::mocks::connection_handler_mock::{MockAdapter, MockCacheManager, MockMetricsInterface};
+use axum::Json;
+use axum::body::to_bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde_json::{Value, json};
+use sockudo::adapter::handler::ConnectionHandler;
+use sockudo::app::admin::AppChanges;
+use sockudo::app::manager::AppManager;
+use sockudo::app::memory_app_manager::MemoryAppManager;
+use sockudo::http_handler::{
+    ClientIp, RotateSecretQuery, admin_create_app, admin_delete_app, admin_get_app,
+    admin_get_app_usage, admin_list_apps, admin_rotate_app_secret, admin_update_app,
+};
+use sockudo::options::ServerOptions;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+fn create_handler() -> Arc<ConnectionHandler> {
+    Arc::new(ConnectionHandler::new(
+        Arc::new(MemoryAppManager::new()) as Arc<dyn AppManager + Send + Sync>,
+        Arc::new(Mutex::new(MockAdapter::new())),
+        Arc::new(Mutex::new(MockCacheManager::new())),
+        Some(Arc::new(Mutex::new(MockMetricsInterface::new()))),
+        None,
+        ServerOptions::default(),
+        None,
+    ))
+}
+
+fn changes(value: Value) -> Json<AppChanges> {
+    Json(serde_json::from_value(value).expect("valid app changes"))
+}
+
+async fn into_parts(response: Response) -> (StatusCode, Value) {
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let value = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&body).unwrap()
+    };
+    (status, value)
+}
+
+#[tokio::test]
+async fn test_create_app_generates_credentials() {
+    let handler = create_handler();
+
+    let response = admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "max_connections": 50, "max_event_payload_in_kb": 20 })),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    let (status, body) = into_parts(response).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    let app_id = body["id"].as_str().unwrap();
+    assert!(!app_id.is_empty());
+    assert_eq!(body["key"].as_str().unwrap().len(), 20);
+    assert_eq!(body["secret"].as_str().unwrap().len(), 64);
+    assert_eq!(body["enabled"], true);
+
+    let stored = handler.app_manager().find_by_id(app_id).await.unwrap();
+    let stored = stored.expect("app was stored");
+    assert_eq!(stored.max_connections, 50);
+    assert_eq!(stored.max_event_payload_in_kb, Some(20));
+}
+
+#[tokio::test]
+async fn test_list_and_get_hide_secrets() {
+    let handler = create_handler();
+    admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "id": "app-1", "key": "key-1" })),
+    )
+    .await
+    .unwrap();
+
+    let response = admin_list_apps(State(handler.clone()))
+        .await
+        .unwrap()
+        .into_response();
+    let (status, body) = into_parts(response).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["apps"][0]["id"], "app-1");
+    assert!(body["apps"][0].get("secret").is_none());
+
+    let response = admin_get_app(Path("app-1".to_string()), State(handler))
+        .await
+        .unwrap()
+        .into_response();
+    let (status, body) = into_parts(response).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["key"], "key-1");
+    assert!(body.get("secret").is_none());
+}
+
+#[tokio::test]
+async fn test_create_rejects_duplicates_and_invalid_apps() {
+    let handler = create_handler();
+    admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "id": "app-1", "key": "key-1" })),
+    )
+    .await
+    .unwrap();
+
+    let duplicate = admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "id": "app-1" })),
+    )
+    .await
+    .err()
+    .unwrap()
+    .into_response();
+    assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+
+    let bad_origin = admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "allowed_origins": ["https://*.*.example.com:*"] })),
+    )
+    .await
+    .err()
+    .unwrap()
+    .into_response();
+    assert_eq!(bad_origin.status(), StatusCode::BAD_REQUEST);
+
+    let bad_webhook = admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({
+            "webhooks": [{ "url": "https://example.com/hook", "event_types": ["channel_exploded"] }]
+        })),
+    )
+    .await
+    .err()
+    .unwrap()
+    .into_response();
+    assert_eq!(bad_webhook.status(), StatusCode::BAD_REQUEST);
+
+    let short_secret = admin_create_app(
+        State(handler),
+        ClientIp(None),
+        changes(json!({ "secret": "short" })),
+    )
+    .await
+    .err()
+    .unwrap()
+    .into_response();
+    assert_eq!(short_secret.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_patch_disables_app_and_clears_limits() {
+    let handler = create_handler();
+    admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "id": "app-1", "max_event_batch_size": 5 })),
+    )
+    .await
+    .unwrap();
+
+    let response = admin_update_app(
+        Path("app-1".to_string()),
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "enabled": false, "max_event_batch_size": null })),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let app = handler
+        .app_manager()
+        .find_by_id("app-1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!app.enabled);
+    assert_eq!(app.max_event_batch_size, None);
+
+    let key_change = admin_update_app(
+        Path("app-1".to_string()),
+        State(handler),
+        ClientIp(None),
+        changes(json!({ "key": "another-key" })),
+    )
+    .await
+    .err()
+    .unwrap()
+    .into_response();
+    assert_eq!(key_change.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_usage_is_reported_without_a_configured_quota_manager() {
+    let handler = create_handler();
+    admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "id": "app-1", "quotas": { "messages_per_day": 100 } })),
+    )
+    .await
+    .unwrap();
+
+    let (status, body) = into_parts(
+        admin_get_app_usage(Path("app-1".to_string()), State(handler.clone()))
+            .await
+            .unwrap()
+            .into_response(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["messages"], json!({ "used": 0, "limit": 100 }));
+
+    let missing = admin_get_app_usage(Path("missing".to_string()), State(handler))
+        .await
+        .err()
+        .unwrap()
+        .into_response();
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_rotate_secret_and_delete() {
+    let handler = create_handler();
+    admin_create_app(
+        State(handler.clone()),
+        ClientIp(None),
+        changes(json!({ "id": "app-1", "secret": "0123456789abcdef0123" })),
+    )
+    .await
+    .unwrap();
+
+    let response = admin_rotate_app_secret(
+        Path("app-1".to_string()),
+        Query(RotateSecretQuery::default()),
+        State(handler.clone()),
+        ClientIp(None),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    let (status, body) = into_parts(response).await;
+    assert_eq!(status, StatusCode::OK);
+    let new_secret = body["secret"].as_str().unwrap().to_string();
+    assert_ne!(new_secret, "0123456789abcdef0123");
+
+    let app = handler
+        .app_manager()
+        .find_by_id("app-1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(app.secret, new_secret);
+    // The previous secret keeps verifying signatures during the grace period
+    assert_eq!(
+        app.accepted_credential_secrets(),
+        vec!["0123456789abcdef0123".to_string()]
+    );
+
+    let (_, listed) = into_parts(
+        admin_get_app(Path("app-1".to_string()), State(handler.clone()))
+            .await
+            .unwrap()
+            .into_response(),
+    )
+    .await;
+    assert_eq!(listed["credentials"][0]["state"], "verify_only");
+    assert!(listed["credentials"][0].get("secret").is_none());
+
+    // Rotating the key as well keeps the previous key working during the grace period
+    let previous_key = app.key.clone();
+    let (_, rotated) = into_parts(
+        admin_rotate_app_secret(
+            Path("app-1".to_string()),
+            Query(RotateSecretQuery {
+                grace_period: None,
+                rotate_key: Some(true),
+            }),
+            State(handler.clone()),
+            ClientIp(None),
+        )
+        .await
+        .unwrap()
+        .into_response(),
+    )
+    .await;
+    let new_key = rotated["key"].as_str().unwrap().to_string();
+    assert_ne!(new_key, previous_key);
+    for key in [&new_key, &previous_key] {
+        let found = handler.app_manager().find_by_key(key).await.unwrap();
+        assert_eq!(found.unwrap().id, "app-1");
+    }
+
+    // Without a grace period every previous pair is revoked at once
+    admin_rotate_app_secret(
+        Path("app-1".to_string()),
+        Query(RotateSecretQuery {
+            grace_period: Some(0),
+            rotate_key: None,
+        }),
+        State(handler.clone()),
+        ClientIp(None),
+    )
+    .await
+    .unwrap();
+    let app = handler
+        .app_manager()
+        .find_by_id("app-1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(app.accepted_credential_secrets().is_empty());
+    let revoked = handler.app_manager().find_by_key(&previous_key).await;
+    assert!(revoked.unwrap().is_none());
+
+    let response = admin_delete_app(
+        Path("app-1".to_string()),
+        State(handler.clone()),
+        ClientIp(None),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let missing = admin_delete_app(Path("app-1".to_string()), State(handler), ClientIp(None))
+        .await
+        .err()
+        .unwrap()
+        .into_response();
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
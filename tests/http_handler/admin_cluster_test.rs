@@ -0,0 +1,37 @@
+use crate::mocks::connection_handler_mock::{
+    MockAppManager, create_test_connection_handler_with_app_manager,
+};
+use axum::body::to_bytes;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use sockudo::http_handler::cluster_topology;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_cluster_topology_reports_single_node_without_horizontal_adapter() {
+    let handler = Arc::new(create_test_connection_handler_with_app_manager(
+        MockAppManager::new(),
+    ));
+
+    let response = cluster_topology(State(handler))
+        .await
+        .unwrap()
+        .into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let topology: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(topology["node_id"], "mock-node");
+    assert_eq!(topology["cleanup_leader"], "mock-node");
+    assert_eq!(topology["is_cleanup_leader"], true);
+
+    let nodes = topology["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0]["node_id"], "mock-node");
+    assert_eq!(nodes[0]["is_local"], true);
+    assert_eq!(nodes[0]["alive"], true);
+    assert_eq!(nodes[0]["connections"], 0);
+    assert_eq!(nodes[0]["channels"], 0);
+}
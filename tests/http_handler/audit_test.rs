@@ -0,0 +1,185 @@
+use crate::mocks::connection_handler_mock::{MockAdapter, MockCacheManager};
+use async_trait::async_trait;
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Path, Query, RawQuery, State};
+use axum::http::{Request, StatusCode, Uri};
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post};
+use serde_json::Value;
+use sockudo::adapter::handler::ConnectionHandler;
+use sockudo::app::config::App;
+use sockudo::app::manager::AppManager;
+use sockudo::app::memory_app_manager::MemoryAppManager;
+use sockudo::audit::sink::AuditSink;
+use sockudo::audit::{AuditEvent, AuditLog};
+use sockudo::http_handler::{ClientIp, EventQuery, terminate_user_connections};
+use sockudo::middleware::{admin_auth_middleware, pusher_api_auth_middleware};
+use sockudo::options::{AuditConfig, ServerOptions};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+
+struct MemorySink(Arc<std::sync::Mutex<Vec<Value>>>);
+
+#[async_trait]
+impl AuditSink for MemorySink {
+    async fn write(&mut self, event: &AuditEvent) -> sockudo::error::Result<()> {
+        self.0.lock().unwrap().push(serde_json::to_value(event)?);
+        Ok(())
+    }
+}
+
+struct Audited {
+    handler: Arc<ConnectionHandler>,
+    audit_log: Arc<AuditLog>,
+    written: Arc<std::sync::Mutex<Vec<Value>>>,
+}
+
+impl Audited {
+    async fn events(&self) -> Vec<Value> {
+        self.audit_log.flush().await;
+        self.written.lock().unwrap().clone()
+    }
+}
+
+async fn create_handler() -> Audited {
+    let app_manager = Arc::new(MemoryAppManager::new());
+    app_manager
+        .create_app(App {
+            id: "app-1".to_string(),
+            key: "app-1-key".to_string(),
+            secret: "app-1-secret".to_string(),
+            enabled: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let mut options = ServerOptions::default();
+    options.admin_api.token = Some("admin-token".to_string());
+
+    let written = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let audit_log = Arc::new(AuditLog::new(
+        &AuditConfig::default(),
+        "node-1".to_string(),
+        Box::new(MemorySink(written.clone())),
+    ));
+    let handler = ConnectionHandler::new(
+        app_manager as Arc<dyn AppManager + Send + Sync>,
+        Arc::new(Mutex::new(MockAdapter::new())),
+        Arc::new(Mutex::new(MockCacheManager::new())),
+        None,
+        None,
+        options,
+        None,
+    )
+    .with_audit_log(audit_log.clone());
+    Audited {
+        handler: Arc::new(handler),
+        audit_log,
+        written,
+    }
+}
+
+fn peer() -> ConnectInfo<SocketAddr> {
+    ConnectInfo(SocketAddr::from(([203, 0, 113, 9], 40000)))
+}
+
+#[tokio::test]
+async fn test_api_auth_failure_is_audited() {
+    let audited = create_handler().await;
+    let router = Router::new()
+        .route("/apps/{appId}/events", post(|| async { StatusCode::OK }))
+        .route_layer(from_fn_with_state(
+            audited.handler.clone(),
+            pusher_api_auth_middleware,
+        ))
+        .with_state(audited.handler.clone());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let request = Request::post(format!(
+        "/apps/app-1/events?auth_key=app-1-key&auth_timestamp={timestamp}\
+         &auth_version=1.0&auth_signature=invalid"
+    ))
+    .header("Content-Type", "application/json")
+    .extension(peer())
+    .body(Body::from("{}"))
+    .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let events = audited.events().await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"], "api_auth_failed");
+    assert_eq!(events[0]["app_id"], "app-1");
+    assert_eq!(events[0]["ip"], "203.0.113.9");
+}
+
+#[tokio::test]
+async fn test_admin_token_failure_is_audited() {
+    let audited = create_handler().await;
+    let router = Router::new()
+        .route("/admin/apps", get(|| async { StatusCode::OK }))
+        .route_layer(from_fn_with_state(
+            audited.handler.clone(),
+            admin_auth_middleware,
+        ))
+        .with_state(audited.handler.clone());
+
+    let request = Request::get("/admin/apps")
+        .header("Authorization", "Bearer admin-token")
+        .extension(peer())
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::get("/admin/apps")
+        .header("Authorization", "Bearer wrong-token")
+        .extension(peer())
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let events = audited.events().await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"], "admin_auth_failed");
+    assert_eq!(events[0]["reason"], "Invalid admin token");
+    assert_eq!(events[0]["path"], "/admin/apps");
+    assert_eq!(events[0]["ip"], "203.0.113.9");
+}
+
+#[tokio::test]
+async fn test_terminate_user_connections_is_audited() {
+    let audited = create_handler().await;
+
+    terminate_user_connections(
+        Path(("app-1".to_string(), "42".to_string())),
+        Query(EventQuery {
+            auth_key: "app-1-key".to_string(),
+            auth_timestamp: String::new(),
+            auth_version: "1.0".to_string(),
+            body_md5: String::new(),
+            auth_signature: String::new(),
+        }),
+        State(audited.handler.clone()),
+        ClientIp(Some("203.0.113.9".parse().unwrap())),
+        Uri::from_static("/apps/app-1/users/42/terminate_connections"),
+        RawQuery(None),
+    )
+    .await
+    .unwrap();
+
+    let events = audited.events().await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"], "user_connections_terminated");
+    assert_eq!(events[0]["app_id"], "app-1");
+    assert_eq!(events[0]["user_id"], "42");
+    assert_eq!(events[0]["ip"], "203.0.113.9");
+}
@@ -0,0 +1,147 @@
+use crate::mocks::connection_handler_mock::{MockAdapter, MockCacheManager};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::extract::{ConnectInfo, Query, State};
+use axum::http::{Request, StatusCode};
+use axum::middleware::from_fn_with_state;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use serde_json::Value;
+use sockudo::adapter::handler::ConnectionHandler;
+use sockudo::app::config::App;
+use sockudo::app::manager::AppManager;
+use sockudo::app::memory_app_manager::MemoryAppManager;
+use sockudo::ban::memory_store::MemoryBanStore;
+use sockudo::ban::{BanManager, BanTarget};
+use sockudo::http_handler::{ClientIp, LiftBanQuery, admin_lift_ban, admin_list_bans};
+use sockudo::middleware::pusher_api_auth_middleware;
+use sockudo::options::{BanConfig, ServerOptions};
+use sockudo::rate_limiter::memory_limiter::MemoryRateLimiter;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+
+fn app(id: &str) -> App {
+    App {
+        id: id.to_string(),
+        key: format!("{id}-key"),
+        secret: format!("{id}-secret"),
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+async fn create_handler(bans: bool) -> Arc<ConnectionHandler> {
+    let app_manager = Arc::new(MemoryAppManager::new());
+    for id in ["app-1", "app-2"] {
+        app_manager.create_app(app(id)).await.unwrap();
+    }
+    let handler = ConnectionHandler::new(
+        app_manager as Arc<dyn AppManager + Send + Sync>,
+        Arc::new(Mutex::new(MockAdapter::new())),
+        Arc::new(Mutex::new(MockCacheManager::new())),
+        None,
+        None,
+        ServerOptions::default(),
+        None,
+    );
+    if !bans {
+        return Arc::new(handler);
+    }
+    let ban_manager = BanManager::new(
+        BanConfig {
+            enabled: true,
+            max_violations: 3,
+            ..Default::default()
+        },
+        Arc::new(MemoryRateLimiter::new(3, 60)),
+        Arc::new(MemoryBanStore::new()),
+    );
+    Arc::new(handler.with_ban_manager(Arc::new(ban_manager)))
+}
+
+fn router(handler: Arc<ConnectionHandler>) -> Router {
+    Router::new()
+        .route("/apps/{appId}/events", post(|| async { StatusCode::OK }))
+        .route_layer(from_fn_with_state(
+            handler.clone(),
+            pusher_api_auth_middleware,
+        ))
+        .with_state(handler)
+}
+
+/// Events API request with an invalid signature
+async fn publish(router: &Router, app_id: &str) -> StatusCode {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let request = Request::post(format!(
+        "/apps/{app_id}/events?auth_key={app_id}-key&auth_timestamp={timestamp}\
+         &auth_version=1.0&auth_signature=invalid"
+    ))
+    // Ignored without a trusted proxy, so it cannot shift the ban to someone else
+    .header("X-Real-IP", "198.51.100.7")
+    .header("Content-Type", "application/json")
+    .extension(ConnectInfo(SocketAddr::from(([203, 0, 113, 9], 40000))))
+    .body(Body::from("{}"))
+    .unwrap();
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_repeated_auth_failures_ban_the_address_in_the_app() {
+    let handler = create_handler(true).await;
+    let router = router(handler.clone());
+
+    for _ in 0..3 {
+        assert_eq!(publish(&router, "app-1").await, StatusCode::UNAUTHORIZED);
+    }
+    assert_eq!(publish(&router, "app-1").await, StatusCode::FORBIDDEN);
+    // The failures were seen by app-1 only
+    assert_eq!(publish(&router, "app-2").await, StatusCode::UNAUTHORIZED);
+
+    let ban_manager = handler.ban_manager().unwrap();
+    let bans = ban_manager.list().await.unwrap();
+    assert_eq!(bans.len(), 1);
+    assert_eq!(
+        bans[0].target,
+        BanTarget::app_ip("app-1", "203.0.113.9".parse().unwrap())
+    );
+    assert!(
+        ban_manager
+            .find_ip(Some("app-1"), "198.51.100.7".parse().unwrap())
+            .await
+            .is_none()
+    );
+
+    let response = admin_lift_ban(
+        Query(LiftBanQuery {
+            ip: Some("203.0.113.9".parse().unwrap()),
+            app_id: Some("app-1".to_string()),
+            user_id: None,
+        }),
+        State(handler.clone()),
+        ClientIp(None),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(publish(&router, "app-1").await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_ban_routes_answer_404_when_disabled() {
+    let handler = create_handler(false).await;
+
+    let response = admin_list_bans(State(handler.clone()))
+        .await
+        .into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert!(body["error"].as_str().unwrap().contains("bans.enabled"));
+}
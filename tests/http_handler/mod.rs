@@ -1 +1,5 @@
+pub mod admin_apps_test;
+pub mod admin_cluster_test;
+pub mod audit_test;
+pub mod ban_test;
 pub mod up_endpoint_test;
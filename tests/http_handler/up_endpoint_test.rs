@@ -1,14 +1,20 @@
 use crate::mocks::connection_handler_mock::{
-    MockAppManager, create_test_connection_handler_with_app_manager,
+    MockAdapter, MockAppManager, MockCacheManager, create_test_connection_handler_with_app_manager,
 };
+use axum::body::Body;
 use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::http::{Request, StatusCode};
 use axum::response::IntoResponse;
+use sockudo::adapter::handler::ConnectionHandler;
 use sockudo::app::config::App;
-use sockudo::http_handler::up;
+use sockudo::app::manager::AppManager;
+use sockudo::http_handler::{drain_routes, up};
+use sockudo::options::ServerOptions;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 use tokio::time::sleep;
+use tower::ServiceExt;
 
 // Helper to create a test app
 fn create_test_app(app_id: &str, enabled: bool) -> App {
@@ -163,6 +169,66 @@ async fn test_up_specific_app_not_found() {
     );
 }
 
+#[tokio::test]
+async fn test_up_returns_draining_when_node_is_draining() {
+    let mut app_manager = MockAppManager::new();
+    let test_app = create_test_app("test_app", true);
+    app_manager.expect_find_by_id("test_app".to_string(), test_app);
+
+    let handler = create_test_connection_handler_with_app_manager(app_manager);
+    assert!(handler.begin_drain());
+    assert!(!handler.begin_drain());
+    let handler_arc = Arc::new(handler);
+
+    let result = up(Some(Path("test_app".to_string())), State(handler_arc)).await;
+
+    assert!(result.is_ok());
+    let response = result.unwrap().into_response();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        response.headers().get("X-Health-Check").unwrap(),
+        "DRAINING"
+    );
+}
+
+#[tokio::test]
+async fn test_drain_requires_the_admin_token() {
+    let mut options = ServerOptions::default();
+    options.admin_api.token = Some("admin-token".to_string());
+    let handler = Arc::new(ConnectionHandler::new(
+        Arc::new(MockAppManager::new()) as Arc<dyn AppManager + Send + Sync>,
+        Arc::new(Mutex::new(MockAdapter::new())),
+        Arc::new(Mutex::new(MockCacheManager::new())),
+        None,
+        None,
+        options,
+        None,
+    ));
+    let router = drain_routes(handler.clone()).with_state(handler.clone());
+
+    for authorization in [None, Some("Bearer wrong-token")] {
+        let mut request = Request::post("/drain");
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let response = router
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!handler.is_draining());
+    }
+
+    let request = Request::post("/drain")
+        .header("Authorization", "Bearer admin-token")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert!(handler.is_draining());
+}
+
 // Custom mock app manager that simulates errors
 struct ErrorMockAppManager;
 
@@ -558,6 +624,14 @@ impl sockudo::cache::manager::CacheManager for FailingCacheManager {
     ) -> sockudo::error::Result<()> {
         Ok(())
     }
+    fn set_if_absent(
+        &self,
+        _key: &str,
+        _value: &str,
+        _ttl_seconds: u64,
+    ) -> futures_util::future::BoxFuture<'static, sockudo::error::Result<bool>> {
+        Box::pin(async { Ok(true) })
+    }
     async fn remove(&mut self, _key: &str) -> sockudo::error::Result<()> {
         Ok(())
     }
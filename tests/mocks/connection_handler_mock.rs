@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use dashmap::{DashMap, DashSet};
 use fastwebsockets::WebSocketWrite;
+use futures_util::future::BoxFuture;
 use hyper::upgrade::Upgraded;
 use hyper_util::rt::TokioIo;
 use serde_json::Value;
@@ -298,6 +299,14 @@ impl CacheManager for MockCacheManager {
     async fn set(&mut self, _key: &str, _value: &str, _ttl_seconds: u64) -> Result<()> {
         Ok(())
     }
+    fn set_if_absent(
+        &self,
+        _key: &str,
+        _value: &str,
+        _ttl_seconds: u64,
+    ) -> BoxFuture<'static, Result<bool>> {
+        Box::pin(async { Ok(true) })
+    }
     async fn remove(&mut self, _key: &str) -> Result<()> {
         Ok(())
     }
@@ -352,6 +361,8 @@ impl MetricsInterface for MockMetricsInterface {
     fn mark_channel_subscription(&self, _app_id: &str, _channel_type: &str) {}
     fn mark_channel_unsubscription(&self, _app_id: &str, _channel_type: &str) {}
     fn update_active_channels(&self, _app_id: &str, _channel_type: &str, _count: i64) {}
+    fn update_quota_usage(&self, _app_id: &str, _quota: &str, _used: u64) {}
+    fn mark_ip_rejected(&self, _app_id: &str, _access: &str) {}
     fn mark_api_message(
         &self,
         _app_id: &str,
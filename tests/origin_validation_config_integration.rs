@@ -31,6 +31,14 @@ mod origin_validation_config_integration_tests {
                 webhooks: None,
                 enable_watchlist_events: Some(false),
                 allowed_origins: None,
+                credentials: None,
+                channel_policy: None,
+                channel_limits: None,
+                quotas: None,
+                jwt_auth: None,
+                api_auth: None,
+                ip_filter: None,
+                authorizer: None,
             },
             // App with specific allowed origins
             App {
@@ -58,6 +66,14 @@ mod origin_validation_config_integration_tests {
                     "*.staging.example.com".to_string(),
                     "http://localhost:3000".to_string(),
                 ]),
+                credentials: None,
+                channel_policy: None,
+                channel_limits: None,
+                quotas: None,
+                jwt_auth: None,
+                api_auth: None,
+                ip_filter: None,
+                authorizer: None,
             },
             // App with wildcard allowing all origins
             App {
@@ -81,6 +97,14 @@ mod origin_validation_config_integration_tests {
                 webhooks: None,
                 enable_watchlist_events: Some(false),
                 allowed_origins: Some(vec!["*".to_string()]),
+                credentials: None,
+                channel_policy: None,
+                channel_limits: None,
+                quotas: None,
+                jwt_auth: None,
+                api_auth: None,
+                ip_filter: None,
+                authorizer: None,
             },
         ];
 